@@ -0,0 +1,33 @@
+// Custom client example talking to `kubectl proxy --unix-socket` over a Unix domain socket.
+#[cfg(unix)]
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    use k8s_openapi::api::core::v1::Pod;
+    use tracing::*;
+
+    use kube::{
+        client::{ClientBuilder, UnixConnector},
+        Api, Client, Config, ResourceExt,
+    };
+
+    tracing_subscriber::fmt::init();
+
+    // Run `kubectl proxy --unix-socket=/tmp/kube.sock` first
+    let socket = std::env::var("KUBE_SOCKET").unwrap_or_else(|_| "/tmp/kube.sock".into());
+    // The host is ignored by the connector, `kubectl proxy` takes care of TLS and auth
+    let config = Config::new("http://localhost".parse()?);
+    let client: Client = ClientBuilder::try_from_connector(config, UnixConnector::new(socket))?.build();
+
+    let pods: Api<Pod> = Api::default_namespaced(client);
+    for p in pods.list(&Default::default()).await? {
+        info!("{}", p.name_any());
+    }
+
+    Ok(())
+}
+
+// Unix domain sockets are not available on other platforms
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("custom_client_unix only runs on Unix");
+}
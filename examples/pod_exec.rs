@@ -4,7 +4,7 @@ use tracing::*;
 
 use kube::{
     api::{
-        Api, AttachParams, AttachedProcess, DeleteParams, ListParams, PostParams, ResourceExt, WatchEvent,
+        Api, AttachParams, AttachedProcess, DeleteParams, PostParams, ResourceExt, WatchEvent, WatchParams,
     },
     Client,
 };
@@ -34,8 +34,8 @@ async fn main() -> anyhow::Result<()> {
     pods.create(&PostParams::default(), &p).await?;
 
     // Wait until the pod is running, otherwise we get 500 error.
-    let lp = ListParams::default().fields("metadata.name=example").timeout(10);
-    let mut stream = pods.watch(&lp, "0").await?.boxed();
+    let wp = WatchParams::default().fields("metadata.name=example").timeout(10);
+    let mut stream = pods.watch(&wp, "0").await?.boxed();
     while let Some(status) = stream.try_next().await? {
         match status {
             WatchEvent::Added(o) => {
@@ -92,14 +92,13 @@ async fn main() -> anyhow::Result<()> {
         println!("{}", stdout);
         assert_eq!(stdout, "test string 1\n");
 
-        // AttachedProcess provides access to a future that resolves with a status object.
-        let status = attached.take_status().unwrap();
+        // AttachedProcess provides access to a future that resolves with the exit status.
+        let status = attached.take_exit_status().unwrap();
         // Send `exit 1` to get a failure status.
         stdin_writer.write_all(b"exit 1\n").await?;
         if let Some(status) = status.await {
             println!("{:?}", status);
-            assert_eq!(status.status, Some("Failure".to_owned()));
-            assert_eq!(status.reason, Some("NonZeroExitCode".to_owned()));
+            assert_eq!(status.code(), Some(1));
         }
     }
 
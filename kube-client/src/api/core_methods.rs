@@ -1,10 +1,21 @@
 use either::Either;
-use futures::Stream;
+use futures::{Stream, TryStreamExt};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
 use crate::{api::Api, Error, Result};
-use kube_core::{object::ObjectList, params::*, response::Status, ErrorResponse, WatchEvent};
+use kube_core::{
+    dynamic::{ApiResource, DynamicObject},
+    metadata::PartialObjectMeta,
+    object::ObjectList,
+    params::*,
+    response::Status,
+    table::Table,
+    ErrorResponse, WatchEvent,
+};
+
+/// The page size used by [`Api::list_stream`] when no limit is set
+const DEFAULT_LIST_PAGE_SIZE: u32 = 500;
 
 /// PUSH/PUT/POST/GET abstractions
 impl<K> Api<K>
@@ -30,13 +41,40 @@ where
     /// This function assumes that the object is expected to always exist, and returns [`Error`] if it does not.
     /// Consider using [`Api::get_opt`] if you need to handle missing objects.
     pub async fn get(&self, name: &str) -> Result<K> {
-        let mut req = self.request.get(name).map_err(Error::BuildRequest)?;
+        self.get_with(name, &GetParams::default()).await
+    }
+
+    /// [Get](`Api::get`) a named resource with a specific resourceVersion policy
+    ///
+    /// This allows opting into cheaper reads served from the apiserver cache,
+    /// at the cost of possibly receiving a stale object.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, GetParams}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     let p: Pod = pods.get_with("blog", &GetParams::any()).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Like [`Api::get`], this returns [`Error`] if the object does not exist.
+    pub async fn get_with(&self, name: &str, gp: &GetParams) -> Result<K> {
+        let mut req = self.request.get(name, gp).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("get");
         self.client.request::<K>(req).await
     }
 
     /// [Get](`Api::get`) a named resource if it exists, returns [`None`] if it doesn't exist
     ///
+    /// Only a `404 Not Found` response is mapped to [`None`],
+    /// any other error (including other `4xx` responses) is returned as [`Error`].
+    ///
     /// ```no_run
     /// use kube::{Api, Client};
     /// use k8s_openapi::api::core::v1::Pod;
@@ -55,7 +93,8 @@ where
     pub async fn get_opt(&self, name: &str) -> Result<Option<K>> {
         match self.get(name).await {
             Ok(obj) => Ok(Some(obj)),
-            Err(Error::Api(ErrorResponse { reason, .. })) if &reason == "NotFound" => Ok(None),
+            // match on the status code rather than the reason, which is lost for non-Status bodies
+            Err(Error::Api(ErrorResponse { code: 404, .. })) => Ok(None),
             Err(err) => Err(err),
         }
     }
@@ -73,7 +112,7 @@ where
     ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
     ///     let lp = ListParams::default().labels("app=blog"); // for this app only
     ///     for p in pods.list(&lp).await? {
-    ///         println!("Found Pod: {}", p.name());
+    ///         println!("Found Pod: {}", p.name_any());
     ///     }
     ///     Ok(())
     /// }
@@ -84,6 +123,119 @@ where
         self.client.request::<ObjectList<K>>(req).await
     }
 
+    /// Stream all resources matching the [`ListParams`], one page at a time
+    ///
+    /// This transparently follows `continue` tokens, so that only a single page of objects
+    /// is buffered at any time. The page size is taken from [`ListParams::limit`],
+    /// and defaults to 500 objects if unset.
+    /// Any [`ListParams::resource_version`] only applies to the first page,
+    /// since later pages are served from the same snapshot.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams, ResourceExt}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::TryStreamExt;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::all(client);
+    ///     let lp = ListParams::default().limit(100);
+    ///     let mut stream = Box::pin(pods.list_stream(&lp));
+    ///     while let Some(p) = stream.try_next().await? {
+    ///         println!("Found Pod: {}", p.name_any());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The stream ends after yielding the first error.
+    /// If the apiserver expires the `continue` token between pages (`410 Gone`),
+    /// the listing has to be restarted from scratch.
+    pub fn list_stream<'a>(&'a self, lp: &ListParams) -> impl Stream<Item = Result<K>> + 'a {
+        let mut lp = lp.clone();
+        if lp.limit.is_none() {
+            lp.limit = Some(DEFAULT_LIST_PAGE_SIZE);
+        }
+        futures::stream::try_unfold(Some(lp), move |lp| async move {
+            let lp = match lp {
+                Some(lp) => lp,
+                None => return Ok(None),
+            };
+            let list = self.list(&lp).await?;
+            // Later pages are served from the snapshot of the first one
+            let next = list.continue_token().map(|token| ListParams {
+                resource_version: None,
+                version_match: None,
+                ..lp.continue_token(token)
+            });
+            let page = futures::stream::iter(list.items.into_iter().map(Ok::<K, Error>));
+            Ok::<_, Error>(Some((page, next)))
+        })
+        .try_flatten()
+    }
+
+    /// Get only the metadata of a named resource as [`PartialObjectMeta`]
+    ///
+    /// This avoids transferring and decoding the rest of the object,
+    /// which is useful when only labels, annotations or owners are of interest.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, GetParams}, Client, ResourceExt};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     let p = pods.get_metadata("blog", &GetParams::default()).await?;
+    ///     println!("Labels: {:?}", p.labels());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_metadata(&self, name: &str, gp: &GetParams) -> Result<PartialObjectMeta<K>> {
+        let mut req = self.request.get_metadata(name, gp).map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("get_metadata");
+        self.client.request::<PartialObjectMeta<K>>(req).await
+    }
+
+    /// Get a list of the metadata of resources as [`PartialObjectMeta`]
+    ///
+    /// See [`Api::get_metadata`] and [`Api::list`] for details.
+    pub async fn list_metadata(&self, lp: &ListParams) -> Result<ObjectList<PartialObjectMeta<K>>> {
+        let mut req = self.request.list_metadata(lp).map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("list_metadata");
+        self.client.request::<ObjectList<PartialObjectMeta<K>>>(req).await
+    }
+
+    /// Get a list of resources rendered as a server-side [`Table`]
+    ///
+    /// This is the representation used by `kubectl get`, and includes any
+    /// additional printer columns defined on the resource.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams}, Client};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+    ///     let table = pods.list_table(&ListParams::default()).await?;
+    ///     let names: Vec<_> = table.column_definitions.iter().map(|c| c.name.as_str()).collect();
+    ///     println!("{}", names.join("\t"));
+    ///     for row in table.rows {
+    ///         let cells: Vec<_> = row.cells.iter().map(|c| c.to_string()).collect();
+    ///         println!("{}", cells.join("\t"));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_table(&self, lp: &ListParams) -> Result<Table> {
+        let mut req = self.request.list_table(lp).map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("list_table");
+        self.client.request::<Table>(req).await
+    }
+
     /// Create a resource
     ///
     /// This function requires a type that Serializes to `K`, which can be:
@@ -149,6 +301,9 @@ where
     ///
     /// 4XX and 5XX status types are returned as an [`Err(kube_client::Error::Api)`](crate::Error::Api).
     ///
+    /// The label and field selectors of the [`ListParams`] restrict which objects are deleted,
+    /// and the [`DeleteParams`] (such as its propagation policy) apply to every deleted object.
+    ///
     /// ```no_run
     /// use kube::{api::{Api, DeleteParams, ListParams, ResourceExt}, Client};
     /// use k8s_openapi::api::core::v1::Pod;
@@ -156,9 +311,10 @@ where
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::try_default().await?;
     ///     let pods: Api<Pod> = Api::namespaced(client, "apps");
-    ///     match pods.delete_collection(&DeleteParams::default(), &ListParams::default()).await? {
+    ///     let lp = ListParams::default().labels("app=blog"); // for this app only
+    ///     match pods.delete_collection(&DeleteParams::background(), &lp).await? {
     ///         either::Left(list) => {
-    ///             let names: Vec<_> = list.iter().map(ResourceExt::name).collect();
+    ///             let names: Vec<_> = list.iter().map(ResourceExt::name_any).collect();
     ///             println!("Deleting collection of pods: {:?}", names);
     ///         },
     ///         either::Right(status) => {
@@ -224,6 +380,40 @@ where
         self.client.request::<K>(req).await
     }
 
+    /// Server-side apply an object, taking over fields owned by other field managers
+    ///
+    /// This is a [`Api::patch`] with a [`Patch::Apply`] of `data`, using [`PatchParams::force`].
+    /// Without forcing, fields owned by other managers fail the apply with an [`Error::Api`],
+    /// whose [`ErrorResponse::apply_conflicts`] lists the conflicting managers and fields:
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, DynamicObject, GroupVersionKind, ApiResource, Patch, PatchParams}, Client, Error};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("apps", "v1", "Deployment"));
+    ///     let deploys: Api<DynamicObject> = Api::default_namespaced_with(client, &ar);
+    ///     let deploy = DynamicObject::new("web", &ar).data(serde_json::json!({ "spec": { "replicas": 2 } }));
+    ///     match deploys.patch("web", &PatchParams::apply("gitops"), &Patch::Apply(&deploy)).await {
+    ///         Err(Error::Api(err)) if !err.apply_conflicts().is_empty() => {
+    ///             for conflict in err.apply_conflicts() {
+    ///                 println!("{}", conflict);
+    ///             }
+    ///             deploys.force_apply("web", "gitops", &deploy).await?;
+    ///         }
+    ///         res => { res?; }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn force_apply(&self, name: &str, field_manager: &str, data: &K) -> Result<K>
+    where
+        K: Serialize,
+    {
+        let pp = PatchParams::apply(field_manager).force();
+        self.patch(name, &pp, &Patch::Apply(data)).await
+    }
+
     /// Replace a resource entirely with a new one
     ///
     /// This is used just like [`Api::create`], but with one additional instruction:
@@ -290,28 +480,33 @@ where
     /// then you can stream the remaining buffered `WatchEvent` objects.
     ///
     /// Note that a `watch` call can terminate for many reasons (even before the specified
-    /// [`ListParams::timeout`] is triggered), and will have to be re-issued
+    /// [`WatchParams::timeout`] is triggered), and will have to be re-issued
     /// with the last seen resource version when or if it closes.
     ///
     /// Consider using a managed [`watcher`] to deal with automatic re-watches and error cases.
     ///
+    /// On Kubernetes >= 1.27, [`WatchParams::streaming_lists`] can be used to receive the
+    /// current state of the collection as `Added` events before any changes, removing the need for
+    /// an initial [`Api::list`]. The end of these initial events is marked by a `Bookmark` for which
+    /// `is_initial_events_end` returns true.
+    ///
     /// ```no_run
-    /// use kube::{api::{Api, ListParams, ResourceExt, WatchEvent}, Client};
+    /// use kube::{api::{Api, WatchParams, ResourceExt, WatchEvent}, Client};
     /// use k8s_openapi::api::batch::v1::Job;
     /// use futures::{StreamExt, TryStreamExt};
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = Client::try_default().await?;
     ///     let jobs: Api<Job> = Api::namespaced(client, "apps");
-    ///     let lp = ListParams::default()
+    ///     let wp = WatchParams::default()
     ///         .fields("metadata.name=my_job")
     ///         .timeout(20); // upper bound of how long we watch for
-    ///     let mut stream = jobs.watch(&lp, "0").await?.boxed();
+    ///     let mut stream = jobs.watch(&wp, "0").await?.boxed();
     ///     while let Some(status) = stream.try_next().await? {
     ///         match status {
-    ///             WatchEvent::Added(s) => println!("Added {}", s.name()),
-    ///             WatchEvent::Modified(s) => println!("Modified: {}", s.name()),
-    ///             WatchEvent::Deleted(s) => println!("Deleted {}", s.name()),
+    ///             WatchEvent::Added(s) => println!("Added {}", s.name_any()),
+    ///             WatchEvent::Modified(s) => println!("Modified: {}", s.name_any()),
+    ///             WatchEvent::Deleted(s) => println!("Deleted {}", s.name_any()),
     ///             WatchEvent::Bookmark(s) => {},
     ///             WatchEvent::Error(s) => println!("{}", s),
     ///         }
@@ -319,15 +514,63 @@ where
     ///     Ok(())
     /// }
     /// ```
-    /// [`ListParams::timeout`]: super::ListParams::timeout
+    /// [`WatchParams::timeout`]: super::WatchParams::timeout
+    /// [`WatchParams::streaming_lists`]: super::WatchParams::streaming_lists
     /// [`watcher`]: https://docs.rs/kube_runtime/*/kube_runtime/watcher/fn.watcher.html
     pub async fn watch(
         &self,
-        lp: &ListParams,
+        wp: &WatchParams,
         version: &str,
     ) -> Result<impl Stream<Item = Result<WatchEvent<K>>>> {
-        let mut req = self.request.watch(lp, version).map_err(Error::BuildRequest)?;
+        let mut req = self.request.watch(wp, version).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("watch");
         self.client.request_events::<K>(req).await
     }
+
+    /// Watch only the metadata of resources as [`PartialObjectMeta`]
+    ///
+    /// See [`Api::watch`] for details on watch semantics.
+    pub async fn watch_metadata(
+        &self,
+        wp: &WatchParams,
+        version: &str,
+    ) -> Result<impl Stream<Item = Result<WatchEvent<PartialObjectMeta<K>>>>> {
+        let mut req = self
+            .request
+            .watch_metadata(wp, version)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("watch_metadata");
+        self.client.request_events::<PartialObjectMeta<K>>(req).await
+    }
+}
+
+/// Checked listing of dynamic resources
+impl Api<DynamicObject> {
+    /// [List](`Api::list`) resources, checking that every item is of the listed resource
+    ///
+    /// Items returned without `apiVersion` and `kind` have them set from `ar`,
+    /// and the list keeps its `resourceVersion` for starting a watch.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ApiResource, DynamicObject, GroupVersionKind, ListParams}, Client};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("apps", "v1", "Deployment"));
+    ///     let deploys: Api<DynamicObject> = Api::all_with(client, &ar);
+    ///     let list = deploys.list_checked(&ListParams::default(), &ar).await?;
+    ///     println!("listed at {:?}", list.resource_version());
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnexpectedType`] if an item is of another apiVersion or kind than `ar`.
+    pub async fn list_checked(&self, lp: &ListParams, ar: &ApiResource) -> Result<ObjectList<DynamicObject>> {
+        self.list(lp)
+            .await?
+            .check_types(ar)
+            .map_err(Error::UnexpectedType)
+    }
 }
@@ -51,6 +51,7 @@ impl<K: Resource + Clone + DeserializeOwned + Debug> Api<K> {
                 api: self,
                 dirtiness: Dirtiness::Clean,
                 name,
+                preconditions: Preconditions::of(&object),
                 object,
             }),
             None => Entry::Vacant(VacantEntry { api: self, name }),
@@ -121,9 +122,27 @@ pub struct OccupiedEntry<'a, K> {
     api: &'a Api<K>,
     dirtiness: Dirtiness,
     name: &'a str,
+    preconditions: Preconditions,
     object: K,
 }
 
+/// The identity of the object as last seen on the API, used to keep replacements conditional
+#[derive(Debug, Default)]
+struct Preconditions {
+    uid: Option<String>,
+    resource_version: Option<String>,
+}
+
+impl Preconditions {
+    fn of<K: Resource>(object: &K) -> Self {
+        let meta = object.meta();
+        Self {
+            uid: meta.uid.clone(),
+            resource_version: meta.resource_version.clone(),
+        }
+    }
+}
+
 #[derive(Debug)]
 enum Dirtiness {
     /// The object has not been modified (locally) since the last API operation
@@ -203,6 +222,7 @@ impl<'a, K> OccupiedEntry<'a, K> {
         };
         if !pp.dry_run {
             self.dirtiness = Dirtiness::Clean;
+            self.preconditions = Preconditions::of(&self.object);
         }
         Ok(())
     }
@@ -210,6 +230,8 @@ impl<'a, K> OccupiedEntry<'a, K> {
     /// Validate that [`Self::object`] is valid, and refers to the same object as the original [`Api::entry`] call
     ///
     /// Defaults `ObjectMeta::name` and `ObjectMeta::namespace` if unset.
+    /// For existing objects, `ObjectMeta::uid` and `ObjectMeta::resource_version` are also defaulted to the
+    /// last seen values, so that replacing an object that was rebuilt from scratch still fails on conflicts.
     fn prepare_for_commit(&mut self) -> Result<(), CommitValidationError>
     where
         K: Resource,
@@ -241,6 +263,21 @@ impl<'a, K> OccupiedEntry<'a, K> {
                 object_generate_name: generate_name.clone(),
             });
         }
+        if let Dirtiness::Dirty = self.dirtiness {
+            match &mut meta.uid {
+                uid @ None => *uid = self.preconditions.uid.clone(),
+                Some(uid) if Some(uid.as_str()) != self.preconditions.uid.as_deref() => {
+                    return Err(CommitValidationError::UidMismatch {
+                        object_uid: uid.clone(),
+                        expected: self.preconditions.uid.clone(),
+                    });
+                }
+                Some(_) => (),
+            }
+            if meta.resource_version.is_none() {
+                meta.resource_version = self.preconditions.resource_version.clone();
+            }
+        }
         Ok(())
     }
 }
@@ -281,6 +318,14 @@ pub enum CommitValidationError {
         /// The set name generation template of the object (`ObjectMeta::generate_name`)
         object_generate_name: String,
     },
+    /// `ObjectMeta::uid` does not match the UID of the object returned by [`Api::entry`]
+    #[error(".metadata.uid does not match the object returned by Api::entry (got: {object_uid:?}, expected: {expected:?})")]
+    UidMismatch {
+        /// The UID of the object (`ObjectMeta::uid`)
+        object_uid: String,
+        /// The UID of the object when it was last read or saved
+        expected: Option<String>,
+    },
 }
 
 /// A view of an object that does not yet exist
@@ -305,6 +350,7 @@ impl<'a, K> VacantEntry<'a, K> {
             api: self.api,
             dirtiness: Dirtiness::New,
             name: self.name,
+            preconditions: Preconditions::default(),
             object,
         }
     }
@@ -321,10 +367,58 @@ mod tests {
     };
 
     use crate::{
-        api::entry::{CommitError, Entry},
+        api::entry::{CommitError, CommitValidationError, Entry},
         Api, Client, Error,
     };
 
+    #[tokio::test]
+    async fn entry_replace_keeps_preconditions() {
+        use futures::pin_mut;
+        use http::{Request, Response};
+        use hyper::Body;
+        use tower_test::mock;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let existing = serde_json::json!({
+                "apiVersion": "v1",
+                "kind": "ConfigMap",
+                "metadata": { "name": "cm", "namespace": "ns", "uid": "some-uid", "resourceVersion": "1" },
+            });
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::GET);
+            send.send_response(Response::new(Body::from(existing.to_string())));
+
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::PUT);
+            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+            let replaced: ConfigMap = serde_json::from_slice(&body).unwrap();
+            assert_eq!(replaced.metadata.uid.as_deref(), Some("some-uid"));
+            assert_eq!(replaced.metadata.resource_version.as_deref(), Some("1"));
+            send.send_response(Response::new(Body::from(body)));
+        });
+
+        let api = Api::<ConfigMap>::namespaced(Client::new(mock_service, "default"), "ns");
+        let mut entry = match api.entry("cm").await.unwrap() {
+            Entry::Occupied(entry) => entry,
+            entry => panic!("entry for existing object must be occupied: {:?}", entry),
+        };
+        // Rebuild the object from scratch, losing the uid and resourceVersion
+        *entry.get_mut() = ConfigMap {
+            data: Some([("key".to_string(), "value".to_string())].into()),
+            ..ConfigMap::default()
+        };
+        entry.commit(&PostParams::default()).await.unwrap();
+        spawned.await.unwrap();
+
+        entry.get_mut().metadata.uid = Some("other-uid".into());
+        assert!(matches!(
+            entry.commit(&PostParams::default()).await,
+            Err(CommitError::Validate(CommitValidationError::UidMismatch { .. }))
+        ));
+    }
+
     #[tokio::test]
     #[ignore] // needs cluster (gets and writes cms)
     async fn entry_create_missing_object() -> Result<(), Box<dyn std::error::Error>> {
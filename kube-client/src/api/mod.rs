@@ -5,7 +5,8 @@ mod core_methods;
 #[cfg(feature = "ws")] mod remote_command;
 use std::fmt::Debug;
 
-#[cfg(feature = "ws")] pub use remote_command::AttachedProcess;
+#[cfg(feature = "ws")]
+pub use remote_command::{AttachedProcess, ExitStatus, TerminalSize, TerminalSizeSender};
 #[cfg(feature = "ws")] mod portforward;
 #[cfg(feature = "ws")] pub use portforward::Portforwarder;
 
@@ -13,7 +14,7 @@ mod subresource;
 #[cfg(feature = "ws")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ws")))]
 pub use subresource::{Attach, AttachParams, Execute, Portforward};
-pub use subresource::{Evict, EvictParams, Log, LogParams, ScaleSpec, ScaleStatus};
+pub use subresource::{EphemeralContainers, Evict, EvictParams, Log, LogParams, Proxy, ScaleSpec, ScaleStatus};
 
 mod util;
 
@@ -25,18 +26,19 @@ pub mod entry;
 pub use kube_core::admission;
 pub(crate) use kube_core::params;
 pub use kube_core::{
-    dynamic::{ApiResource, DynamicObject},
+    dynamic::{ApiResource, DynamicObject, LazyDynamicObject, UnexpectedTypeError},
     gvk::{GroupVersionKind, GroupVersionResource},
-    metadata::{ListMeta, ObjectMeta, TypeMeta},
-    object::{NotUsed, Object, ObjectList},
+    metadata::{ListMeta, ObjectMeta, PartialObjectMeta, TypeMeta, WithTypeMeta},
+    object::{NotUsed, Object, ObjectList, StatusApply},
     request::Request,
+    table::{Table, TableColumnDefinition, TableRow, TableRowCondition},
     watch::WatchEvent,
     Resource, ResourceExt,
 };
 use kube_core::{DynamicResourceScope, NamespaceResourceScope};
 pub use params::{
-    DeleteParams, ListParams, Patch, PatchParams, PostParams, Preconditions, PropagationPolicy,
-    ValidationDirective,
+    DeleteParams, GetParams, ListParams, Patch, PatchParams, PostParams, Preconditions, PropagationPolicy,
+    ValidationDirective, VersionMatch, WatchParams,
 };
 
 use crate::Client;
@@ -256,4 +258,121 @@ mod test {
         let _: Api<corev1::PersistentVolume> = Api::all(client.clone());
         let _: Api<corev1::ConfigMap> = Api::namespaced(client.clone(), "default");
     }
+
+    #[tokio::test]
+    async fn get_opt_only_maps_not_found() {
+        use futures::pin_mut;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            for (code, body) in [
+                (404, "404 page not found"),
+                (
+                    403,
+                    r#"{"kind":"Status","apiVersion":"v1","status":"Failure","reason":"Forbidden","code":403}"#,
+                ),
+            ] {
+                let (_, send) = handle.next_request().await.expect("service not called");
+                send.send_response(Response::builder().status(code).body(Body::from(body)).unwrap());
+            }
+        });
+
+        let pods: Api<corev1::Pod> = Api::namespaced(Client::new(mock_service, "default"), "ns");
+        assert!(pods.get_opt("missing").await.unwrap().is_none());
+        match pods.get_opt("forbidden").await {
+            Err(crate::Error::Api(ae)) => assert_eq!(ae.code, 403),
+            other => panic!("expected a 403 error, got {:?}", other),
+        }
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn scale_patches_replicas() {
+        use futures::pin_mut;
+        use k8s_openapi::api::apps::v1 as appsv1;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.method(), http::Method::PATCH);
+            assert_eq!(
+                request.uri().to_string(),
+                "/apis/apps/v1/namespaces/ns/deployments/blog/scale?"
+            );
+            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+            let patch: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(patch, serde_json::json!({ "spec": { "replicas": 3 } }));
+            let scale = serde_json::json!({
+                "apiVersion": "autoscaling/v1",
+                "kind": "Scale",
+                "metadata": { "name": "blog", "namespace": "ns" },
+                "spec": { "replicas": 3 },
+                "status": { "replicas": 1 },
+            });
+            send.send_response(Response::builder().body(Body::from(scale.to_string())).unwrap());
+        });
+
+        let deploys: Api<appsv1::Deployment> = Api::namespaced(Client::new(mock_service, "default"), "ns");
+        let scale = deploys.scale("blog", 3).await.unwrap();
+        assert_eq!(scale.spec.unwrap().replicas, Some(3));
+        spawned.await.unwrap();
+    }
+
+    async fn list_pod_pages(lp: crate::api::ListParams, uris: [&'static str; 2]) -> Vec<String> {
+        use futures::{pin_mut, TryStreamExt};
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            for (uri, continue_, name) in [(uris[0], "next", "a"), (uris[1], "", "b")] {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.uri().to_string(), uri);
+                let list = serde_json::json!({
+                    "apiVersion": "v1",
+                    "kind": "PodList",
+                    "metadata": { "continue": continue_, "resourceVersion": "1" },
+                    "items": [{ "apiVersion": "v1", "kind": "Pod", "metadata": { "name": name } }],
+                });
+                send.send_response(
+                    Response::builder()
+                        .body(Body::from(serde_json::to_vec(&list).unwrap()))
+                        .unwrap(),
+                );
+            }
+        });
+
+        let pods: Api<corev1::Pod> = Api::namespaced(Client::new(mock_service, "default"), "ns");
+        let names = pods
+            .list_stream(&lp)
+            .map_ok(|p| p.metadata.name.unwrap())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        spawned.await.unwrap();
+        names
+    }
+
+    #[tokio::test]
+    async fn list_stream_follows_continue_tokens() {
+        let lp = crate::api::ListParams::default().limit(1);
+        let names = list_pod_pages(lp, [
+            "/api/v1/namespaces/ns/pods?&limit=1",
+            "/api/v1/namespaces/ns/pods?&limit=1&continue=next",
+        ])
+        .await;
+        assert_eq!(names, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn list_stream_drops_resource_version_when_following_continue_tokens() {
+        let lp = crate::api::ListParams::default().limit(1).at("0");
+        let names = list_pod_pages(lp, [
+            "/api/v1/namespaces/ns/pods?&limit=1&resourceVersion=0",
+            "/api/v1/namespaces/ns/pods?&limit=1&continue=next",
+        ])
+        .await;
+        assert_eq!(names, vec!["a", "b"]);
+    }
 }
@@ -2,14 +2,16 @@ use std::future::Future;
 
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::Status;
 
+use bytes::Bytes;
 use futures::{
-    channel::oneshot,
+    channel::{mpsc, oneshot},
     future::{
-        select,
+        self, select,
         Either::{Left, Right},
     },
-    FutureExt, SinkExt, StreamExt,
+    stream, FutureExt, SinkExt, StreamExt,
 };
+use serde::Serialize;
 use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, DuplexStream};
 use tokio_tungstenite::{tungstenite as ws, WebSocketStream};
@@ -19,6 +21,23 @@ use super::AttachParams;
 type StatusReceiver = oneshot::Receiver<Status>;
 type StatusSender = oneshot::Sender<Status>;
 
+type TerminalSizeReceiver = mpsc::Receiver<TerminalSize>;
+/// The sending half of the terminal size channel of an [`AttachedProcess`]
+///
+/// Obtained from [`AttachedProcess::terminal_size`].
+pub type TerminalSizeSender = mpsc::Sender<TerminalSize>;
+
+/// TerminalSize define the size of a terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(docsrs, doc(cfg(feature = "ws")))]
+#[serde(rename_all = "PascalCase")]
+pub struct TerminalSize {
+    /// width of the terminal
+    pub width: u16,
+    /// height of the terminal
+    pub height: u16,
+}
+
 /// Errors from attaching to a pod.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -57,6 +76,14 @@ pub enum Error {
     /// Failed to send status object
     #[error("failed to send status object")]
     SendStatus,
+
+    /// Failed to serialize TerminalSize object
+    #[error("failed to serialize TerminalSize object: {0}")]
+    SerializeTerminalSize(#[source] serde_json::Error),
+
+    /// Failed to send terminal size message
+    #[error("failed to send terminal size message: {0}")]
+    SendTerminalSize(#[source] ws::Error),
 }
 
 const MAX_BUF_SIZE: usize = 1024;
@@ -78,6 +105,7 @@ pub struct AttachedProcess {
     stdout_reader: Option<DuplexStream>,
     stderr_reader: Option<DuplexStream>,
     status_rx: Option<StatusReceiver>,
+    terminal_size_tx: Option<TerminalSizeSender>,
     task: tokio::task::JoinHandle<Result<(), Error>>,
 }
 
@@ -102,6 +130,9 @@ impl AttachedProcess {
             (None, None)
         };
         let (status_tx, status_rx) = oneshot::channel();
+        // Resizing only makes sense with a TTY, so the sender is dropped (closing the channel) otherwise
+        let (terminal_size_tx, terminal_size_rx) = mpsc::channel(1);
+        let terminal_size_tx = if ap.tty { Some(terminal_size_tx) } else { None };
 
         let task = tokio::spawn(start_message_loop(
             stream,
@@ -109,6 +140,7 @@ impl AttachedProcess {
             stdout_writer,
             stderr_writer,
             status_tx,
+            terminal_size_rx,
         ));
 
         AttachedProcess {
@@ -120,6 +152,7 @@ impl AttachedProcess {
             stdout_reader,
             stderr_reader,
             status_rx: Some(status_rx),
+            terminal_size_tx,
         }
     }
 
@@ -162,6 +195,17 @@ impl AttachedProcess {
         self.stderr_reader.take()
     }
 
+    /// Sender to resize the TTY of the process.
+    /// ```ignore
+    /// let mut terminal_size = attached.terminal_size().unwrap();
+    /// terminal_size.send(TerminalSize { width: 80, height: 24 }).await?;
+    /// ```
+    /// Only available if [`AttachParams`](super::AttachParams) had `tty`.
+    /// Returns `None` if called more than once.
+    pub fn terminal_size(&mut self) -> Option<TerminalSizeSender> {
+        self.terminal_size_tx.take()
+    }
+
     /// Abort the background task, causing remote command to fail.
     #[inline]
     pub fn abort(&self) {
@@ -179,6 +223,72 @@ impl AttachedProcess {
     pub fn take_status(&mut self) -> Option<impl Future<Output = Option<Status>>> {
         self.status_rx.take().map(|recv| recv.map(|res| res.ok()))
     }
+
+    /// Take a future that resolves with the [`ExitStatus`] of the remote command.
+    ///
+    /// The future resolves to `None` if the connection ended without the server reporting a status,
+    /// in which case [`AttachedProcess::join`] returns the transport error (if any).
+    ///
+    /// This shares the status with [`AttachedProcess::take_status`]; only one of them can be taken.
+    /// Returns `None` if called more than once.
+    pub fn take_exit_status(&mut self) -> Option<impl Future<Output = Option<ExitStatus>>> {
+        self.take_status()
+            .map(|status| status.map(|status| status.map(ExitStatus::from)))
+    }
+}
+
+/// The outcome of a remote command, as reported by the server on the status channel.
+#[cfg_attr(docsrs, doc(cfg(feature = "ws")))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitStatus {
+    /// The command ran and exited with code 0
+    Success,
+    /// The command ran and exited with a non-zero code
+    NonZeroExitCode(i32),
+    /// The command could not be run, or the server reported some other failure
+    Failure(Status),
+}
+
+impl ExitStatus {
+    /// The exit code of the command, if it ran to completion
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            ExitStatus::Success => Some(0),
+            ExitStatus::NonZeroExitCode(code) => Some(*code),
+            ExitStatus::Failure(_) => None,
+        }
+    }
+
+    /// Whether the command ran and exited with code 0
+    pub fn success(&self) -> bool {
+        matches!(self, ExitStatus::Success)
+    }
+}
+
+impl From<Status> for ExitStatus {
+    fn from(status: Status) -> Self {
+        if status.status.as_deref() == Some("Success") {
+            return ExitStatus::Success;
+        }
+        if status.reason.as_deref() == Some("NonZeroExitCode") {
+            // The exit code is reported as a cause with the reason `ExitCode`
+            let code = status
+                .details
+                .as_ref()
+                .and_then(|details| details.causes.as_ref())
+                .and_then(|causes| {
+                    causes
+                        .iter()
+                        .find(|cause| cause.reason.as_deref() == Some("ExitCode"))
+                })
+                .and_then(|cause| cause.message.as_ref())
+                .and_then(|message| message.parse().ok());
+            if let Some(code) = code {
+                return ExitStatus::NonZeroExitCode(code);
+            }
+        }
+        ExitStatus::Failure(status)
+    }
 }
 
 const STDIN_CHANNEL: u8 = 0;
@@ -186,7 +296,18 @@ const STDOUT_CHANNEL: u8 = 1;
 const STDERR_CHANNEL: u8 = 2;
 // status channel receives `Status` object on exit.
 const STATUS_CHANNEL: u8 = 3;
-// const RESIZE_CHANNEL: u8 = 4;
+// resize channel is used to send TerminalSize object to change the size of the terminal
+const RESIZE_CHANNEL: u8 = 4;
+
+/// Messages to forward to the server.
+enum Input {
+    /// Data (or a read error) from stdin
+    Stdin(std::io::Result<Bytes>),
+    /// Stdin was closed
+    StdinClosed,
+    /// The terminal was resized
+    Resize(TerminalSize),
+}
 
 async fn start_message_loop<S>(
     stream: WebSocketStream<S>,
@@ -194,21 +315,27 @@ async fn start_message_loop<S>(
     mut stdout: Option<impl AsyncWrite + Unpin>,
     mut stderr: Option<impl AsyncWrite + Unpin>,
     status_tx: StatusSender,
+    terminal_size_rx: TerminalSizeReceiver,
 ) -> Result<(), Error>
 where
     S: AsyncRead + AsyncWrite + Unpin + Sized + Send + 'static,
 {
-    let mut stdin_stream = tokio_util::io::ReaderStream::new(stdin);
+    // Stdin closing ends the session, while the resize channel closing does not,
+    // so the end of stdin is marked explicitly in the merged stream.
+    let stdin_stream = tokio_util::io::ReaderStream::new(stdin)
+        .map(Input::Stdin)
+        .chain(stream::once(future::ready(Input::StdinClosed)));
+    let mut input_stream = stream::select(stdin_stream, terminal_size_rx.map(Input::Resize));
     let (mut server_send, raw_server_recv) = stream.split();
     // Work with filtered messages to reduce noise.
     let mut server_recv = raw_server_recv.filter_map(filter_message).boxed();
     let mut server_msg = server_recv.next();
-    let mut next_stdin = stdin_stream.next();
+    let mut next_input = input_stream.next();
 
     loop {
-        match select(server_msg, next_stdin).await {
+        match select(server_msg, next_input).await {
             // from server
-            Left((Some(message), p_next_stdin)) => {
+            Left((Some(message), p_next_input)) => {
                 match message {
                     Ok(Message::Stdout(bin)) => {
                         if let Some(stdout) = stdout.as_mut() {
@@ -234,7 +361,7 @@ where
                     }
                 }
                 server_msg = server_recv.next();
-                next_stdin = p_next_stdin;
+                next_input = p_next_input;
             }
 
             Left((None, _)) => {
@@ -243,7 +370,7 @@ where
             }
 
             // from stdin
-            Right((Some(Ok(bytes)), p_server_msg)) => {
+            Right((Some(Input::Stdin(Ok(bytes))), p_server_msg)) => {
                 if !bytes.is_empty() {
                     let mut vec = Vec::with_capacity(bytes.len() + 1);
                     vec.push(STDIN_CHANNEL);
@@ -254,14 +381,26 @@ where
                         .map_err(Error::SendStdin)?;
                 }
                 server_msg = p_server_msg;
-                next_stdin = stdin_stream.next();
+                next_input = input_stream.next();
             }
 
-            Right((Some(Err(err)), _)) => {
+            Right((Some(Input::Stdin(Err(err))), _)) => {
                 return Err(Error::ReadStdin(err));
             }
 
-            Right((None, _)) => {
+            // from the terminal size channel
+            Right((Some(Input::Resize(size)), p_server_msg)) => {
+                let mut vec = vec![RESIZE_CHANNEL];
+                serde_json::to_writer(&mut vec, &size).map_err(Error::SerializeTerminalSize)?;
+                server_send
+                    .send(ws::Message::binary(vec))
+                    .await
+                    .map_err(Error::SendTerminalSize)?;
+                server_msg = p_server_msg;
+                next_input = input_stream.next();
+            }
+
+            Right((Some(Input::StdinClosed) | None, _)) => {
                 // Stdin closed (writer half dropped).
                 // Let the server know and disconnect.
                 server_send.close().await.map_err(Error::SendClose)?;
@@ -304,3 +443,39 @@ async fn filter_message(wsm: Result<ws::Message, ws::Error>) -> Option<Result<Me
         Err(err) => Some(Err(err)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ExitStatus, Status};
+
+    #[test]
+    fn exit_status_from_status() {
+        let success: Status = serde_json::from_value(serde_json::json!({
+            "metadata": {},
+            "status": "Success"
+        }))
+        .unwrap();
+        assert_eq!(ExitStatus::from(success).code(), Some(0));
+
+        let exited: Status = serde_json::from_value(serde_json::json!({
+            "metadata": {},
+            "status": "Failure",
+            "message": "command terminated with non-zero exit code: error executing command [sh -c exit 3], exit code 3",
+            "reason": "NonZeroExitCode",
+            "details": { "causes": [{ "reason": "ExitCode", "message": "3" }] }
+        }))
+        .unwrap();
+        assert_eq!(ExitStatus::from(exited), ExitStatus::NonZeroExitCode(3));
+
+        let failed: Status = serde_json::from_value(serde_json::json!({
+            "metadata": {},
+            "status": "Failure",
+            "message": "exec: \"nope\": executable file not found in $PATH"
+        }))
+        .unwrap();
+        let failed = ExitStatus::from(failed);
+        assert!(matches!(failed, ExitStatus::Failure(_)));
+        assert_eq!(failed.code(), None);
+        assert!(!failed.success());
+    }
+}
@@ -1,14 +1,14 @@
-use bytes::Bytes;
-use futures::Stream;
+use futures::AsyncBufRead;
+use hyper::Body;
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
 use crate::{
-    api::{Api, Patch, PatchParams, PostParams},
+    api::{Api, Patch, PatchParams, PostParams, Resource, StatusApply},
     Error, Result,
 };
 
-use kube_core::response::Status;
+use kube_core::{object::HasStatus, response::Status};
 pub use kube_core::subresource::{EvictParams, LogParams};
 
 #[cfg(feature = "ws")]
@@ -16,11 +16,19 @@ pub use kube_core::subresource::{EvictParams, LogParams};
 pub use kube_core::subresource::AttachParams;
 
 pub use k8s_openapi::api::autoscaling::v1::{Scale, ScaleSpec, ScaleStatus};
+use k8s_openapi::api::{
+    authentication::v1::TokenRequest,
+    core::v1::{Binding, Pod, ServiceAccount},
+};
 
 #[cfg(feature = "ws")] use crate::api::portforward::Portforwarder;
 #[cfg(feature = "ws")] use crate::api::remote_command::AttachedProcess;
 
 /// Methods for [scale subresource](https://kubernetes.io/docs/tasks/access-kubernetes-api/custom-resources/custom-resource-definitions/#scale-subresource).
+///
+/// These work for any resource exposing a `scale` subresource, such as `Deployment`, `StatefulSet`,
+/// `ReplicaSet`, or a custom resource with `subresources.scale` configured,
+/// and always return the generic `autoscaling/v1` [`Scale`] object.
 impl<K> Api<K>
 where
     K: Clone + DeserializeOwned,
@@ -59,6 +67,26 @@ where
         req.extensions_mut().insert("replace_scale");
         self.client.request::<Scale>(req).await
     }
+
+    /// Set the desired number of replicas through the scale subresource
+    ///
+    /// This is a merge patch of `spec.replicas`, leaving any other fields untouched.
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::apps::v1::Deployment;
+    /// # use kube::{Api, Client};
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let deploys: Api<Deployment> = Api::default_namespaced(client);
+    /// let scale = deploys.scale("blog", 3).await?;
+    /// println!("scaled to {:?}", scale.spec.and_then(|s| s.replicas));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scale(&self, name: &str, replicas: i32) -> Result<Scale> {
+        let patch = serde_json::json!({ "spec": { "replicas": replicas } });
+        self.patch_scale(name, &PatchParams::default(), &Patch::Merge(&patch)).await
+    }
 }
 
 /// Arbitrary subresources
@@ -76,6 +104,28 @@ where
         self.client.request::<K>(req).await
     }
 
+    /// Create an instance of the subresource
+    ///
+    /// Unlike the other subresource methods, the response is deserialized as `T`, since subresources
+    /// created this way (such as evictions or token requests) rarely return the parent resource.
+    pub async fn create_subresource<T>(
+        &self,
+        subresource_name: &str,
+        name: &str,
+        pp: &PostParams,
+        data: Vec<u8>,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let mut req = self
+            .request
+            .create_subresource(subresource_name, name, pp, data)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("create_subresource");
+        self.client.request::<T>(req).await
+    }
+
     /// Patch an instance of the subresource
     pub async fn patch_subresource<P: serde::Serialize + Debug>(
         &self,
@@ -167,6 +217,47 @@ where
         self.client.request::<K>(req).await
     }
 
+    /// Apply fields on the status object using server-side apply
+    ///
+    /// Only the fields set in `status` become owned by the field manager in `pp`, so multiple
+    /// controllers can manage distinct parts of the status without clobbering each other.
+    /// This wraps the status in a [`StatusApply`] and calls [`Api::patch_status`] with [`Patch::Apply`].
+    ///
+    /// NB: Requires that the resource has a status subresource.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, PatchParams}, Client, CustomResource};
+    /// use schemars::JsonSchema;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
+    /// #[kube(group = "clux.dev", version = "v1", kind = "Foo", namespaced, status = "FooStatus")]
+    /// struct FooSpec {}
+    ///
+    /// #[derive(Deserialize, Serialize, Clone, Debug, Default, JsonSchema)]
+    /// struct FooStatus {
+    ///     #[serde(skip_serializing_if = "Option::is_none")]
+    ///     ready: Option<bool>,
+    /// }
+    ///
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let foos: Api<Foo> = Api::default_namespaced(client);
+    /// let status = FooStatus { ready: Some(true) };
+    /// foos.apply_status("baz", &PatchParams::apply("foo-controller"), status).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apply_status(&self, name: &str, pp: &PatchParams, status: K::Status) -> Result<K>
+    where
+        K: Resource + HasStatus,
+        K::DynamicType: Default,
+        K::Status: serde::Serialize + Debug,
+    {
+        let patch = StatusApply::new::<K>(status);
+        self.patch_status(name, pp, &Patch::Apply(&patch)).await
+    }
+
     /// Replace every field on the status object
     ///
     /// This works similarly to the [`Api::replace`] method, but `.spec` is ignored.
@@ -229,11 +320,33 @@ where
         self.client.request_text(req).await
     }
 
-    /// Fetch logs as a stream of bytes
-    pub async fn log_stream(&self, name: &str, lp: &LogParams) -> Result<impl Stream<Item = Result<Bytes>>> {
+    /// Stream the logs via [`AsyncBufRead`]
+    ///
+    /// Log lines can be processed using [`AsyncBufReadExt`](futures::AsyncBufReadExt) methods,
+    /// which take care of reassembling lines split across chunk boundaries:
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::core::v1::Pod;
+    /// # use kube::{api::{Api, LogParams}, Client};
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// use futures::{AsyncBufReadExt, TryStreamExt};
+    ///
+    /// let pods: Api<Pod> = Api::default_namespaced(client);
+    /// let mut logs = pods
+    ///     .log_stream("my-pod", &LogParams::default()).await?
+    ///     .lines();
+    ///
+    /// while let Some(line) = logs.try_next().await? {
+    ///     println!("{}", line);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn log_stream(&self, name: &str, lp: &LogParams) -> Result<impl AsyncBufRead> {
         let mut req = self.request.logs(name, lp).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("log_stream");
-        self.client.request_text_stream(req).await
+        self.client.request_stream(req).await
     }
 }
 
@@ -247,8 +360,22 @@ fn evict_path() {
     use k8s_openapi::api::core::v1 as corev1;
     let ep = EvictParams::default();
     let url = corev1::Pod::url_path(&(), Some("ns"));
-    let req = Request::new(url).evict("foo", &ep).unwrap();
+    let req = Request::new(&url).evict("foo", &ep).unwrap();
     assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/foo/eviction?");
+
+    let ep = EvictParams {
+        delete_options: Some(crate::api::DeleteParams::default().grace_period(10)),
+        ..EvictParams::default()
+    };
+    let req = Request::new(url).evict("foo", &ep).unwrap();
+    let body: serde_json::Value = serde_json::from_slice(req.body()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "deleteOptions": { "gracePeriodSeconds": 10 },
+            "metadata": { "name": "foo" }
+        })
+    );
 }
 
 /// Marker trait for objects that can be evicted
@@ -261,6 +388,25 @@ where
     K: DeserializeOwned + Evict,
 {
     /// Create an eviction
+    ///
+    /// Evictions respect the PodDisruptionBudgets covering the pod.
+    /// If evicting the pod would violate a budget, the apiserver rejects the eviction with
+    /// a `429 Too Many Requests` [`Error::Api`], and the eviction should be retried later.
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::core::v1::Pod;
+    /// # use kube::{api::{Api, EvictParams}, Client};
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let pods: Api<Pod> = Api::default_namespaced(client);
+    /// match pods.evict("blog", &EvictParams::default()).await {
+    ///     Ok(_) => println!("evicted"),
+    ///     Err(kube::Error::Api(ae)) if ae.code == 429 => println!("blocked by a disruption budget"),
+    ///     Err(err) => return Err(err.into()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn evict(&self, name: &str, ep: &EvictParams) -> Result<Status> {
         let mut req = self.request.evict(name, ep).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("evict");
@@ -268,6 +414,242 @@ where
     }
 }
 
+// ----------------------------------------------------------------------------
+// Ephemeral containers subresource
+// ----------------------------------------------------------------------------
+
+#[test]
+fn ephemeral_containers_path() {
+    use crate::api::{Request, Resource};
+    use k8s_openapi::api::core::v1 as corev1;
+    let url = corev1::Pod::url_path(&(), Some("ns"));
+    let req = Request::new(&url)
+        .get_subresource("ephemeralcontainers", "foo")
+        .unwrap();
+    assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/foo/ephemeralcontainers");
+    let patch = Patch::Strategic(serde_json::json!({ "spec": { "ephemeralContainers": [] } }));
+    let req = Request::new(url)
+        .patch_subresource("ephemeralcontainers", "foo", &PatchParams::default(), &patch)
+        .unwrap();
+    assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/foo/ephemeralcontainers?");
+    assert_eq!(req.method(), "PATCH");
+}
+
+/// Marker trait for objects that can have ephemeral containers injected
+pub trait EphemeralContainers {}
+
+impl EphemeralContainers for k8s_openapi::api::core::v1::Pod {}
+
+/// Methods for the `ephemeralcontainers` subresource, used to inject debug containers into running pods
+///
+/// Requires Kubernetes 1.23 or later, where the subresource operates on the whole `Pod`.
+/// Only `spec.ephemeralContainers` can be changed through it, and containers can be added but not removed.
+impl<K> Api<K>
+where
+    K: DeserializeOwned + EphemeralContainers,
+{
+    /// Get the pod along with its ephemeral containers
+    pub async fn get_ephemeral_containers(&self, name: &str) -> Result<K> {
+        let mut req = self
+            .request
+            .get_subresource("ephemeralcontainers", name)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("get_ephemeral_containers");
+        self.client.request::<K>(req).await
+    }
+
+    /// Patch the ephemeral containers of a pod
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::core::v1::Pod;
+    /// # use kube::{api::{Api, Patch, PatchParams}, Client};
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let pods: Api<Pod> = Api::default_namespaced(client);
+    /// let patch = serde_json::json!({
+    ///     "spec": {
+    ///         "ephemeralContainers": [{
+    ///             "name": "debugger",
+    ///             "image": "busybox",
+    ///             "stdin": true,
+    ///             "tty": true,
+    ///             "targetContainerName": "app"
+    ///         }]
+    ///     }
+    /// });
+    /// pods.patch_ephemeral_containers("blog", &PatchParams::default(), &Patch::Strategic(patch))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn patch_ephemeral_containers<P: serde::Serialize + Debug>(
+        &self,
+        name: &str,
+        pp: &PatchParams,
+        patch: &Patch<P>,
+    ) -> Result<K> {
+        let mut req = self
+            .request
+            .patch_subresource("ephemeralcontainers", name, pp, patch)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("patch_ephemeral_containers");
+        self.client.request::<K>(req).await
+    }
+
+    /// Replace the ephemeral containers of a pod
+    ///
+    /// Requires `metadata.resourceVersion` set in data
+    pub async fn replace_ephemeral_containers(
+        &self,
+        name: &str,
+        pp: &PostParams,
+        data: Vec<u8>,
+    ) -> Result<K> {
+        let mut req = self
+            .request
+            .replace_subresource("ephemeralcontainers", name, pp, data)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("replace_ephemeral_containers");
+        self.client.request::<K>(req).await
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Token subresource
+// ----------------------------------------------------------------------------
+
+/// Methods for the `serviceaccounts/token` subresource
+impl Api<ServiceAccount> {
+    /// Request a short-lived token for a service account
+    ///
+    /// The token can be bound to the lifetime of another object through `spec.boundObjectRef`,
+    /// and is returned in `status.token` of the returned [`TokenRequest`].
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::{authentication::v1::{TokenRequest, TokenRequestSpec}, core::v1::ServiceAccount};
+    /// # use kube::{api::{Api, PostParams}, Client};
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let sas: Api<ServiceAccount> = Api::default_namespaced(client);
+    /// let tr = TokenRequest {
+    ///     spec: TokenRequestSpec {
+    ///         audiences: vec!["api".into()],
+    ///         expiration_seconds: Some(600),
+    ///         ..TokenRequestSpec::default()
+    ///     },
+    ///     ..TokenRequest::default()
+    /// };
+    /// let tr = sas.create_token_request("builder", &PostParams::default(), &tr).await?;
+    /// let token = tr.status.map(|s| s.token);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_token_request(
+        &self,
+        name: &str,
+        pp: &PostParams,
+        token_request: &TokenRequest,
+    ) -> Result<TokenRequest> {
+        let bytes = serde_json::to_vec(token_request).map_err(Error::SerdeError)?;
+        let mut req = self
+            .request
+            .create_subresource("token", name, pp, bytes)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("create_token_request");
+        self.client.request::<TokenRequest>(req).await
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Binding subresource
+// ----------------------------------------------------------------------------
+
+/// Methods for the `pods/binding` subresource
+impl Api<Pod> {
+    /// Bind a pod to a node
+    ///
+    /// This is how schedulers assign pods to nodes, and only succeeds for pods
+    /// that have not already been bound. The `metadata.name` of the binding must match `name`.
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::core::v1::{Binding, ObjectReference, Pod};
+    /// # use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    /// # use kube::{api::{Api, PostParams}, Client};
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let pods: Api<Pod> = Api::default_namespaced(client);
+    /// let binding = Binding {
+    ///     metadata: ObjectMeta {
+    ///         name: Some("blog".into()),
+    ///         ..ObjectMeta::default()
+    ///     },
+    ///     target: ObjectReference {
+    ///         api_version: Some("v1".into()),
+    ///         kind: Some("Node".into()),
+    ///         name: Some("worker-1".into()),
+    ///         ..ObjectReference::default()
+    ///     },
+    /// };
+    /// pods.create_binding("blog", &PostParams::default(), &binding).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_binding(&self, name: &str, pp: &PostParams, binding: &Binding) -> Result<Status> {
+        let bytes = serde_json::to_vec(binding).map_err(Error::SerdeError)?;
+        let mut req = self
+            .request
+            .create_subresource("binding", name, pp, bytes)
+            .map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("create_binding");
+        self.client.request::<Status>(req).await
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Proxy subresource
+// ----------------------------------------------------------------------------
+
+/// Marker trait for objects that can be proxied to through the apiserver
+pub trait Proxy {}
+
+impl Proxy for k8s_openapi::api::core::v1::Node {}
+impl Proxy for k8s_openapi::api::core::v1::Pod {}
+impl Proxy for k8s_openapi::api::core::v1::Service {}
+
+impl<K> Api<K>
+where
+    K: Proxy,
+{
+    /// Send a request through the apiserver to a pod, service, or node, and return the raw response
+    ///
+    /// Only the path and query of `request` are used to address the target, and may be relative to its root.
+    /// The `name` can select a port (`name:port`), and for services also a scheme (`https:name:port`).
+    ///
+    /// The response is returned as is, so error statuses returned by the target are not turned into [`Error::Api`].
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::core::v1::Node;
+    /// # use kube::{Api, Client};
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// let nodes: Api<Node> = Api::all(client);
+    /// let req = http::Request::get("/stats/summary").body(vec![])?;
+    /// let res = nodes.proxy_request("worker-1", req).await?;
+    /// let stats = hyper::body::to_bytes(res.into_body()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn proxy_request(
+        &self,
+        name: &str,
+        request: http::Request<Vec<u8>>,
+    ) -> Result<http::Response<Body>> {
+        let mut req = self.request.proxy(name, request).map_err(Error::BuildRequest)?;
+        req.extensions_mut().insert("proxy_request");
+        self.client.send(req.map(Body::from)).await
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Attach subresource
 // ----------------------------------------------------------------------------
@@ -385,6 +767,12 @@ fn portforward_path() {
         req.uri(),
         "/api/v1/namespaces/ns/pods/foo/portforward?&ports=80%2C1234"
     );
+    assert!(Request::new("/api/v1/namespaces/ns/pods")
+        .portforward("foo", &[0])
+        .is_err());
+    assert!(Request::new("/api/v1/namespaces/ns/pods")
+        .portforward("foo", &[80, 80])
+        .is_err());
 }
 
 /// Marker trait for objects that has portforward
@@ -400,6 +788,28 @@ where
     K: Clone + DeserializeOwned + Portforward,
 {
     /// Forward ports of a pod
+    ///
+    /// Each port is exposed as an `AsyncRead + AsyncWrite` stream on the returned [`Portforwarder`],
+    /// without binding any local ports:
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::core::v1::Pod;
+    /// # use kube::{api::Api, Client};
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client: Client = todo!();
+    /// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    ///
+    /// let pods: Api<Pod> = Api::default_namespaced(client);
+    /// let mut pf = pods.portforward("example", &[80]).await?;
+    /// let mut port = pf.take_stream(80).unwrap();
+    /// port.write_all(b"GET / HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n").await?;
+    /// let mut response = vec![];
+    /// port.read_to_end(&mut response).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// See the `pod_portforward_bind` example for forwarding local TCP connections.
     pub async fn portforward(&self, name: &str, ports: &[u16]) -> Result<Portforwarder> {
         let req = self
             .request
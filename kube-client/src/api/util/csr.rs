@@ -19,6 +19,51 @@ impl Api<CertificateSigningRequest> {
         self.client.request::<CertificateSigningRequest>(req).await
     }
 
+    /// Approve the specified CertificateSigningRequest
+    ///
+    /// Adds an `Approved` condition through the approval subresource, after which the signer
+    /// will issue the certificate. The `reason` should be a short CamelCase identifier, and the
+    /// `message` a human readable explanation.
+    pub async fn approve(
+        &self,
+        name: &str,
+        reason: &str,
+        message: &str,
+    ) -> Result<CertificateSigningRequest> {
+        self.set_approval_condition(name, "Approved", reason, message)
+            .await
+    }
+
+    /// Deny the specified CertificateSigningRequest
+    ///
+    /// Adds a `Denied` condition through the approval subresource, after which the signer
+    /// will refuse to issue the certificate.
+    pub async fn deny(&self, name: &str, reason: &str, message: &str) -> Result<CertificateSigningRequest> {
+        self.set_approval_condition(name, "Denied", reason, message).await
+    }
+
+    async fn set_approval_condition(
+        &self,
+        name: &str,
+        type_: &str,
+        reason: &str,
+        message: &str,
+    ) -> Result<CertificateSigningRequest> {
+        // The apiserver fills in the timestamps of new conditions
+        let patch = serde_json::json!({
+            "status": {
+                "conditions": [{
+                    "type": type_,
+                    "status": "True",
+                    "reason": reason,
+                    "message": message,
+                }]
+            }
+        });
+        self.patch_approval(name, &PatchParams::default(), &Patch::Strategic(patch))
+            .await
+    }
+
     /// Get the CertificateSigningRequest. May differ from get(name)
     pub async fn get_approval(&self, name: &str) -> Result<CertificateSigningRequest> {
         self.get_subresource("approval", name).await
@@ -15,8 +15,11 @@ where
     K: Restart + Resource + DeserializeOwned,
 {
     /// Trigger a restart of a Resource.
+    ///
+    /// This is equivalent to `kubectl rollout restart`, and sets the same pod template annotation
+    /// through server-side apply to roll out new pods.
     pub async fn restart(&self, name: &str) -> Result<K> {
-        let mut req = self.request.restart(name).map_err(Error::BuildRequest)?;
+        let mut req = self.request.restart::<K>(name).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("restart");
         self.client.request::<K>(req).await
     }
@@ -24,6 +27,9 @@ where
 
 impl Api<Node> {
     /// Cordon a Node.
+    ///
+    /// Marks the node as unschedulable by merge patching `spec.unschedulable`,
+    /// so no new pods are scheduled onto it. Pods already running on the node are left alone.
     pub async fn cordon(&self, name: &str) -> Result<Node> {
         let mut req = self.request.cordon(name).map_err(Error::BuildRequest)?;
         req.extensions_mut().insert("cordon");
@@ -31,9 +37,11 @@ impl Api<Node> {
     }
 
     /// Uncordon a Node.
+    ///
+    /// Marks the node as schedulable again, reverting [`Api::cordon`].
     pub async fn uncordon(&self, name: &str) -> Result<Node> {
         let mut req = self.request.uncordon(name).map_err(Error::BuildRequest)?;
-        req.extensions_mut().insert("cordon");
+        req.extensions_mut().insert("uncordon");
         self.client.request::<Node>(req).await
     }
 }
@@ -0,0 +1,244 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::token_request::{https_client, send, Error as TokenRequestError};
+use crate::config::ExecConfig;
+
+#[derive(Error, Debug)]
+/// Possible errors when acquiring an Azure AD token
+pub enum Error {
+    /// A required setting was neither passed to kubelogin nor set in the environment
+    #[error("missing '{0}' for azure workload identity")]
+    MissingConfig(&'static str),
+
+    /// Failed to read the federated service account token
+    #[error("failed to read federated token file '{1:?}': {0}")]
+    ReadFederatedToken(#[source] std::io::Error, PathBuf),
+
+    /// Failed to build a request to Azure AD
+    #[error("failed to build request: {0}")]
+    BuildRequest(#[source] http::Error),
+
+    /// Failed to request a token from Azure AD
+    #[error("failed to request azure ad token: {0}")]
+    TokenRequest(#[source] TokenRequestError),
+}
+
+const KUBELOGIN: &str = "kubelogin";
+const WORKLOAD_IDENTITY: &str = "workloadidentity";
+const DEFAULT_AUTHORITY_HOST: &str = "https://login.microsoftonline.com/";
+const CLIENT_ASSERTION_TYPE: &str = "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+/// Tokens expiring within this window are refreshed ahead of time
+const EXPIRY_MARGIN_SECS: i64 = 60;
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+// Azure workload identity, as implemented by `kubelogin get-token --login workloadidentity`:
+// - the projected service account token in `AZURE_FEDERATED_TOKEN_FILE` is exchanged for an Azure AD token
+// - kubelogin flags take precedence over the `AZURE_*` environment variables set by the webhook
+// - the Azure AD token is cached until it expires, and the federated token is re-read for every exchange
+pub struct WorkloadIdentity {
+    authority_host: String,
+    tenant_id: String,
+    client_id: String,
+    federated_token_file: PathBuf,
+    server_id: String,
+    token: Option<(SecretString, DateTime<Utc>)>,
+}
+
+impl std::fmt::Debug for WorkloadIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkloadIdentity")
+            .field("authority_host", &self.authority_host)
+            .field("tenant_id", &self.tenant_id)
+            .field("client_id", &self.client_id)
+            .field("federated_token_file", &self.federated_token_file)
+            .field("server_id", &self.server_id)
+            .finish()
+    }
+}
+
+impl WorkloadIdentity {
+    /// Whether the exec config runs kubelogin with the workload identity login method
+    pub(crate) fn is_workload_identity(exec: &ExecConfig) -> bool {
+        let command = std::path::Path::new(&exec.command);
+        let is_kubelogin = command.file_stem().map_or(false, |stem| stem == KUBELOGIN);
+        is_kubelogin && kubelogin_flags(exec).get("login").map(String::as_str) == Some(WORKLOAD_IDENTITY)
+    }
+
+    pub(crate) fn from_exec(exec: &ExecConfig) -> Result<Self, Error> {
+        let flags = kubelogin_flags(exec);
+        let env = exec_env(exec);
+        let get = |flag: &'static str, var: &str| {
+            flags
+                .get(flag)
+                .or_else(|| env.get(var))
+                .cloned()
+                .or_else(|| std::env::var(var).ok())
+        };
+        Ok(Self {
+            authority_host: get("authority-host", "AZURE_AUTHORITY_HOST")
+                .unwrap_or_else(|| DEFAULT_AUTHORITY_HOST.to_owned()),
+            tenant_id: get("tenant-id", "AZURE_TENANT_ID").ok_or(Error::MissingConfig("tenant-id"))?,
+            client_id: get("client-id", "AZURE_CLIENT_ID").ok_or(Error::MissingConfig("client-id"))?,
+            federated_token_file: get("federated-token-file", "AZURE_FEDERATED_TOKEN_FILE")
+                .ok_or(Error::MissingConfig("federated-token-file"))?
+                .into(),
+            server_id: flags
+                .get("server-id")
+                .cloned()
+                .ok_or(Error::MissingConfig("server-id"))?,
+            token: None,
+        })
+    }
+
+    /// Get a valid access token, exchanging the federated token for a new one if it has expired
+    pub async fn token(&mut self) -> Result<String, Error> {
+        match &self.token {
+            Some((token, expiry)) if Utc::now() + Duration::seconds(EXPIRY_MARGIN_SECS) < *expiry => {
+                Ok(token.expose_secret().clone())
+            }
+            _ => {
+                let (token, expiry) = self.request_token().await?;
+                self.token = Some((SecretString::from(token.clone()), expiry));
+                Ok(token)
+            }
+        }
+    }
+
+    async fn request_token(&self) -> Result<(String, DateTime<Utc>), Error> {
+        // The projected token is rotated by the kubelet, so it must not be cached
+        let assertion = std::fs::read_to_string(&self.federated_token_file)
+            .map_err(|err| Error::ReadFederatedToken(err, self.federated_token_file.clone()))?;
+        let form = form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", "client_credentials")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("scope", &format!("{}/.default", self.server_id))
+            .append_pair("client_assertion_type", CLIENT_ASSERTION_TYPE)
+            .append_pair("client_assertion", assertion.trim())
+            .finish();
+        let req = http::Request::post(self.token_endpoint())
+            .header(http::header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(hyper::Body::from(form))
+            .map_err(Error::BuildRequest)?;
+
+        let requested_at = Utc::now();
+        let client = https_client(None).map_err(Error::TokenRequest)?;
+        let res: TokenResponse = send(&client, req).await.map_err(Error::TokenRequest)?;
+        Ok((res.access_token, requested_at + Duration::seconds(res.expires_in)))
+    }
+
+    fn token_endpoint(&self) -> String {
+        format!(
+            "{}/{}/oauth2/v2.0/token",
+            self.authority_host.trim_end_matches('/'),
+            self.tenant_id
+        )
+    }
+}
+
+/// Collect the `--flag value` and `--flag=value` arguments passed to kubelogin
+fn kubelogin_flags(exec: &ExecConfig) -> HashMap<String, String> {
+    let mut flags = HashMap::new();
+    let mut args = exec.args.iter().flatten();
+    while let Some(arg) = args.next() {
+        let name = match arg.strip_prefix("--") {
+            Some(name) => name,
+            // `-l` is the only shorthand kubelogin offers for the flags used here
+            None if arg == "-l" => "login",
+            None => continue,
+        };
+        if let Some((name, value)) = name.split_once('=') {
+            flags.insert(name.to_owned(), value.to_owned());
+        } else if let Some(value) = args.next() {
+            flags.insert(name.to_owned(), value.clone());
+        }
+    }
+    flags
+}
+
+/// The environment variables the exec config would pass to kubelogin
+fn exec_env(exec: &ExecConfig) -> HashMap<String, String> {
+    exec.env
+        .iter()
+        .flatten()
+        .filter_map(|env| Some((env.get("name")?.clone(), env.get("value")?.clone())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exec(command: &str, args: &[&str]) -> ExecConfig {
+        serde_json::from_value(serde_json::json!({
+            "command": command,
+            "args": args,
+            "env": [{ "name": "AZURE_CLIENT_ID", "value": "env-client" }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn detects_workload_identity() {
+        let args = [
+            "get-token",
+            "--login",
+            "workloadidentity",
+            "--server-id",
+            "server",
+        ];
+        assert!(WorkloadIdentity::is_workload_identity(&exec("kubelogin", &args)));
+        assert!(WorkloadIdentity::is_workload_identity(&exec(
+            "/usr/local/bin/kubelogin",
+            &args
+        )));
+        assert!(WorkloadIdentity::is_workload_identity(&exec(
+            "kubelogin",
+            &["get-token", "-l", "workloadidentity"]
+        )));
+        assert!(!WorkloadIdentity::is_workload_identity(&exec(
+            "kubelogin",
+            &["get-token", "--login", "devicecode"]
+        )));
+        assert!(!WorkloadIdentity::is_workload_identity(&exec("aws", &args)));
+    }
+
+    #[test]
+    fn reads_flags_and_env() {
+        let wi = WorkloadIdentity::from_exec(&exec(
+            "kubelogin",
+            &[
+                "get-token",
+                "--login=workloadidentity",
+                "--server-id",
+                "6dae42f8-4368-4678-94ff-3960e28e3630",
+                "--tenant-id",
+                "my-tenant",
+                "--federated-token-file",
+                "/var/run/secrets/azure/tokens/azure-identity-token",
+            ],
+        ))
+        .unwrap();
+        assert_eq!(wi.client_id, "env-client");
+        assert_eq!(
+            wi.token_endpoint(),
+            "https://login.microsoftonline.com/my-tenant/oauth2/v2.0/token"
+        );
+        assert_eq!(wi.server_id, "6dae42f8-4368-4678-94ff-3960e28e3630");
+
+        assert!(matches!(
+            WorkloadIdentity::from_exec(&exec("kubelogin", &["get-token", "--login", "workloadidentity"])),
+            Err(Error::MissingConfig(_))
+        ));
+    }
+}
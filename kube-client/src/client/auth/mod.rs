@@ -1,6 +1,6 @@
 use std::{
     path::{Path, PathBuf},
-    process::Command,
+    process::{Command, Stdio},
     sync::Arc,
 };
 
@@ -17,10 +17,17 @@ use thiserror::Error;
 use tokio::sync::{Mutex, RwLock};
 use tower::{filter::AsyncPredicate, BoxError};
 
-use crate::config::{AuthInfo, AuthProviderConfig, ExecConfig};
+use crate::config::{AuthInfo, AuthProviderConfig, ExecAuthCluster, ExecConfig, ExecInteractiveMode};
 
+#[cfg(feature = "azure")] mod azure;
+#[cfg(feature = "azure")] pub use azure::Error as AzureError;
 #[cfg(feature = "oauth")] mod oauth;
 #[cfg(feature = "oauth")] pub use oauth::Error as OAuthError;
+#[cfg(feature = "oidc")] mod oidc;
+#[cfg(feature = "oidc")] pub use oidc::Error as OidcError;
+#[cfg(any(feature = "oidc", feature = "azure"))] mod token_request;
+#[cfg(any(feature = "oidc", feature = "azure"))]
+pub use token_request::Error as TokenRequestError;
 
 #[derive(Error, Debug)]
 /// Client auth errors
@@ -64,6 +71,10 @@ pub enum Error {
     #[error("failed to parse auth exec output: {0}")]
     AuthExecParse(#[source] serde_json::Error),
 
+    /// Failed to serialize the exec plugin input
+    #[error("failed to serialize auth exec input: {0}")]
+    AuthExecSerialize(#[source] serde_json::Error),
+
     /// Failed to exec auth
     #[error("failed exec auth: {0}")]
     AuthExec(String),
@@ -81,6 +92,18 @@ pub enum Error {
     #[cfg_attr(docsrs, doc(cfg(feature = "oauth")))]
     #[error("failed OAuth: {0}")]
     OAuth(#[source] OAuthError),
+
+    /// OIDC error
+    #[cfg(feature = "oidc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "oidc")))]
+    #[error("failed OIDC: {0}")]
+    Oidc(#[source] OidcError),
+
+    /// Azure workload identity error
+    #[cfg(feature = "azure")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "azure")))]
+    #[error("failed Azure workload identity: {0}")]
+    Azure(#[source] AzureError),
 }
 
 #[derive(Debug, Clone)]
@@ -106,7 +129,7 @@ impl TokenFile {
             .map_err(|source| Error::ReadTokenFile(source, path.as_ref().to_owned()))?;
         Ok(Self {
             path: path.as_ref().to_owned(),
-            token: SecretString::from(token),
+            token: SecretString::from(token.trim().to_owned()),
             // Try to reload at least once a minute
             expires_at: Utc::now() + Duration::seconds(60),
         })
@@ -129,7 +152,8 @@ impl TokenFile {
             // > limit credential exposure.
             // > https://github.com/kubernetes/kubernetes/issues/68164
             if let Ok(token) = std::fs::read_to_string(&self.path) {
-                self.token = SecretString::from(token);
+                // Like client-go, tolerate surrounding whitespace such as a trailing newline
+                self.token = SecretString::from(token.trim().to_owned());
             }
             self.expires_at = Utc::now() + Duration::seconds(60);
         }
@@ -144,6 +168,8 @@ impl TokenFile {
 // - token-file refreshed at least once per minute
 // - gcp: command based token source (exec)
 // - gcp: application credential based token source (requires `oauth` feature)
+// - oidc: id-token refreshed with the refresh-token (requires `oidc` feature)
+// - kubelogin workload identity login handled in-process (requires `azure` feature)
 //
 // Note that the visibility must be `pub` for `impl Layer for AuthLayer`, but this is not exported from the crate.
 // It's not accessible from outside and not shown on docs.
@@ -153,6 +179,10 @@ pub enum RefreshableToken {
     File(Arc<RwLock<TokenFile>>),
     #[cfg(feature = "oauth")]
     GcpOauth(Arc<Mutex<oauth::Gcp>>),
+    #[cfg(feature = "oidc")]
+    Oidc(Arc<Mutex<oidc::Oidc>>),
+    #[cfg(feature = "azure")]
+    Azure(Arc<Mutex<azure::WorkloadIdentity>>),
 }
 
 // For use with `AsyncFilterLayer` to add `Authorization` header with a refreshed token.
@@ -201,6 +231,10 @@ impl RefreshableToken {
                         Auth::RefreshableToken(RefreshableToken::File(_)) => unreachable!(),
                         #[cfg(feature = "oauth")]
                         Auth::RefreshableToken(RefreshableToken::GcpOauth(_)) => unreachable!(),
+                        #[cfg(feature = "oidc")]
+                        Auth::RefreshableToken(RefreshableToken::Oidc(_)) => unreachable!(),
+                        #[cfg(feature = "azure")]
+                        Auth::RefreshableToken(RefreshableToken::Azure(_)) => unreachable!(),
                     }
                 }
 
@@ -225,6 +259,20 @@ impl RefreshableToken {
                 let token = (*gcp_oauth).token().await.map_err(Error::OAuth)?;
                 bearer_header(&token.access_token)
             }
+
+            #[cfg(feature = "oidc")]
+            RefreshableToken::Oidc(data) => {
+                let mut oidc = data.lock().await;
+                let token = oidc.id_token().await.map_err(Error::Oidc)?;
+                bearer_header(&token)
+            }
+
+            #[cfg(feature = "azure")]
+            RefreshableToken::Azure(data) => {
+                let mut azure = data.lock().await;
+                let token = azure.token().await.map_err(Error::Azure)?;
+                bearer_header(&token)
+            }
         }
     }
 }
@@ -248,6 +296,13 @@ impl TryFrom<&AuthInfo> for Auth {
                     return Ok(Self::Bearer(SecretString::from(token)));
                 }
 
+                #[cfg(feature = "oidc")]
+                ProviderToken::OidcRefreshable(oidc) => {
+                    return Ok(Self::RefreshableToken(RefreshableToken::Oidc(Arc::new(
+                        Mutex::new(oidc),
+                    ))));
+                }
+
                 ProviderToken::GcpCommand(token, Some(expiry)) => {
                     let mut info = auth_info.clone();
                     let mut provider = provider.clone();
@@ -289,6 +344,15 @@ impl TryFrom<&AuthInfo> for Auth {
         }
 
         if let Some(exec) = &auth_info.exec {
+            // Exchange the federated token directly, so that kubelogin isn't needed in the container
+            #[cfg(feature = "azure")]
+            if azure::WorkloadIdentity::is_workload_identity(exec) {
+                let azure = azure::WorkloadIdentity::from_exec(exec).map_err(Error::Azure)?;
+                return Ok(Self::RefreshableToken(RefreshableToken::Azure(Arc::new(
+                    Mutex::new(azure),
+                ))));
+            }
+
             let creds = auth_exec(exec)?;
             let status = creds.status.ok_or(Error::ExecPluginFailed)?;
             let expiration = status
@@ -312,6 +376,8 @@ impl TryFrom<&AuthInfo> for Auth {
 // We need to differentiate providers because the keys/formats to store token expiration differs.
 enum ProviderToken {
     Oidc(String),
+    #[cfg(feature = "oidc")]
+    OidcRefreshable(oidc::Oidc),
     // "access-token", "expiry" (RFC3339)
     GcpCommand(String, Option<DateTime<Utc>>),
     #[cfg(feature = "oauth")]
@@ -332,6 +398,16 @@ fn token_from_provider(provider: &AuthProviderConfig) -> Result<ProviderToken, E
 }
 
 fn token_from_oidc_provider(provider: &AuthProviderConfig) -> Result<ProviderToken, Error> {
+    // Without a refresh token, the id-token is used as is until it expires
+    #[cfg(feature = "oidc")]
+    {
+        if oidc::Oidc::is_refreshable(&provider.config) {
+            return Ok(ProviderToken::OidcRefreshable(
+                oidc::Oidc::from_config(&provider.config).map_err(Error::Oidc)?,
+            ));
+        }
+    }
+
     match provider.config.get("id-token") {
         Some(id_token) => Ok(ProviderToken::Oidc(id_token.clone())),
         None => Err(Error::AuthExec(
@@ -340,6 +416,10 @@ fn token_from_oidc_provider(provider: &AuthProviderConfig) -> Result<ProviderTok
     }
 }
 
+// The keys client-go reads from the command output when `token-key` and `expiry-key` are not set
+const DEFAULT_GCP_TOKEN_KEY: &str = "{.access_token}";
+const DEFAULT_GCP_EXPIRY_KEY: &str = "{.token_expiry}";
+
 fn token_from_gcp_provider(provider: &AuthProviderConfig) -> Result<ProviderToken, Error> {
     if let Some(id_token) = provider.config.get("id-token") {
         return Ok(ProviderToken::GcpCommand(id_token.clone(), None));
@@ -361,9 +441,9 @@ fn token_from_gcp_provider(provider: &AuthProviderConfig) -> Result<ProviderToke
     if let Some(cmd) = provider.config.get("cmd-path") {
         let params = provider.config.get("cmd-args").cloned().unwrap_or_default();
 
-        // TODO splitting args by space is not safe
+        // Arguments are split on whitespace, like client-go does
         let output = Command::new(cmd)
-            .args(params.trim().split(' '))
+            .args(params.split_whitespace())
             .output()
             .map_err(|e| Error::AuthExec(format!("Executing {:} failed: {:?}", cmd, e)))?;
 
@@ -375,24 +455,38 @@ fn token_from_gcp_provider(provider: &AuthProviderConfig) -> Result<ProviderToke
             });
         }
 
-        if let Some(field) = provider.config.get("token-key") {
-            let json_output: serde_json::Value =
-                serde_json::from_slice(&output.stdout).map_err(Error::ParseTokenKey)?;
-            let token = extract_value(&json_output, field)?;
-            if let Some(field) = provider.config.get("expiry-key") {
-                let expiry = extract_value(&json_output, field)?;
+        // Without explicit keys, the output of `gcloud config config-helper --format=json` is expected
+        let token_key = provider.config.get("token-key");
+        let json_output = match serde_json::from_slice::<serde_json::Value>(&output.stdout) {
+            Ok(json_output) => json_output,
+            Err(err) => {
+                if token_key.is_some() {
+                    return Err(Error::ParseTokenKey(err));
+                }
+                // Plugins printing the bare token are still supported
+                let token = std::str::from_utf8(&output.stdout)
+                    .map_err(|e| Error::AuthExec(format!("Result is not a string {:?} ", e)))?
+                    .trim()
+                    .to_owned();
+                return Ok(ProviderToken::GcpCommand(token, None));
+            }
+        };
+        let token_key = token_key.map_or(DEFAULT_GCP_TOKEN_KEY, String::as_str);
+        let token = extract_value(&json_output, token_key)?;
+        let expiry_key = provider.config.get("expiry-key");
+        match extract_value(
+            &json_output,
+            expiry_key.map_or(DEFAULT_GCP_EXPIRY_KEY, String::as_str),
+        ) {
+            Ok(expiry) => {
                 let expiry = expiry
                     .parse::<DateTime<Utc>>()
                     .map_err(Error::MalformedTokenExpirationDate)?;
                 return Ok(ProviderToken::GcpCommand(token, Some(expiry)));
-            } else {
-                return Ok(ProviderToken::GcpCommand(token, None));
             }
-        } else {
-            let token = std::str::from_utf8(&output.stdout)
-                .map_err(|e| Error::AuthExec(format!("Result is not a string {:?} ", e)))?
-                .to_owned();
-            return Ok(ProviderToken::GcpCommand(token, None));
+            // An explicitly configured expiry must be present
+            Err(err) if expiry_key.is_some() => return Err(err),
+            Err(_) => return Ok(ProviderToken::GcpCommand(token, None)),
         }
     }
 
@@ -446,7 +540,12 @@ pub struct ExecCredential {
 /// ExecCredenitalSpec holds request and runtime specific information provided
 /// by transport.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct ExecCredentialSpec {}
+pub struct ExecCredentialSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interactive: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster: Option<ExecAuthCluster>,
+}
 
 /// ExecCredentialStatus holds credentials for the transport to use.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -460,6 +559,9 @@ pub struct ExecCredentialStatus {
     pub client_key_data: Option<String>,
 }
 
+// The environment variable used to pass an `ExecCredential` with the spec to the plugin
+const KUBERNETES_EXEC_INFO: &str = "KUBERNETES_EXEC_INFO";
+
 fn auth_exec(auth: &ExecConfig) -> Result<ExecCredential, Error> {
     let mut cmd = Command::new(&auth.command);
     if let Some(args) = &auth.args {
@@ -474,6 +576,30 @@ fn auth_exec(auth: &ExecConfig) -> Result<ExecCredential, Error> {
             });
         cmd.envs(envs);
     }
+
+    // Plugins can only prompt when they are handed our stdin and stderr. Detecting whether stdin is a
+    // terminal is not possible on our MSRV, so only plugins that always need it are treated as interactive.
+    let interactive = auth.interactive_mode == Some(ExecInteractiveMode::Always);
+    if interactive {
+        cmd.stdin(Stdio::inherit()).stderr(Stdio::inherit());
+    } else {
+        cmd.stdin(Stdio::null());
+    }
+
+    let exec_info = ExecCredential {
+        kind: Some("ExecCredential".into()),
+        api_version: auth.api_version.clone(),
+        spec: Some(ExecCredentialSpec {
+            interactive: Some(interactive),
+            cluster: auth.cluster.clone(),
+        }),
+        status: None,
+    };
+    cmd.env(
+        KUBERNETES_EXEC_INFO,
+        serde_json::to_string(&exec_info).map_err(Error::AuthExecSerialize)?,
+    );
+
     let out = cmd.output().map_err(Error::AuthExecStart)?;
     if !out.status.success() {
         return Err(Error::AuthExecRun {
@@ -539,6 +665,34 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn gcp_command_default_keys() {
+        let expiry = (Utc::now() + Duration::seconds(60 * 60)).to_rfc3339();
+        let provider = |args: String| AuthProviderConfig {
+            name: "gcp".into(),
+            config: [
+                ("cmd-path".to_owned(), "echo".to_owned()),
+                ("cmd-args".to_owned(), args),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let json = format!(r#"{{"access_token":  "my_token", "token_expiry": "{}"}}"#, expiry);
+        match token_from_gcp_provider(&provider(json)).unwrap() {
+            ProviderToken::GcpCommand(token, Some(exp)) => {
+                assert_eq!(token, "my_token");
+                assert_eq!(exp, expiry.parse::<DateTime<Utc>>().unwrap());
+            }
+            _ => unreachable!(),
+        }
+
+        match token_from_gcp_provider(&provider("bare_token".into())).unwrap() {
+            ProviderToken::GcpCommand(token, None) => assert_eq!(token, "bare_token"),
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn token_file() {
         let file = tempfile::NamedTempFile::new().unwrap();
@@ -548,7 +702,7 @@ mod test {
         assert!(!token_file.is_expiring());
         assert_eq!(token_file.token(), "token1");
         // Doesn't reload unless expiring
-        std::fs::write(file.path(), "token2").unwrap();
+        std::fs::write(file.path(), "token2\n").unwrap();
         assert_eq!(token_file.token(), "token1");
 
         token_file.expires_at = Utc::now();
@@ -0,0 +1,452 @@
+use std::{collections::HashMap, io::Write, path::Path};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::token_request::{https_client, send, Error as TokenRequestError};
+use crate::config::{certs, kubeconfig_paths, load_from_base64_or_file, LoadDataError};
+
+#[derive(Error, Debug)]
+/// Possible errors when refreshing an OIDC id token
+pub enum Error {
+    /// The auth provider config is missing a required key
+    #[error("missing '{0}' in oidc auth provider config")]
+    MissingConfig(&'static str),
+
+    /// Failed to build a request to the identity provider
+    #[error("failed to build request: {0}")]
+    BuildRequest(#[source] http::Error),
+
+    /// Failed to request tokens from the identity provider
+    #[error("failed to request oidc tokens: {0}")]
+    TokenRequest(#[source] TokenRequestError),
+
+    /// The token response did not contain an id token
+    #[error("token response did not contain an id_token")]
+    MissingIdToken,
+
+    /// Failed to load the certificate authority of the identity provider
+    #[error("failed to load idp certificate authority: {0}")]
+    LoadCertificateAuthority(#[source] LoadDataError),
+
+    /// Failed to parse the certificate authority of the identity provider
+    #[error("failed to parse idp certificate authority: {0}")]
+    ParseCertificateAuthority(#[source] pem::PemError),
+}
+
+const ID_TOKEN: &str = "id-token";
+const REFRESH_TOKEN: &str = "refresh-token";
+const ISSUER_URL: &str = "idp-issuer-url";
+const CLIENT_ID: &str = "client-id";
+const CLIENT_SECRET: &str = "client-secret";
+const IDP_CA: &str = "idp-certificate-authority";
+const IDP_CA_DATA: &str = "idp-certificate-authority-data";
+const EXTRA_SCOPES: &str = "extra-scopes";
+
+/// Tokens expiring within this window are refreshed ahead of time
+const EXPIRY_MARGIN_SECS: i64 = 10;
+
+#[derive(Deserialize)]
+struct ProviderMetadata {
+    token_endpoint: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    exp: i64,
+}
+
+// The `oidc` auth provider, as implemented by client-go:
+// - the `id-token` is used as long as it has not expired
+// - otherwise a new one is requested from the issuer using the `refresh-token`
+// - the new tokens are written back to the kubeconfig the provider was loaded from
+// - the issuer is trusted through the `idp-certificate-authority(-data)`, if set
+// - the `extra-scopes` are requested along with `openid`
+pub struct Oidc {
+    id_token: SecretString,
+    expiry: Option<DateTime<Utc>>,
+    refresh_token: SecretString,
+    issuer_url: String,
+    client_id: String,
+    client_secret: Option<SecretString>,
+    root_certs: Option<Vec<Vec<u8>>>,
+    extra_scopes: Vec<String>,
+}
+
+impl std::fmt::Debug for Oidc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Oidc")
+            .field("expiry", &self.expiry)
+            .field("issuer_url", &self.issuer_url)
+            .field("client_id", &self.client_id)
+            .field("extra_scopes", &self.extra_scopes)
+            .finish()
+    }
+}
+
+impl Oidc {
+    /// Whether the provider config carries enough information to refresh the id token
+    pub(crate) fn is_refreshable(config: &HashMap<String, String>) -> bool {
+        config.contains_key(REFRESH_TOKEN) && config.contains_key(ISSUER_URL)
+    }
+
+    pub(crate) fn from_config(config: &HashMap<String, String>) -> Result<Self, Error> {
+        let get = |key: &'static str| config.get(key).cloned().ok_or(Error::MissingConfig(key));
+        // An empty id token is fine, it is simply refreshed before the first request
+        let id_token = config.get(ID_TOKEN).cloned().unwrap_or_default();
+        let root_certs = if config.contains_key(IDP_CA_DATA) || config.contains_key(IDP_CA) {
+            let pem =
+                load_from_base64_or_file(&config.get(IDP_CA_DATA).map(String::as_str), &config.get(IDP_CA))
+                    .map_err(Error::LoadCertificateAuthority)?;
+            Some(certs(&pem).map_err(Error::ParseCertificateAuthority)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            expiry: token_expiry(&id_token),
+            id_token: SecretString::from(id_token),
+            refresh_token: SecretString::from(get(REFRESH_TOKEN)?),
+            issuer_url: get(ISSUER_URL)?,
+            client_id: get(CLIENT_ID)?,
+            client_secret: config.get(CLIENT_SECRET).cloned().map(SecretString::from),
+            root_certs,
+            extra_scopes: config
+                .get(EXTRA_SCOPES)
+                .into_iter()
+                .flat_map(|scopes| scopes.split(','))
+                .map(str::trim)
+                .filter(|scope| !scope.is_empty())
+                .map(String::from)
+                .collect(),
+        })
+    }
+
+    fn is_expiring(&self) -> bool {
+        match self.expiry {
+            Some(expiry) => Utc::now() + Duration::seconds(EXPIRY_MARGIN_SECS) >= expiry,
+            // Tokens without a readable expiry are refreshed, as there is no way to tell if they are valid
+            None => true,
+        }
+    }
+
+    /// Get a valid id token, refreshing it if it has expired
+    pub async fn id_token(&mut self) -> Result<String, Error> {
+        if self.is_expiring() {
+            self.refresh().await?;
+        }
+        Ok(self.id_token.expose_secret().clone())
+    }
+
+    async fn refresh(&mut self) -> Result<(), Error> {
+        let client = https_client(self.root_certs.as_ref()).map_err(Error::TokenRequest)?;
+
+        let discovery_url = format!(
+            "{}/.well-known/openid-configuration",
+            self.issuer_url.trim_end_matches('/')
+        );
+        let req = http::Request::get(discovery_url)
+            .body(hyper::Body::empty())
+            .map_err(Error::BuildRequest)?;
+        let metadata: ProviderMetadata = send(&client, req).await.map_err(Error::TokenRequest)?;
+
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        form.append_pair("grant_type", "refresh_token")
+            .append_pair("refresh_token", self.refresh_token.expose_secret())
+            .append_pair("client_id", &self.client_id);
+        if let Some(secret) = &self.client_secret {
+            form.append_pair("client_secret", secret.expose_secret());
+        }
+        if !self.extra_scopes.is_empty() {
+            let scopes = std::iter::once("openid")
+                .chain(self.extra_scopes.iter().map(String::as_str))
+                .collect::<Vec<_>>();
+            form.append_pair("scope", &scopes.join(" "));
+        }
+        let req = http::Request::post(metadata.token_endpoint)
+            .header(http::header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(hyper::Body::from(form.finish()))
+            .map_err(Error::BuildRequest)?;
+        let tokens: TokenResponse = send(&client, req).await.map_err(Error::TokenRequest)?;
+
+        let id_token = tokens.id_token.ok_or(Error::MissingIdToken)?;
+        let old_refresh_token = self.refresh_token.expose_secret().clone();
+        // Identity providers may rotate the refresh token, but are not required to
+        let refresh_token = tokens.refresh_token.unwrap_or_else(|| old_refresh_token.clone());
+        let (persisted_id_token, persisted_refresh_token) = (id_token.clone(), refresh_token.clone());
+        // Writing the kubeconfig blocks, so keep it off the async runtime
+        let persisted = tokio::task::spawn_blocking(move || {
+            persist(&old_refresh_token, &persisted_id_token, &persisted_refresh_token);
+        });
+        if let Err(err) = persisted.await {
+            tracing::warn!("failed to persist refreshed oidc tokens: {}", err);
+        }
+
+        self.expiry = token_expiry(&id_token);
+        self.id_token = SecretString::from(id_token);
+        self.refresh_token = SecretString::from(refresh_token);
+        Ok(())
+    }
+}
+
+/// Read the expiry from the `exp` claim of a JWT, without verifying it
+fn token_expiry(token: &str) -> Option<DateTime<Utc>> {
+    let payload = token.split('.').nth(1)?;
+    let decoded = base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()?;
+    let claims: Claims = serde_json::from_slice(&decoded).ok()?;
+    Utc.timestamp_opt(claims.exp, 0).single()
+}
+
+/// Write refreshed tokens back to the kubeconfig that holds `old_refresh_token`
+///
+/// This is best effort, failing to persist only means that the next process has to refresh again.
+fn persist(old_refresh_token: &str, id_token: &str, refresh_token: &str) {
+    for path in kubeconfig_paths() {
+        match update_kubeconfig(&path, old_refresh_token, id_token, refresh_token) {
+            Ok(true) => return,
+            Ok(false) => {}
+            Err(err) => {
+                tracing::warn!("failed to persist refreshed oidc tokens to {:?}: {}", path, err);
+                return;
+            }
+        }
+    }
+}
+
+fn update_kubeconfig(
+    path: &Path,
+    old_refresh_token: &str,
+    id_token: &str,
+    refresh_token: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    // Write to the target of symlinks, rather than replacing the link
+    let path = match std::fs::canonicalize(path) {
+        Ok(path) => path,
+        // Missing files in KUBECONFIG are skipped, just like when loading
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err.into()),
+    };
+    let text = std::fs::read_to_string(&path)?;
+    match update_provider_config(&text, old_refresh_token, id_token, refresh_token) {
+        Some(updated) => {
+            write_atomically(&path, updated.as_bytes())?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Replaces the contents of the file at `path` by renaming a new file over it,
+/// so that it is never half-written
+fn write_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+    let permissions = std::fs::metadata(path)?.permissions();
+    let written = (|| {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        // Kubeconfigs hold credentials, so the new file must not be readable by others before it gets
+        // the permissions of the old one
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        std::fs::set_permissions(&tmp_path, permissions)?;
+        std::fs::rename(&tmp_path, path)
+    })();
+    if written.is_err() {
+        std::fs::remove_file(&tmp_path).ok();
+    }
+    written
+}
+
+/// Replaces the tokens of the auth provider config that holds `old_refresh_token`,
+/// returning the updated kubeconfig
+///
+/// Only the lines of the tokens are rewritten, so the rest of the kubeconfig
+/// (including comments and formatting) is left as it is, and the new tokens are written as double-quoted strings.
+/// Configs in flow style (such as JSON kubeconfigs) are not found, and so not updated.
+fn update_provider_config(
+    kubeconfig: &str,
+    old_refresh_token: &str,
+    id_token: &str,
+    refresh_token: &str,
+) -> Option<String> {
+    let mut lines = kubeconfig.split_inclusive('\n').collect::<Vec<_>>();
+    let refresh_line = lines.iter().position(
+        |line| matches!(parse_entry(line), Some((_, REFRESH_TOKEN, value)) if value == old_refresh_token),
+    )?;
+    let (indent, _, _) = parse_entry(lines[refresh_line])?;
+    // The other entries of the provider config are the surrounding lines that are indented at least as much
+    let in_config = |line: &&&str| line.trim().is_empty() || parse_indent(line) >= indent;
+    let first = refresh_line - lines[..refresh_line].iter().rev().take_while(in_config).count();
+    let last = refresh_line + lines[refresh_line + 1..].iter().take_while(in_config).count();
+    let id_line = (first..=last).find(
+        |&i| matches!(parse_entry(lines[i]), Some((entry_indent, ID_TOKEN, _)) if entry_indent == indent),
+    );
+
+    let line_ending = if lines[refresh_line].ends_with("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let entry = |key: &str, value: &str| {
+        // JSON strings are valid double-quoted YAML scalars
+        let value = serde_json::to_string(value).expect("strings always serialize");
+        format!("{}{}: {}{}", " ".repeat(indent), key, value, line_ending)
+    };
+    let refresh_entry = entry(REFRESH_TOKEN, refresh_token);
+    let id_entry = entry(ID_TOKEN, id_token);
+    lines[refresh_line] = &refresh_entry;
+    match id_line {
+        Some(id_line) => lines[id_line] = &id_entry,
+        None => lines.insert(refresh_line, &id_entry),
+    }
+    Some(lines.concat())
+}
+
+/// The number of spaces that `line` is indented by
+fn parse_indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Parses a `key: value` line of a block mapping into its indentation, key and scalar value
+fn parse_entry(line: &str) -> Option<(usize, &str, String)> {
+    let (key, value) = line.trim().split_once(':')?;
+    let value = value.trim();
+    let value = if value.starts_with('"') {
+        serde_json::from_str(value).ok()?
+    } else if let Some(value) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        value.replace("''", "'")
+    } else {
+        // Plain scalars end at a comment
+        value.split(" #").next().unwrap_or_default().to_string()
+    };
+    Some((parse_indent(line), key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jwt(exp: i64) -> String {
+        let payload = base64::encode_config(format!(r#"{{"exp":{}}}"#, exp), base64::URL_SAFE_NO_PAD);
+        format!("e30.{}.c2ln", payload)
+    }
+
+    #[test]
+    fn reads_token_expiry() {
+        assert_eq!(
+            token_expiry(&jwt(1_600_000_000)),
+            Utc.timestamp_opt(1_600_000_000, 0).single()
+        );
+        assert_eq!(token_expiry("not-a-jwt"), None);
+        assert_eq!(token_expiry(""), None);
+    }
+
+    #[test]
+    fn expired_tokens_need_refresh() {
+        let mut config = HashMap::new();
+        config.insert(REFRESH_TOKEN.to_string(), "refresh".to_string());
+        config.insert(ISSUER_URL.to_string(), "https://dex.example.com".to_string());
+        config.insert(CLIENT_ID.to_string(), "kubernetes".to_string());
+        assert!(Oidc::is_refreshable(&config));
+
+        config.insert(ID_TOKEN.to_string(), jwt(Utc::now().timestamp() + 3600));
+        assert!(!Oidc::from_config(&config).unwrap().is_expiring());
+        config.insert(ID_TOKEN.to_string(), jwt(Utc::now().timestamp() - 60));
+        assert!(Oidc::from_config(&config).unwrap().is_expiring());
+
+        config.remove(CLIENT_ID);
+        assert!(matches!(
+            Oidc::from_config(&config),
+            Err(Error::MissingConfig(CLIENT_ID))
+        ));
+    }
+
+    #[test]
+    fn reads_idp_config() {
+        let mut config = HashMap::new();
+        config.insert(REFRESH_TOKEN.to_string(), "refresh".to_string());
+        config.insert(ISSUER_URL.to_string(), "https://dex.example.com".to_string());
+        config.insert(CLIENT_ID.to_string(), "kubernetes".to_string());
+        config.insert(EXTRA_SCOPES.to_string(), "groups, email,".to_string());
+        let oidc = Oidc::from_config(&config).unwrap();
+        assert_eq!(oidc.extra_scopes, vec!["groups", "email"]);
+        assert!(oidc.root_certs.is_none());
+
+        config.insert(IDP_CA_DATA.to_string(), "not base64!".to_string());
+        assert!(matches!(
+            Oidc::from_config(&config),
+            Err(Error::LoadCertificateAuthority(_))
+        ));
+    }
+
+    const KUBECONFIG: &str = r#"users:
+- name: other
+  user:
+    token: abc
+- name: dex
+  user:
+    auth-provider:
+      name: oidc
+      config:
+        # managed by kubectl oidc-login
+        client-id: kubernetes
+        id-token: old-id
+        refresh-token: 'old-refresh'
+
+        idp-issuer-url: https://dex.example.com
+"#;
+
+    #[test]
+    fn updates_matching_user() {
+        assert_eq!(
+            update_provider_config(KUBECONFIG, "unknown", "new-id", "new-refresh"),
+            None
+        );
+        assert_eq!(
+            update_provider_config(KUBECONFIG, "old-refresh", "new-id", "new-refresh").unwrap(),
+            KUBECONFIG
+                .replace("id-token: old-id", r#"id-token: "new-id""#)
+                .replace("refresh-token: 'old-refresh'", r#"refresh-token: "new-refresh""#)
+        );
+
+        let without_id_token = KUBECONFIG.replace("        id-token: old-id\n", "");
+        let updated =
+            update_provider_config(&without_id_token, "old-refresh", "new-id", "new-refresh").unwrap();
+        let config: serde_yaml::Value = serde_yaml::from_str(&updated).unwrap();
+        let provider_config = &config["users"][1]["user"]["auth-provider"]["config"];
+        assert_eq!(provider_config["id-token"].as_str(), Some("new-id"));
+        assert_eq!(provider_config["refresh-token"].as_str(), Some("new-refresh"));
+        assert_eq!(provider_config["client-id"].as_str(), Some("kubernetes"));
+    }
+
+    #[test]
+    fn updates_kubeconfig_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config");
+        std::fs::write(&path, KUBECONFIG).unwrap();
+        assert!(!update_kubeconfig(
+            &dir.path().join("missing"),
+            "old-refresh",
+            "new-id",
+            "new-refresh"
+        )
+        .unwrap());
+        assert!(update_kubeconfig(&path, "old-refresh", "new-id", "new-refresh").unwrap());
+        let updated = std::fs::read_to_string(&path).unwrap();
+        assert!(updated.contains(r#"refresh-token: "new-refresh""#));
+        assert!(updated.contains("# managed by kubectl oidc-login"));
+        // The temporary file was renamed over the kubeconfig
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+//! Requesting tokens from an identity provider, shared by the `oidc` and `azure` auth providers
+use thiserror::Error;
+
+use crate::client::tls;
+
+#[derive(Error, Debug)]
+/// Possible errors when requesting tokens from an identity provider
+pub enum Error {
+    /// Failed to send a request to the identity provider
+    #[error("failed to send request to identity provider: {0}")]
+    Request(#[source] hyper::Error),
+
+    /// The identity provider rejected a request
+    #[error("identity provider responded with {0}: {1}")]
+    Unsuccessful(http::StatusCode, String),
+
+    /// Failed to parse a response from the identity provider
+    #[error("failed to parse identity provider response: {0}")]
+    ParseResponse(#[source] serde_json::Error),
+
+    /// Failed to create OpenSSL HTTPS connector
+    #[cfg(feature = "openssl-tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "openssl-tls")))]
+    #[error("failed to create OpenSSL HTTPS connector: {0}")]
+    OpensslTls(#[source] tls::openssl_tls::Error),
+
+    /// Failed to create native-tls connector
+    #[cfg(feature = "native-tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "native-tls")))]
+    #[error("failed to create native-tls connector: {0}")]
+    NativeTls(#[source] tls::native_tls::Error),
+
+    /// Failed to create rustls client config
+    #[cfg(feature = "rustls-tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rustls-tls")))]
+    #[error("failed to create rustls client config: {0}")]
+    RustlsTls(#[source] tls::rustls_tls::Error),
+}
+
+/// Sends `req`, and parses the JSON body of a successful response
+pub(super) async fn send<C, T>(
+    client: &hyper::Client<C, hyper::Body>,
+    req: http::Request<hyper::Body>,
+) -> Result<T, Error>
+where
+    C: hyper::client::connect::Connect + Clone + Send + Sync + 'static,
+    T: serde::de::DeserializeOwned,
+{
+    let res = client.request(req).await.map_err(Error::Request)?;
+    let status = res.status();
+    let body = hyper::body::to_bytes(res.into_body())
+        .await
+        .map_err(Error::Request)?;
+    if !status.is_success() {
+        return Err(Error::Unsuccessful(
+            status,
+            String::from_utf8_lossy(&body).into_owned(),
+        ));
+    }
+    serde_json::from_slice(&body).map_err(Error::ParseResponse)
+}
+
+/// Creates a client trusting `root_certs`, or the system roots if there are none
+#[cfg(feature = "openssl-tls")]
+pub(super) fn https_client(
+    root_certs: Option<&Vec<Vec<u8>>>,
+) -> Result<hyper::Client<hyper_openssl::HttpsConnector<hyper::client::HttpConnector>>, Error> {
+    let ssl = tls::openssl_tls::ssl_connector_builder(None, root_certs)
+        .map_err(|e| Error::OpensslTls(tls::openssl_tls::Error::CreateSslConnector(e)))?;
+    let mut http = hyper::client::HttpConnector::new();
+    http.enforce_http(false);
+    let https = hyper_openssl::HttpsConnector::with_connector(http, ssl)
+        .map_err(|e| Error::OpensslTls(tls::openssl_tls::Error::CreateHttpsConnector(e)))?;
+    Ok(hyper::Client::builder().build(https))
+}
+
+/// Creates a client trusting `root_certs`, or the system roots if there are none
+#[cfg(all(not(feature = "openssl-tls"), feature = "native-tls"))]
+pub(super) fn https_client(
+    root_certs: Option<&Vec<Vec<u8>>>,
+) -> Result<hyper::Client<hyper_tls::HttpsConnector<hyper::client::HttpConnector>>, Error> {
+    let tls = tls::native_tls::native_tls_connector(None, root_certs, false).map_err(Error::NativeTls)?;
+    let mut http = hyper::client::HttpConnector::new();
+    http.enforce_http(false);
+    let https = hyper_tls::HttpsConnector::from((http, tokio_native_tls::TlsConnector::from(tls)));
+    Ok(hyper::Client::builder().build(https))
+}
+
+/// Creates a client trusting `root_certs`, or the system roots if there are none
+#[cfg(all(
+    not(any(feature = "openssl-tls", feature = "native-tls")),
+    feature = "rustls-tls"
+))]
+pub(super) fn https_client(
+    root_certs: Option<&Vec<Vec<u8>>>,
+) -> Result<hyper::Client<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>>, Error> {
+    let config = tls::rustls_tls::rustls_client_config(None, root_certs.map(Vec::as_slice), false)
+        .map_err(Error::RustlsTls)?;
+    let mut http = hyper::client::HttpConnector::new();
+    http.enforce_http(false);
+    let https = hyper_rustls::HttpsConnector::from((http, std::sync::Arc::new(config)));
+    Ok(hyper::Client::builder().build(https))
+}
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls-tls", feature = "openssl-tls")))]
+compile_error!(
+    "At least one of native-tls or rustls-tls or openssl-tls feature must be enabled to use oidc or azure feature"
+);
@@ -1,13 +1,19 @@
-use bytes::Bytes;
+use bytes::{Buf, Bytes};
 use http::{Request, Response};
-use hyper::{self, client::HttpConnector};
+use hyper::{
+    self,
+    client::{connect::Connect, HttpConnector},
+};
 use hyper_timeout::TimeoutConnector;
 pub use kube_core::response::Status;
-use tower::{util::BoxService, BoxError, Layer, Service, ServiceBuilder};
+use tower::{util::BoxService, BoxError, Layer, Service, ServiceBuilder, ServiceExt};
 use tower_http::{
     classify::ServerErrorsFailureClass, map_response_body::MapResponseBodyLayer, trace::TraceLayer,
 };
 
+use super::proxy::ProxyConnector;
+#[cfg(any(feature = "native-tls", feature = "rustls-tls", feature = "openssl-tls"))]
+use super::tls::IdentityReloadingConnector;
 use crate::{client::ConfigExt, Client, Config, Error, Result};
 
 /// HTTP body of a dynamic backing type.
@@ -19,6 +25,7 @@ pub type DynBody = dyn http_body::Body<Data = Bytes, Error = BoxError> + Send +
 pub struct ClientBuilder<Svc> {
     service: Svc,
     default_ns: String,
+    request_timeout: Option<std::time::Duration>,
 }
 
 impl<Svc> ClientBuilder<Svc> {
@@ -33,6 +40,7 @@ impl<Svc> ClientBuilder<Svc> {
         Self {
             service,
             default_ns: default_namespace.into(),
+            request_timeout: None,
         }
     }
 
@@ -41,23 +49,33 @@ impl<Svc> ClientBuilder<Svc> {
         let Self {
             service: stack,
             default_ns,
+            request_timeout,
         } = self;
         ClientBuilder {
             service: layer.layer(stack),
             default_ns,
+            request_timeout,
         }
     }
 
+    /// Set the timeout for requests whose response is read in full, see [`Config::request_timeout`].
+    #[must_use]
+    pub fn with_request_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
     /// Build a [`Client`] instance with the current [`Service`] stack.
     pub fn build<B>(self) -> Client
     where
         Svc: Service<Request<hyper::Body>, Response = Response<B>> + Send + 'static,
         Svc::Future: Send + 'static,
         Svc::Error: Into<BoxError>,
-        B: http_body::Body<Data = bytes::Bytes> + Send + 'static,
+        B: http_body::Body + Send + 'static,
+        B::Data: Send,
         B::Error: Into<BoxError>,
     {
-        Client::new(self.service, self.default_ns)
+        Client::new(self.service, self.default_ns).with_request_timeout(self.request_timeout)
     }
 }
 
@@ -66,63 +84,154 @@ impl TryFrom<Config> for ClientBuilder<BoxService<Request<hyper::Body>, Response
 
     /// Builds a default [`ClientBuilder`] stack from a given configuration
     fn try_from(config: Config) -> Result<Self> {
-        use std::time::Duration;
-
-        use http::header::HeaderMap;
-        use tracing::Span;
+        let client = Self::default_http_client(&config)?;
+        Self::try_from_service(config, client)
+    }
+}
 
-        let default_ns = config.default_namespace.clone();
+impl ClientBuilder<BoxService<Request<hyper::Body>, Response<Box<DynBody>>, BoxError>> {
+    /// Builds the default HTTP client from a given configuration
+    ///
+    /// The client handles TLS, proxies and timeouts, and is the innermost [`Service`] of the default stack.
+    /// It can be wrapped in custom layers and passed to [`ClientBuilder::try_from_service`].
+    pub fn default_http_client(
+        config: &Config,
+    ) -> Result<hyper::Client<impl Connect + Clone + Send + Sync + 'static, hyper::Body>> {
+        let mut connector = HttpConnector::new();
+        connector.enforce_http(false);
+        // TLS is layered on top of the proxy tunnel, so it is still negotiated with the cluster itself
+        let connector = ProxyConnector::new(connector, config.proxy_url.clone());
 
-        let client: hyper::Client<_, hyper::Body> = {
-            let mut connector = HttpConnector::new();
-            connector.enforce_http(false);
-
-            // Current TLS feature precedence when more than one are set:
-            // 1. openssl-tls
-            // 2. native-tls
-            // 3. rustls-tls
-            // Create a custom client to use something else.
-            // If TLS features are not enabled, http connector will be used.
-            #[cfg(feature = "openssl-tls")]
-            let connector = config.openssl_https_connector_with_connector(connector)?;
-            #[cfg(all(not(feature = "openssl-tls"), feature = "native-tls"))]
-            let connector = hyper_tls::HttpsConnector::from((
-                connector,
+        // Current TLS feature precedence when more than one are set:
+        // 1. openssl-tls
+        // 2. native-tls
+        // 3. rustls-tls
+        // Create a custom client to use something else.
+        // If TLS features are not enabled, http connector will be used.
+        #[cfg(feature = "openssl-tls")]
+        let make_connector =
+            move |config: &Config| config.openssl_https_connector_with_connector(connector.clone());
+        #[cfg(all(not(feature = "openssl-tls"), feature = "native-tls"))]
+        let make_connector = move |config: &Config| -> Result<_> {
+            Ok(hyper_tls::HttpsConnector::from((
+                connector.clone(),
                 tokio_native_tls::TlsConnector::from(config.native_tls_connector()?),
-            ));
-            #[cfg(all(
-                not(any(feature = "openssl-tls", feature = "native-tls")),
-                feature = "rustls-tls"
-            ))]
-            let connector = hyper_rustls::HttpsConnector::from((
-                connector,
+            )))
+        };
+        #[cfg(all(
+            not(any(feature = "openssl-tls", feature = "native-tls")),
+            feature = "rustls-tls"
+        ))]
+        let make_connector = move |config: &Config| -> Result<_> {
+            Ok(hyper_rustls::HttpsConnector::from((
+                connector.clone(),
                 std::sync::Arc::new(config.rustls_client_config()?),
-            ));
+            )))
+        };
+        // Client certificates are re-read when rotated on disk
+        #[cfg(any(feature = "native-tls", feature = "rustls-tls", feature = "openssl-tls"))]
+        let connector = IdentityReloadingConnector::new(config.clone(), make_connector)?;
 
-            let mut connector = TimeoutConnector::new(connector);
+        let mut connector = TimeoutConnector::new(connector);
 
-            // Set the timeout for the client and fallback to default deprecated timeout until it's removed
-            #[allow(deprecated)]
-            {
-                connector.set_connect_timeout(config.connect_timeout.or(config.timeout));
-                connector.set_read_timeout(config.read_timeout.or(config.timeout));
-                connector.set_write_timeout(config.write_timeout);
-            }
+        // Set the timeout for the client and fallback to default deprecated timeout until it's removed
+        #[allow(deprecated)]
+        {
+            connector.set_connect_timeout(config.connect_timeout.or(config.timeout));
+            connector.set_read_timeout(config.read_timeout.or(config.timeout));
+            connector.set_write_timeout(config.write_timeout);
+        }
 
-            hyper::Client::builder().build(connector)
-        };
+        Ok(hyper_builder(config).build(connector))
+    }
+
+    /// Builds a default [`ClientBuilder`] stack from a given configuration and a custom connector
+    ///
+    /// The connector replaces the default one, which handles TLS, proxies and timeouts from the configuration.
+    /// This allows reaching the API server over other transports, such as a [`UnixConnector`](super::UnixConnector),
+    /// while keeping the auth and tracing layers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// use hyper::client::HttpConnector;
+    /// use kube::{client::ClientBuilder, Client, Config};
+    ///
+    /// // Talk to `kubectl proxy` without TLS
+    /// let config = Config::new("http://127.0.0.1:8001".parse()?);
+    /// let client: Client = ClientBuilder::try_from_connector(config, HttpConnector::new())?.build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_connector<C>(config: Config, connector: C) -> Result<Self>
+    where
+        C: Connect + Clone + Send + Sync + 'static,
+    {
+        let client = hyper_builder(&config).build(connector);
+        Self::try_from_service(config, client)
+    }
+
+    /// Builds a default [`ClientBuilder`] stack from a given configuration on top of a custom [`Service`]
+    ///
+    /// The kube layers (base URI, authentication, extra headers, rate limits and tracing) wrap `service`,
+    /// which is usually a [`hyper::Client`] with custom layers of its own, such as retries or recording.
+    /// See [`ClientBuilder::default_http_client`] to start from the default HTTP client.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// use http::{HeaderValue, Request};
+    /// use kube::{client::ClientBuilder, Client, Config};
+    /// use tower::{util::MapRequestLayer, ServiceBuilder};
+    ///
+    /// let config = Config::infer().await?;
+    /// let service = ServiceBuilder::new()
+    ///     .layer(MapRequestLayer::new(|mut req: Request<hyper::Body>| {
+    ///         req.headers_mut().insert("x-team", HeaderValue::from_static("platform"));
+    ///         req
+    ///     }))
+    ///     .service(ClientBuilder::default_http_client(&config)?);
+    /// let client: Client = ClientBuilder::try_from_service(config, service)?.build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_from_service<S, B>(config: Config, service: S) -> Result<Self>
+    where
+        S: Service<Request<hyper::Body>, Response = Response<B>> + Send + 'static,
+        S::Future: Send + 'static,
+        S::Error: Into<BoxError>,
+        B: http_body::Body + Send + 'static,
+        B::Data: Send,
+        B::Error: Into<BoxError>,
+    {
+        use std::time::Duration;
+
+        use http::header::HeaderMap;
+        use tracing::Span;
+
+        let default_ns = config.default_namespace.clone();
+        // Normalize the response body and error, so the layers below don't depend on `service`
+        let service = MapResponseBodyLayer::new(|body: B| {
+            let body =
+                http_body::Body::map_data(Box::pin(body), |mut data| data.copy_to_bytes(data.remaining()));
+            Box::new(http_body::Body::map_err(body, Into::<BoxError>::into)) as Box<DynBody>
+        })
+        .layer(ServiceExt::map_err(service, Into::<BoxError>::into));
 
         let stack = ServiceBuilder::new().layer(config.base_uri_layer()).into_inner();
         #[cfg(feature = "gzip")]
         let stack = ServiceBuilder::new()
             .layer(stack)
-            .layer(tower_http::decompression::DecompressionLayer::new())
+            .layer(super::middleware::DecompressionLayer::new())
             .into_inner();
 
         let service = ServiceBuilder::new()
             .layer(stack)
             .option_layer(config.auth_layer()?)
             .layer(config.extra_headers_layer()?)
+            .option_layer(config.rate_limit_layer())
             .layer(
                 // Attribute names follow [Semantic Conventions].
                 // [Semantic Conventions]: https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/trace/semantic_conventions/http.md
@@ -141,7 +250,7 @@ impl TryFrom<Config> for ClientBuilder<BoxService<Request<hyper::Body>, Response
                     .on_request(|_req: &Request<hyper::Body>, _span: &Span| {
                         tracing::debug!("requesting");
                     })
-                    .on_response(|res: &Response<hyper::Body>, _latency: Duration, span: &Span| {
+                    .on_response(|res: &Response<Box<DynBody>>, _latency: Duration, span: &Span| {
                         let status = res.status();
                         span.record("http.status_code", &status.as_u16());
                         if status.is_client_error() || status.is_server_error() {
@@ -171,7 +280,7 @@ impl TryFrom<Config> for ClientBuilder<BoxService<Request<hyper::Body>, Response
                         }
                     }),
             )
-            .service(client);
+            .service(service);
 
         Ok(Self::new(
             BoxService::new(
@@ -181,6 +290,21 @@ impl TryFrom<Config> for ClientBuilder<BoxService<Request<hyper::Body>, Response
                 .layer(service),
             ),
             default_ns,
-        ))
+        )
+        .with_request_timeout(config.request_timeout))
+    }
+}
+
+// Connection pool and HTTP/2 settings of the HTTP client
+fn hyper_builder(config: &Config) -> hyper::client::Builder {
+    let mut builder = hyper::Client::builder();
+    builder
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .pool_max_idle_per_host(config.pool_max_idle_per_host);
+    if config.http2 {
+        builder
+            .http2_keep_alive_interval(config.http2_keep_alive_interval)
+            .http2_keep_alive_while_idle(true);
     }
+    builder
 }
@@ -8,7 +8,7 @@ use tower::{filter::AsyncFilterLayer, util::Either};
 use super::tls;
 use super::{
     auth::Auth,
-    middleware::{AddAuthorizationLayer, AuthLayer, BaseUriLayer, ExtraHeadersLayer},
+    middleware::{AddAuthorizationLayer, AuthLayer, BaseUriLayer, ExtraHeadersLayer, RateLimitLayer},
 };
 use crate::{Config, Error, Result};
 
@@ -27,6 +27,9 @@ pub trait ConfigExt: private::Sealed {
     /// Layer to add non-authn HTTP headers depending on the config.
     fn extra_headers_layer(&self) -> Result<ExtraHeadersLayer>;
 
+    /// Optional layer to delay requests according to the configured [`RateLimits`](crate::config::RateLimits).
+    fn rate_limit_layer(&self) -> Option<RateLimitLayer>;
+
     /// Create [`hyper_tls::HttpsConnector`] based on config.
     ///
     /// # Example
@@ -121,6 +124,9 @@ pub trait ConfigExt: private::Sealed {
     fn openssl_https_connector(&self) -> Result<hyper_openssl::HttpsConnector<hyper::client::HttpConnector>>;
 
     /// Create [`hyper_openssl::HttpsConnector`] based on config and `connector`.
+    ///
+    /// The `connector` is usually an [`HttpConnector`](hyper::client::HttpConnector),
+    /// or a connector wrapping one.
     /// # Example
     ///
     /// ```rust
@@ -136,10 +142,22 @@ pub trait ConfigExt: private::Sealed {
     /// ```
     #[cfg_attr(docsrs, doc(cfg(feature = "openssl-tls")))]
     #[cfg(feature = "openssl-tls")]
-    fn openssl_https_connector_with_connector(
+    fn openssl_https_connector_with_connector<H>(
         &self,
-        connector: hyper::client::HttpConnector,
-    ) -> Result<hyper_openssl::HttpsConnector<hyper::client::HttpConnector>>;
+        connector: H,
+    ) -> Result<hyper_openssl::HttpsConnector<H>>
+    where
+        H: hyper::service::Service<http::Uri> + Send,
+        H::Response: tokio::io::AsyncRead
+            + tokio::io::AsyncWrite
+            + hyper::client::connect::Connection
+            + Unpin
+            + std::fmt::Debug
+            + Sync
+            + Send
+            + 'static,
+        H::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        H::Future: Unpin + Send + 'static;
 
     /// Create [`openssl::ssl::SslConnectorBuilder`] based on config.
     /// # Example
@@ -163,6 +181,19 @@ pub trait ConfigExt: private::Sealed {
     fn openssl_ssl_connector_builder(&self) -> Result<openssl::ssl::SslConnectorBuilder>;
 }
 
+// Extra keys are percent-encoded, as they can contain characters that are not allowed in header names
+fn encode_extra_key(key: &str) -> String {
+    key.bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"-!#$&'*+.^_`|~".contains(&b) {
+                char::from(b).to_string()
+            } else {
+                format!("%{:02x}", b)
+            }
+        })
+        .collect()
+}
+
 mod private {
     pub trait Sealed {}
     impl Sealed for super::Config {}
@@ -208,11 +239,31 @@ impl ConfigExt for Config {
                 ));
             }
         }
+        if let Some(impersonate_extra) = &self.auth_info.impersonate_extra {
+            for (key, values) in impersonate_extra {
+                let name =
+                    HeaderName::from_bytes(format!("impersonate-extra-{}", encode_extra_key(key)).as_bytes())
+                        .map_err(http::Error::from)
+                        .map_err(Error::HttpError)?;
+                for value in values {
+                    headers.push((
+                        name.clone(),
+                        HeaderValue::from_str(value)
+                            .map_err(http::Error::from)
+                            .map_err(Error::HttpError)?,
+                    ));
+                }
+            }
+        }
         Ok(ExtraHeadersLayer {
             headers: Arc::new(headers),
         })
     }
 
+    fn rate_limit_layer(&self) -> Option<RateLimitLayer> {
+        RateLimitLayer::new(&self.rate_limits)
+    }
+
     #[cfg(feature = "native-tls")]
     fn native_tls_connector(&self) -> Result<tokio_native_tls::native_tls::TlsConnector> {
         tls::native_tls::native_tls_connector(
@@ -233,12 +284,16 @@ impl ConfigExt for Config {
 
     #[cfg(feature = "rustls-tls")]
     fn rustls_client_config(&self) -> Result<rustls::ClientConfig> {
-        tls::rustls_tls::rustls_client_config(
+        let mut config = tls::rustls_tls::rustls_client_config(
             self.identity_pem().as_deref(),
             self.root_cert.as_deref(),
             self.accept_invalid_certs,
         )
-        .map_err(Error::RustlsTls)
+        .map_err(Error::RustlsTls)?;
+        if self.http2 {
+            config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        }
+        Ok(config)
     }
 
     #[cfg(feature = "rustls-tls")]
@@ -251,8 +306,17 @@ impl ConfigExt for Config {
 
     #[cfg(feature = "openssl-tls")]
     fn openssl_ssl_connector_builder(&self) -> Result<openssl::ssl::SslConnectorBuilder> {
-        tls::openssl_tls::ssl_connector_builder(self.identity_pem().as_ref(), self.root_cert.as_ref())
-            .map_err(|e| Error::OpensslTls(tls::openssl_tls::Error::CreateSslConnector(e)))
+        let mut builder =
+            tls::openssl_tls::ssl_connector_builder(self.identity_pem().as_ref(), self.root_cert.as_ref())
+                .map_err(|e| Error::OpensslTls(tls::openssl_tls::Error::CreateSslConnector(e)))?;
+        if self.http2 {
+            builder.set_alpn_protos(b"\x02h2\x08http/1.1").map_err(|e| {
+                Error::OpensslTls(tls::openssl_tls::Error::CreateSslConnector(
+                    tls::openssl_tls::SslConnectorError::SetAlpnProtocols(e),
+                ))
+            })?;
+        }
+        Ok(builder)
     }
 
     #[cfg(feature = "openssl-tls")]
@@ -263,10 +327,23 @@ impl ConfigExt for Config {
     }
 
     #[cfg(feature = "openssl-tls")]
-    fn openssl_https_connector_with_connector(
+    fn openssl_https_connector_with_connector<H>(
         &self,
-        connector: hyper::client::HttpConnector,
-    ) -> Result<hyper_openssl::HttpsConnector<hyper::client::HttpConnector>> {
+        connector: H,
+    ) -> Result<hyper_openssl::HttpsConnector<H>>
+    where
+        H: hyper::service::Service<http::Uri> + Send,
+        H::Response: tokio::io::AsyncRead
+            + tokio::io::AsyncWrite
+            + hyper::client::connect::Connection
+            + Unpin
+            + std::fmt::Debug
+            + Sync
+            + Send
+            + 'static,
+        H::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        H::Future: Unpin + Send + 'static,
+    {
         let mut https =
             hyper_openssl::HttpsConnector::with_connector(connector, self.openssl_ssl_connector_builder()?)
                 .map_err(|e| Error::OpensslTls(tls::openssl_tls::Error::CreateHttpsConnector(e)))?;
@@ -279,3 +356,34 @@ impl ConfigExt for Config {
         Ok(https)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn impersonation_headers() {
+        let config = Config::new("http://localhost".parse().unwrap()).impersonate(
+            "jane",
+            vec!["developers".into(), "admins".into()],
+            [("Scopes.example.com/ns".to_string(), vec!["a".into(), "b".into()])]
+                .into_iter()
+                .collect(),
+        );
+        let headers = config.extra_headers_layer().unwrap().headers;
+        let headers = headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.to_str().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            headers,
+            vec![
+                ("impersonate-user", "jane"),
+                ("impersonate-group", "developers"),
+                ("impersonate-group", "admins"),
+                ("impersonate-extra-scopes.example.com%2fns", "a"),
+                ("impersonate-extra-scopes.example.com%2fns", "b"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Record mutating requests for an audit trail.
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use http::{Method, Request, Response, StatusCode};
+use tower::{BoxError, Layer, Service};
+
+// Namespace subresources, which are not the resources of a namespaced path
+const NAMESPACE_SUBRESOURCES: &[&str] = &["status", "finalize"];
+
+/// Mutating request recorded by [`AuditLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// Verb of the request, such as `create`, `patch` or `deletecollection`
+    pub verb: &'static str,
+    /// API group of the resource, empty for the core group
+    pub group: String,
+    /// API version of the resource
+    pub version: String,
+    /// Plural name of the resource
+    pub resource: String,
+    /// Subresource, such as `status` or `scale`
+    pub subresource: Option<String>,
+    /// Namespace of the object, `None` for cluster-scoped resources
+    pub namespace: Option<String>,
+    /// Name of the object, `None` when creating objects or deleting collections
+    pub name: Option<String>,
+    /// Field manager set on the request
+    pub field_manager: Option<String>,
+    /// Whether the request was a dry run, which changes nothing
+    pub dry_run: bool,
+    /// Status of the response, `None` if the request failed without a response
+    pub status: Option<StatusCode>,
+}
+
+type Sink = dyn Fn(AuditRecord) + Send + Sync;
+
+/// Layer that applies [`Audit`], which passes every mutating request to a sink once it completed.
+///
+/// Reads, watches, and requests outside of the resource APIs (such as `/version`) are not recorded.
+/// The sink is called on the task sending the request, and should hand records off rather than block.
+///
+/// # Example
+///
+/// ```rust
+/// # async fn doc() -> Result<(), Box<dyn std::error::Error>> {
+/// use kube::{
+///     client::{middleware::AuditLayer, ClientBuilder},
+///     Client, Config,
+/// };
+///
+/// let config = Config::infer().await?;
+/// let audit = AuditLayer::new(|record| {
+///     tracing::info!(
+///         verb = record.verb,
+///         resource = %record.resource,
+///         namespace = ?record.namespace,
+///         name = ?record.name,
+///         field_manager = ?record.field_manager,
+///         dry_run = record.dry_run,
+///         status = ?record.status,
+///         "audit"
+///     )
+/// });
+/// let client: Client = ClientBuilder::try_from(config)?.with_layer(&audit).build();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AuditLayer {
+    sink: Arc<Sink>,
+}
+
+impl AuditLayer {
+    /// Create a layer passing records to `sink`.
+    pub fn new<F>(sink: F) -> Self
+    where
+        F: Fn(AuditRecord) + Send + Sync + 'static,
+    {
+        Self { sink: Arc::new(sink) }
+    }
+}
+
+impl<S> Layer<S> for AuditLayer {
+    type Service = Audit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Audit {
+            inner,
+            sink: self.sink.clone(),
+        }
+    }
+}
+
+/// Middleware that records mutating requests, see [`AuditLayer`].
+#[derive(Clone)]
+pub struct Audit<S> {
+    inner: S,
+    sink: Arc<Sink>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for Audit<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError>,
+{
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    type Response = S::Response;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let record = audit_record(&req);
+        let sink = self.sink.clone();
+        let response = self.inner.call(req);
+        Box::pin(async move {
+            let result = response.await.map_err(Into::<BoxError>::into);
+            if let Some(mut record) = record {
+                record.status = result.as_ref().ok().map(Response::status);
+                sink(record);
+            }
+            result
+        })
+    }
+}
+
+// Describe the request if it is mutating a resource
+fn audit_record<B>(req: &Request<B>) -> Option<AuditRecord> {
+    let verb = match *req.method() {
+        Method::POST => "create",
+        Method::PUT => "update",
+        Method::PATCH => "patch",
+        Method::DELETE => "delete",
+        _ => return None,
+    };
+    let segments = req.uri().path().trim_matches('/').split('/').collect::<Vec<_>>();
+    let (group, version, mut parts) = match segments.as_slice() {
+        ["api", version, parts @ ..] => ("", *version, parts),
+        ["apis", group, version, parts @ ..] => (*group, *version, parts),
+        _ => return None,
+    };
+    let mut namespace = None;
+    if let ["namespaces", ns, rest @ ..] = parts {
+        namespace = Some(ns.to_string());
+        if !rest.is_empty() && !NAMESPACE_SUBRESOURCES.contains(&rest[0]) {
+            parts = rest;
+        }
+    }
+    let (resource, name, subresource) = match parts {
+        [resource] => (resource, None, None),
+        [resource, name] => (resource, Some(name.to_string()), None),
+        [resource, name, subresource] => (resource, Some(name.to_string()), Some(subresource.to_string())),
+        _ => return None,
+    };
+    // The namespace is the object itself when it is the resource
+    if *resource == "namespaces" {
+        namespace = None;
+    }
+    let verb = if verb == "delete" && name.is_none() {
+        "deletecollection"
+    } else {
+        verb
+    };
+
+    let mut field_manager = None;
+    let mut dry_run = false;
+    for (key, value) in form_urlencoded::parse(req.uri().query().unwrap_or_default().as_bytes()) {
+        match &*key {
+            "fieldManager" => field_manager = Some(value.into_owned()),
+            "dryRun" => dry_run = value == "All",
+            _ => {}
+        }
+    }
+    Some(AuditRecord {
+        verb,
+        group: group.to_owned(),
+        version: version.to_owned(),
+        resource: resource.to_string(),
+        subresource,
+        namespace,
+        name,
+        field_manager,
+        dry_run,
+        status: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use futures::pin_mut;
+    use hyper::Body;
+    use tokio_test::assert_ready_ok;
+    use tower_test::mock;
+
+    fn record(method: Method, uri: &str) -> Option<AuditRecord> {
+        audit_record(&Request::builder().method(method).uri(uri).body(()).unwrap())
+    }
+
+    #[test]
+    fn describes_mutating_requests() {
+        assert_eq!(
+            record(
+                Method::PATCH,
+                "/apis/apps/v1/namespaces/ns/deployments/web/scale?&fieldManager=my%20controller&dryRun=All"
+            ),
+            Some(AuditRecord {
+                verb: "patch",
+                group: "apps".into(),
+                version: "v1".into(),
+                resource: "deployments".into(),
+                subresource: Some("scale".into()),
+                namespace: Some("ns".into()),
+                name: Some("web".into()),
+                field_manager: Some("my controller".into()),
+                dry_run: true,
+                status: None,
+            })
+        );
+
+        let create = record(Method::POST, "/api/v1/namespaces/ns/pods").unwrap();
+        assert_eq!(
+            (create.verb, create.namespace.as_deref(), create.name),
+            ("create", Some("ns"), None)
+        );
+        let collection = record(Method::DELETE, "/api/v1/namespaces/ns/pods?&labelSelector=app").unwrap();
+        assert_eq!(collection.verb, "deletecollection");
+        let finalize = record(Method::PUT, "/api/v1/namespaces/ns/finalize").unwrap();
+        assert_eq!(
+            (
+                finalize.resource.as_str(),
+                finalize.namespace,
+                finalize.name.as_deref()
+            ),
+            ("namespaces", None, Some("ns"))
+        );
+        let node = record(Method::DELETE, "/api/v1/nodes/node-1").unwrap();
+        assert_eq!((node.namespace, node.name.as_deref()), (None, Some("node-1")));
+    }
+
+    #[test]
+    fn ignores_reads_and_non_resource_paths() {
+        assert_eq!(record(Method::GET, "/api/v1/namespaces/ns/pods/foo"), None);
+        assert_eq!(record(Method::POST, "/version"), None);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn records_response_status() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let layer = AuditLayer::new({
+            let records = records.clone();
+            move |record| records.lock().unwrap().push(record)
+        });
+        let (mut service, handle) = mock::spawn_layer::<Request<Body>, Response<Body>, _>(layer);
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            for status in [200, 409] {
+                let (_, send) = handle.next_request().await.expect("service not called");
+                send.send_response(Response::builder().status(status).body(Body::empty()).unwrap());
+            }
+        });
+
+        assert_ready_ok!(service.poll_ready());
+        let request = Request::get("/api/v1/namespaces/ns/pods/foo")
+            .body(Body::empty())
+            .unwrap();
+        service.call(request).await.unwrap();
+        assert_ready_ok!(service.poll_ready());
+        let request = Request::put("/api/v1/namespaces/ns/pods/foo")
+            .body(Body::empty())
+            .unwrap();
+        service.call(request).await.unwrap();
+        spawned.await.unwrap();
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].verb, "update");
+        assert_eq!(records[0].status, Some(StatusCode::CONFLICT));
+    }
+}
@@ -0,0 +1,107 @@
+//! Request compressed responses and decompress them.
+use std::task::{Context, Poll};
+
+use http::{header::ACCEPT_ENCODING, HeaderValue, Request};
+use tower::{Layer, Service};
+
+/// Layer that applies [`Decompression`], which requests gzip-compressed responses and decompresses them.
+///
+/// Large responses such as lists compress well, and the API server compresses them when asked to.
+/// Streaming requests (watches and followed logs) are sent with `Accept-Encoding: identity`,
+/// so that every event is delivered as soon as it is sent rather than being held back by the decoder.
+#[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+#[derive(Debug, Clone, Default)]
+pub struct DecompressionLayer;
+
+impl DecompressionLayer {
+    /// Create a layer decompressing responses.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for DecompressionLayer {
+    type Service = Decompression<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Decompression {
+            inner: tower_http::decompression::Decompression::new(inner),
+        }
+    }
+}
+
+/// Middleware that decompresses responses of non-streaming requests, see [`DecompressionLayer`].
+#[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+#[derive(Debug, Clone)]
+pub struct Decompression<S> {
+    inner: tower_http::decompression::Decompression<S>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for Decompression<S>
+where
+    tower_http::decompression::Decompression<S>: Service<Request<ReqBody>>,
+{
+    type Error = <tower_http::decompression::Decompression<S> as Service<Request<ReqBody>>>::Error;
+    type Future = <tower_http::decompression::Decompression<S> as Service<Request<ReqBody>>>::Future;
+    type Response = <tower_http::decompression::Decompression<S> as Service<Request<ReqBody>>>::Response;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        // An explicit encoding is kept as is by the inner middleware
+        if is_streaming(&req) && !req.headers().contains_key(ACCEPT_ENCODING) {
+            req.headers_mut()
+                .insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+        }
+        self.inner.call(req)
+    }
+}
+
+// Watches and followed logs, whose responses are read as they arrive
+fn is_streaming<B>(req: &Request<B>) -> bool {
+    req.uri().query().map_or(false, |query| {
+        query
+            .split('&')
+            .any(|pair| matches!(pair, "watch=true" | "watch=1" | "follow=true" | "follow=1"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::pin_mut;
+    use http::Response;
+    use hyper::Body;
+    use tokio_test::assert_ready_ok;
+    use tower_test::mock;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn streaming_requests_are_not_compressed() {
+        let (mut service, handle) =
+            mock::spawn_layer::<Request<Body>, Response<Body>, _>(DecompressionLayer::new());
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            for expected in ["gzip", "identity", "identity"] {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.headers().get(ACCEPT_ENCODING).unwrap(), expected);
+                send.send_response(Response::builder().body(Body::empty()).unwrap());
+            }
+        });
+
+        for uri in [
+            "/api/v1/pods?limit=500",
+            "/api/v1/pods?&watch=true&resourceVersion=0",
+            "/api/v1/namespaces/default/pods/foo/log?&follow=true",
+        ] {
+            assert_ready_ok!(service.poll_ready());
+            service
+                .call(Request::get(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+        }
+        spawned.await.unwrap();
+    }
+}
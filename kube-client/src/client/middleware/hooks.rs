@@ -0,0 +1,231 @@
+//! Call user-registered hooks around every request.
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::future::BoxFuture;
+use http::{Method, Request, Response, StatusCode};
+use tower::{BoxError, Layer, Service};
+
+/// Description of a request passed to the hooks of [`HooksLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    /// HTTP method of the request
+    pub method: Method,
+    /// Path of the request URL, without the query
+    pub path: String,
+    /// Name of the operation, such as `list` or `patch`, when made through [`Api`](crate::Api)
+    pub name: Option<&'static str>,
+}
+
+type OnRequest = dyn Fn(&RequestInfo) + Send + Sync;
+type OnResponse = dyn Fn(&RequestInfo, StatusCode, Duration) + Send + Sync;
+type OnError = dyn Fn(&RequestInfo, &(dyn std::error::Error + 'static), Duration) + Send + Sync;
+
+#[derive(Clone, Default)]
+struct Callbacks {
+    on_request: Vec<Arc<OnRequest>>,
+    on_response: Vec<Arc<OnResponse>>,
+    on_error: Vec<Arc<OnError>>,
+}
+
+/// Layer that applies [`Hooks`], which calls registered hooks for every request.
+///
+/// This allows plugging metrics or other telemetry into the client without replacing its stack.
+/// Latencies are measured until the response headers are received, and do not include reading the body.
+///
+/// # Example
+///
+/// ```rust
+/// # async fn doc() -> Result<(), Box<dyn std::error::Error>> {
+/// use kube::{
+///     client::{middleware::HooksLayer, ClientBuilder},
+///     Client, Config,
+/// };
+///
+/// let config = Config::infer().await?;
+/// let hooks = HooksLayer::new()
+///     .on_response(|req, status, latency| {
+///         println!("{} {} -> {} in {:?}", req.method, req.path, status, latency);
+///     })
+///     .on_error(|req, err, _latency| eprintln!("{} {} failed: {}", req.method, req.path, err));
+/// let client: Client = ClientBuilder::try_from(config)?.with_layer(&hooks).build();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct HooksLayer {
+    callbacks: Arc<Callbacks>,
+}
+
+impl HooksLayer {
+    /// Create a layer without any hooks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a hook called before each request is sent.
+    #[must_use]
+    pub fn on_request<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RequestInfo) + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.callbacks).on_request.push(Arc::new(hook));
+        self
+    }
+
+    /// Register a hook called with the status and latency of each response, including error statuses.
+    #[must_use]
+    pub fn on_response<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RequestInfo, StatusCode, Duration) + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.callbacks)
+            .on_response
+            .push(Arc::new(hook));
+        self
+    }
+
+    /// Register a hook called when a request fails without a response.
+    #[must_use]
+    pub fn on_error<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RequestInfo, &(dyn std::error::Error + 'static), Duration) + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.callbacks).on_error.push(Arc::new(hook));
+        self
+    }
+}
+
+impl<S> Layer<S> for HooksLayer {
+    type Service = Hooks<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Hooks {
+            inner,
+            callbacks: self.callbacks.clone(),
+        }
+    }
+}
+
+/// Middleware that calls the hooks registered on [`HooksLayer`].
+#[derive(Clone)]
+pub struct Hooks<S> {
+    inner: S,
+    callbacks: Arc<Callbacks>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for Hooks<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError>,
+{
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    type Response = S::Response;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let info = RequestInfo {
+            method: req.method().clone(),
+            path: req.uri().path().to_owned(),
+            name: req.extensions().get::<&'static str>().copied(),
+        };
+        for hook in &self.callbacks.on_request {
+            hook(&info);
+        }
+
+        let callbacks = self.callbacks.clone();
+        let start = Instant::now();
+        let response = self.inner.call(req);
+        Box::pin(async move {
+            let result = response.await.map_err(Into::<BoxError>::into);
+            let latency = start.elapsed();
+            match &result {
+                Ok(res) => {
+                    for hook in &callbacks.on_response {
+                        hook(&info, res.status(), latency);
+                    }
+                }
+                Err(err) => {
+                    for hook in &callbacks.on_error {
+                        hook(&info, &**err, latency);
+                    }
+                }
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use futures::pin_mut;
+    use hyper::Body;
+    use tokio_test::assert_ready_ok;
+    use tower_test::mock;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn calls_hooks() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let layer = HooksLayer::new()
+            .on_request({
+                let calls = calls.clone();
+                move |req| {
+                    calls
+                        .lock()
+                        .unwrap()
+                        .push(format!("request {} {}", req.method, req.path))
+                }
+            })
+            .on_response({
+                let calls = calls.clone();
+                move |req, status, _| {
+                    calls
+                        .lock()
+                        .unwrap()
+                        .push(format!("response {:?} {}", req.name, status.as_u16()))
+                }
+            })
+            .on_error({
+                let calls = calls.clone();
+                move |_, err, _| calls.lock().unwrap().push(format!("error {}", err))
+            });
+        let (mut service, handle) = mock::spawn_layer::<Request<Body>, Response<Body>, _>(layer);
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(Response::builder().status(404).body(Body::empty()).unwrap());
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_error("connection reset");
+        });
+
+        assert_ready_ok!(service.poll_ready());
+        let mut request = Request::get("/api/v1/pods?limit=1").body(Body::empty()).unwrap();
+        request.extensions_mut().insert("list");
+        service.call(request).await.unwrap();
+        assert_ready_ok!(service.poll_ready());
+        let request = Request::delete("/api/v1/namespaces/default/pods/foo")
+            .body(Body::empty())
+            .unwrap();
+        service.call(request).await.unwrap_err();
+        spawned.await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec![
+            "request GET /api/v1/pods",
+            "response Some(\"list\") 404",
+            "request DELETE /api/v1/namespaces/default/pods/foo",
+            "error connection reset",
+        ]);
+    }
+}
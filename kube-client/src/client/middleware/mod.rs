@@ -1,12 +1,23 @@
-//! Middleware types returned from `ConfigExt` methods.
+//! Middleware types returned from `ConfigExt` methods, and optional layers for custom stacks.
 use tower::{filter::AsyncFilterLayer, util::Either, Layer};
 pub(crate) use tower_http::auth::AddAuthorizationLayer;
 
+mod audit;
 mod base_uri;
+#[cfg(feature = "gzip")] mod decompression;
 mod extra_headers;
+mod hooks;
+mod rate_limit;
+mod retry;
 
+pub use audit::{Audit, AuditLayer, AuditRecord};
 pub use base_uri::{BaseUri, BaseUriLayer};
+#[cfg(feature = "gzip")]
+pub use decompression::{Decompression, DecompressionLayer};
 pub use extra_headers::{ExtraHeaders, ExtraHeadersLayer};
+pub use hooks::{Hooks, HooksLayer, RequestInfo};
+pub use rate_limit::{RateLimitLayer, RateLimiter};
+pub use retry::{Retry, RetryLayer, RetryPolicy};
 
 use super::auth::RefreshableToken;
 /// Layer to set up `Authorization` header depending on the config.
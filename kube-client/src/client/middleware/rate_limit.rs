@@ -0,0 +1,194 @@
+//! Delay requests to stay within client-side rate limits.
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::future::BoxFuture;
+use http::{Method, Request};
+use tower::{
+    filter::{AsyncFilter, AsyncFilterLayer, AsyncPredicate},
+    BoxError, Layer,
+};
+
+use crate::config::{RateLimit, RateLimits};
+
+/// Layer that delays requests until the configured [`RateLimits`] allow them.
+#[derive(Clone)]
+pub struct RateLimitLayer(AsyncFilterLayer<RateLimiter>);
+
+impl RateLimitLayer {
+    /// Create a layer enforcing `limits`, returns `None` when no limits are set.
+    pub fn new(limits: &RateLimits) -> Option<Self> {
+        let limiter = RateLimiter {
+            default: limits.default.and_then(TokenBucket::shared),
+            read: limits.read.and_then(TokenBucket::shared),
+            write: limits.write.and_then(TokenBucket::shared),
+        };
+        if limiter.default.is_none() && limiter.read.is_none() && limiter.write.is_none() {
+            return None;
+        }
+        Some(Self(AsyncFilterLayer::new(limiter)))
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = AsyncFilter<S, RateLimiter>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        self.0.layer(inner)
+    }
+}
+
+/// Predicate used by [`RateLimitLayer`], waits for a token from the bucket matching the request.
+///
+/// Clones share the same buckets.
+#[derive(Clone)]
+pub struct RateLimiter {
+    default: Option<Arc<Mutex<TokenBucket>>>,
+    read: Option<Arc<Mutex<TokenBucket>>>,
+    write: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl<B> AsyncPredicate<Request<B>> for RateLimiter
+where
+    B: Send + 'static,
+{
+    type Future = BoxFuture<'static, Result<Request<B>, BoxError>>;
+    type Request = Request<B>;
+
+    fn check(&mut self, request: Self::Request) -> Self::Future {
+        let specific = if matches!(*request.method(), Method::GET | Method::HEAD) {
+            &self.read
+        } else {
+            &self.write
+        };
+        let delay = specific
+            .as_ref()
+            .or(self.default.as_ref())
+            .map(|bucket| {
+                bucket
+                    .lock()
+                    .expect("rate limit lock poisoned")
+                    .reserve(Instant::now())
+            })
+            .unwrap_or_default();
+        Box::pin(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            Ok(request)
+        })
+    }
+}
+
+// Longest delay handed out, so that tiny rates cannot overflow a `Duration`
+const MAX_DELAY_SECS: f64 = u32::MAX as f64;
+
+// Token bucket that hands out reservations, so waiting requests are served in order.
+struct TokenBucket {
+    qps: f64,
+    burst: f64,
+    // Negative when requests are waiting for tokens that have not been refilled yet
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit, now: Instant) -> Option<Self> {
+        // A non-positive rate would never refill, treat it as unlimited instead of stalling forever.
+        // An infinite rate is unlimited as well.
+        if !limit.qps.is_finite() || limit.qps <= 0.0 {
+            return None;
+        }
+        let burst = f64::from(limit.burst.max(1));
+        Some(Self {
+            qps: f64::from(limit.qps),
+            burst,
+            tokens: burst,
+            updated: now,
+        })
+    }
+
+    fn shared(limit: RateLimit) -> Option<Arc<Mutex<Self>>> {
+        Self::new(limit, Instant::now()).map(|bucket| Arc::new(Mutex::new(bucket)))
+    }
+
+    /// Take a token, returning how long to wait until it is available
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.qps).min(self.burst) - 1.0;
+        self.updated = now;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((-self.tokens / self.qps).min(MAX_DELAY_SECS))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_burst_then_spaces_requests() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimit::new(2.0, 3), start).unwrap();
+        for _ in 0..3 {
+            assert_eq!(bucket.reserve(start), Duration::ZERO);
+        }
+        assert_eq!(bucket.reserve(start), Duration::from_millis(500));
+        assert_eq!(bucket.reserve(start), Duration::from_secs(1));
+        // Refilled tokens pay off the queued reservations first
+        assert_eq!(
+            bucket.reserve(start + Duration::from_secs(1)),
+            Duration::from_millis(500)
+        );
+        assert_eq!(bucket.reserve(start + Duration::from_secs(10)), Duration::ZERO);
+    }
+
+    #[test]
+    fn non_positive_qps_is_unlimited() {
+        assert!(TokenBucket::new(RateLimit::new(0.0, 10), Instant::now()).is_none());
+        assert!(TokenBucket::new(RateLimit::new(-1.0, 10), Instant::now()).is_none());
+        assert!(TokenBucket::new(RateLimit::new(f32::NAN, 10), Instant::now()).is_none());
+        assert!(TokenBucket::new(RateLimit::new(f32::INFINITY, 10), Instant::now()).is_none());
+        assert!(RateLimitLayer::new(&RateLimits {
+            default: Some(RateLimit::new(0.0, 10)),
+            ..RateLimits::default()
+        })
+        .is_none());
+    }
+
+    #[test]
+    fn tiny_qps_does_not_overflow() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimit::new(f32::MIN_POSITIVE, 1), start).unwrap();
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::from_secs(u64::from(u32::MAX)));
+    }
+
+    #[tokio::test]
+    async fn reads_and_writes_use_separate_buckets() {
+        let mut limiter = RateLimiter {
+            default: None,
+            read: TokenBucket::shared(RateLimit::new(1.0, 1)),
+            write: None,
+        };
+        let get = || Request::get("/").body(()).unwrap();
+        let post = || Request::post("/").body(()).unwrap();
+
+        limiter.check(get()).await.unwrap();
+        // The read bucket is empty, but writes are not limited
+        limiter.check(post()).await.unwrap();
+        let delay = limiter
+            .read
+            .as_ref()
+            .unwrap()
+            .lock()
+            .unwrap()
+            .reserve(Instant::now());
+        assert!(delay > Duration::from_millis(900));
+    }
+}
@@ -0,0 +1,331 @@
+//! Retry idempotent requests that were throttled or failed to connect.
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use http::{header::RETRY_AFTER, request::Parts, Method, Request, Response, StatusCode};
+use hyper::Body;
+use tower::{retry::budget::Budget, BoxError, Layer, Service, ServiceExt};
+
+// Headers sent by API Priority and Fairness when a request is rejected
+const FLOW_SCHEMA_HEADER: &str = "x-kubernetes-pf-flowschema-uid";
+const PRIORITY_LEVEL_HEADER: &str = "x-kubernetes-pf-prioritylevel-uid";
+
+/// Settings for [`RetryLayer`].
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of retries of a single request.
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for every following retry.
+    pub initial_backoff: Duration,
+    /// Upper bound on the backoff, also applied to delays requested with `Retry-After`.
+    pub max_backoff: Duration,
+    /// Number of retries per second that are always allowed by the budget.
+    pub min_retries_per_sec: u32,
+    /// Fraction of requests that may additionally be retried, `0.2` allows one retry for every five requests.
+    pub retry_ratio: f32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            min_retries_per_sec: 10,
+            retry_ratio: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, retries: u32) -> Duration {
+        self.initial_backoff
+            .checked_mul(1 << retries.min(31))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    // Delay before retrying a response, or `None` if it should be returned as is
+    fn response_delay<B>(&self, response: &Response<B>, retries: u32) -> Option<Duration> {
+        if !matches!(
+            response.status(),
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        ) {
+            return None;
+        }
+        let headers = response.headers();
+        if let Some(flow_schema) = headers.get(FLOW_SCHEMA_HEADER) {
+            tracing::debug!(
+                ?flow_schema,
+                priority_level = ?headers.get(PRIORITY_LEVEL_HEADER),
+                "request was throttled by API Priority and Fairness"
+            );
+        }
+        let retry_after = headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(|secs| Duration::from_secs(secs).min(self.max_backoff));
+        Some(retry_after.unwrap_or_else(|| self.backoff(retries)))
+    }
+}
+
+/// Layer that applies [`Retry`], which retries idempotent requests on `429 Too Many Requests`,
+/// `503 Service Unavailable`, and connection failures.
+///
+/// The `Retry-After` header is honored when present, otherwise retries back off exponentially.
+/// Retries are limited by a budget shared by all clones of the layer, so that clients back off
+/// as a whole when the API server is under pressure instead of multiplying its load.
+///
+/// It should wrap the HTTP client directly, see [`ClientBuilder::try_from_service`](crate::client::ClientBuilder::try_from_service).
+///
+/// # Example
+///
+/// ```rust
+/// # async fn doc() -> Result<(), Box<dyn std::error::Error>> {
+/// use kube::{
+///     client::{middleware::RetryLayer, ClientBuilder},
+///     Client, Config,
+/// };
+/// use tower::Layer;
+///
+/// let config = Config::infer().await?;
+/// let service = RetryLayer::default().layer(ClientBuilder::default_http_client(&config)?);
+/// let client: Client = ClientBuilder::try_from_service(config, service)?.build();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RetryLayer {
+    policy: Arc<RetryPolicy>,
+    budget: Arc<Budget>,
+}
+
+impl RetryLayer {
+    /// Create a layer retrying according to `policy`.
+    pub fn new(policy: RetryPolicy) -> Self {
+        let budget = Budget::new(
+            Duration::from_secs(10),
+            policy.min_retries_per_sec,
+            policy.retry_ratio,
+        );
+        Self {
+            policy: Arc::new(policy),
+            budget: Arc::new(budget),
+        }
+    }
+}
+
+impl Default for RetryLayer {
+    fn default() -> Self {
+        Self::new(RetryPolicy::default())
+    }
+}
+
+impl<S> Layer<S> for RetryLayer {
+    type Service = Retry<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Retry {
+            inner,
+            policy: self.policy.clone(),
+            budget: self.budget.clone(),
+        }
+    }
+}
+
+/// Middleware that retries idempotent requests, see [`RetryLayer`].
+///
+/// Request bodies are buffered so they can be sent again.
+/// Only the `&'static str` request name is kept from the extensions of retried requests.
+#[derive(Clone)]
+pub struct Retry<S> {
+    inner: S,
+    policy: Arc<RetryPolicy>,
+    budget: Arc<Budget>,
+}
+
+impl<S, B> Service<Request<Body>> for Retry<S>
+where
+    S: Service<Request<Body>, Response = Response<B>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError>,
+    B: Send + 'static,
+{
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Response<B>, BoxError>>;
+    type Response = Response<B>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // The clone may not be ready, so keep the service that `poll_ready` was called on
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        if !is_idempotent(request.method()) {
+            let response = inner.call(request);
+            return Box::pin(async move { response.await.map_err(Into::into) });
+        }
+
+        let policy = self.policy.clone();
+        let budget = self.budget.clone();
+        budget.deposit();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            let mut retries = 0;
+            loop {
+                let request = copy_request(&parts, Body::from(body.clone()));
+                let result = ServiceExt::<Request<Body>>::ready(&mut inner)
+                    .await
+                    .map_err(Into::<BoxError>::into)?
+                    .call(request)
+                    .await
+                    .map_err(Into::<BoxError>::into);
+                let delay = match &result {
+                    Ok(response) => policy.response_delay(response, retries),
+                    Err(err) if is_transient(err) => Some(policy.backoff(retries)),
+                    Err(_) => None,
+                };
+                match delay {
+                    Some(delay) if retries < policy.max_retries && budget.withdraw().is_ok() => {
+                        tracing::debug!(?delay, retries, "retrying request");
+                        // Release the connection of a throttled response while waiting
+                        drop(result);
+                        tokio::time::sleep(delay).await;
+                        retries += 1;
+                    }
+                    _ => return result,
+                }
+            }
+        })
+    }
+}
+
+// Methods that can safely be sent more than once
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+    )
+}
+
+// Errors where the request never reached the API server, or the connection was lost before the response
+fn is_transient(err: &BoxError) -> bool {
+    err.downcast_ref::<hyper::Error>()
+        .map_or(false, |err| err.is_connect() || err.is_incomplete_message())
+}
+
+fn copy_request(parts: &Parts, body: Body) -> Request<Body> {
+    let mut request = Request::new(body);
+    *request.method_mut() = parts.method.clone();
+    *request.uri_mut() = parts.uri.clone();
+    *request.version_mut() = parts.version;
+    *request.headers_mut() = parts.headers.clone();
+    if let Some(name) = parts.extensions.get::<&'static str>() {
+        request.extensions_mut().insert(*name);
+    }
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::pin_mut;
+    use tokio_test::assert_ready_ok;
+    use tower_test::mock;
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(10), Duration::from_secs(30));
+        assert_eq!(policy.backoff(40), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn honors_retry_after() {
+        let policy = RetryPolicy::default();
+        let throttled = |retry_after: &str| {
+            Response::builder()
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .header(RETRY_AFTER, retry_after)
+                .body(())
+                .unwrap()
+        };
+        assert_eq!(
+            policy.response_delay(&throttled("3"), 0),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            policy.response_delay(&throttled("3600"), 0),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            policy.response_delay(&throttled("soon"), 1),
+            Some(Duration::from_secs(1))
+        );
+        let conflict = Response::builder().status(StatusCode::CONFLICT).body(()).unwrap();
+        assert_eq!(policy.response_delay(&conflict, 0), None);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn retries_throttled_reads() {
+        let (mut service, handle) =
+            mock::spawn_layer::<Request<Body>, Response<Body>, _>(RetryLayer::default());
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(
+                Response::builder()
+                    .status(StatusCode::TOO_MANY_REQUESTS)
+                    .header(RETRY_AFTER, "0")
+                    .body(Body::empty())
+                    .unwrap(),
+            );
+            let (request, send) = handle.next_request().await.expect("request not retried");
+            assert_eq!(request.uri(), "/api/v1/pods");
+            assert_eq!(request.extensions().get::<&'static str>(), Some(&"list"));
+            send.send_response(Response::builder().body(Body::empty()).unwrap());
+        });
+
+        assert_ready_ok!(service.poll_ready());
+        let mut request = Request::get("/api/v1/pods").body(Body::empty()).unwrap();
+        request.extensions_mut().insert("list");
+        let response = service.call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn does_not_retry_writes() {
+        let (mut service, handle) =
+            mock::spawn_layer::<Request<Body>, Response<Body>, _>(RetryLayer::default());
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(
+                Response::builder()
+                    .status(StatusCode::SERVICE_UNAVAILABLE)
+                    .body(Body::empty())
+                    .unwrap(),
+            );
+        });
+
+        assert_ready_ok!(service.poll_ready());
+        let request = Request::post("/api/v1/namespaces/default/pods")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = service.call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        spawned.await.unwrap();
+    }
+}
@@ -7,15 +7,16 @@
 //!
 //! The [`Client`] can also be used with [`Discovery`](crate::Discovery) to dynamically
 //! retrieve the resources served by the kubernetes API.
-use bytes::Bytes;
+use bytes::{Buf, Bytes};
 use either::{Either, Left, Right};
-use futures::{self, Stream, StreamExt, TryStream, TryStreamExt};
+use futures::{self, AsyncBufRead, Stream, StreamExt, TryStream, TryStreamExt};
 use http::{self, Request, Response, StatusCode};
 use hyper::Body;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1 as k8s_meta_v1;
 pub use kube_core::response::Status;
 use serde::de::DeserializeOwned;
 use serde_json::{self, Value};
+use std::time::Duration;
 #[cfg(feature = "ws")]
 use tokio_tungstenite::{tungstenite as ws, WebSocketStream};
 use tokio_util::{
@@ -36,6 +37,8 @@ mod config_ext;
 pub use auth::Error as AuthError;
 pub use config_ext::ConfigExt;
 pub mod middleware;
+mod proxy;
+pub use proxy::Error as ProxyError;
 #[cfg(any(feature = "native-tls", feature = "rustls-tls", feature = "openssl-tls"))]
 mod tls;
 
@@ -43,15 +46,29 @@ mod tls;
 #[cfg(feature = "openssl-tls")]
 pub use tls::openssl_tls::Error as OpensslTlsError;
 #[cfg(feature = "rustls-tls")] pub use tls::rustls_tls::Error as RustlsTlsError;
+#[cfg(unix)] mod unix;
 #[cfg(feature = "ws")] mod upgrade;
 
+#[cfg(feature = "azure")]
+#[cfg_attr(docsrs, doc(cfg(feature = "azure")))]
+pub use auth::AzureError;
+
 #[cfg(feature = "oauth")]
 #[cfg_attr(docsrs, doc(cfg(feature = "oauth")))]
 pub use auth::OAuthError;
 
+#[cfg(feature = "oidc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "oidc")))]
+pub use auth::OidcError;
+
+#[cfg(any(feature = "oidc", feature = "azure"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "oidc", feature = "azure"))))]
+pub use auth::TokenRequestError;
+
 #[cfg(feature = "ws")] pub use upgrade::UpgradeConnectionError;
 
 pub use builder::{ClientBuilder, DynBody};
+#[cfg(unix)] pub use unix::{UnixConnector, UnixStream};
 
 /// Client for connecting with a Kubernetes cluster.
 ///
@@ -66,8 +83,31 @@ pub struct Client {
     // - `BoxService` for dynamic response future type
     inner: Buffer<BoxService<Request<Body>, Response<Body>, BoxError>, Request<Body>>,
     default_ns: String,
+    request_timeout: Option<Duration>,
 }
 
+/// Request extension overriding the [`request_timeout`](crate::Config::request_timeout) of a single request.
+///
+/// It applies to requests whose response is read in full, such as [`Client::request`].
+///
+/// # Example
+///
+/// ```rust
+/// # async fn doc(client: kube::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+///
+/// use k8s_openapi::api::core::v1::Pod;
+/// use kube::{api::ListParams, client::RequestTimeout, core::{ObjectList, Request}};
+///
+/// let mut request = Request::new("/api/v1/pods").list(&ListParams::default())?;
+/// request.extensions_mut().insert(RequestTimeout(Duration::from_secs(10)));
+/// let pods: ObjectList<Pod> = client.request(request).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestTimeout(pub Duration);
+
 impl Client {
     /// Create a [`Client`] using a custom `Service` stack.
     ///
@@ -80,6 +120,9 @@ impl Client {
     /// To create with the default stack with an inferred [`Config`], use
     /// [`Client::try_default`].
     ///
+    /// To create with the default stack over a custom connector, such as a
+    /// [`UnixConnector`], use [`ClientBuilder::try_from_connector`].
+    ///
     /// # Example
     ///
     /// ```rust
@@ -101,20 +144,33 @@ impl Client {
         S: Service<Request<Body>, Response = Response<B>> + Send + 'static,
         S::Future: Send + 'static,
         S::Error: Into<BoxError>,
-        B: http_body::Body<Data = bytes::Bytes> + Send + 'static,
+        B: http_body::Body + Send + 'static,
+        B::Data: Send,
         B::Error: Into<BoxError>,
         T: Into<String>,
     {
         // Transform response body to `hyper::Body` and use type erased error to avoid type parameters.
-        let service = MapResponseBodyLayer::new(|b: B| Body::wrap_stream(b.into_stream()))
+        let service = MapResponseBodyLayer::new(|b: B| {
+            Body::wrap_stream(b.into_stream().map_ok(|mut data| data.copy_to_bytes(data.remaining())))
+        })
             .layer(service)
             .map_err(|e| e.into());
         Self {
             inner: Buffer::new(BoxService::new(service), 1024),
             default_ns: default_namespace.into(),
+            request_timeout: None,
         }
     }
 
+    /// Set the timeout for requests whose response is read in full, see [`Config::request_timeout`].
+    ///
+    /// This allows using a different timeout for the [`Api`](crate::Api)s created from this client.
+    #[must_use]
+    pub fn with_request_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
     /// Create and initialize a [`Client`] using the inferred configuration.
     ///
     /// Will use [`Config::infer`] which attempts to load the local kubec-config first,
@@ -221,30 +277,91 @@ impl Client {
 
     /// Perform a raw HTTP request against the API and get back the response
     /// as a string
+    ///
+    /// Fails with [`Error::RequestTimeout`] if the response is not read within the request timeout.
     pub async fn request_text(&self, request: Request<Vec<u8>>) -> Result<String> {
-        let res = self.send(request.map(Body::from)).await?;
-        let status = res.status();
-        // trace!("Status = {:?} for {}", status, res.url());
-        let body_bytes = hyper::body::to_bytes(res.into_body())
-            .await
-            .map_err(Error::HyperError)?;
+        let timeout = request
+            .extensions()
+            .get::<RequestTimeout>()
+            .map(|t| t.0)
+            .or(self.request_timeout);
+        let read = async {
+            let res = self.send(request.map(Body::from)).await?;
+            let status = res.status();
+            // trace!("Status = {:?} for {}", status, res.url());
+            let body_bytes = hyper::body::to_bytes(res.into_body())
+                .await
+                .map_err(Error::HyperError)?;
+            Ok::<_, Error>((status, body_bytes))
+        };
+        let (status, body_bytes) = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, read)
+                .await
+                .map_err(|_| Error::RequestTimeout(timeout))??,
+            None => read.await?,
+        };
         let text = String::from_utf8(body_bytes.to_vec()).map_err(Error::FromUtf8)?;
         handle_api_errors(&text, status)?;
 
         Ok(text)
     }
 
+    /// Perform a raw HTTP request against the API and get back the response
+    /// as an [`AsyncBufRead`]
+    ///
+    /// This is useful for line based streaming responses, such as logs,
+    /// which can be consumed with [`AsyncBufReadExt::lines`](futures::AsyncBufReadExt::lines).
+    /// Unsuccessful responses are returned as [`Error::Api`].
+    pub async fn request_stream(&self, request: Request<Vec<u8>>) -> Result<impl AsyncBufRead> {
+        let res = check_status(self.send(request.map(Body::from)).await?).await?;
+        Ok(res
+            .into_body()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+            .into_async_read())
+    }
+
     /// Perform a raw HTTP request against the API and get back the response
     /// as a stream of bytes
+    ///
+    /// Unsuccessful responses are returned as [`Error::Api`].
     pub async fn request_text_stream(
         &self,
         request: Request<Vec<u8>>,
     ) -> Result<impl Stream<Item = Result<Bytes>>> {
-        let res = self.send(request.map(Body::from)).await?;
+        let res = check_status(self.send(request.map(Body::from)).await?).await?;
         // trace!("Status = {:?} for {}", res.status(), res.url());
         Ok(res.into_body().map_err(Error::HyperError))
     }
 
+    /// Get an arbitrary path of the API server as a string
+    ///
+    /// This is useful for endpoints that are not modeled as resources, such as `/metrics`,
+    /// `/livez?verbose` or paths of aggregated APIs. The path can include a query.
+    /// Unsuccessful responses are returned as [`Error::Api`].
+    ///
+    /// ```no_run
+    /// # async fn doc(client: kube::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let metrics = client.get_raw("/metrics").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_raw(&self, path: &str) -> Result<String> {
+        let mut req = Request::get(path).body(vec![]).map_err(Error::HttpError)?;
+        req.extensions_mut().insert("get_raw");
+        self.request_text(req).await
+    }
+
+    /// Get an arbitrary path of the API server as an [`AsyncBufRead`]
+    ///
+    /// Like [`Client::get_raw`], but the response is streamed rather than read in full,
+    /// which suits long-running responses such as followed logs.
+    /// Unsuccessful responses are returned as [`Error::Api`].
+    pub async fn get_raw_stream(&self, path: &str) -> Result<impl AsyncBufRead> {
+        let mut req = Request::get(path).body(vec![]).map_err(Error::HttpError)?;
+        req.extensions_mut().insert("get_raw_stream");
+        self.request_stream(req).await
+    }
+
     /// Perform a raw HTTP request against the API and get back either an object
     /// deserialized as JSON or a [`Status`] Object.
     pub async fn request_status<T>(&self, request: Request<Vec<u8>>) -> Result<Either<T, Status>>
@@ -442,6 +559,7 @@ fn handle_api_errors(text: &str, s: StatusCode) -> Result<()> {
                 code: s.as_u16(),
                 message: format!("{:?}", text),
                 reason: "Failed to parse error data".into(),
+                details: None,
             };
             tracing::debug!("Unsuccessful: {:?} (reconstruct)", ae);
             Err(Error::Api(ae))
@@ -451,6 +569,21 @@ fn handle_api_errors(text: &str, s: StatusCode) -> Result<()> {
     }
 }
 
+/// Return unsuccessful responses of streaming requests as errors
+///
+/// These carry a Status object rather than the requested stream, so their body is read in full.
+async fn check_status(res: Response<Body>) -> Result<Response<Body>> {
+    let status = res.status();
+    if status.is_client_error() || status.is_server_error() {
+        let body = hyper::body::to_bytes(res.into_body())
+            .await
+            .map_err(Error::HyperError)?;
+        let text = String::from_utf8_lossy(&body);
+        return Err(handle_api_errors(&text, status).expect_err("error status codes are always errors"));
+    }
+    Ok(res)
+}
+
 impl TryFrom<Config> for Client {
     type Error = Error;
 
@@ -462,7 +595,10 @@ impl TryFrom<Config> for Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Api, Client};
+    use std::time::Duration;
+
+    use super::RequestTimeout;
+    use crate::{Api, Client, Error};
 
     use futures::pin_mut;
     use http::{Request, Response};
@@ -503,4 +639,52 @@ mod tests {
         assert_eq!(pod.metadata.annotations.unwrap().get("kube-rs").unwrap(), "test");
         spawned.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn raw_requests_map_error_statuses() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().to_string(), "/livez?verbose");
+            send.send_response(Response::builder().body(Body::from("[+]ping ok")).unwrap());
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(
+                Response::builder()
+                    .status(404)
+                    .body(Body::from(
+                        r#"{"kind":"Status","apiVersion":"v1","status":"Failure","message":"the server could not find the requested resource","reason":"NotFound","code":404}"#,
+                    ))
+                    .unwrap(),
+            );
+        });
+
+        let client = Client::new(mock_service, "default");
+        assert_eq!(client.get_raw("/livez?verbose").await.unwrap(), "[+]ping ok");
+        match client.get_raw_stream("/apis/metrics.k8s.io/v1beta1/nodes").await {
+            Err(Error::Api(err)) => assert_eq!(err.reason, "NotFound"),
+            _ => panic!("expected an API error"),
+        }
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        // The handle is kept but never responds
+        let (mock_service, _handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let client = Client::new(mock_service, "default").with_request_timeout(Some(Duration::from_secs(60)));
+
+        let mut request = Request::get("/api/v1/namespaces/default/pods/test")
+            .body(Vec::new())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(RequestTimeout(Duration::from_millis(10)));
+        let err = client.request::<Pod>(request).await.unwrap_err();
+        assert!(matches!(err, Error::RequestTimeout(timeout) if timeout == Duration::from_millis(10)));
+
+        let client = client.with_request_timeout(Some(Duration::from_millis(10)));
+        let pods: Api<Pod> = Api::default_namespaced(client);
+        assert!(matches!(pods.get("test").await, Err(Error::RequestTimeout(_))));
+    }
 }
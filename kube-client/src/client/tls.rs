@@ -1,3 +1,81 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::SystemTime,
+};
+
+use http::Uri;
+use tower::{util::Oneshot, Service, ServiceExt};
+
+use crate::{Config, Result};
+
+/// Connector that is rebuilt whenever the client certificate or key files change on disk
+///
+/// This lets long-running clients pick up certificates rotated by tools like cert-manager without restarting.
+/// Only new connections use the reloaded certificate, pooled connections keep using the one they were opened with.
+#[derive(Clone)]
+pub struct IdentityReloadingConnector<C> {
+    config: Arc<Config>,
+    files: Arc<[PathBuf]>,
+    make_connector: Arc<dyn Fn(&Config) -> Result<C> + Send + Sync>,
+    state: Arc<Mutex<(Vec<Option<SystemTime>>, C)>>,
+}
+
+impl<C: Clone> IdentityReloadingConnector<C> {
+    pub fn new<F>(config: Config, make_connector: F) -> Result<Self>
+    where
+        F: Fn(&Config) -> Result<C> + Send + Sync + 'static,
+    {
+        let files: Arc<[PathBuf]> = config.auth_info.identity_files().into();
+        let connector = make_connector(&config)?;
+        Ok(Self {
+            state: Arc::new(Mutex::new((modified_times(&files), connector))),
+            config: Arc::new(config),
+            files,
+            make_connector: Arc::new(make_connector),
+        })
+    }
+
+    fn current(&self) -> C {
+        let modified = modified_times(&self.files);
+        let mut state = self.state.lock().expect("identity reloading lock poisoned");
+        if state.0 != modified {
+            match (self.make_connector)(&self.config) {
+                Ok(connector) => *state = (modified, connector),
+                // The files may be half-written during a rotation, so retry on the next connection
+                Err(err) => tracing::warn!("failed to reload client certificate: {}", err),
+            }
+        }
+        state.1.clone()
+    }
+}
+
+fn modified_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| std::fs::metadata(file).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+impl<C> Service<Uri> for IdentityReloadingConnector<C>
+where
+    C: Service<Uri> + Clone,
+{
+    type Error = C::Error;
+    type Future = Oneshot<C, Uri>;
+    type Response = C::Response;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness is checked on the connector that ends up handling the call
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        self.current().oneshot(uri)
+    }
+}
+
 #[cfg(feature = "native-tls")]
 pub mod native_tls {
     use thiserror::Error;
@@ -258,6 +336,10 @@ pub mod openssl_tls {
         /// Failed to add a root certificate
         #[error("failed to add a root certificate: {0}")]
         AddRootCertificate(#[source] openssl::error::ErrorStack),
+
+        /// Failed to set the ALPN protocols
+        #[error("failed to set the ALPN protocols: {0}")]
+        SetAlpnProtocols(#[source] openssl::error::ErrorStack),
     }
 
     /// Create `openssl::ssl::SslConnectorBuilder` required for `hyper_openssl::HttpsConnector`.
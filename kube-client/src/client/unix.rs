@@ -0,0 +1,83 @@
+use std::{
+    future::Future,
+    io,
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::Uri;
+use hyper::client::connect::{Connected, Connection};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tower::Service;
+
+/// Connector that connects to a Unix domain socket regardless of the requested URI
+///
+/// Useful for API servers that are only exposed on a local socket, such as `kubectl proxy --unix-socket`.
+/// Pair it with [`ClientBuilder::try_from_connector`](crate::client::ClientBuilder::try_from_connector),
+/// the cluster URL of the [`Config`](crate::Config) is then only used to build request paths.
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+#[derive(Clone, Debug)]
+pub struct UnixConnector {
+    path: Arc<PathBuf>,
+}
+
+impl UnixConnector {
+    /// Create a connector for the socket at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Arc::new(path.into()),
+        }
+    }
+}
+
+impl Service<Uri> for UnixConnector {
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+    type Response = UnixStream;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let path = self.path.clone();
+        Box::pin(async move { tokio::net::UnixStream::connect(&*path).await.map(UnixStream) })
+    }
+}
+
+/// Connection to a Unix domain socket, returned by [`UnixConnector`]
+#[cfg_attr(docsrs, doc(cfg(unix)))]
+#[derive(Debug)]
+pub struct UnixStream(tokio::net::UnixStream);
+
+impl Connection for UnixStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+impl AsyncRead for UnixStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for UnixStream {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
@@ -195,6 +195,10 @@ pub struct AuthInfo {
     #[serde(rename = "as-groups")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub impersonate_groups: Option<Vec<String>>,
+    /// Extra information about the impersonated user, such as scopes.
+    #[serde(rename = "as-user-extra")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impersonate_extra: Option<HashMap<String, Vec<String>>>,
 
     /// Specifies a custom authentication plugin for the kubernetes cluster.
     #[serde(rename = "auth-provider")]
@@ -240,9 +244,79 @@ pub struct ExecConfig {
     pub args: Option<Vec<String>>,
     /// Env defines additional environment variables to expose to the process.
     ///
-    /// TODO: These are unioned with the host's environment, as well as variables client-go uses to pass argument to the plugin.
+    /// These are unioned with the host's environment, as well as variables client-go uses to pass argument to the plugin.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub env: Option<Vec<HashMap<String, String>>>,
+    /// Whether the plugin may use stdin to prompt the user. Defaults to `IfAvailable`.
+    #[serde(rename = "interactiveMode")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interactive_mode: Option<ExecInteractiveMode>,
+    /// Whether to pass the cluster of the current context to the plugin, through the `KUBERNETES_EXEC_INFO` environment variable.
+    #[serde(default, rename = "provideClusterInfo")]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub provide_cluster_info: bool,
+    /// The cluster information passed to the plugin when `provide_cluster_info` is set.
+    ///
+    /// This is filled in from the cluster of the selected context when the kubeconfig is loaded.
+    #[serde(skip)]
+    pub cluster: Option<ExecAuthCluster>,
+}
+
+/// ExecInteractiveMode defines how the exec plugin may interact with the user through stdin.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExecInteractiveMode {
+    /// The plugin never uses stdin.
+    Never,
+    /// The plugin uses stdin when it is available.
+    IfAvailable,
+    /// The plugin requires stdin.
+    Always,
+}
+
+/// The cluster information passed to exec plugins that set `provideClusterInfo`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct ExecAuthCluster {
+    /// The address of the kubernetes cluster (https://hostname:port).
+    pub server: String,
+    /// Skips the validity check for the server's certificate.
+    #[serde(rename = "insecureSkipTLSVerify")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insecure_skip_tls_verify: Option<bool>,
+    /// Base64 encoded PEM certificate authority certificates.
+    #[serde(rename = "certificateAuthorityData")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate_authority_data: Option<String>,
+    /// URL to the proxy to be used for all requests.
+    #[serde(rename = "proxyURL")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+    /// The `client.authentication.k8s.io/exec` extension of the cluster, with plugin specific configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+}
+
+/// The name of the cluster extension passed to exec plugins as [`ExecAuthCluster::config`]
+const CLUSTER_EXTENSION_KEY: &str = "client.authentication.k8s.io/exec";
+
+impl TryFrom<&Cluster> for ExecAuthCluster {
+    type Error = KubeconfigError;
+
+    fn try_from(cluster: &Cluster) -> Result<Self, KubeconfigError> {
+        let config = cluster.extensions.as_ref().and_then(|extensions| {
+            extensions
+                .iter()
+                .find(|extension| extension.name == CLUSTER_EXTENSION_KEY)
+                .map(|extension| extension.extension.clone())
+        });
+        Ok(Self {
+            server: cluster.server.clone(),
+            insecure_skip_tls_verify: cluster.insecure_skip_tls_verify,
+            certificate_authority_data: cluster.load_certificate_authority()?.map(base64::encode),
+            proxy_url: cluster.proxy_url.clone(),
+            config,
+        })
+    }
 }
 
 /// NamedContext associates name with context.
@@ -340,26 +414,38 @@ impl Kubeconfig {
     /// Create `Kubeconfig` from `KUBECONFIG` environment variable.
     /// Supports list of files to be merged.
     ///
+    /// Files that don't exist are skipped, as long as at least one of the listed files does.
+    ///
     /// # Panics
     ///
     /// Panics if `KUBECONFIG` value contains the NUL character.
     pub fn from_env() -> Result<Option<Self>, KubeconfigError> {
-        match std::env::var_os(KUBECONFIG) {
-            Some(value) => {
-                let paths = std::env::split_paths(&value)
-                    .filter(|p| !p.as_os_str().is_empty())
-                    .collect::<Vec<_>>();
-                if paths.is_empty() {
-                    return Ok(None);
-                }
+        match kubeconfig_env_paths() {
+            Some(paths) => Self::read_from_all(&paths).map(Some),
+            None => Ok(None),
+        }
+    }
 
-                let merged = paths.iter().try_fold(Kubeconfig::default(), |m, p| {
-                    Kubeconfig::read_from(p).and_then(|c| m.merge(c))
-                })?;
-                Ok(Some(merged))
+    /// Read and merge the files in order, skipping the ones that don't exist
+    fn read_from_all(paths: &[PathBuf]) -> Result<Self, KubeconfigError> {
+        let mut merged = Kubeconfig::default();
+        let mut not_found = None;
+        let mut found_any = false;
+        for path in paths {
+            match Kubeconfig::read_from(path) {
+                Ok(config) => {
+                    merged = merged.merge(config)?;
+                    found_any = true;
+                }
+                Err(KubeconfigError::ReadConfig(err, path)) if err.kind() == std::io::ErrorKind::NotFound => {
+                    not_found.get_or_insert(KubeconfigError::ReadConfig(err, path));
+                }
+                Err(err) => return Err(err),
             }
-
-            None => Ok(None),
+        }
+        match not_found {
+            Some(err) if !found_any => Err(err),
+            _ => Ok(merged),
         }
     }
 
@@ -390,7 +476,13 @@ impl Kubeconfig {
         append_new_named(&mut self.auth_infos, next.auth_infos, |x| &x.name);
         append_new_named(&mut self.contexts, next.contexts, |x| &x.name);
         self.current_context = self.current_context.or(next.current_context);
-        self.extensions = self.extensions.or(next.extensions);
+        self.extensions = match (self.extensions, next.extensions) {
+            (Some(mut extensions), Some(next)) => {
+                append_new_named(&mut extensions, next, |x| &x.name);
+                Some(extensions)
+            }
+            (extensions, next) => extensions.or(next),
+        };
         Ok(self)
     }
 }
@@ -452,6 +544,19 @@ impl AuthInfo {
         Ok(buffer)
     }
 
+    /// The client certificate and key files, which are not overridden by inline data
+    pub(crate) fn identity_files(&self) -> Vec<PathBuf> {
+        let certificate = self
+            .client_certificate
+            .as_ref()
+            .filter(|_| self.client_certificate_data.is_none());
+        let key = self
+            .client_key
+            .as_ref()
+            .filter(|_| self.client_key_data.is_none());
+        certificate.into_iter().chain(key).map(PathBuf::from).collect()
+    }
+
     pub(crate) fn load_client_certificate(&self) -> Result<Vec<u8>, KubeconfigError> {
         // TODO Shouldn't error when `self.client_certificate_data.is_none() && self.client_certificate.is_none()`
 
@@ -473,7 +578,7 @@ impl AuthInfo {
     }
 }
 
-fn load_from_base64_or_file<P: AsRef<Path>>(
+pub(crate) fn load_from_base64_or_file<P: AsRef<Path>>(
     value: &Option<&str>,
     file: &Option<P>,
 ) -> Result<Vec<u8>, LoadDataError> {
@@ -501,6 +606,25 @@ fn ensure_trailing_newline(mut data: Vec<u8>) -> Vec<u8> {
     data
 }
 
+/// Returns the non-empty paths listed in `KUBECONFIG`, if there are any
+fn kubeconfig_env_paths() -> Option<Vec<PathBuf>> {
+    let value = std::env::var_os(KUBECONFIG)?;
+    let paths = std::env::split_paths(&value)
+        .filter(|p| !p.as_os_str().is_empty())
+        .collect::<Vec<_>>();
+    if paths.is_empty() {
+        None
+    } else {
+        Some(paths)
+    }
+}
+
+/// Returns the kubeconfig files in use, either from `KUBECONFIG` or the default location
+#[cfg(feature = "oidc")]
+pub(crate) fn kubeconfig_paths() -> Vec<PathBuf> {
+    kubeconfig_env_paths().unwrap_or_else(|| default_kube_path().into_iter().collect())
+}
+
 /// Returns kubeconfig path from `$HOME/.kube/config`.
 fn default_kube_path() -> Option<PathBuf> {
     use dirs::home_dir;
@@ -567,6 +691,71 @@ mod tests {
         assert_eq!(merged.auth_infos[1].name, "green-user".to_owned());
     }
 
+    #[test]
+    fn kubeconfig_merge_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first");
+        let second = dir.path().join("second");
+        let missing = dir.path().join("missing");
+        std::fs::write(
+            &first,
+            "clusters: []
+users: []
+contexts:
+- name: a
+  context: { cluster: a, user: a }
+current-context: a
+",
+        )
+        .unwrap();
+        std::fs::write(
+            &second,
+            "clusters: []
+users: []
+contexts:
+- name: a
+  context: { cluster: b, user: b }
+- name: b
+  context: { cluster: b, user: b }
+current-context: b
+",
+        )
+        .unwrap();
+
+        let merged = Kubeconfig::read_from_all(&[first, missing.clone(), second]).unwrap();
+        assert_eq!(merged.current_context.as_deref(), Some("a"));
+        let contexts = merged
+            .contexts
+            .iter()
+            .map(|c| (c.name.as_str(), c.context.cluster.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(contexts, vec![("a", "a"), ("b", "b")]);
+
+        assert!(matches!(
+            Kubeconfig::read_from_all(&[missing]),
+            Err(KubeconfigError::ReadConfig(_, _))
+        ));
+    }
+
+    #[test]
+    fn identity_files_skip_inline_data() {
+        let auth_info = AuthInfo {
+            client_certificate: Some("/certs/tls.crt".into()),
+            client_key: Some("/certs/tls.key".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            auth_info.identity_files(),
+            vec![PathBuf::from("/certs/tls.crt"), PathBuf::from("/certs/tls.key")]
+        );
+
+        let auth_info = AuthInfo {
+            client_key_data: Some(SecretString::from_str("a2V5").unwrap()),
+            ..auth_info
+        };
+        assert_eq!(auth_info.identity_files(), vec![PathBuf::from("/certs/tls.crt")]);
+    }
+
     #[test]
     fn kubeconfig_deserialize() {
         let config_yaml = "apiVersion: v1
@@ -709,11 +898,43 @@ password: kube_rs
         token: None, token_file: None, client_certificate: None, \
         client_certificate_data: None, client_key: None, \
         client_key_data: None, impersonate: None, \
-        impersonate_groups: None, \
+        impersonate_groups: None, impersonate_extra: None, \
         auth_provider: None, \
         exec: None \
         }";
 
         assert_eq!(authinfo_debug_output, expected_output)
     }
+
+    #[test]
+    fn exec_cluster_info() {
+        let exec_yaml = r#"
+apiVersion: client.authentication.k8s.io/v1beta1
+command: kubelogin
+interactiveMode: Never
+provideClusterInfo: true
+"#;
+        let exec: ExecConfig = serde_yaml::from_str(exec_yaml).unwrap();
+        assert_eq!(exec.interactive_mode, Some(ExecInteractiveMode::Never));
+        assert!(exec.provide_cluster_info);
+
+        let cluster_yaml = r#"
+server: https://cluster.example.com
+certificate-authority-data: Zm9v
+extensions:
+- name: client.authentication.k8s.io/exec
+  extension:
+    audience: foo
+"#;
+        let cluster: Cluster = serde_yaml::from_str(cluster_yaml).unwrap();
+        let exec_cluster = ExecAuthCluster::try_from(&cluster).unwrap();
+        assert_eq!(
+            serde_json::to_value(&exec_cluster).unwrap(),
+            serde_json::json!({
+                "server": "https://cluster.example.com",
+                "certificateAuthorityData": base64::encode("foo\n"),
+                "config": { "audience": "foo" }
+            })
+        );
+    }
 }
@@ -1,5 +1,7 @@
+use std::net::IpAddr;
+
 use super::{
-    file_config::{AuthInfo, Cluster, Context, Kubeconfig},
+    file_config::{AuthInfo, Cluster, Context, ExecAuthCluster, Kubeconfig},
     KubeconfigError,
 };
 
@@ -89,10 +91,17 @@ impl ConfigLoader {
             .map(|named_user| &named_user.auth_info)
             .ok_or_else(|| KubeconfigError::FindUser(user_name.clone()))?;
 
+        let mut user = user.clone();
+        if let Some(exec) = &mut user.exec {
+            if exec.provide_cluster_info {
+                exec.cluster = Some(ExecAuthCluster::try_from(cluster)?);
+            }
+        }
+
         Ok(ConfigLoader {
             current_context: current_context.clone(),
             cluster: cluster.clone(),
-            user: user.clone(),
+            user,
         })
     }
 
@@ -106,22 +115,96 @@ impl ConfigLoader {
         }
     }
 
-    pub fn proxy_url(&self) -> Result<Option<http::Uri>, KubeconfigError> {
+    /// Proxy to reach `cluster_url` through
+    ///
+    /// The cluster `proxy-url` takes precedence, otherwise `HTTPS_PROXY` or `HTTP_PROXY` is used
+    /// depending on the scheme of the cluster, unless its host is excluded by `NO_PROXY`.
+    pub fn proxy_url(&self, cluster_url: &http::Uri) -> Result<Option<http::Uri>, KubeconfigError> {
         let nonempty = |o: Option<String>| o.filter(|s| !s.is_empty());
+        let env = |name: &str| {
+            nonempty(std::env::var(name).ok()).or_else(|| nonempty(std::env::var(name.to_lowercase()).ok()))
+        };
 
-        if let Some(proxy) = nonempty(self.cluster.proxy_url.clone())
-            .or_else(|| nonempty(std::env::var("HTTP_PROXY").ok()))
-            .or_else(|| nonempty(std::env::var("http_proxy").ok()))
-            .or_else(|| nonempty(std::env::var("HTTPS_PROXY").ok()))
-            .or_else(|| nonempty(std::env::var("https_proxy").ok()))
-        {
-            Ok(Some(
-                proxy
-                    .parse::<http::Uri>()
-                    .map_err(KubeconfigError::ParseProxyUrl)?,
-            ))
+        let proxy = if let Some(proxy) = nonempty(self.cluster.proxy_url.clone()) {
+            Some(proxy)
+        } else if env("NO_PROXY").map_or(false, |no_proxy| {
+            no_proxy_matches(&no_proxy, cluster_url.host().unwrap_or_default())
+        }) {
+            None
+        } else if cluster_url.scheme_str() == Some("http") {
+            env("HTTP_PROXY")
         } else {
-            Ok(None)
+            env("HTTPS_PROXY")
+        };
+
+        proxy
+            .map(|proxy| proxy.parse::<http::Uri>().map_err(KubeconfigError::ParseProxyUrl))
+            .transpose()
+    }
+}
+
+/// Whether `host` is matched by a `NO_PROXY` list of domains, IP addresses and CIDR blocks
+fn no_proxy_matches(no_proxy: &str, host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']').to_lowercase();
+    let ip = host.parse::<IpAddr>().ok();
+    no_proxy
+        .split(',')
+        .map(|entry| entry.trim().trim_start_matches('[').trim_end_matches(']'))
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            if entry == "*" {
+                true
+            } else if let Some((network, prefix)) = entry.split_once('/') {
+                match (ip, network.parse::<IpAddr>(), prefix.parse::<u32>()) {
+                    (Some(ip), Ok(network), Ok(prefix)) => cidr_contains(network, prefix, ip),
+                    _ => false,
+                }
+            } else {
+                // Domains also match their subdomains, with or without a leading dot
+                let domain = entry
+                    .trim_start_matches("*.")
+                    .trim_start_matches('.')
+                    .to_lowercase();
+                host == domain || host.ends_with(&format!(".{}", domain))
+            }
+        })
+}
+
+fn cidr_contains(network: IpAddr, prefix: u32, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) if prefix <= 32 => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) if prefix <= 128 => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
         }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::no_proxy_matches;
+
+    #[test]
+    fn no_proxy_matches_domains() {
+        assert!(no_proxy_matches("*", "cluster.example.com"));
+        assert!(no_proxy_matches("localhost, example.com", "cluster.example.com"));
+        assert!(no_proxy_matches(".example.com", "cluster.example.com"));
+        assert!(no_proxy_matches("Example.com", "example.com"));
+        assert!(!no_proxy_matches("example.com", "notexample.com"));
+        assert!(!no_proxy_matches("", "example.com"));
+    }
+
+    #[test]
+    fn no_proxy_matches_ips() {
+        assert!(no_proxy_matches("10.0.0.1", "10.0.0.1"));
+        assert!(no_proxy_matches("10.0.0.0/8", "10.96.0.1"));
+        assert!(!no_proxy_matches("10.0.0.0/8", "192.168.0.1"));
+        assert!(no_proxy_matches("fd00::/8", "[fd00::1]"));
+        assert!(no_proxy_matches("[::1]", "[::1]"));
+        assert!(no_proxy_matches("0.0.0.0/0", "192.168.0.1"));
     }
 }
@@ -148,13 +148,41 @@ pub struct Config {
         note = "replaced by more granular members `connect_timeout`, `read_timeout` and `write_timeout`. This member will be removed in 0.78.0."
     )]
     pub timeout: Option<std::time::Duration>,
+    /// Set the overall timeout for requests whose response is read in full, such as `get` and `list`.
+    ///
+    /// This covers sending the request and reading the whole response body.
+    /// Streaming requests such as watches and logs are not affected.
+    /// It can be overridden for a single request with the [`RequestTimeout`](crate::client::RequestTimeout) extension.
+    ///
+    /// A value of `None` means no timeout
+    pub request_timeout: Option<std::time::Duration>,
+    /// Whether to negotiate HTTP/2 with the Kubernetes API.
+    ///
+    /// HTTP/2 is offered with ALPN, which is only supported with the `rustls-tls` and `openssl-tls` features.
+    /// HTTP/1.1 is used when the server doesn't support it, and for plain HTTP.
+    pub http2: bool,
+    /// Set the interval of HTTP/2 pings, which detect broken connections even when they are idle.
+    ///
+    /// A value of `None` means no pings
+    pub http2_keep_alive_interval: Option<std::time::Duration>,
+    /// Set the timeout after which idle connections are closed.
+    ///
+    /// A value of `None` means idle connections are never closed
+    pub pool_idle_timeout: Option<std::time::Duration>,
+    /// Set the maximum number of idle connections kept open per host.
+    pub pool_max_idle_per_host: usize,
     /// Whether to accept invalid certificates
     pub accept_invalid_certs: bool,
     /// Stores information to tell the cluster who you are.
     pub auth_info: AuthInfo,
-    // TODO Actually support proxy or create an example with custom client
     /// Optional proxy URL.
+    ///
+    /// HTTP proxies are used through `CONNECT` tunnels, and `socks5://` URLs through SOCKS5.
     pub proxy_url: Option<http::Uri>,
+    /// Client-side rate limits for requests to the API server.
+    ///
+    /// No limits are applied by default.
+    pub rate_limits: RateLimits,
 }
 
 impl Config {
@@ -173,9 +201,15 @@ impl Config {
             read_timeout: Some(DEFAULT_READ_TIMEOUT),
             write_timeout: None,
             timeout: Some(DEFAULT_TIMEOUT),
+            request_timeout: None,
+            http2: false,
+            http2_keep_alive_interval: None,
+            pool_idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+            pool_max_idle_per_host: usize::MAX,
             accept_invalid_certs: false,
             auth_info: AuthInfo::default(),
             proxy_url: None,
+            rate_limits: RateLimits::default(),
         }
     }
 
@@ -211,6 +245,9 @@ impl Config {
     /// This follows the standard [API Access from a Pod](https://kubernetes.io/docs/tasks/access-application-cluster/access-cluster/#accessing-the-api-from-a-pod)
     /// and relies on you having the service account's token mounted,
     /// as well as having given the service account rbac access to do what you need.
+    ///
+    /// The token is re-read from the mounted file at least once a minute,
+    /// so that bound service account tokens rotated by the kubelet are picked up before they expire.
     pub fn from_cluster_env() -> Result<Self, InClusterError> {
         let cluster_url = incluster_config::kube_dns();
         let default_namespace = incluster_config::load_default_ns()?;
@@ -225,12 +262,18 @@ impl Config {
             read_timeout: Some(DEFAULT_READ_TIMEOUT),
             write_timeout: None,
             timeout: Some(DEFAULT_TIMEOUT),
+            request_timeout: None,
+            http2: false,
+            http2_keep_alive_interval: None,
+            pool_idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+            pool_max_idle_per_host: usize::MAX,
             accept_invalid_certs: false,
             auth_info: AuthInfo {
                 token_file: Some(incluster_config::token_file()),
                 ..Default::default()
             },
             proxy_url: None,
+            rate_limits: RateLimits::default(),
         })
     }
 
@@ -262,6 +305,8 @@ impl Config {
             .parse::<http::Uri>()
             .map_err(KubeconfigError::ParseClusterUrl)?;
 
+        let proxy_url = loader.proxy_url(&cluster_url)?;
+
         let default_namespace = loader
             .current_context
             .namespace
@@ -287,8 +332,14 @@ impl Config {
             read_timeout: Some(DEFAULT_READ_TIMEOUT),
             write_timeout: None,
             timeout: Some(DEFAULT_TIMEOUT),
+            request_timeout: None,
+            http2: false,
+            http2_keep_alive_interval: None,
+            pool_idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+            pool_max_idle_per_host: usize::MAX,
             accept_invalid_certs,
-            proxy_url: loader.proxy_url()?,
+            proxy_url,
+            rate_limits: RateLimits::default(),
             auth_info: loader.user,
         })
     }
@@ -331,13 +382,75 @@ impl Config {
         }
     }
 
+    /// Impersonate another user, optionally with groups and extra information
+    ///
+    /// Requests are then authorized as that user, through the `Impersonate-User`, `Impersonate-Group`
+    /// and `Impersonate-Extra-*` headers. The authenticated user needs to be allowed to `impersonate` them.
+    /// This replaces any impersonation set in the kubeconfig.
+    ///
+    /// ```rust
+    /// # async fn doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// use kube::{Client, Config};
+    ///
+    /// let config = Config::infer().await?.impersonate(
+    ///     "system:serviceaccount:default:foo",
+    ///     vec!["system:serviceaccounts".into()],
+    ///     Default::default(),
+    /// );
+    /// let client = Client::try_from(config)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn impersonate(
+        mut self,
+        user: impl Into<String>,
+        groups: Vec<String>,
+        extra: std::collections::HashMap<String, Vec<String>>,
+    ) -> Self {
+        self.auth_info.impersonate = Some(user.into());
+        self.auth_info.impersonate_groups = Some(groups).filter(|groups| !groups.is_empty());
+        self.auth_info.impersonate_extra = Some(extra).filter(|extra| !extra.is_empty());
+        self
+    }
+
     /// Client certificate and private key in PEM.
     pub(crate) fn identity_pem(&self) -> Option<Vec<u8>> {
         self.auth_info.identity_pem().ok()
     }
 }
 
-fn certs(data: &[u8]) -> Result<Vec<Vec<u8>>, pem::PemError> {
+/// Client-side rate limit, like the QPS and burst settings of client-go
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Sustained number of requests per second
+    pub qps: f32,
+    /// Number of requests that can be sent at once before being held to `qps`
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Construct a limit of `qps` requests per second, with bursts of up to `burst` requests
+    pub fn new(qps: f32, burst: u32) -> Self {
+        Self { qps, burst }
+    }
+}
+
+/// Client-side rate limits depending on the kind of request
+///
+/// Requests are delayed until the matching limit allows them, rather than failed.
+/// A watch counts as a single request when it is opened.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimits {
+    /// Limit shared by requests without a more specific limit
+    pub default: Option<RateLimit>,
+    /// Limit for reads (`GET` and `HEAD` requests, including lists and watches)
+    pub read: Option<RateLimit>,
+    /// Limit for writes (all other requests)
+    pub write: Option<RateLimit>,
+}
+
+pub(crate) fn certs(data: &[u8]) -> Result<Vec<Vec<u8>>, pem::PemError> {
     Ok(pem::parse_many(data)?
         .into_iter()
         .filter_map(|p| {
@@ -355,6 +468,8 @@ fn certs(data: &[u8]) -> Result<Vec<Vec<u8>>, pem::PemError> {
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(295);
 const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(295);
+// Same as hyper's default
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
 
 // temporary catalina hack for openssl only
 #[cfg(all(target_os = "macos", feature = "native-tls"))]
@@ -374,9 +489,11 @@ fn hacky_cert_lifetime_for_macos(_: &[u8]) -> bool {
 
 // Expose raw config structs
 pub use file_config::{
-    AuthInfo, AuthProviderConfig, Cluster, Context, ExecConfig, Kubeconfig, NamedAuthInfo, NamedCluster,
-    NamedContext, NamedExtension, Preferences,
+    AuthInfo, AuthProviderConfig, Cluster, Context, ExecAuthCluster, ExecConfig, ExecInteractiveMode,
+    Kubeconfig, NamedAuthInfo, NamedCluster, NamedContext, NamedExtension, Preferences,
 };
+#[cfg(feature = "oidc")]
+pub(crate) use file_config::{kubeconfig_paths, load_from_base64_or_file};
 
 
 #[cfg(test)]
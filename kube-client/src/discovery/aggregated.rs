@@ -0,0 +1,216 @@
+//! Aggregated discovery, which serves all groups of `/apis` or `/api` in a single response
+//!
+//! Served by Kubernetes 1.27+ (and 1.26 behind a feature gate) when requested through the `Accept` header.
+//! These types mirror `apidiscovery.k8s.io/v2`, which `k8s_openapi` does not include.
+use http::{header::ACCEPT, HeaderValue, Request};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{APIResource, APIResourceList};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{Client, Error, Result};
+
+// Ask for the aggregated list, falling back to the regular discovery documents on older servers
+const ACCEPT_AGGREGATED: &str = "application/json;g=apidiscovery.k8s.io;v=v2;as=APIGroupDiscoveryList,\
+                                 application/json;g=apidiscovery.k8s.io;v=v2beta1;as=APIGroupDiscoveryList,\
+                                 application/json";
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct APIGroupDiscoveryList {
+    pub(crate) kind: String,
+    #[serde(default)]
+    pub(crate) items: Vec<APIGroupDiscovery>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct APIGroupDiscovery {
+    #[serde(default)]
+    pub(crate) metadata: GroupMeta,
+    /// Versions in order of preference
+    #[serde(default)]
+    pub(crate) versions: Vec<APIVersionDiscovery>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub(crate) struct GroupMeta {
+    /// Empty for the core group
+    #[serde(default)]
+    pub(crate) name: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct APIVersionDiscovery {
+    pub(crate) version: String,
+    #[serde(default)]
+    pub(crate) resources: Vec<APIResourceDiscovery>,
+    /// `Stale` when an aggregated API server could not be reached
+    #[serde(default)]
+    pub(crate) freshness: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct APIResourceDiscovery {
+    resource: String,
+    response_kind: Option<ResponseKind>,
+    scope: String,
+    #[serde(default)]
+    singular_resource: String,
+    #[serde(default)]
+    verbs: Vec<String>,
+    short_names: Option<Vec<String>>,
+    categories: Option<Vec<String>>,
+    #[serde(default)]
+    subresources: Vec<APISubresourceDiscovery>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct APISubresourceDiscovery {
+    subresource: String,
+    response_kind: Option<ResponseKind>,
+    #[serde(default)]
+    verbs: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ResponseKind {
+    #[serde(default)]
+    group: String,
+    #[serde(default)]
+    version: String,
+    kind: String,
+}
+
+impl APIVersionDiscovery {
+    pub(crate) fn is_stale(&self) -> bool {
+        self.freshness.as_deref() == Some("Stale")
+    }
+
+    /// Convert to the regular discovery document of the group version, listing subresources as `resource/subresource`
+    pub(crate) fn into_resource_list(self, group: &str) -> APIResourceList {
+        let group_version = if group.is_empty() {
+            self.version
+        } else {
+            format!("{}/{}", group, self.version)
+        };
+        let mut resources = vec![];
+        for res in self.resources {
+            let namespaced = res.scope == "Namespaced";
+            let kind = res.response_kind.map(|rk| rk.kind).unwrap_or_default();
+            for sub in res.subresources {
+                let (group, version, kind) = match sub.response_kind {
+                    // Empty when the same as the containing group version
+                    Some(rk) => (
+                        Some(rk.group).filter(|g| !g.is_empty()),
+                        Some(rk.version).filter(|v| !v.is_empty()),
+                        rk.kind,
+                    ),
+                    None => (None, None, kind.clone()),
+                };
+                resources.push(APIResource {
+                    name: format!("{}/{}", res.resource, sub.subresource),
+                    namespaced,
+                    group,
+                    version,
+                    kind,
+                    verbs: sub.verbs,
+                    ..APIResource::default()
+                });
+            }
+            resources.push(APIResource {
+                name: res.resource,
+                singular_name: res.singular_resource,
+                namespaced,
+                kind,
+                verbs: res.verbs,
+                short_names: res.short_names,
+                categories: res.categories,
+                ..APIResource::default()
+            });
+        }
+        APIResourceList {
+            group_version,
+            resources,
+        }
+    }
+}
+
+/// Discovery document served at `/apis` or `/api`
+pub(crate) enum Discovered<T> {
+    /// Resources of all groups, from servers supporting aggregated discovery
+    Aggregated(Vec<APIGroupDiscovery>),
+    /// The regular document, which only lists groups or versions
+    Legacy(T),
+}
+
+/// Query the discovery document at `path`, aggregated if the server supports it
+pub(crate) async fn query<T: DeserializeOwned>(client: &Client, path: &str) -> Result<Discovered<T>> {
+    let mut req = Request::get(path).body(vec![]).map_err(Error::HttpError)?;
+    req.headers_mut()
+        .insert(ACCEPT, HeaderValue::from_static(ACCEPT_AGGREGATED));
+    req.extensions_mut().insert("discovery");
+    let text = client.request_text(req).await?;
+    match serde_json::from_str::<APIGroupDiscoveryList>(&text) {
+        Ok(list) if list.kind == "APIGroupDiscoveryList" => Ok(Discovered::Aggregated(list.items)),
+        _ => serde_json::from_str(&text)
+            .map(Discovered::Legacy)
+            .map_err(Error::SerdeError),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_resource_list() {
+        let list: APIGroupDiscoveryList = serde_json::from_value(serde_json::json!({
+            "kind": "APIGroupDiscoveryList",
+            "apiVersion": "apidiscovery.k8s.io/v2",
+            "items": [{
+                "metadata": { "name": "apps" },
+                "versions": [{
+                    "version": "v1",
+                    "resources": [{
+                        "resource": "deployments",
+                        "responseKind": { "group": "", "version": "", "kind": "Deployment" },
+                        "scope": "Namespaced",
+                        "singularResource": "deployment",
+                        "verbs": ["create", "get", "list"],
+                        "shortNames": ["deploy"],
+                        "categories": ["all"],
+                        "subresources": [{
+                            "subresource": "scale",
+                            "responseKind": { "group": "autoscaling", "version": "v1", "kind": "Scale" },
+                            "verbs": ["get", "patch", "update"]
+                        }]
+                    }],
+                    "freshness": "Current"
+                }]
+            }]
+        }))
+        .unwrap();
+        let group = list.items.into_iter().next().unwrap();
+        assert_eq!(group.metadata.name, "apps");
+        let version = group.versions.into_iter().next().unwrap();
+        assert!(!version.is_stale());
+
+        let resources = version.into_resource_list("apps");
+        assert_eq!(resources.group_version, "apps/v1");
+        let names = resources
+            .resources
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["deployments/scale", "deployments"]);
+        let scale = &resources.resources[0];
+        assert_eq!(
+            (scale.kind.as_str(), scale.group.as_deref()),
+            ("Scale", Some("autoscaling"))
+        );
+        let deployments = &resources.resources[1];
+        assert!(deployments.namespaced);
+        assert_eq!(deployments.kind, "Deployment");
+        assert_eq!(deployments.short_names, Some(vec!["deploy".to_string()]));
+    }
+}
@@ -1,4 +1,7 @@
-use super::parse::{self, GroupVersionData};
+use super::{
+    aggregated::APIGroupDiscovery,
+    parse::{self, GroupVersionData},
+};
 use crate::{error::DiscoveryError, Client, Error, Result};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::{APIGroup, APIVersions};
 pub use kube_core::discovery::{verbs, ApiCapabilities, ApiResource, Scope};
@@ -49,7 +52,7 @@ use std::cmp::Reverse;
 ///     let (ar, caps) = apigroup.recommended_kind("APIService").unwrap();
 ///     let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
 ///     for service in api.list(&Default::default()).await? {
-///         println!("Found APIService: {}", service.name());
+///         println!("Found APIService: {}", service.name_any());
 ///     }
 ///     Ok(())
 /// }
@@ -116,6 +119,38 @@ impl ApiGroup {
         Ok(group)
     }
 
+    /// Convert a group from aggregated discovery, returns `None` if none of its versions could be discovered
+    pub(crate) fn from_aggregated(g: APIGroupDiscovery) -> Result<Option<Self>> {
+        let name = g.metadata.name;
+        let mut data = vec![];
+        let mut preferred = None;
+        for version in g.versions {
+            // Stale versions belong to unavailable aggregated apiservers, skip them rather than fail
+            if version.is_stale() {
+                tracing::debug!(
+                    group = name.as_str(),
+                    version = version.version.as_str(),
+                    "Skipping stale group version"
+                );
+                continue;
+            }
+            // Versions are listed in order of preference
+            preferred.get_or_insert_with(|| version.version.clone());
+            let vers = version.version.clone();
+            data.push(GroupVersionData::new(vers, version.into_resource_list(&name))?);
+        }
+        if data.is_empty() {
+            return Ok(None);
+        }
+        let mut group = ApiGroup {
+            name,
+            data,
+            preferred,
+        };
+        group.sort_versions();
+        Ok(Some(group))
+    }
+
     fn sort_versions(&mut self) {
         self.data
             .sort_by_cached_key(|gvd| Reverse(Version::parse(gvd.version.as_str()).priority()))
@@ -231,7 +266,7 @@ impl ApiGroup {
     ///         }
     ///         let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
     ///         for inst in api.list(&Default::default()).await? {
-    ///             println!("Found {}: {}", ar.kind, inst.name());
+    ///             println!("Found {}: {}", ar.kind, inst.name_any());
     ///         }
     ///     }
     ///     Ok(())
@@ -255,7 +290,7 @@ impl ApiGroup {
     ///     let (ar, caps) = apigroup.recommended_kind("APIService").unwrap();
     ///     let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
     ///     for service in api.list(&Default::default()).await? {
-    ///         println!("Found APIService: {}", service.name());
+    ///         println!("Found APIService: {}", service.name_any());
     ///     }
     ///     Ok(())
     /// }
@@ -263,12 +298,136 @@ impl ApiGroup {
     ///
     /// This is equivalent to filtering the [`ApiGroup::versioned_resources`] at [`ApiGroup::preferred_version_or_latest`] against a chosen `kind`.
     pub fn recommended_kind(&self, kind: &str) -> Option<(ApiResource, ApiCapabilities)> {
-        let ver = self.preferred_version_or_latest();
-        for (ar, caps) in self.versioned_resources(ver) {
-            if ar.kind == kind {
-                return Some((ar, caps));
-            }
+        self.resolve_kind(kind, VersionSelection::Preferred)
+    }
+
+    /// Returns the version of the `kind` chosen by a [`VersionSelection`] strategy (if found)
+    ///
+    /// ```no_run
+    /// use kube::{Client, discovery::{self, VersionSelection}};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let apigroup = discovery::group(&client, "autoscaling").await?;
+    ///     // The preferred version may not serve the kind, fall back to the latest one that does
+    ///     let (ar, caps) = apigroup.resolve_kind("HorizontalPodAutoscaler", VersionSelection::Any).unwrap();
+    ///     println!("Using {}", ar.api_version);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn resolve_kind(
+        &self,
+        kind: &str,
+        selection: VersionSelection,
+    ) -> Option<(ApiResource, ApiCapabilities)> {
+        match selection {
+            VersionSelection::Preferred => self.find_kind(self.preferred_version_or_latest(), kind),
+            VersionSelection::Latest => self.find_kind(self.versions().next()?, kind),
+            VersionSelection::Any => self
+                .find_kind(self.preferred_version_or_latest(), kind)
+                .or_else(|| self.kind_versions(kind).into_iter().next()),
         }
-        None
+    }
+
+    /// Returns the `kind` at every served version that contains it
+    ///
+    /// Versions are returned in descending order of [`Version`](kube_core::Version), like [`ApiGroup::versions`].
+    /// This allows tooling for multi-version resources such as CRDs to pick a version deliberately.
+    pub fn kind_versions(&self, kind: &str) -> Vec<(ApiResource, ApiCapabilities)> {
+        self.versions()
+            .filter_map(|version| self.find_kind(version, kind))
+            .collect()
+    }
+
+    fn find_kind(&self, version: &str, kind: &str) -> Option<(ApiResource, ApiCapabilities)> {
+        self.data
+            .iter()
+            .find(|gvd| gvd.version == version)?
+            .resources
+            .iter()
+            .find(|(ar, _)| ar.kind == kind)
+            .cloned()
+    }
+}
+
+/// Strategy for choosing between the versions that serve a kind, see [`ApiGroup::resolve_kind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionSelection {
+    /// The version preferred by the server, or the latest version if it has no preference
+    ///
+    /// This is what [`ApiGroup::recommended_kind`] uses.
+    Preferred,
+    /// The latest version, by [kubernetes version priority](https://kubernetes.io/docs/tasks/extend-kubernetes/custom-resources/custom-resource-definition-versioning/#version-priority)
+    ///
+    /// Stable versions are chosen over beta and alpha versions, regardless of the server's preference.
+    Latest,
+    /// The preferred version if it serves the kind, otherwise the latest version that serves it
+    Any,
+}
+
+impl Default for VersionSelection {
+    fn default() -> Self {
+        Self::Preferred
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(preferred: &str, versions: &[(&str, &[&str])]) -> ApiGroup {
+        let data = versions
+            .iter()
+            .map(|(version, kinds)| GroupVersionData {
+                version: version.to_string(),
+                resources: kinds
+                    .iter()
+                    .map(|kind| {
+                        let gvk = GroupVersionKind::gvk("example.com", version, kind);
+                        let ar = ApiResource::from_gvk_with_plural(&gvk, &kind.to_ascii_lowercase());
+                        let caps = ApiCapabilities {
+                            scope: Scope::Namespaced,
+                            subresources: vec![],
+                            operations: vec![],
+                            short_names: vec![],
+                            categories: vec![],
+                        };
+                        (ar, caps)
+                    })
+                    .collect(),
+            })
+            .collect();
+        let mut group = ApiGroup {
+            name: "example.com".into(),
+            data,
+            preferred: Some(preferred.into()),
+        };
+        group.sort_versions();
+        group
+    }
+
+    #[test]
+    fn selects_versions() {
+        let group = group("v1beta1", &[
+            ("v1alpha1", &["Widget", "Gadget"]),
+            ("v1beta1", &["Widget"]),
+            ("v1", &["Widget"]),
+        ]);
+        let version = |selection| group.resolve_kind("Widget", selection).map(|(ar, _)| ar.version);
+        assert_eq!(version(VersionSelection::Preferred).as_deref(), Some("v1beta1"));
+        assert_eq!(version(VersionSelection::Latest).as_deref(), Some("v1"));
+        assert_eq!(version(VersionSelection::Any).as_deref(), Some("v1beta1"));
+
+        assert!(group.resolve_kind("Gadget", VersionSelection::Preferred).is_none());
+        assert!(group.resolve_kind("Gadget", VersionSelection::Latest).is_none());
+        let (gadget, _) = group.resolve_kind("Gadget", VersionSelection::Any).unwrap();
+        assert_eq!(gadget.version, "v1alpha1");
+
+        let versions = group
+            .kind_versions("Widget")
+            .into_iter()
+            .map(|(ar, _)| ar.version)
+            .collect::<Vec<_>>();
+        assert_eq!(versions, vec!["v1", "v1beta1", "v1alpha1"]);
     }
 }
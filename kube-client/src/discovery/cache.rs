@@ -0,0 +1,322 @@
+//! Shared discovery cache with expiring entries
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::ApiGroup;
+use crate::{error::DiscoveryError, Client, Error, Result};
+use kube_core::{
+    discovery::{ApiCapabilities, ApiResource},
+    gvk::{GroupVersion, GroupVersionKind},
+};
+
+/// How long discovered resources are cached by default
+const DEFAULT_TTL: Duration = Duration::from_secs(10 * 60);
+/// How long kinds that could not be resolved are cached by default
+const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(5);
+
+/// A shared cache of the resources served at each group version
+///
+/// Unlike [`Discovery`](crate::discovery::Discovery), which crawls all api groups up front,
+/// group versions are only queried when first needed, and again once their entry is older than the TTL.
+/// This suits dynamic controllers that resolve a GVK on every event.
+///
+/// Kinds that could not be resolved are remembered for a shorter [`negative_ttl`](Self::negative_ttl),
+/// so that events of a resource that is not installed (yet) do not query the apiserver every time.
+///
+/// Clones share the same cache.
+///
+/// ```no_run
+/// use kube::{Client, api::{Api, DynamicObject, GroupVersionKind}, discovery::CachedDiscovery};
+/// use std::time::Duration;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let discovery = CachedDiscovery::new(client.clone()).ttl(Duration::from_secs(60));
+///     let gvk = GroupVersionKind::gvk("apps", "v1", "Deployment");
+///     // Only the first call queries the apiserver
+///     let (ar, _caps) = discovery.resolve_gvk(&gvk).await?;
+///     let (ar, _caps) = discovery.resolve_gvk(&gvk).await?;
+///     let api: Api<DynamicObject> = Api::all_with(client, &ar);
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "client")))]
+#[derive(Clone)]
+pub struct CachedDiscovery {
+    client: Client,
+    ttl: Duration,
+    negative_ttl: Duration,
+    entries: Arc<Mutex<HashMap<GroupVersion, Entry>>>,
+    misses: Arc<Mutex<HashMap<GroupVersionKind, Instant>>>,
+}
+
+struct Entry {
+    resources: Arc<Vec<(ApiResource, ApiCapabilities)>>,
+    fetched: Instant,
+}
+
+impl CachedDiscovery {
+    /// Construct an empty cache, keeping entries for 10 minutes and misses for 5 seconds
+    #[must_use]
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            ttl: DEFAULT_TTL,
+            negative_ttl: DEFAULT_NEGATIVE_TTL,
+            entries: Arc::default(),
+            misses: Arc::default(),
+        }
+    }
+
+    /// Configure how long the resources of a group version are cached
+    #[must_use]
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Configure how long a kind that could not be resolved is reported as missing without querying again
+    #[must_use]
+    pub fn negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Returns the resources served at a group version, querying them if not cached or expired
+    pub async fn resources(&self, gv: &GroupVersion) -> Result<Arc<Vec<(ApiResource, ApiCapabilities)>>> {
+        match self.cached(gv) {
+            Some(resources) => Ok(resources),
+            None => self.fetch(gv).await,
+        }
+    }
+
+    /// Finds an [`ApiResource`] and its [`ApiCapabilities`] by matching a GVK
+    ///
+    /// A kind that is missing from a cached group version is queried again,
+    /// as it may be a newly installed custom resource, unless it was already missing
+    /// within the [`negative_ttl`](Self::negative_ttl).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DiscoveryError::MissingKind`] if the kind or its whole group version is not served,
+    /// whether that was just queried or is remembered from an earlier miss.
+    pub async fn resolve_gvk(&self, gvk: &GroupVersionKind) -> Result<(ApiResource, ApiCapabilities)> {
+        let gv = GroupVersion::gv(&gvk.group, &gvk.version);
+        if let Some(found) = self
+            .cached(&gv)
+            .and_then(|resources| find_kind(&resources, &gvk.kind))
+        {
+            return Ok(found);
+        }
+        let missing = || Error::Discovery(DiscoveryError::MissingKind(format!("{:?}", gvk)));
+        if self.recently_missed(gvk) {
+            return Err(missing());
+        }
+        let found = match self.fetch(&gv).await {
+            Ok(resources) => find_kind(&resources, &gvk.kind),
+            Err(Error::Api(err)) if err.code == 404 => None,
+            Err(err) => return Err(err),
+        };
+        match found {
+            Some(found) => {
+                self.lock_misses().remove(gvk);
+                Ok(found)
+            }
+            None => {
+                self.lock_misses().insert(gvk.clone(), Instant::now());
+                Err(missing())
+            }
+        }
+    }
+
+    /// Drops all cached entries and misses, so that they are queried again when next needed
+    pub fn invalidate(&self) {
+        self.lock().clear();
+        self.lock_misses().clear();
+    }
+
+    /// Drops the cached entry and misses of a group version, so that it is queried again when next needed
+    pub fn invalidate_group_version(&self, gv: &GroupVersion) {
+        self.lock().remove(gv);
+        self.lock_misses()
+            .retain(|gvk| gvk.group != gv.group || gvk.version != gv.version);
+    }
+
+    /// Queries all cached group versions again
+    ///
+    /// Group versions that are no longer served are dropped from the cache.
+    pub async fn refresh(&self) -> Result<()> {
+        let cached = self.lock().keys().cloned().collect::<Vec<_>>();
+        for gv in cached {
+            match self.fetch(&gv).await {
+                Ok(_) => {}
+                Err(Error::Api(err)) if err.code == 404 => self.invalidate_group_version(&gv),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    fn cached(&self, gv: &GroupVersion) -> Option<Arc<Vec<(ApiResource, ApiCapabilities)>>> {
+        self.lock()
+            .get(gv)
+            .filter(|entry| entry.fetched.elapsed() < self.ttl)
+            .map(|entry| entry.resources.clone())
+    }
+
+    async fn fetch(&self, gv: &GroupVersion) -> Result<Arc<Vec<(ApiResource, ApiCapabilities)>>> {
+        let group = ApiGroup::query_gv(&self.client, gv).await?;
+        let resources = Arc::new(group.versioned_resources(&gv.version));
+        self.lock().insert(
+            gv.clone(),
+            Entry {
+                resources: resources.clone(),
+                fetched: Instant::now(),
+            },
+        );
+        Ok(resources)
+    }
+
+    fn recently_missed(&self, gvk: &GroupVersionKind) -> bool {
+        self.lock_misses()
+            .get(gvk)
+            .map_or(false, |missed| missed.elapsed() < self.negative_ttl)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<GroupVersion, Entry>> {
+        self.entries.lock().expect("discovery cache lock poisoned")
+    }
+
+    fn lock_misses(&self) -> std::sync::MutexGuard<'_, HashMap<GroupVersionKind, Instant>> {
+        self.misses.lock().expect("discovery cache lock poisoned")
+    }
+}
+
+fn find_kind(
+    resources: &[(ApiResource, ApiCapabilities)],
+    kind: &str,
+) -> Option<(ApiResource, ApiCapabilities)> {
+    resources.iter().find(|(ar, _)| ar.kind == kind).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::pin_mut;
+    use http::{Request, Response};
+    use hyper::Body;
+    use tower_test::mock;
+
+    fn apps_v1(kinds: &[(&str, &str)]) -> Response<Body> {
+        let resources = kinds
+            .iter()
+            .map(|(name, kind)| {
+                serde_json::json!({
+                    "name": name,
+                    "singularName": "",
+                    "namespaced": true,
+                    "kind": kind,
+                    "verbs": ["get", "list", "watch"],
+                })
+            })
+            .collect::<Vec<_>>();
+        let list = serde_json::json!({
+            "kind": "APIResourceList",
+            "apiVersion": "v1",
+            "groupVersion": "apps/v1",
+            "resources": resources,
+        });
+        Response::new(Body::from(serde_json::to_vec(&list).unwrap()))
+    }
+
+    #[tokio::test]
+    async fn caches_group_versions() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/apis/apps/v1");
+            send.send_response(apps_v1(&[("deployments", "Deployment")]));
+            // A missing kind is queried again
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(apps_v1(&[("deployments", "Deployment"), ("widgets", "Widget")]));
+            // Invalidated entries are queried again
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(apps_v1(&[("deployments", "Deployment")]));
+        });
+
+        let discovery = CachedDiscovery::new(Client::new(mock_service, "default"));
+        let deployment = GroupVersionKind::gvk("apps", "v1", "Deployment");
+        let (ar, _) = discovery.resolve_gvk(&deployment).await.unwrap();
+        assert_eq!(ar.plural, "deployments");
+        discovery.resolve_gvk(&deployment).await.unwrap();
+        let widget = GroupVersionKind::gvk("apps", "v1", "Widget");
+        assert_eq!(discovery.resolve_gvk(&widget).await.unwrap().0.plural, "widgets");
+        discovery.invalidate();
+        discovery.resolve_gvk(&deployment).await.unwrap();
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn caches_missing_kinds() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(apps_v1(&[("deployments", "Deployment")]));
+            // The miss is cached, and the client is dropped without querying again
+            assert!(handle.next_request().await.is_none());
+        });
+
+        let discovery = CachedDiscovery::new(Client::new(mock_service, "default"));
+        let widget = GroupVersionKind::gvk("apps", "v1", "Widget");
+        for _ in 0..2 {
+            assert!(matches!(
+                discovery.resolve_gvk(&widget).await,
+                Err(Error::Discovery(DiscoveryError::MissingKind(_)))
+            ));
+        }
+        drop(discovery);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn caches_missing_group_versions() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/apis/widgets.example.com/v1");
+            let status = serde_json::json!({
+                "kind": "Status",
+                "apiVersion": "v1",
+                "status": "Failure",
+                "reason": "NotFound",
+                "code": 404,
+            });
+            send.send_response(
+                Response::builder()
+                    .status(404)
+                    .body(Body::from(status.to_string()))
+                    .unwrap(),
+            );
+            assert!(handle.next_request().await.is_none());
+        });
+
+        let discovery = CachedDiscovery::new(Client::new(mock_service, "default"));
+        let widget = GroupVersionKind::gvk("widgets.example.com", "v1", "Widget");
+        // The first miss is reported like the cached one
+        for _ in 0..2 {
+            assert!(matches!(
+                discovery.resolve_gvk(&widget).await,
+                Err(Error::Discovery(DiscoveryError::MissingKind(_)))
+            ));
+        }
+        drop(discovery);
+        spawned.await.unwrap();
+    }
+}
@@ -1,12 +1,19 @@
 //! High-level utilities for runtime API discovery.
 
-use crate::{Client, Result};
+use crate::{error::DiscoveryError, Client, Error, Result};
+use aggregated::{APIGroupDiscovery, Discovered};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::APIGroupList;
 pub use kube_core::discovery::{verbs, ApiCapabilities, ApiResource, Scope};
 use kube_core::gvk::GroupVersionKind;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+mod aggregated;
 mod apigroup;
+mod cache;
 pub mod oneshot;
-pub use apigroup::ApiGroup;
+mod openapi;
+pub use apigroup::{ApiGroup, VersionSelection};
+pub use cache::CachedDiscovery;
+pub use openapi::{ExplainedField, Explanation, OpenApi, OpenApiDocument};
 mod parse;
 
 // re-export one-shots
@@ -27,6 +34,31 @@ impl DiscoveryMode {
             Self::Block(blocked) => !blocked.contains(group),
         }
     }
+
+    fn is_core_only(&self) -> bool {
+        match &self {
+            Self::Allow(allowed) => allowed.iter().all(|group| group == ApiGroup::CORE_GROUP),
+            Self::Block(_) => false,
+        }
+    }
+}
+
+/// How to pick a resource when a name is served by several api groups
+///
+/// This happens when a kind moves between groups, such as `Event` in the core group and `events.k8s.io`,
+/// or when custom resources reuse a name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResolutionPolicy {
+    /// Pick the first match like kubectl, from the core group and then the other groups in alphabetical order
+    FirstMatch,
+    /// Fail with [`DiscoveryError::Ambiguous`] when more than one group matches
+    Unique,
+}
+
+impl Default for ResolutionPolicy {
+    fn default() -> Self {
+        Self::FirstMatch
+    }
 }
 
 /// A caching client for running API discovery against the Kubernetes API.
@@ -67,6 +99,32 @@ impl Discovery {
         Self { client, groups, mode }
     }
 
+    /// Construct a caching api discovery client that has discovered a single apigroup
+    ///
+    /// This only queries the named group, rather than crawling every group like [`Discovery::run`].
+    /// Re-running it keeps the discovery restricted to this group.
+    ///
+    /// ```no_run
+    /// use kube::{Client, api::{Api, DynamicObject}, discovery::Discovery, ResourceExt};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let discovery = Discovery::single_group(client.clone(), "networking.k8s.io").await?;
+    ///     let (ar, _caps) = discovery.get("networking.k8s.io").unwrap().recommended_kind("Ingress").unwrap();
+    ///     let api: Api<DynamicObject> = Api::all_with(client, &ar);
+    ///     for ingress in api.list(&Default::default()).await? {
+    ///         println!("Found Ingress: {}", ingress.name_any());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn single_group(client: Client, group: &str) -> Result<Self> {
+        let apigroup = oneshot::group(&client, group).await?;
+        let groups = HashMap::from([(group.to_string(), apigroup)]);
+        let mode = DiscoveryMode::Allow(vec![group.to_string()]);
+        Ok(Self { client, groups, mode })
+    }
+
     /// Configure the discovery client to only look for the listed apigroups
     #[must_use]
     pub fn filter(mut self, allow: &[&str]) -> Self {
@@ -85,6 +143,7 @@ impl Discovery {
     ///
     /// The cache is empty cleared when this is started. By default, every api group found is checked,
     /// causing `N+2` queries to the api server (where `N` is number of api groups).
+    /// Servers supporting aggregated discovery (Kubernetes 1.27+) serve all groups in two queries instead.
     ///
     /// ```no_run
     /// use kube::{Client, api::{Api, DynamicObject}, discovery::{Discovery, verbs, Scope}, ResourceExt};
@@ -100,7 +159,7 @@ impl Discovery {
     ///             let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
     ///             // can now api.list() to emulate kubectl get all --all
     ///             for obj in api.list(&Default::default()).await? {
-    ///                 println!("{} {}: {}", ar.api_version, ar.kind, obj.name());
+    ///                 println!("{} {}: {}", ar.api_version, ar.kind, obj.name_any());
     ///             }
     ///         }
     ///     }
@@ -110,24 +169,46 @@ impl Discovery {
     /// See a bigger example in [examples/dynamic.api](https://github.com/kube-rs/kube-rs/blob/master/examples/dynamic_api.rs)
     pub async fn run(mut self) -> Result<Self> {
         self.groups.clear();
-        let api_groups = self.client.list_api_groups().await?;
-        // query regular groups + crds under /apis
-        for g in api_groups.groups {
-            let key = g.name.clone();
-            if self.mode.is_queryable(&key) {
-                let apigroup = ApiGroup::query_apis(&self.client, g).await?;
-                self.groups.insert(key, apigroup);
+        // query regular groups + crds under /apis, unless only the core group is allowed
+        if !self.mode.is_core_only() {
+            match aggregated::query(&self.client, "/apis").await? {
+                Discovered::Aggregated(groups) => self.insert_aggregated(groups)?,
+                Discovered::Legacy(APIGroupList { groups, .. }) => {
+                    for g in groups {
+                        let key = g.name.clone();
+                        if self.mode.is_queryable(&key) {
+                            let apigroup = ApiGroup::query_apis(&self.client, g).await?;
+                            self.groups.insert(key, apigroup);
+                        }
+                    }
+                }
             }
         }
         // query core versions under /api
         let corekey = ApiGroup::CORE_GROUP.to_string();
         if self.mode.is_queryable(&corekey) {
-            let coreapis = self.client.list_core_api_versions().await?;
-            let apigroup = ApiGroup::query_core(&self.client, coreapis).await?;
-            self.groups.insert(corekey, apigroup);
+            match aggregated::query(&self.client, "/api").await? {
+                Discovered::Aggregated(groups) => self.insert_aggregated(groups)?,
+                Discovered::Legacy(coreapis) => {
+                    let apigroup = ApiGroup::query_core(&self.client, coreapis).await?;
+                    self.groups.insert(corekey, apigroup);
+                }
+            }
         }
         Ok(self)
     }
+
+    fn insert_aggregated(&mut self, groups: Vec<APIGroupDiscovery>) -> Result<()> {
+        for g in groups {
+            if !self.mode.is_queryable(&g.metadata.name) {
+                continue;
+            }
+            if let Some(apigroup) = ApiGroup::from_aggregated(g)? {
+                self.groups.insert(apigroup.name().to_string(), apigroup);
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Interface to the Discovery cache
@@ -167,4 +248,285 @@ impl Discovery {
             .into_iter()
             .find(|res| res.0.kind == gvk.kind)
     }
+
+    /// Finds an [`ApiResource`] and its [`ApiCapabilities`] after discovery by a name as accepted by kubectl
+    ///
+    /// The name is matched case-insensitively against the plural (`deployments`), the kind (`deployment`),
+    /// and then the short names (`deploy`) of the resources at the recommended version of each group.
+    /// It can be qualified with a group (`deployments.apps`) or a version and group (`deployments.v1.apps`).
+    ///
+    /// Like kubectl, the core group takes precedence, followed by the other groups in alphabetical order.
+    ///
+    /// ```no_run
+    /// use kube::{Client, api::{Api, DynamicObject}, discovery::Discovery, ResourceExt};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let discovery = Discovery::new(client.clone()).run().await?;
+    ///     let (ar, _caps) = discovery.resolve("deploy").unwrap();
+    ///     let api: Api<DynamicObject> = Api::default_namespaced_with(client, &ar);
+    ///     for deploy in api.list(&Default::default()).await? {
+    ///         println!("Found Deployment: {}", deploy.name_any());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn resolve(&self, name: &str) -> Option<(ApiResource, ApiCapabilities)> {
+        self.resolve_all(name).into_iter().next()
+    }
+
+    /// Finds a resource by a name as accepted by kubectl, choosing between matching groups by a [`ResolutionPolicy`]
+    ///
+    /// ```no_run
+    /// use kube::{Client, discovery::{Discovery, ResolutionPolicy}};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let discovery = Discovery::new(client).run().await?;
+    ///     match discovery.resolve_with("events", ResolutionPolicy::Unique) {
+    ///         Ok((ar, _caps)) => println!("using {}", ar.api_version),
+    ///         Err(err) => eprintln!("warning: {}", err),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DiscoveryError::MissingResource`] if nothing matches the name,
+    /// and [`DiscoveryError::Ambiguous`] if several groups match with [`ResolutionPolicy::Unique`].
+    pub fn resolve_with(
+        &self,
+        name: &str,
+        policy: ResolutionPolicy,
+    ) -> Result<(ApiResource, ApiCapabilities)> {
+        let mut found = self.resolve_all(name);
+        if policy == ResolutionPolicy::Unique && found.len() > 1 {
+            let candidates = found
+                .iter()
+                .map(|(ar, _)| format!("{}.{}.{}", ar.plural, ar.version, ar.group))
+                .collect();
+            return Err(Error::Discovery(DiscoveryError::Ambiguous(
+                name.to_string(),
+                candidates,
+            )));
+        }
+        if found.is_empty() {
+            return Err(Error::Discovery(DiscoveryError::MissingResource(
+                name.to_string(),
+            )));
+        }
+        Ok(found.swap_remove(0))
+    }
+
+    /// Finds every resource matching a name as accepted by kubectl, in the order of [`Discovery::resolve`]
+    ///
+    /// Short names are only considered when no plural or kind matches.
+    /// More than one result means that the name is ambiguous.
+    pub fn resolve_all(&self, name: &str) -> Vec<(ApiResource, ApiCapabilities)> {
+        let name = name.to_ascii_lowercase();
+        let (resource, candidates) = match name.split_once('.') {
+            None => {
+                let candidates = self
+                    .search_order()
+                    .into_iter()
+                    .map(|g| (g, g.preferred_version_or_latest()))
+                    .collect::<Vec<_>>();
+                (name.as_str(), candidates)
+            }
+            Some((resource, qualifier)) => {
+                let mut candidates = vec![];
+                if let Some((version, group)) = qualifier.split_once('.') {
+                    candidates.extend(self.get(group).map(|g| (g, version)));
+                }
+                candidates.extend(self.get(qualifier).map(|g| (g, g.preferred_version_or_latest())));
+                (resource, candidates)
+            }
+        };
+        let resources = candidates
+            .into_iter()
+            .flat_map(|(group, version)| group.versioned_resources(version))
+            .collect::<Vec<_>>();
+        let (named, short): (Vec<_>, Vec<_>) = resources
+            .into_iter()
+            .filter(|(ar, caps)| {
+                ar.plural == resource
+                    || ar.kind.to_ascii_lowercase() == resource
+                    || caps.short_names.iter().any(|short| short == resource)
+            })
+            .partition(|(ar, _)| ar.plural == resource || ar.kind.to_ascii_lowercase() == resource);
+        if named.is_empty() {
+            short
+        } else {
+            named
+        }
+    }
+
+    /// Returns the kinds served by more than one group at their recommended versions
+    ///
+    /// Resources are listed in the order of [`Discovery::resolve`], so the first one is what an unqualified kind resolves to.
+    pub fn ambiguous_kinds(&self) -> BTreeMap<String, Vec<ApiResource>> {
+        let mut kinds = BTreeMap::<String, Vec<ApiResource>>::new();
+        for (ar, _) in self
+            .search_order()
+            .into_iter()
+            .flat_map(ApiGroup::recommended_resources)
+        {
+            kinds.entry(ar.kind.clone()).or_default().push(ar);
+        }
+        kinds.retain(|_, resources| resources.len() > 1);
+        kinds
+    }
+
+    /// Returns all resources of a category after discovery, like `kubectl get all`
+    ///
+    /// Resources are taken at the recommended version of each group,
+    /// with the core group first and the other groups in alphabetical order.
+    pub fn resolve_category(&self, category: &str) -> Vec<(ApiResource, ApiCapabilities)> {
+        self.search_order()
+            .into_iter()
+            .flat_map(ApiGroup::recommended_resources)
+            .filter(|(_, caps)| caps.in_category(category))
+            .collect()
+    }
+
+    /// Returns the resources of every group at its recommended version, like `kubectl api-resources`
+    ///
+    /// Groups are ordered with the core group first and the others alphabetically,
+    /// and resources are ordered by name within each group.
+    /// Their [`ApiCapabilities`] describe the scope, supported verbs, and subresources of each resource.
+    ///
+    /// ```no_run
+    /// use kube::{Client, discovery::{verbs, Discovery, Scope}};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let discovery = Discovery::new(client).run().await?;
+    ///     for (ar, caps) in discovery.api_resources() {
+    ///         if caps.supports_operation(verbs::LIST) && caps.supports_operation(verbs::WATCH) {
+    ///             let namespaced = caps.scope == Scope::Namespaced;
+    ///             println!("{}\t{}\t{}\t{}", ar.plural, ar.api_version, namespaced, ar.kind);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn api_resources(&self) -> impl Iterator<Item = (ApiResource, ApiCapabilities)> + '_ {
+        self.search_order().into_iter().flat_map(|group| {
+            let mut resources = group.recommended_resources();
+            resources.sort_by(|(a, _), (b, _)| a.plural.cmp(&b.plural));
+            resources
+        })
+    }
+
+    // The core group first, then alphabetical order
+    fn search_order(&self) -> Vec<&ApiGroup> {
+        let mut groups = self.groups_alphabetical();
+        groups.sort_by_key(|g| g.name() != ApiGroup::CORE_GROUP);
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http::{Request, Response};
+    use hyper::Body;
+    use tower_test::mock;
+
+    fn apigroup(name: &str, resources: serde_json::Value) -> ApiGroup {
+        let discovery = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": name },
+            "versions": [{ "version": "v1", "resources": resources }]
+        }))
+        .unwrap();
+        ApiGroup::from_aggregated(discovery).unwrap().unwrap()
+    }
+
+    fn resource(plural: &str, kind: &str, short_names: &[&str]) -> serde_json::Value {
+        serde_json::json!({
+            "resource": plural,
+            "responseKind": { "kind": kind },
+            "scope": "Namespaced",
+            "shortNames": short_names,
+            "categories": ["all"],
+        })
+    }
+
+    #[tokio::test]
+    async fn resolves_kubectl_names() {
+        let (mock_service, _handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let mut discovery = Discovery::new(Client::new(mock_service, "default"));
+        for group in [
+            apigroup("", serde_json::json!([resource("pods", "Pod", &["po"])])),
+            apigroup(
+                "apps",
+                serde_json::json!([resource("deployments", "Deployment", &["deploy"])]),
+            ),
+            apigroup(
+                "example.com",
+                serde_json::json!([
+                    resource("pods", "Pod", &[]),
+                    resource("widgets", "Widget", &["po"])
+                ]),
+            ),
+        ] {
+            discovery.groups.insert(group.name().to_string(), group);
+        }
+
+        let group_of = |name| discovery.resolve(name).map(|(ar, _)| (ar.group, ar.plural));
+        let expected = |group: &str, plural: &str| Some((group.to_string(), plural.to_string()));
+        assert_eq!(group_of("deploy"), expected("apps", "deployments"));
+        assert_eq!(group_of("Deployment"), expected("apps", "deployments"));
+        assert_eq!(group_of("deployments.apps"), expected("apps", "deployments"));
+        assert_eq!(group_of("deployments.v1.apps"), expected("apps", "deployments"));
+        assert_eq!(group_of("deployments.v2.apps"), None);
+        // The core group takes precedence, and names over short names
+        assert_eq!(group_of("pods"), expected("", "pods"));
+        assert_eq!(group_of("po"), expected("", "pods"));
+        assert_eq!(group_of("pods.example.com"), expected("example.com", "pods"));
+        assert_eq!(group_of("po.example.com"), expected("example.com", "widgets"));
+
+        let names = discovery
+            .api_resources()
+            .map(|(ar, _)| format!("{}.{}", ar.plural, ar.group))
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![
+            "pods.",
+            "deployments.apps",
+            "pods.example.com",
+            "widgets.example.com"
+        ]);
+        assert_eq!(discovery.resolve_category("all").len(), 4);
+        assert!(discovery.resolve_category("none").is_empty());
+
+        let ambiguous = discovery.ambiguous_kinds();
+        assert_eq!(ambiguous.keys().collect::<Vec<_>>(), vec!["Pod"]);
+        let groups = ambiguous["Pod"]
+            .iter()
+            .map(|ar| ar.group.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(groups, vec!["", "example.com"]);
+        assert_eq!(discovery.resolve_all("pod").len(), 2);
+        assert_eq!(discovery.resolve_all("po").len(), 2);
+        assert_eq!(discovery.resolve_all("deploy").len(), 1);
+        let first = discovery
+            .resolve_with("pod", ResolutionPolicy::FirstMatch)
+            .unwrap();
+        assert_eq!(first.0.group, "");
+        assert!(matches!(
+            discovery.resolve_with("pod", ResolutionPolicy::Unique),
+            Err(Error::Discovery(DiscoveryError::Ambiguous(name, candidates)))
+                if name == "pod" && candidates == vec!["pods.v1.", "pods.v1.example.com"]
+        ));
+        // The candidates are fully qualified names, as accepted by kubectl
+        assert_eq!(group_of("pods.v1."), expected("", "pods"));
+        assert_eq!(group_of("pods.v1.example.com"), expected("example.com", "pods"));
+        assert!(discovery.resolve_with("widget", ResolutionPolicy::Unique).is_ok());
+        assert!(matches!(
+            discovery.resolve_with("gadget", ResolutionPolicy::Unique),
+            Err(Error::Discovery(DiscoveryError::MissingResource(_)))
+        ));
+    }
 }
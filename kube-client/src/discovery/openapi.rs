@@ -0,0 +1,516 @@
+//! Client for the OpenAPI v3 documents served by the apiserver
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use http::Request;
+use kube_core::gvk::{GroupVersion, GroupVersionKind};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::Discovery;
+use crate::{Client, Error, Result};
+
+const GVK_EXTENSION: &str = "x-kubernetes-group-version-kind";
+
+#[derive(Deserialize)]
+struct Index {
+    paths: HashMap<String, IndexEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexEntry {
+    /// URL of the document, including a hash of its content
+    server_relative_url: String,
+}
+
+/// A caching client for the OpenAPI v3 documents served at `/openapi/v3`
+///
+/// The apiserver serves one document per group version, with the schemas of all its kinds.
+/// Documents are fetched when first needed, and cached until their content changes,
+/// which is checked when the index is reloaded with [`OpenApi::refresh`].
+///
+/// This is the basis for tools implementing `kubectl explain`, client-side validation, or structural diffs.
+///
+/// Clones share the same cache.
+///
+/// ```no_run
+/// use kube::{Client, api::GroupVersionKind, discovery::OpenApi};
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let openapi = OpenApi::new(client);
+///     let gvk = GroupVersionKind::gvk("apps", "v1", "Deployment");
+///     if let Some(schema) = openapi.schema(&gvk).await? {
+///         println!("{}", schema["description"]);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "client")))]
+#[derive(Clone)]
+pub struct OpenApi {
+    client: Client,
+    // Document URLs by group version path, such as `apis/apps/v1`
+    index: Arc<Mutex<Option<Arc<HashMap<String, String>>>>>,
+    // Documents by URL, which change whenever the document does
+    documents: Arc<Mutex<HashMap<String, Arc<OpenApiDocument>>>>,
+}
+
+impl OpenApi {
+    /// Construct a client with an empty cache
+    #[must_use]
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            index: Arc::default(),
+            documents: Arc::default(),
+        }
+    }
+
+    /// Returns the group versions that have a document, such as `apis/apps/v1` or `api/v1`
+    ///
+    /// Paths outside of the resource APIs, such as `version`, are included too.
+    pub async fn paths(&self) -> Result<Vec<String>> {
+        Ok(self.index().await?.keys().cloned().collect())
+    }
+
+    /// Returns the document of a group version, or `None` if it is not served
+    pub async fn group_version(&self, gv: &GroupVersion) -> Result<Option<Arc<OpenApiDocument>>> {
+        let path = if gv.group.is_empty() {
+            format!("api/{}", gv.version)
+        } else {
+            format!("apis/{}/{}", gv.group, gv.version)
+        };
+        self.document(&path).await
+    }
+
+    /// Returns the schema of a kind, or `None` if it is not served
+    ///
+    /// Nested schemas are referenced with `$ref`, see [`OpenApiDocument::resolve`].
+    pub async fn schema(&self, gvk: &GroupVersionKind) -> Result<Option<Value>> {
+        let gv = GroupVersion::gv(&gvk.group, &gvk.version);
+        Ok(self
+            .group_version(&gv)
+            .await?
+            .and_then(|doc| doc.schema(gvk).cloned()))
+    }
+
+    /// Looks up the documentation of a field, like `kubectl explain`
+    ///
+    /// The path starts with a resource name as accepted by [`Discovery::resolve`],
+    /// followed by the names of nested fields, such as `deploy.spec.strategy`.
+    /// Returns `None` if the resource or the field is unknown.
+    ///
+    /// ```no_run
+    /// use kube::{Client, discovery::{Discovery, OpenApi}};
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let discovery = Discovery::new(client.clone()).run().await?;
+    ///     let openapi = OpenApi::new(client);
+    ///     if let Some(explanation) = openapi.explain(&discovery, "deployment.spec.strategy").await? {
+    ///         println!("{}", explanation.field.description.unwrap_or_default());
+    ///         for field in explanation.fields {
+    ///             println!("{}\t<{}>", field.name, field.type_name);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn explain(&self, discovery: &Discovery, path: &str) -> Result<Option<Explanation>> {
+        let mut segments = path.split('.');
+        let resource = segments.next().unwrap_or_default();
+        let (ar, _) = match discovery.resolve(resource) {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let gvk = GroupVersionKind::from(&ar);
+        let gv = GroupVersion::gv(&ar.group, &ar.version);
+        let doc = match self.group_version(&gv).await? {
+            Some(doc) => doc,
+            None => return Ok(None),
+        };
+        Ok(doc.explain(&gvk, &segments.collect::<Vec<_>>()))
+    }
+
+    /// Reloads the index of documents
+    ///
+    /// Documents whose content changed are fetched again when next needed, the others stay cached.
+    pub async fn refresh(&self) -> Result<()> {
+        let index = self.fetch_index().await?;
+        let current = index.values().collect::<Vec<_>>();
+        self.documents().retain(|url, _| current.contains(&url));
+        Ok(())
+    }
+
+    /// Returns the document at a path of the index, or `None` if it is not served
+    pub async fn document(&self, path: &str) -> Result<Option<Arc<OpenApiDocument>>> {
+        let url = match self.index().await?.get(path) {
+            Some(url) => url.clone(),
+            None => return Ok(None),
+        };
+        let cached = self.documents().get(&url).cloned();
+        if cached.is_some() {
+            return Ok(cached);
+        }
+        let doc = Arc::new(OpenApiDocument::new(self.get(&url).await?));
+        self.documents().insert(url, doc.clone());
+        Ok(Some(doc))
+    }
+
+    async fn index(&self) -> Result<Arc<HashMap<String, String>>> {
+        let cached = self.index.lock().expect("openapi cache lock poisoned").clone();
+        if let Some(index) = cached {
+            return Ok(index);
+        }
+        self.fetch_index().await
+    }
+
+    async fn fetch_index(&self) -> Result<Arc<HashMap<String, String>>> {
+        let index: Index = self.get("/openapi/v3").await?;
+        let index = Arc::new(
+            index
+                .paths
+                .into_iter()
+                .map(|(path, entry)| (path, entry.server_relative_url))
+                .collect::<HashMap<_, _>>(),
+        );
+        *self.index.lock().expect("openapi cache lock poisoned") = Some(index.clone());
+        Ok(index)
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let mut req = Request::get(url).body(vec![]).map_err(Error::HttpError)?;
+        req.extensions_mut().insert("openapi");
+        self.client.request(req).await
+    }
+
+    fn documents(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<OpenApiDocument>>> {
+        self.documents.lock().expect("openapi cache lock poisoned")
+    }
+}
+
+/// OpenAPI v3 document of a group version
+///
+/// The document is kept as JSON, with an index of the schemas of its kinds.
+#[derive(Debug)]
+pub struct OpenApiDocument {
+    document: Value,
+    kinds: HashMap<GroupVersionKind, String>,
+}
+
+impl OpenApiDocument {
+    /// Index the kinds of a document by their `x-kubernetes-group-version-kind` extension
+    pub fn new(document: Value) -> Self {
+        let mut kinds = HashMap::new();
+        if let Some(schemas) = document["components"]["schemas"].as_object() {
+            for (name, schema) in schemas {
+                let gvks = schema[GVK_EXTENSION].as_array().into_iter().flatten();
+                for gvk in gvks.filter_map(|gvk| serde_json::from_value(gvk.clone()).ok()) {
+                    kinds.insert(gvk, name.clone());
+                }
+            }
+        }
+        Self { document, kinds }
+    }
+
+    /// Returns the raw document
+    pub fn document(&self) -> &Value {
+        &self.document
+    }
+
+    /// Returns the kinds that have a schema in this document
+    pub fn kinds(&self) -> impl Iterator<Item = &GroupVersionKind> {
+        self.kinds.keys()
+    }
+
+    /// Returns the schema of a kind
+    pub fn schema(&self, gvk: &GroupVersionKind) -> Option<&Value> {
+        self.component(self.kinds.get(gvk)?)
+    }
+
+    /// Returns a schema of the document by name, such as `io.k8s.api.apps.v1.Deployment`
+    pub fn component(&self, name: &str) -> Option<&Value> {
+        self.document["components"]["schemas"].get(name)
+    }
+
+    /// Follows a `$ref` such as `#/components/schemas/io.k8s.api.core.v1.PodSpec` within the document
+    ///
+    /// References to other documents are not resolved.
+    pub fn resolve(&self, reference: &str) -> Option<&Value> {
+        self.component(reference.strip_prefix("#/components/schemas/")?)
+    }
+
+    /// Looks up the documentation of a field nested in a kind, like `kubectl explain`
+    ///
+    /// Fields of arrays and maps are looked up in their items, so `["spec", "containers", "image"]`
+    /// explains the image of the containers in a pod.
+    /// Returns `None` if the kind or the field is unknown.
+    pub fn explain(&self, gvk: &GroupVersionKind, path: &[&str]) -> Option<Explanation> {
+        let mut schema = self.schema(gvk)?;
+        let mut field = ExplainedField {
+            name: gvk.kind.clone(),
+            type_name: gvk.kind.clone(),
+            description: self.description(schema),
+            required: false,
+        };
+        for name in path {
+            let parent = self.target(schema);
+            schema = parent["properties"].get(*name)?;
+            field = self.field(parent, name, schema);
+        }
+        let target = self.target(schema);
+        let fields = target["properties"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, schema)| self.field(target, name, schema))
+            .collect();
+        Some(Explanation {
+            gvk: gvk.clone(),
+            path: path.iter().map(ToString::to_string).collect(),
+            field,
+            fields,
+        })
+    }
+
+    fn field(&self, parent: &Value, name: &str, schema: &Value) -> ExplainedField {
+        let required = parent["required"]
+            .as_array()
+            .map_or(false, |required| required.iter().any(|r| r == name));
+        ExplainedField {
+            name: name.to_string(),
+            type_name: self.type_name(schema),
+            description: self.description(schema),
+            required,
+        }
+    }
+
+    // Descriptions of references are set next to them, falling back to the description of the referenced schema
+    fn description(&self, schema: &Value) -> Option<String> {
+        schema["description"]
+            .as_str()
+            .or_else(|| self.follow(schema)["description"].as_str())
+            .map(String::from)
+    }
+
+    // Follows a `$ref`, which is wrapped in `allOf` when the description is overridden
+    fn follow<'a>(&'a self, schema: &'a Value) -> &'a Value {
+        let reference = schema["$ref"]
+            .as_str()
+            .or_else(|| schema["allOf"][0]["$ref"].as_str());
+        reference.and_then(|r| self.resolve(r)).unwrap_or(schema)
+    }
+
+    // The schema holding the nested fields, through arrays and maps
+    fn target<'a>(&'a self, schema: &'a Value) -> &'a Value {
+        let schema = self.follow(schema);
+        if schema["type"] == "array" {
+            self.target(&schema["items"])
+        } else if schema["additionalProperties"].is_object() && schema.get("properties").is_none() {
+            self.target(&schema["additionalProperties"])
+        } else {
+            schema
+        }
+    }
+
+    fn type_name(&self, schema: &Value) -> String {
+        let reference = schema["$ref"]
+            .as_str()
+            .or_else(|| schema["allOf"][0]["$ref"].as_str());
+        if let Some(reference) = reference {
+            return reference.rsplit('.').next().unwrap_or(reference).to_string();
+        }
+        if schema["x-kubernetes-int-or-string"] == true {
+            return "IntOrString".into();
+        }
+        match schema["type"].as_str() {
+            Some("array") => format!("[]{}", self.type_name(&schema["items"])),
+            Some("object") if schema["additionalProperties"].is_object() => {
+                format!("map[string]{}", self.type_name(&schema["additionalProperties"]))
+            }
+            Some("object") | None => "Object".into(),
+            Some(other) => other.into(),
+        }
+    }
+}
+
+/// Documentation of a field and its nested fields, see [`OpenApi::explain`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The kind containing the field
+    pub gvk: GroupVersionKind,
+    /// Path of the field within the kind, empty for the kind itself
+    pub path: Vec<String>,
+    /// The explained field
+    pub field: ExplainedField,
+    /// Fields nested in the explained field, by name
+    pub fields: Vec<ExplainedField>,
+}
+
+/// Documentation of a single field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainedField {
+    /// Name of the field
+    pub name: String,
+    /// Type of the field, such as `string`, `[]Container`, or `map[string]string`
+    pub type_name: String,
+    /// Description of the field
+    pub description: Option<String>,
+    /// Whether the field is required by its parent
+    pub required: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::pin_mut;
+    use http::Response;
+    use hyper::Body;
+    use tower_test::mock;
+
+    #[test]
+    fn indexes_kinds() {
+        let doc = OpenApiDocument::new(serde_json::json!({
+            "openapi": "3.0.0",
+            "components": {
+                "schemas": {
+                    "io.k8s.api.apps.v1.Deployment": {
+                        "description": "Deployment enables declarative updates for Pods and ReplicaSets.",
+                        "properties": {
+                            "spec": { "allOf": [{ "$ref": "#/components/schemas/io.k8s.api.apps.v1.DeploymentSpec" }] }
+                        },
+                        "x-kubernetes-group-version-kind": [{ "group": "apps", "kind": "Deployment", "version": "v1" }]
+                    },
+                    "io.k8s.api.apps.v1.DeploymentSpec": {
+                        "description": "DeploymentSpec is the specification of the desired behavior of the Deployment."
+                    }
+                }
+            }
+        }));
+        let gvk = GroupVersionKind::gvk("apps", "v1", "Deployment");
+        assert_eq!(doc.kinds().collect::<Vec<_>>(), vec![&gvk]);
+        let schema = doc.schema(&gvk).unwrap();
+        let reference = schema["properties"]["spec"]["allOf"][0]["$ref"].as_str().unwrap();
+        assert!(doc.resolve(reference).is_some());
+        assert!(doc
+            .schema(&GroupVersionKind::gvk("apps", "v1", "StatefulSet"))
+            .is_none());
+    }
+
+    #[test]
+    fn explains_fields() {
+        let doc = OpenApiDocument::new(serde_json::json!({
+            "components": {
+                "schemas": {
+                    "io.k8s.api.core.v1.Pod": {
+                        "description": "Pod is a collection of containers.",
+                        "properties": {
+                            "spec": {
+                                "allOf": [{ "$ref": "#/components/schemas/io.k8s.api.core.v1.PodSpec" }],
+                                "description": "Specification of the desired behavior of the pod."
+                            }
+                        },
+                        "x-kubernetes-group-version-kind": [{ "group": "", "kind": "Pod", "version": "v1" }]
+                    },
+                    "io.k8s.api.core.v1.PodSpec": {
+                        "properties": {
+                            "containers": {
+                                "type": "array",
+                                "items": { "allOf": [{ "$ref": "#/components/schemas/io.k8s.api.core.v1.Container" }] }
+                            },
+                            "nodeSelector": {
+                                "type": "object",
+                                "additionalProperties": { "type": "string" }
+                            }
+                        },
+                        "required": ["containers"]
+                    },
+                    "io.k8s.api.core.v1.Container": {
+                        "description": "A single application container.",
+                        "properties": {
+                            "image": { "type": "string", "description": "Container image name." }
+                        }
+                    }
+                }
+            }
+        }));
+        let pod = GroupVersionKind::gvk("", "v1", "Pod");
+
+        let explained = doc.explain(&pod, &[]).unwrap();
+        let description = explained.field.description.as_deref();
+        assert_eq!(description, Some("Pod is a collection of containers."));
+        assert_eq!(explained.fields[0].type_name, "PodSpec");
+
+        let spec = doc.explain(&pod, &["spec"]).unwrap();
+        assert_eq!(
+            spec.field.description.as_deref(),
+            Some("Specification of the desired behavior of the pod.")
+        );
+        let fields = spec
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.type_name.as_str(), f.required))
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec![
+            ("containers", "[]Container", true),
+            ("nodeSelector", "map[string]string", false)
+        ]);
+
+        let image = doc.explain(&pod, &["spec", "containers", "image"]).unwrap();
+        assert_eq!(image.field.type_name, "string");
+        assert_eq!(image.field.description.as_deref(), Some("Container image name."));
+        assert!(image.fields.is_empty());
+        assert!(doc.explain(&pod, &["spec", "missing"]).is_none());
+    }
+
+    fn json(value: Value) -> Response<Body> {
+        Response::new(Body::from(serde_json::to_vec(&value).unwrap()))
+    }
+
+    fn index(hash: &str) -> Response<Body> {
+        json(serde_json::json!({
+            "paths": {
+                "apis/apps/v1": { "serverRelativeURL": format!("/openapi/v3/apis/apps/v1?hash={}", hash) }
+            }
+        }))
+    }
+
+    #[tokio::test]
+    async fn caches_documents() {
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().path(), "/openapi/v3");
+            send.send_response(index("A"));
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("hash=A"));
+            send.send_response(json(serde_json::json!({
+                "components": { "schemas": { "io.k8s.api.apps.v1.Deployment": {
+                    "x-kubernetes-group-version-kind": [{ "group": "apps", "kind": "Deployment", "version": "v1" }]
+                }}}
+            })));
+            // Documents with a changed hash are fetched again
+            let (_, send) = handle.next_request().await.expect("service not called");
+            send.send_response(index("B"));
+            let (request, send) = handle.next_request().await.expect("service not called");
+            assert_eq!(request.uri().query(), Some("hash=B"));
+            send.send_response(json(serde_json::json!({ "components": { "schemas": {} } })));
+        });
+
+        let openapi = OpenApi::new(Client::new(mock_service, "default"));
+        let deployment = GroupVersionKind::gvk("apps", "v1", "Deployment");
+        assert!(openapi.schema(&deployment).await.unwrap().is_some());
+        assert!(openapi.schema(&deployment).await.unwrap().is_some());
+        let batch = GroupVersion::gv("batch", "v1");
+        assert!(openapi.group_version(&batch).await.unwrap().is_none());
+        openapi.refresh().await.unwrap();
+        assert!(openapi.schema(&deployment).await.unwrap().is_none());
+        spawned.await.unwrap();
+    }
+}
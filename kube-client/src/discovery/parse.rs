@@ -56,6 +56,8 @@ pub(crate) fn parse_apicapabilities(list: &APIResourceList, name: &str) -> Resul
         scope,
         subresources,
         operations: ar.verbs.clone(),
+        short_names: ar.short_names.clone().unwrap_or_default(),
+        categories: ar.categories.clone().unwrap_or_default(),
     })
 }
 
@@ -1,7 +1,7 @@
 //! Error handling in [`kube`][crate]
 use thiserror::Error;
 
-pub use kube_core::ErrorResponse;
+pub use kube_core::{ApplyConflict, ErrorResponse};
 
 /// Possible errors when working with [`kube`][crate]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "config", feature = "client"))))]
@@ -24,6 +24,10 @@ pub enum Error {
     #[cfg(feature = "client")]
     #[error("ServiceError: {0}")]
     Service(#[source] tower::BoxError),
+    /// Request did not complete within its [`request_timeout`](crate::Config::request_timeout)
+    #[cfg(feature = "client")]
+    #[error("request timed out after {0:?}")]
+    RequestTimeout(std::time::Duration),
 
     /// UTF-8 Error
     #[error("UTF-8 Error: {0}")]
@@ -47,6 +51,10 @@ pub enum Error {
     #[error("Error deserializing response")]
     SerdeError(#[source] serde_json::Error),
 
+    /// A listed object is of another type than the listed resource
+    #[error("Unexpected type in list: {0}")]
+    UnexpectedType(#[source] kube_core::UnexpectedTypeError),
+
     /// Failed to build request
     #[error("Failed to build request: {0}")]
     BuildRequest(#[source] kube_core::request::Error),
@@ -112,4 +120,10 @@ pub enum DiscoveryError {
     /// Empty ApiGroup
     #[error("Empty Api Group: {0}")]
     EmptyApiGroup(String),
+
+    /// A resource name is served by several api groups
+    ///
+    /// The candidates are fully qualified as `plural.version.group`, like kubectl accepts them.
+    #[error("Ambiguous resource {0}, served as {1:?}")]
+    Ambiguous(String, Vec<String>),
 }
@@ -47,7 +47,7 @@
 //!
 //!     // List pods in the configured namespace
 //!     for p in pods.list(&ListParams::default()).await? {
-//!         println!("found pod {}", p.name());
+//!         println!("found pod {}", p.name_any());
 //!     }
 //!
 //!     Ok(())
@@ -135,7 +135,7 @@ mod test {
         client::ConfigExt,
         Api, Client, Config, ResourceExt,
     };
-    use futures::{StreamExt, TryStreamExt};
+    use futures::{AsyncBufReadExt, StreamExt, TryStreamExt};
     use k8s_openapi::api::core::v1::Pod;
     use kube_core::params::{DeleteParams, Patch};
     use serde_json::json;
@@ -202,10 +202,26 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    #[ignore] // needs cluster (lists api resources)
+    #[cfg(feature = "client")]
+    async fn single_group_discovery() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{core::DynamicObject, discovery::Discovery};
+        let client = Client::try_default().await?;
+        let discovery = Discovery::single_group(client.clone(), "apiregistration.k8s.io").await?;
+        assert_eq!(discovery.groups().count(), 1);
+        let apigroup = discovery.get("apiregistration.k8s.io").unwrap();
+        let (ar, _caps) = apigroup.recommended_kind("APIService").unwrap();
+        let api: Api<DynamicObject> = Api::all_with(client.clone(), &ar);
+        api.list(&Default::default()).await?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[ignore] // needs cluster (will create and edit a pod)
     async fn pod_can_use_core_apis() -> Result<(), Box<dyn std::error::Error>> {
-        use kube::api::{DeleteParams, ListParams, Patch, PatchParams, PostParams, WatchEvent};
+        use kube::api::{DeleteParams, Patch, PatchParams, PostParams, WatchEvent, WatchParams};
 
         let client = Client::try_default().await?;
         let pods: Api<Pod> = Api::default_namespaced(client);
@@ -231,17 +247,17 @@ mod test {
 
         let pp = PostParams::default();
         match pods.create(&pp, &p).await {
-            Ok(o) => assert_eq!(p.name(), o.name()),
+            Ok(o) => assert_eq!(p.name_unchecked(), o.name_unchecked()),
             Err(crate::Error::Api(ae)) => assert_eq!(ae.code, 409), // if we failed to clean-up
             Err(e) => return Err(e.into()),                         // any other case if a failure
         }
 
         // Manual watch-api for it to become ready
         // NB: don't do this; using conditions (see pod_api example) is easier and less error prone
-        let lp = ListParams::default()
+        let wp = WatchParams::default()
             .fields(&format!("metadata.name={}", "busybox-kube1"))
             .timeout(15);
-        let mut stream = pods.watch(&lp, "0").await?.boxed();
+        let mut stream = pods.watch(&wp, "0").await?.boxed();
         while let Some(ev) = stream.try_next().await? {
             // can debug format watch event
             let _ = format!("we: {:?}", ev);
@@ -276,7 +292,7 @@ mod test {
         // Delete it
         let dp = DeleteParams::default();
         pods.delete("busybox-kube1", &dp).await?.map_left(|pdel| {
-            assert_eq!(pdel.name(), "busybox-kube1");
+            assert_eq!(pdel.name_unchecked(), "busybox-kube1");
         });
 
         Ok(())
@@ -286,7 +302,7 @@ mod test {
     #[ignore] // needs cluster (will create and attach to a pod)
     #[cfg(all(feature = "ws"))]
     async fn pod_can_exec_and_write_to_stdin() -> Result<(), Box<dyn std::error::Error>> {
-        use crate::api::{DeleteParams, ListParams, Patch, PatchParams, WatchEvent};
+        use crate::api::{DeleteParams, Patch, PatchParams, WatchEvent, WatchParams};
 
         let client = Client::try_default().await?;
         let pods: Api<Pod> = Api::default_namespaced(client);
@@ -311,17 +327,17 @@ mod test {
         }))?;
 
         match pods.create(&Default::default(), &p).await {
-            Ok(o) => assert_eq!(p.name(), o.name()),
+            Ok(o) => assert_eq!(p.name_unchecked(), o.name_unchecked()),
             Err(crate::Error::Api(ae)) => assert_eq!(ae.code, 409), // if we failed to clean-up
             Err(e) => return Err(e.into()),                         // any other case if a failure
         }
 
         // Manual watch-api for it to become ready
         // NB: don't do this; using conditions (see pod_api example) is easier and less error prone
-        let lp = ListParams::default()
+        let wp = WatchParams::default()
             .fields(&format!("metadata.name={}", "busybox-kube2"))
             .timeout(15);
-        let mut stream = pods.watch(&lp, "0").await?.boxed();
+        let mut stream = pods.watch(&wp, "0").await?.boxed();
         while let Some(ev) = stream.try_next().await? {
             match ev {
                 WatchEvent::Modified(o) => {
@@ -388,7 +404,7 @@ mod test {
         // Delete it
         let dp = DeleteParams::default();
         pods.delete("busybox-kube2", &dp).await?.map_left(|pdel| {
-            assert_eq!(pdel.name(), "busybox-kube2");
+            assert_eq!(pdel.name_unchecked(), "busybox-kube2");
         });
 
         Ok(())
@@ -398,7 +414,7 @@ mod test {
     #[ignore] // needs cluster (will create and tail logs from a pod)
     async fn can_get_pod_logs_and_evict() -> Result<(), Box<dyn std::error::Error>> {
         use crate::{
-            api::{DeleteParams, EvictParams, ListParams, Patch, PatchParams, WatchEvent},
+            api::{DeleteParams, EvictParams, Patch, PatchParams, WatchEvent, WatchParams},
             core::subresource::LogParams,
         };
 
@@ -425,17 +441,17 @@ mod test {
         }))?;
 
         match pods.create(&Default::default(), &p).await {
-            Ok(o) => assert_eq!(p.name(), o.name()),
+            Ok(o) => assert_eq!(p.name_unchecked(), o.name_unchecked()),
             Err(crate::Error::Api(ae)) => assert_eq!(ae.code, 409), // if we failed to clean-up
             Err(e) => return Err(e.into()),                         // any other case if a failure
         }
 
         // Manual watch-api for it to become ready
         // NB: don't do this; using conditions (see pod_api example) is easier and less error prone
-        let lp = ListParams::default()
+        let wp = WatchParams::default()
             .fields(&format!("metadata.name={}", "busybox-kube3"))
             .timeout(15);
-        let mut stream = pods.watch(&lp, "0").await?.boxed();
+        let mut stream = pods.watch(&wp, "0").await?.boxed();
         while let Some(ev) = stream.try_next().await? {
             match ev {
                 WatchEvent::Modified(o) => {
@@ -455,9 +471,9 @@ mod test {
             follow: true,
             ..LogParams::default()
         };
-        let mut logs_stream = pods.log_stream("busybox-kube3", &lp).await?.boxed();
+        let mut logs_stream = pods.log_stream("busybox-kube3", &lp).await?.lines();
         let log_line = logs_stream.try_next().await?.unwrap();
-        assert_eq!(log_line, "kube 1\n");
+        assert_eq!(log_line, "kube 1");
 
         // wait for container to finish
         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
@@ -466,10 +482,10 @@ mod test {
         assert_eq!(all_logs, "kube 1\nkube 2\nkube 3\nkube 4\nkube 5\n");
 
         // remaining logs should have been buffered internally
-        assert_eq!(logs_stream.try_next().await?.unwrap(), "kube 2\n");
-        assert_eq!(logs_stream.try_next().await?.unwrap(), "kube 3\n");
-        assert_eq!(logs_stream.try_next().await?.unwrap(), "kube 4\n");
-        assert_eq!(logs_stream.try_next().await?.unwrap(), "kube 5\n");
+        assert_eq!(logs_stream.try_next().await?.unwrap(), "kube 2");
+        assert_eq!(logs_stream.try_next().await?.unwrap(), "kube 3");
+        assert_eq!(logs_stream.try_next().await?.unwrap(), "kube 4");
+        assert_eq!(logs_stream.try_next().await?.unwrap(), "kube 5");
 
         // evict the pod
         let ep = EvictParams::default();
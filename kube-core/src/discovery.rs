@@ -1,5 +1,8 @@
 //! Type information structs for API discovery
-use crate::{gvk::GroupVersionKind, resource::Resource};
+use crate::{gvk::GroupVersionKind, resource::Resource, Version};
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::{
+    CustomResourceDefinition, CustomResourceDefinitionVersion,
+};
 use serde::{Deserialize, Serialize};
 
 /// Information about a Kubernetes API resource
@@ -55,6 +58,17 @@ impl ApiResource {
     pub fn from_gvk(gvk: &GroupVersionKind) -> Self {
         ApiResource::from_gvk_with_plural(gvk, &to_plural(&gvk.kind.to_ascii_lowercase()))
     }
+
+    /// Creates an ApiResource for a served version of a CustomResourceDefinition.
+    ///
+    /// Without a `version`, the storage version is picked if it is served,
+    /// and otherwise the latest served version by [`Version`] priority.
+    /// Returns `None` if no such version is served.
+    pub fn from_crd(crd: &CustomResourceDefinition, version: Option<&str>) -> Option<Self> {
+        let version = served_crd_version(crd, version)?;
+        let gvk = GroupVersionKind::gvk(&crd.spec.group, &version.name, &crd.spec.names.kind);
+        Some(ApiResource::from_gvk_with_plural(&gvk, &crd.spec.names.plural))
+    }
 }
 
 /// Resource scope
@@ -99,6 +113,10 @@ pub struct ApiCapabilities {
     pub subresources: Vec<(ApiResource, ApiCapabilities)>,
     /// Supported operations on this resource
     pub operations: Vec<String>,
+    /// Abbreviations accepted by kubectl, such as `deploy` for deployments
+    pub short_names: Vec<String>,
+    /// Groupings the resource belongs to, such as `all`
+    pub categories: Vec<String>,
 }
 
 impl ApiCapabilities {
@@ -106,6 +124,86 @@ impl ApiCapabilities {
     pub fn supports_operation(&self, operation: &str) -> bool {
         self.operations.iter().any(|op| op == operation)
     }
+
+    /// Checks that the resource belongs to the given category.
+    pub fn in_category(&self, category: &str) -> bool {
+        self.categories.iter().any(|c| c == category)
+    }
+
+    /// Creates the ApiCapabilities of a served version of a CustomResourceDefinition.
+    ///
+    /// The version is picked like in [`ApiResource::from_crd`].
+    /// Custom resources support all verbs, along with the status and scale subresources if enabled.
+    pub fn from_crd(crd: &CustomResourceDefinition, version: Option<&str>) -> Option<Self> {
+        let ar = ApiResource::from_crd(crd, version)?;
+        let version = served_crd_version(crd, Some(&ar.version))?;
+        let scope = if crd.spec.scope == "Namespaced" {
+            Scope::Namespaced
+        } else {
+            Scope::Cluster
+        };
+        let subresource = |group: &str, version: &str, kind: &str, plural: &str| {
+            let ar = ApiResource {
+                group: group.to_string(),
+                version: version.to_string(),
+                api_version: ar.api_version.clone(),
+                kind: kind.to_string(),
+                plural: plural.to_string(),
+            };
+            let caps = ApiCapabilities {
+                scope: scope.clone(),
+                subresources: vec![],
+                operations: vec![verbs::GET.into(), verbs::PATCH.into(), verbs::UPDATE.into()],
+                short_names: vec![],
+                categories: vec![],
+            };
+            (ar, caps)
+        };
+        let mut subresources = vec![];
+        if let Some(sub) = &version.subresources {
+            if sub.status.is_some() {
+                subresources.push(subresource(&ar.group, &ar.version, &ar.kind, "status"));
+            }
+            if sub.scale.is_some() {
+                subresources.push(subresource("autoscaling", "v1", "Scale", "scale"));
+            }
+        }
+        let operations = [
+            verbs::CREATE,
+            verbs::DELETE,
+            verbs::DELETE_COLLECTION,
+            verbs::GET,
+            verbs::LIST,
+            verbs::PATCH,
+            verbs::UPDATE,
+            verbs::WATCH,
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        Some(ApiCapabilities {
+            scope,
+            subresources,
+            operations,
+            short_names: crd.spec.names.short_names.clone().unwrap_or_default(),
+            categories: crd.spec.names.categories.clone().unwrap_or_default(),
+        })
+    }
+}
+
+// Finds the requested version, or the storage version, or the latest version among the served ones
+fn served_crd_version<'a>(
+    crd: &'a CustomResourceDefinition,
+    version: Option<&str>,
+) -> Option<&'a CustomResourceDefinitionVersion> {
+    let mut served = crd.spec.versions.iter().filter(|v| v.served);
+    match version {
+        Some(version) => served.find(|v| v.name == version),
+        None => served
+            .clone()
+            .find(|v| v.storage)
+            .or_else(|| served.max_by_key(|v| Version::parse(&v.name).priority())),
+    }
 }
 
 // Simple pluralizer. Handles the special cases.
@@ -204,3 +302,38 @@ fn test_to_plural_native() {
         assert_eq!(to_plural(&kind.to_ascii_lowercase()), plural);
     }
 }
+
+#[test]
+fn test_from_crd() {
+    let crd: CustomResourceDefinition = serde_json::from_value(serde_json::json!({
+        "apiVersion": "apiextensions.k8s.io/v1",
+        "kind": "CustomResourceDefinition",
+        "metadata": { "name": "foxes.example.com" },
+        "spec": {
+            "group": "example.com",
+            "names": { "kind": "Fox", "plural": "foxes", "singular": "fox", "shortNames": ["fx"] },
+            "scope": "Cluster",
+            "versions": [
+                { "name": "v1alpha1", "served": false, "storage": false },
+                { "name": "v1beta1", "served": true, "storage": true, "subresources": { "status": {} } },
+                { "name": "v1", "served": true, "storage": false },
+            ]
+        }
+    }))
+    .unwrap();
+
+    let ar = ApiResource::from_crd(&crd, None).unwrap();
+    assert_eq!(ar.api_version, "example.com/v1beta1");
+    assert_eq!((ar.kind.as_str(), ar.plural.as_str()), ("Fox", "foxes"));
+    assert_eq!(ApiResource::from_crd(&crd, Some("v1")).unwrap().version, "v1");
+    assert!(ApiResource::from_crd(&crd, Some("v1alpha1")).is_none());
+
+    let caps = ApiCapabilities::from_crd(&crd, None).unwrap();
+    assert_eq!(caps.scope, Scope::Cluster);
+    assert_eq!(caps.short_names, vec!["fx"]);
+    assert!(caps.supports_operation(verbs::WATCH));
+    assert_eq!(caps.subresources.len(), 1);
+    assert_eq!(caps.subresources[0].0.plural, "status");
+    let caps = ApiCapabilities::from_crd(&crd, Some("v1")).unwrap();
+    assert!(caps.subresources.is_empty());
+}
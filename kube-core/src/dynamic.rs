@@ -4,11 +4,48 @@
 
 pub use crate::discovery::ApiResource;
 use crate::{
+    error::TypeMismatchError,
     metadata::TypeMeta,
+    object::{Object, ObjectList},
     resource::{DynamicResourceScope, Resource},
 };
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
-use std::borrow::Cow;
+use serde::{
+    de::{self, Deserialize, DeserializeOwned, Deserializer, MapAccess, Visitor},
+    ser::{SerializeMap, Serializer},
+    Serialize,
+};
+use serde_json::{value::RawValue, Value};
+use std::{borrow::Cow, collections::BTreeMap, fmt};
+use thiserror::Error;
+
+/// Failed to convert between a [`DynamicObject`] and a typed resource
+#[derive(Debug, Error)]
+pub enum ParseDynamicObjectError {
+    /// The object is of another type than the requested resource
+    #[error("object is of another type: {0}")]
+    TypeMismatch(#[source] TypeMismatchError),
+
+    /// The object could not be (de)serialized as the requested resource
+    #[error("failed to convert object: {0}")]
+    Serde(#[source] serde_json::Error),
+}
+
+/// Failed to set a nested field of a [`DynamicObject`]
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("cannot set field {0}: a parent is not an object or an existing array index")]
+pub struct SetPathError(pub String);
+
+/// An item of a listed [`ObjectList`] is of another type than the listed resource
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("object {name:?} is of another type: {mismatch}")]
+pub struct UnexpectedTypeError {
+    /// Name of the object, if set
+    pub name: Option<String>,
+    /// The listed and actual types of the object
+    #[source]
+    pub mismatch: TypeMismatchError,
+}
 
 /// A dynamic representation of a kubernetes object
 ///
@@ -56,6 +93,149 @@ impl DynamicObject {
         self.metadata.namespace = Some(ns.into());
         self
     }
+
+    /// Returns the value of a nested field of the payload, such as `&["spec", "replicas"]`
+    ///
+    /// Array items are addressed by their index, such as `&["spec", "containers", "0", "image"]`.
+    /// The path is relative to [`DynamicObject::data`], so it excludes the metadata and type fields.
+    pub fn get_path(&self, path: &[&str]) -> Option<&Value> {
+        path.iter().try_fold(&self.data, |value, key| match value {
+            Value::Object(map) => map.get(*key),
+            Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a nested field of the payload, see [`DynamicObject::get_path`]
+    pub fn get_path_mut(&mut self, path: &[&str]) -> Option<&mut Value> {
+        path.iter().try_fold(&mut self.data, |value, key| match value {
+            Value::Object(map) => map.get_mut(*key),
+            Value::Array(items) => items.get_mut(key.parse::<usize>().ok()?),
+            _ => None,
+        })
+    }
+
+    /// Returns a nested field of the payload deserialized as `T`, see [`DynamicObject::get_path`]
+    ///
+    /// Returns `None` if the field is missing or is not a `T`.
+    ///
+    /// ```
+    /// # use kube_core::{ApiResource, DynamicObject, GroupVersionKind};
+    /// # let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("apps", "v1", "Deployment"));
+    /// let deploy = DynamicObject::new("web", &ar).data(serde_json::json!({ "spec": { "replicas": 3 } }));
+    /// assert_eq!(deploy.get_path_as::<i64>(&["spec", "replicas"]), Some(3));
+    /// assert_eq!(deploy.get_path_as::<&str>(&["spec", "replicas"]), None);
+    /// ```
+    pub fn get_path_as<'a, T: Deserialize<'a>>(&'a self, path: &[&str]) -> Option<T> {
+        T::deserialize(self.get_path(path)?).ok()
+    }
+
+    /// Sets a nested field of the payload, returning its previous value, see [`DynamicObject::get_path`]
+    ///
+    /// Missing or null parents are created as objects.
+    /// Fails if a parent is another kind of value, or if an array index is out of bounds.
+    pub fn set_path(&mut self, path: &[&str], value: Value) -> Result<Option<Value>, SetPathError> {
+        let err = || SetPathError(path.join("."));
+        let mut current = &mut self.data;
+        for key in path {
+            if current.is_null() {
+                *current = Value::Object(Default::default());
+            }
+            current = match current {
+                Value::Object(map) => map.entry(*key).or_insert(Value::Null),
+                Value::Array(items) => key
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| items.get_mut(i))
+                    .ok_or_else(err)?,
+                _ => return Err(err()),
+            };
+        }
+        let previous = std::mem::replace(current, value);
+        Ok(Some(previous).filter(|previous| !previous.is_null()))
+    }
+
+    /// Removes a nested field of the payload, returning its value, see [`DynamicObject::get_path`]
+    pub fn remove_path(&mut self, path: &[&str]) -> Option<Value> {
+        let (key, parent) = path.split_last()?;
+        match self.get_path_mut(parent)? {
+            Value::Object(map) => map.remove(*key),
+            Value::Array(items) => {
+                let index = key.parse::<usize>().ok().filter(|i| *i < items.len())?;
+                Some(items.remove(index))
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts the object into a typed resource
+    ///
+    /// The `apiVersion` and `kind` of the object must match those of `K` if present,
+    /// otherwise they are assumed to be those of `K`.
+    pub fn try_parse<K>(self) -> Result<K, ParseDynamicObjectError>
+    where
+        K: Resource + DeserializeOwned,
+        K::DynamicType: Default,
+    {
+        let dt = K::DynamicType::default();
+        let expected = TypeMeta {
+            api_version: K::api_version(&dt).into_owned(),
+            kind: K::kind(&dt).into_owned(),
+        };
+        if let Some(found) = self.types.as_ref().filter(|found| *found != &expected) {
+            return Err(ParseDynamicObjectError::TypeMismatch(TypeMismatchError::new(
+                &expected, found,
+            )));
+        }
+        reserialize(&DynamicObject {
+            types: Some(expected),
+            ..self
+        })
+    }
+
+    /// Converts a typed resource into a DynamicObject
+    ///
+    /// This is the reverse of [`DynamicObject::try_parse`].
+    ///
+    /// `TryFrom` cannot be implemented for every `K`, since it would overlap with the blanket
+    /// `impl<T, U: Into<T>> TryFrom<U> for T` of the standard library. It is implemented for [`Object`].
+    pub fn try_from_resource<K>(resource: &K) -> Result<Self, ParseDynamicObjectError>
+    where
+        K: Resource + Serialize,
+        K::DynamicType: Default,
+    {
+        let mut obj: Self = reserialize(resource)?;
+        if obj.types.is_none() {
+            let dt = K::DynamicType::default();
+            obj.types = Some(TypeMeta {
+                api_version: K::api_version(&dt).into_owned(),
+                kind: K::kind(&dt).into_owned(),
+            });
+        }
+        Ok(obj)
+    }
+}
+
+/// Converts between types that serialize to the same JSON
+fn reserialize<T: Serialize, R: DeserializeOwned>(value: &T) -> Result<R, ParseDynamicObjectError> {
+    let value = serde_json::to_value(value).map_err(ParseDynamicObjectError::Serde)?;
+    serde_json::from_value(value).map_err(ParseDynamicObjectError::Serde)
+}
+
+impl<P: Serialize, U: Serialize> TryFrom<Object<P, U>> for DynamicObject {
+    type Error = ParseDynamicObjectError;
+
+    fn try_from(obj: Object<P, U>) -> Result<Self, Self::Error> {
+        reserialize(&obj)
+    }
+}
+
+impl<P: DeserializeOwned, U: DeserializeOwned> TryFrom<DynamicObject> for Object<P, U> {
+    type Error = ParseDynamicObjectError;
+
+    fn try_from(obj: DynamicObject) -> Result<Self, Self::Error> {
+        reserialize(&obj)
+    }
 }
 
 impl Resource for DynamicObject {
@@ -91,6 +271,176 @@ impl Resource for DynamicObject {
     }
 }
 
+/// A [`DynamicObject`] that only parses its payload on demand
+///
+/// The metadata is parsed eagerly, but every other top level field, such as `spec` or `status`,
+/// is kept as unparsed JSON until requested.
+/// This avoids allocating the JSON tree of large objects for controllers that mostly look at metadata.
+///
+/// It can be used with the dynamic `Api` constructors like a [`DynamicObject`].
+#[derive(Clone, Debug)]
+pub struct LazyDynamicObject {
+    /// The type fields, not always present
+    pub types: Option<TypeMeta>,
+    /// Object metadata
+    pub metadata: ObjectMeta,
+
+    /// All other keys, unparsed
+    payload: BTreeMap<String, Box<RawValue>>,
+}
+
+impl LazyDynamicObject {
+    /// Returns the unparsed JSON of a top level field, such as `spec`
+    pub fn raw_field(&self, key: &str) -> Option<&RawValue> {
+        self.payload.get(key).map(|raw| &**raw)
+    }
+
+    /// Returns the names of the top level fields besides the metadata and type fields
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.payload.keys().map(String::as_str)
+    }
+
+    /// Parses a top level field, such as `spec`, or returns `None` if it is missing
+    pub fn parse_field<'a, T: Deserialize<'a>>(&'a self, key: &str) -> Option<Result<T, serde_json::Error>> {
+        self.raw_field(key).map(|raw| serde_json::from_str(raw.get()))
+    }
+
+    /// Parses the whole payload into a [`DynamicObject`]
+    pub fn parse(&self) -> Result<DynamicObject, serde_json::Error> {
+        let data = self
+            .payload
+            .iter()
+            .map(|(key, raw)| Ok((key.clone(), serde_json::from_str(raw.get())?)))
+            .collect::<Result<serde_json::Map<_, _>, serde_json::Error>>()?;
+        Ok(DynamicObject {
+            types: self.types.clone(),
+            metadata: self.metadata.clone(),
+            data: Value::Object(data),
+        })
+    }
+}
+
+impl Serialize for LazyDynamicObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(types) = &self.types {
+            map.serialize_entry("apiVersion", &types.api_version)?;
+            map.serialize_entry("kind", &types.kind)?;
+        }
+        map.serialize_entry("metadata", &self.metadata)?;
+        for (key, raw) in &self.payload {
+            map.serialize_entry(key, raw)?;
+        }
+        map.end()
+    }
+}
+
+// Flattening would buffer the payload, so the fields are collected by hand
+impl<'de> Deserialize<'de> for LazyDynamicObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LazyVisitor;
+
+        impl<'de> Visitor<'de> for LazyVisitor {
+            type Value = LazyDynamicObject;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a kubernetes object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let (mut api_version, mut kind, mut metadata) = (None, None, None);
+                let mut payload = BTreeMap::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "apiVersion" => api_version = Some(map.next_value()?),
+                        "kind" => kind = Some(map.next_value()?),
+                        "metadata" => metadata = Some(map.next_value()?),
+                        _ => {
+                            payload.insert(key, map.next_value()?);
+                        }
+                    }
+                }
+                let types = match (api_version, kind) {
+                    (Some(api_version), Some(kind)) => Some(TypeMeta { api_version, kind }),
+                    _ => None,
+                };
+                let metadata = metadata.ok_or_else(|| <A::Error as de::Error>::missing_field("metadata"))?;
+                Ok(LazyDynamicObject {
+                    types,
+                    metadata,
+                    payload,
+                })
+            }
+        }
+
+        deserializer.deserialize_map(LazyVisitor)
+    }
+}
+
+impl Resource for LazyDynamicObject {
+    type DynamicType = ApiResource;
+    type Scope = DynamicResourceScope;
+
+    fn group(dt: &ApiResource) -> Cow<'_, str> {
+        dt.group.as_str().into()
+    }
+
+    fn version(dt: &ApiResource) -> Cow<'_, str> {
+        dt.version.as_str().into()
+    }
+
+    fn kind(dt: &ApiResource) -> Cow<'_, str> {
+        dt.kind.as_str().into()
+    }
+
+    fn api_version(dt: &ApiResource) -> Cow<'_, str> {
+        dt.api_version.as_str().into()
+    }
+
+    fn plural(dt: &ApiResource) -> Cow<'_, str> {
+        dt.plural.as_str().into()
+    }
+
+    fn meta(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+
+    fn meta_mut(&mut self) -> &mut ObjectMeta {
+        &mut self.metadata
+    }
+}
+
+impl ObjectList<DynamicObject> {
+    /// The `resourceVersion` of the list, from which a watch can be started
+    pub fn resource_version(&self) -> Option<&str> {
+        self.metadata.resource_version.as_deref()
+    }
+
+    /// Checks that every item is of the listed resource
+    ///
+    /// Items without `apiVersion` and `kind`, as returned when listing built-in types,
+    /// have them set from `resource`.
+    pub fn check_types(mut self, resource: &ApiResource) -> Result<Self, UnexpectedTypeError> {
+        let expected = TypeMeta {
+            api_version: resource.api_version.clone(),
+            kind: resource.kind.clone(),
+        };
+        for obj in &mut self.items {
+            match &obj.types {
+                Some(found) if found != &expected => {
+                    return Err(UnexpectedTypeError {
+                        name: obj.metadata.name.clone(),
+                        mismatch: TypeMismatchError::new(&expected, found),
+                    });
+                }
+                Some(_) => {}
+                None => obj.types = Some(expected.clone()),
+            }
+        }
+        Ok(self)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
@@ -117,6 +467,140 @@ mod test {
         assert_eq!(req.method(), "PATCH");
     }
 
+    #[test]
+    fn typed_conversions() {
+        use super::ParseDynamicObjectError;
+        use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+
+        let obj: DynamicObject = serde_json::from_value(serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": { "name": "config" },
+            "data": { "key": "value" }
+        }))
+        .unwrap();
+        let cm: ConfigMap = obj.clone().try_parse().unwrap();
+        assert_eq!(cm.metadata.name.as_deref(), Some("config"));
+        assert_eq!(cm.data.unwrap()["key"], "value");
+        assert!(matches!(
+            obj.clone().try_parse::<Pod>(),
+            Err(ParseDynamicObjectError::TypeMismatch(_))
+        ));
+
+        // Missing types are assumed to match
+        let untyped = DynamicObject { types: None, ..obj };
+        let cm: ConfigMap = untyped.try_parse().unwrap();
+
+        let obj = DynamicObject::try_from_resource(&cm).unwrap();
+        assert_eq!(obj.types.unwrap().kind, "ConfigMap");
+        assert_eq!(obj.metadata.name.as_deref(), Some("config"));
+        assert_eq!(obj.data["data"]["key"], "value");
+    }
+
+    #[test]
+    fn object_conversions() {
+        use crate::object::{NotUsed, Object};
+        use std::collections::BTreeMap;
+
+        type Foo = Object<BTreeMap<String, i32>, NotUsed>;
+        let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("clux.dev", "v1", "Foo"));
+        let foo = Foo::new("foo", &ar, [("replicas".to_string(), 2)].into());
+        let obj = DynamicObject::try_from(foo).unwrap();
+        assert_eq!(obj.types.as_ref().unwrap().kind, "Foo");
+        assert_eq!(obj.data["spec"]["replicas"], 2);
+        let foo = Foo::try_from(obj).unwrap();
+        assert_eq!(foo.metadata.name.as_deref(), Some("foo"));
+        assert_eq!(foo.spec["replicas"], 2);
+    }
+
+    #[test]
+    fn nested_paths() {
+        use super::SetPathError;
+
+        let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("apps", "v1", "Deployment"));
+        let mut obj = DynamicObject::new("web", &ar).data(serde_json::json!({
+            "spec": {
+                "replicas": 1,
+                "template": { "spec": { "containers": [{ "name": "web", "image": "nginx" }] } }
+            }
+        }));
+        let image = ["spec", "template", "spec", "containers", "0", "image"];
+        assert_eq!(obj.get_path(&image).and_then(|v| v.as_str()), Some("nginx"));
+        assert_eq!(obj.get_path_as::<u32>(&["spec", "replicas"]), Some(1));
+        assert!(obj.get_path(&["spec", "template", "containers"]).is_none());
+        assert!(obj.get_path(&["spec", "replicas", "value"]).is_none());
+
+        *obj.get_path_mut(&["spec", "replicas"]).unwrap() = 2.into();
+        assert_eq!(obj.set_path(&image, "httpd".into()), Ok(Some("nginx".into())));
+        assert_eq!(obj.set_path(&["spec", "paused"], true.into()), Ok(None));
+        assert_eq!(obj.set_path(&["status", "replicas"], 2.into()), Ok(None));
+        assert_eq!(
+            obj.set_path(&["spec", "replicas", "value"], 2.into()),
+            Err(SetPathError("spec.replicas.value".into()))
+        );
+        assert_eq!(obj.remove_path(&["spec", "paused"]), Some(true.into()));
+        assert_eq!(obj.remove_path(&["spec", "paused"]), None);
+        assert_eq!(
+            obj.data,
+            serde_json::json!({
+                "spec": {
+                    "replicas": 2,
+                    "template": { "spec": { "containers": [{ "name": "web", "image": "httpd" }] } }
+                },
+                "status": { "replicas": 2 }
+            })
+        );
+    }
+
+    #[test]
+    fn lazy_payload() {
+        use super::LazyDynamicObject;
+
+        let json = r#"{"apiVersion":"clux.dev/v1","kind":"Foo","metadata":{"name":"foo"},"spec":{"replicas":2,"image":"nginx"},"status":{"ready":true}}"#;
+        let obj: LazyDynamicObject = serde_json::from_str(json).unwrap();
+        assert_eq!(obj.types.as_ref().unwrap().kind, "Foo");
+        assert_eq!(obj.metadata.name.as_deref(), Some("foo"));
+        assert_eq!(obj.field_names().collect::<Vec<_>>(), vec!["spec", "status"]);
+        let spec = obj.raw_field("spec").unwrap();
+        assert_eq!(spec.get(), r#"{"replicas":2,"image":"nginx"}"#);
+        let status: serde_json::Value = obj.parse_field("status").unwrap().unwrap();
+        assert_eq!(status["ready"], true);
+        assert!(obj.parse_field::<serde_json::Value>("missing").is_none());
+
+        let parsed = obj.parse().unwrap();
+        assert_eq!(parsed.data["spec"]["replicas"], 2);
+        assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+    }
+
+    #[test]
+    fn checked_list_types() {
+        use super::{ObjectList, TypeMismatchError, UnexpectedTypeError};
+
+        let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("apps", "v1", "Deployment"));
+        let list: ObjectList<DynamicObject> = serde_json::from_value(serde_json::json!({
+            "metadata": { "resourceVersion": "42" },
+            "items": [
+                { "metadata": { "name": "web" } },
+                { "apiVersion": "apps/v1", "kind": "Deployment", "metadata": { "name": "api" } },
+            ]
+        }))
+        .unwrap();
+        assert_eq!(list.resource_version(), Some("42"));
+        let mut list = list.check_types(&ar).unwrap();
+        for obj in list.iter() {
+            assert_eq!(obj.types.as_ref().unwrap().kind, "Deployment");
+        }
+
+        list.items[1].types.as_mut().unwrap().kind = "StatefulSet".into();
+        assert_eq!(list.check_types(&ar).unwrap_err(), UnexpectedTypeError {
+            name: Some("api".into()),
+            mismatch: TypeMismatchError {
+                expected: "apps/v1/Deployment".into(),
+                found: "apps/v1/StatefulSet".into(),
+            },
+        });
+    }
+
     #[test]
     fn raw_resource_in_default_group() {
         let gvk = GroupVersionKind::gvk("", "v1", "Service");
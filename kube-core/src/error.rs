@@ -1,4 +1,6 @@
+use crate::{metadata::TypeMeta, response::StatusDetails};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use thiserror::Error;
 
 /// An error response from the API.
@@ -15,4 +17,142 @@ pub struct ErrorResponse {
     pub reason: String,
     /// The error code
     pub code: u16,
+    /// Extended data associated with the reason
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<StatusDetails>,
+}
+
+impl ErrorResponse {
+    /// Returns the field manager conflicts that failed a server-side apply
+    ///
+    /// The list is empty for any other error.
+    /// Conflicts can be overridden by applying with [`PatchParams::force`](crate::params::PatchParams::force).
+    pub fn apply_conflicts(&self) -> Vec<ApplyConflict> {
+        if self.code != 409 {
+            return vec![];
+        }
+        self.details
+            .iter()
+            .flat_map(|details| &details.causes)
+            .filter(|cause| cause.reason == "FieldManagerConflict")
+            .filter_map(|cause| ApplyConflict::parse(&cause.message, &cause.field))
+            .collect()
+    }
+}
+
+/// A field owned by another field manager, which failed a server-side apply
+///
+/// See [`ErrorResponse::apply_conflicts`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ApplyConflict {
+    /// The conflicting field, such as `.spec.replicas`
+    pub field: String,
+    /// The field manager that owns the field
+    pub manager: String,
+    /// The apiVersion that the field manager used to set the field
+    pub api_version: String,
+    /// The subresource that the field manager used to set the field, such as `status`
+    pub subresource: Option<String>,
+}
+
+impl ApplyConflict {
+    // Parses messages such as `conflict with "manager" with subresource "status" using v1`
+    fn parse(message: &str, field: &str) -> Option<Self> {
+        let (manager, rest) = message.strip_prefix("conflict with \"")?.split_once('"')?;
+        let (subresource, rest) = match rest.strip_prefix(" with subresource \"") {
+            Some(rest) => {
+                let (subresource, rest) = rest.split_once('"')?;
+                (Some(subresource.to_string()), rest)
+            }
+            None => (None, rest),
+        };
+        Some(Self {
+            field: field.to_string(),
+            manager: manager.to_string(),
+            api_version: rest.strip_prefix(" using ")?.to_string(),
+            subresource,
+        })
+    }
+}
+
+impl fmt::Display for ApplyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is owned by {:?}", self.field, self.manager)?;
+        write!(f, " using {}", self.api_version)?;
+        if let Some(subresource) = &self.subresource {
+            write!(f, " with subresource {:?}", subresource)?;
+        }
+        Ok(())
+    }
+}
+
+/// An object or reference is of another type than the requested one
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+#[error("expected {expected}, found {found}")]
+pub struct TypeMismatchError {
+    /// apiVersion and kind of the requested type, such as `apps/v1/Deployment`
+    pub expected: String,
+    /// apiVersion and kind of the object or reference
+    pub found: String,
+}
+
+impl TypeMismatchError {
+    pub(crate) fn new(expected: &TypeMeta, found: &TypeMeta) -> Self {
+        Self {
+            expected: format!("{}/{}", expected.api_version, expected.kind),
+            found: format!("{}/{}", found.api_version, found.kind),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ApplyConflict, ErrorResponse};
+
+    #[test]
+    fn apply_conflicts() {
+        let err: ErrorResponse = serde_json::from_value(serde_json::json!({
+            "kind": "Status",
+            "apiVersion": "v1",
+            "metadata": {},
+            "status": "Failure",
+            "message": "Apply failed with 2 conflicts: conflict with \"kubectl-client-side-apply\" using apps/v1: .spec.replicas\nconflict with \"hpa\" with subresource \"scale\" using autoscaling/v1: .spec.replicas",
+            "reason": "Conflict",
+            "details": {
+                "causes": [
+                    {
+                        "reason": "FieldManagerConflict",
+                        "message": "conflict with \"kubectl-client-side-apply\" using apps/v1",
+                        "field": ".spec.replicas"
+                    },
+                    {
+                        "reason": "FieldManagerConflict",
+                        "message": "conflict with \"hpa\" with subresource \"scale\" using autoscaling/v1",
+                        "field": ".spec.replicas"
+                    }
+                ]
+            },
+            "code": 409
+        }))
+        .unwrap();
+        let conflicts = err.apply_conflicts();
+        assert_eq!(conflicts, vec![
+            ApplyConflict {
+                field: ".spec.replicas".into(),
+                manager: "kubectl-client-side-apply".into(),
+                api_version: "apps/v1".into(),
+                subresource: None,
+            },
+            ApplyConflict {
+                field: ".spec.replicas".into(),
+                manager: "hpa".into(),
+                api_version: "autoscaling/v1".into(),
+                subresource: Some("scale".into()),
+            },
+        ]);
+        assert_eq!(
+            conflicts[1].to_string(),
+            r#".spec.replicas is owned by "hpa" using autoscaling/v1 with subresource "scale""#
+        );
+    }
 }
@@ -0,0 +1,326 @@
+//! Type safe field selector logic
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("failed to parse field selector: {0}")]
+/// Indicates failure to parse a field selector
+pub struct ParseFieldSelectorError(pub String);
+
+/// A field selector requirement
+///
+/// The apiserver only supports equality based requirements on fields,
+/// and only for a limited set of fields per resource type.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FieldExpression {
+    /// Field is equal to the given value
+    ///
+    /// ```
+    /// use kube_core::fields::{FieldExpression, FieldSelector};
+    /// let selector: FieldSelector = FieldExpression::Equal("spec.nodeName".into(), "foo".into()).into();
+    /// assert_eq!(selector.to_string(), "spec.nodeName=foo");
+    /// ```
+    Equal(String, String),
+
+    /// Field is not equal to the given value
+    ///
+    /// ```
+    /// use kube_core::fields::{FieldExpression, FieldSelector};
+    /// let selector: FieldSelector = FieldExpression::NotEqual("status.phase".into(), "Running".into()).into();
+    /// assert_eq!(selector.to_string(), "status.phase!=Running");
+    /// ```
+    NotEqual(String, String),
+}
+
+impl Display for FieldExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldExpression::Equal(field, value) => write!(f, "{}={}", field, escape_value(value)),
+            FieldExpression::NotEqual(field, value) => write!(f, "{}!={}", field, escape_value(value)),
+        }
+    }
+}
+
+impl FromStr for FieldExpression {
+    type Err = ParseFieldSelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (field, op, raw_value) = split_requirement(s)?;
+        let field = field.trim();
+        validate_field(field)?;
+        let value = unescape_value(raw_value)?;
+        match op {
+            "!=" => Ok(FieldExpression::NotEqual(field.into(), value)),
+            _ => Ok(FieldExpression::Equal(field.into(), value)),
+        }
+    }
+}
+
+/// A field selector built from a list of requirements that must all hold
+///
+/// ```
+/// use kube_core::fields::FieldSelector;
+/// let selector = FieldSelector::default()
+///     .equal("spec.nodeName", "node-1")
+///     .not_equal("status.phase", "Succeeded");
+/// assert_eq!(selector.to_string(), "spec.nodeName=node-1,status.phase!=Succeeded");
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct FieldSelector(Vec<FieldExpression>);
+
+impl FieldSelector {
+    /// Require `field` to equal `value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `field` is not a valid field path.
+    /// Use [`FieldSelector::try_equal`] for a fallible version.
+    #[must_use]
+    pub fn equal(self, field: &str, value: &str) -> Self {
+        self.try_equal(field, value).expect("invalid field selector")
+    }
+
+    /// Require `field` to not equal `value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `field` is not a valid field path.
+    /// Use [`FieldSelector::try_not_equal`] for a fallible version.
+    #[must_use]
+    pub fn not_equal(self, field: &str, value: &str) -> Self {
+        self.try_not_equal(field, value).expect("invalid field selector")
+    }
+
+    /// Require `field` to equal `value`, validating the field path
+    pub fn try_equal(mut self, field: &str, value: &str) -> Result<Self, ParseFieldSelectorError> {
+        validate_field(field)?;
+        self.0.push(FieldExpression::Equal(field.into(), value.into()));
+        Ok(self)
+    }
+
+    /// Require `field` to not equal `value`, validating the field path
+    pub fn try_not_equal(mut self, field: &str, value: &str) -> Result<Self, ParseFieldSelectorError> {
+        validate_field(field)?;
+        self.0.push(FieldExpression::NotEqual(field.into(), value.into()));
+        Ok(self)
+    }
+
+    /// Indicates whether this field selector matches all objects
+    pub fn selects_all(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the requirements of this selector
+    pub fn expressions(&self) -> &[FieldExpression] {
+        &self.0
+    }
+}
+
+impl Display for FieldSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exprs: Vec<String> = self.0.iter().map(|e| e.to_string()).collect();
+        write!(f, "{}", exprs.join(","))
+    }
+}
+
+impl FromStr for FieldSelector {
+    type Err = ParseFieldSelectorError;
+
+    /// Parse a field selector string such as `metadata.name=foo,status.phase!=Running`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        split_unescaped(s, ',')
+            .into_iter()
+            .map(FieldExpression::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl From<FieldExpression> for FieldSelector {
+    fn from(value: FieldExpression) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl FromIterator<FieldExpression> for FieldSelector {
+    fn from_iter<T: IntoIterator<Item = FieldExpression>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for FieldSelector {
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type Item = FieldExpression;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Field paths are dot separated identifiers like `metadata.name` or `spec.nodeName`
+fn validate_field(field: &str) -> Result<(), ParseFieldSelectorError> {
+    if field.is_empty() {
+        return Err(ParseFieldSelectorError("field must not be empty".into()));
+    }
+    let valid = field.split('.').all(|seg| {
+        !seg.is_empty()
+            && seg
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    if !valid {
+        return Err(ParseFieldSelectorError(format!("invalid field path {:?}", field)));
+    }
+    Ok(())
+}
+
+/// Split a single requirement into field, operator and (escaped) value
+fn split_requirement(s: &str) -> Result<(&str, &str, &str), ParseFieldSelectorError> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '!' if s[i + 1..].starts_with('=') => return Ok((&s[..i], "!=", &s[i + 2..])),
+            '=' if s[i + 1..].starts_with('=') => return Ok((&s[..i], "==", &s[i + 2..])),
+            '=' => return Ok((&s[..i], "=", &s[i + 1..])),
+            _ => {}
+        }
+    }
+    Err(ParseFieldSelectorError(format!(
+        "{:?} has no supported operator (only =, == and != are supported)",
+        s
+    )))
+}
+
+/// Split on a separator that is not preceded by a backslash
+fn split_unescaped(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == sep {
+            parts.push(&s[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Escape a value the same way as apimachinery's `fields.EscapeValue`
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | ',' | '=') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn unescape_value(value: &str) -> Result<String, ParseFieldSelectorError> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(e @ ('\\' | ',' | '=')) => unescaped.push(e),
+                Some(e) => {
+                    return Err(ParseFieldSelectorError(format!(
+                        "invalid escape sequence \\{} in {:?}",
+                        e, value
+                    )))
+                }
+                None => {
+                    return Err(ParseFieldSelectorError(format!(
+                        "unterminated escape sequence in {:?}",
+                        value
+                    )))
+                }
+            },
+            // an unescaped = means the value contains another operator
+            '=' => {
+                return Err(ParseFieldSelectorError(format!(
+                    "unescaped = in value {:?}",
+                    value
+                )))
+            }
+            c => unescaped.push(c),
+        }
+    }
+    Ok(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_serializes() {
+        let selector = FieldSelector::default()
+            .equal("metadata.name", "foo")
+            .not_equal("status.phase", "Running");
+        assert_eq!(selector.to_string(), "metadata.name=foo,status.phase!=Running");
+        assert!(FieldSelector::default().selects_all());
+    }
+
+    #[test]
+    fn values_are_escaped() {
+        let selector = FieldSelector::default().equal("metadata.name", r"a,b=c\d");
+        assert_eq!(selector.to_string(), r"metadata.name=a\,b\=c\\d");
+        let parsed: FieldSelector = selector.to_string().parse().unwrap();
+        assert_eq!(parsed, selector);
+    }
+
+    #[test]
+    fn parses_supported_operators() {
+        let parsed: FieldSelector = "metadata.name==foo, spec.nodeName=bar,status.phase!=Failed"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            parsed,
+            FieldSelector::default()
+                .equal("metadata.name", "foo")
+                .equal("spec.nodeName", "bar")
+                .not_equal("status.phase", "Failed")
+        );
+        assert_eq!("".parse::<FieldSelector>().unwrap(), FieldSelector::default());
+    }
+
+    #[test]
+    fn rejects_unsupported_operators() {
+        for invalid in [
+            "metadata.name",
+            "!metadata.name",
+            "metadata.name in (foo)",
+            "metadata.name=a=b",
+            "=foo",
+            "metadata..name=foo",
+            r"metadata.name=foo\",
+        ] {
+            assert!(
+                invalid.parse::<FieldSelector>().is_err(),
+                "{} should fail",
+                invalid
+            );
+        }
+        assert!(FieldSelector::default()
+            .try_equal("spec nodeName", "foo")
+            .is_err());
+    }
+}
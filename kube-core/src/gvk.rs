@@ -1,7 +1,7 @@
 //! Type information structs for dynamic resources.
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
-use crate::TypeMeta;
+use crate::{discovery::ApiResource, TypeMeta};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -10,6 +10,11 @@ use thiserror::Error;
 /// Failed to parse group version
 pub struct ParseGroupVersionError(pub String);
 
+#[derive(Debug, Error)]
+#[error("failed to parse group version kind: {0}")]
+/// Failed to parse group version kind
+pub struct ParseGroupVersionKindError(pub String);
+
 /// Core information about an API Resource.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GroupVersionKind {
@@ -47,6 +52,46 @@ impl TryFrom<TypeMeta> for GroupVersionKind {
     }
 }
 
+impl From<&ApiResource> for GroupVersionKind {
+    fn from(ar: &ApiResource) -> Self {
+        Self::gvk(&ar.group, &ar.version, &ar.kind)
+    }
+}
+
+/// Parses either `group/version/Kind` (`v1/Kind` for the core group) or `Kind.version.group` (`Kind.version`)
+impl FromStr for GroupVersionKind {
+    type Err = ParseGroupVersionKindError;
+
+    fn from_str(gvk: &str) -> Result<Self, Self::Err> {
+        let err = || ParseGroupVersionKindError(gvk.into());
+        let (group, version, kind) = if let Some((gv, kind)) = gvk.rsplit_once('/') {
+            let gv = GroupVersion::from_str(gv).map_err(|_| err())?;
+            (gv.group, gv.version, kind)
+        } else {
+            match *gvk.splitn(3, '.').collect::<Vec<_>>().as_slice() {
+                [kind, version, group] => (group.to_string(), version.to_string(), kind),
+                [kind, version] => ("".to_string(), version.to_string(), kind),
+                _ => return Err(err()),
+            }
+        };
+        if version.is_empty() || version.contains('/') || kind.is_empty() {
+            return Err(err());
+        }
+        Ok(Self {
+            group,
+            version,
+            kind: kind.to_string(),
+        })
+    }
+}
+
+/// Formats as `group/version/Kind`, or `version/Kind` for the core group
+impl fmt::Display for GroupVersionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.api_version(), self.kind)
+    }
+}
+
 /// Core information about a family of API Resources
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GroupVersion {
@@ -88,6 +133,13 @@ impl FromStr for GroupVersion {
     }
 }
 
+/// Formats as the apiVersion string, `group/version` or `version` for the core group
+impl fmt::Display for GroupVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.api_version())
+    }
+}
+
 impl GroupVersion {
     /// Generate the apiVersion string used in a kind's yaml
     pub fn api_version(&self) -> String {
@@ -144,6 +196,19 @@ impl GroupVersionResource {
     }
 }
 
+impl From<&ApiResource> for GroupVersionResource {
+    fn from(ar: &ApiResource) -> Self {
+        Self::gvr(&ar.group, &ar.version, &ar.plural)
+    }
+}
+
+/// Formats as `group/version/resource`, or `version/resource` for the core group
+impl fmt::Display for GroupVersionResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.api_version, self.resource)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -163,4 +228,38 @@ metadata:
         assert_eq!(gvk.version, "v1");
         assert_eq!(gvk.kind, gvk2.kind);
     }
+
+    #[test]
+    fn gvk_parse_display() {
+        use crate::{discovery::ApiResource, GroupVersionKind, GroupVersionResource};
+        let deploy = GroupVersionKind::gvk("apps", "v1", "Deployment");
+        let pod = GroupVersionKind::gvk("", "v1", "Pod");
+        for (input, expected) in [
+            ("apps/v1/Deployment", &deploy),
+            ("Deployment.v1.apps", &deploy),
+            ("v1/Pod", &pod),
+            ("Pod.v1", &pod),
+        ] {
+            assert_eq!(&input.parse::<GroupVersionKind>().unwrap(), expected);
+        }
+        let ingress: GroupVersionKind = "Ingress.v1.networking.k8s.io".parse().unwrap();
+        assert_eq!(ingress.group, "networking.k8s.io");
+        for invalid in [
+            "Deployment",
+            "apps/v1/",
+            "/Deployment",
+            "a/b/c/Deployment",
+            "Deployment.",
+        ] {
+            assert!(invalid.parse::<GroupVersionKind>().is_err(), "{}", invalid);
+        }
+
+        assert_eq!(deploy.to_string(), "apps/v1/Deployment");
+        assert_eq!(pod.to_string(), "v1/Pod");
+        assert_eq!(deploy.to_string().parse::<GroupVersionKind>().unwrap(), deploy);
+
+        let ar = ApiResource::from_gvk_with_plural(&deploy, "deployments");
+        assert_eq!(GroupVersionKind::from(&ar), deploy);
+        assert_eq!(GroupVersionResource::from(&ar).to_string(), "apps/v1/deployments");
+    }
 }
@@ -0,0 +1,591 @@
+//! Type safe label selector logic
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, LabelSelectorRequirement};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+    ops::BitAnd,
+};
+use thiserror::Error;
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for super::Expression {}
+    impl Sealed for super::Selector {}
+}
+
+#[derive(Debug, Error)]
+#[error("failed to parse value as expression: {0}")]
+/// Indicates failure of conversion to Expression
+pub struct ParseExpressionError(pub String);
+
+/// Selector extension trait for querying selector like objects
+pub trait SelectorExt: private::Sealed {
+    /// Perform a match check on a set of labels
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool;
+}
+
+/// A selector expression with existing operations
+///
+/// These map onto the set based and equality based requirements supported by the apiserver.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Expression {
+    /// Key exists and its value is one of the given values
+    ///
+    /// ```
+    /// use kube_core::labels::Selector;
+    /// use kube_core::labels::Expression;
+    /// let selector: Selector = Expression::In("foo".into(), ["bar".to_string()].into()).into();
+    /// assert_eq!(selector.to_string(), "foo in (bar)");
+    /// ```
+    In(String, BTreeSet<String>),
+
+    /// Key does not exist, or its value is not one of the given values
+    ///
+    /// ```
+    /// use kube_core::labels::Selector;
+    /// use kube_core::labels::Expression;
+    /// let selector: Selector = Expression::NotIn("foo".into(), ["bar".to_string()].into()).into();
+    /// assert_eq!(selector.to_string(), "foo notin (bar)");
+    /// ```
+    NotIn(String, BTreeSet<String>),
+
+    /// Key exists and its value is equal to the given value
+    ///
+    /// ```
+    /// use kube_core::labels::Selector;
+    /// use kube_core::labels::Expression;
+    /// let selector: Selector = Expression::Equal("foo".into(), "bar".into()).into();
+    /// assert_eq!(selector.to_string(), "foo=bar");
+    /// ```
+    Equal(String, String),
+
+    /// Key does not exist, or its value is not equal to the given value
+    ///
+    /// ```
+    /// use kube_core::labels::Selector;
+    /// use kube_core::labels::Expression;
+    /// let selector: Selector = Expression::NotEqual("foo".into(), "bar".into()).into();
+    /// assert_eq!(selector.to_string(), "foo!=bar");
+    /// ```
+    NotEqual(String, String),
+
+    /// Key exists
+    ///
+    /// ```
+    /// use kube_core::labels::Selector;
+    /// use kube_core::labels::Expression;
+    /// let selector: Selector = Expression::Exists("foo".into()).into();
+    /// assert_eq!(selector.to_string(), "foo");
+    /// ```
+    Exists(String),
+
+    /// Key does not exist
+    ///
+    /// ```
+    /// use kube_core::labels::Selector;
+    /// use kube_core::labels::Expression;
+    /// let selector: Selector = Expression::DoesNotExist("foo".into()).into();
+    /// assert_eq!(selector.to_string(), "!foo");
+    /// ```
+    DoesNotExist(String),
+}
+
+/// Perform selection on a list of expressions
+///
+/// All expressions must match for the selector to match, and an empty selector selects everything.
+///
+/// ```
+/// use kube_core::labels::{Expression, Selector};
+/// let selector = Selector::from(Expression::In("env".into(), ["prod".to_string()].into()))
+///     & Expression::Exists("team".into());
+/// assert_eq!(selector.to_string(), "env in (prod),team");
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Selector(Vec<Expression>);
+
+impl Selector {
+    /// Create a selector from a list of expressions
+    fn from_expressions(exprs: Vec<Expression>) -> Self {
+        Self(exprs)
+    }
+
+    /// Create a selector from a map of key=value label matches
+    fn from_map(map: BTreeMap<String, String>) -> Self {
+        Self(map.into_iter().map(|(k, v)| Expression::Equal(k, v)).collect())
+    }
+
+    /// Indicates whether this label selector matches all objects
+    pub fn selects_all(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Extend the list of expressions for the selector
+    ///
+    /// ```
+    /// use kube_core::labels::{Expression, Selector};
+    /// let mut selector = Selector::default();
+    /// selector.extend(Expression::Equal("environment".into(), "production".into()));
+    /// selector.extend([Expression::Exists("bar".into()), Expression::Exists("foo".into())]);
+    /// assert_eq!(selector.to_string(), "environment=production,bar,foo");
+    /// ```
+    pub fn extend(&mut self, exprs: impl IntoIterator<Item = Expression>) -> &mut Self {
+        self.0.extend(exprs);
+        self
+    }
+}
+
+impl SelectorExt for Selector {
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.0.iter().all(|expr| expr.matches(labels))
+    }
+}
+
+impl SelectorExt for Expression {
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        match self {
+            Expression::In(key, values) => match labels.get(key) {
+                Some(v) => values.contains(v),
+                None => false,
+            },
+            Expression::NotIn(key, values) => match labels.get(key) {
+                Some(v) => !values.contains(v),
+                None => true,
+            },
+            Expression::Exists(key) => labels.contains_key(key),
+            Expression::DoesNotExist(key) => !labels.contains_key(key),
+            Expression::Equal(key, value) => labels.get(key) == Some(value),
+            Expression::NotEqual(key, value) => labels.get(key) != Some(value),
+        }
+    }
+}
+
+impl Display for Expression {
+    /// Perform conversion to string
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::In(key, values) => {
+                write!(
+                    f,
+                    "{} in ({})",
+                    key,
+                    values.iter().cloned().collect::<Vec<_>>().join(",")
+                )
+            }
+            Expression::NotIn(key, values) => {
+                write!(
+                    f,
+                    "{} notin ({})",
+                    key,
+                    values.iter().cloned().collect::<Vec<_>>().join(",")
+                )
+            }
+            Expression::Equal(key, value) => write!(f, "{}={}", key, value),
+            Expression::NotEqual(key, value) => write!(f, "{}!={}", key, value),
+            Expression::Exists(key) => write!(f, "{}", key),
+            Expression::DoesNotExist(key) => write!(f, "!{}", key),
+        }
+    }
+}
+
+impl Display for Selector {
+    /// Convert a selector to a string for the API
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let selectors: Vec<String> = self.0.iter().map(|e| e.to_string()).collect();
+        write!(f, "{}", selectors.join(","))
+    }
+}
+
+impl IntoIterator for Expression {
+    type IntoIter = std::option::IntoIter<Self::Item>;
+    type Item = Self;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Some(self).into_iter()
+    }
+}
+
+impl IntoIterator for Selector {
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type Item = Expression;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FromIterator<(String, String)> for Selector {
+    fn from_iter<T: IntoIterator<Item = (String, String)>>(iter: T) -> Self {
+        Self::from_map(iter.into_iter().collect())
+    }
+}
+
+impl FromIterator<(&'static str, &'static str)> for Selector {
+    /// ```
+    /// use kube_core::labels::Selector;
+    /// let selector: Selector = [("foo", "bar")].into_iter().collect();
+    /// assert_eq!(selector.to_string(), "foo=bar");
+    /// ```
+    fn from_iter<T: IntoIterator<Item = (&'static str, &'static str)>>(iter: T) -> Self {
+        Self::from_map(
+            iter.into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl FromIterator<Expression> for Selector {
+    fn from_iter<T: IntoIterator<Item = Expression>>(iter: T) -> Self {
+        Self::from_expressions(iter.into_iter().collect())
+    }
+}
+
+impl From<Expression> for Selector {
+    fn from(value: Expression) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl BitAnd<Expression> for Selector {
+    type Output = Selector;
+
+    fn bitand(mut self, rhs: Expression) -> Self::Output {
+        self.0.push(rhs);
+        self
+    }
+}
+
+impl BitAnd<Selector> for Selector {
+    type Output = Selector;
+
+    fn bitand(mut self, rhs: Selector) -> Self::Output {
+        self.0.extend(rhs.0);
+        self
+    }
+}
+
+impl BitAnd<Expression> for Expression {
+    type Output = Selector;
+
+    fn bitand(self, rhs: Expression) -> Self::Output {
+        Selector(vec![self, rhs])
+    }
+}
+
+impl TryFrom<LabelSelector> for Selector {
+    type Error = ParseExpressionError;
+
+    fn try_from(value: LabelSelector) -> Result<Self, Self::Error> {
+        let mut equality: Selector = value
+            .match_labels
+            .map(|labels| labels.into_iter().collect())
+            .unwrap_or_default();
+        let sets = value.match_expressions.unwrap_or_default();
+        for set in sets {
+            equality.extend(Expression::try_from(set)?);
+        }
+
+        Ok(equality)
+    }
+}
+
+impl TryFrom<LabelSelectorRequirement> for Expression {
+    type Error = ParseExpressionError;
+
+    fn try_from(requirement: LabelSelectorRequirement) -> Result<Self, Self::Error> {
+        let key = requirement.key;
+        let values = requirement.values.map(|values| values.into_iter().collect());
+        match requirement.operator.as_str() {
+            "In" => match values {
+                Some(values) => Ok(Expression::In(key, values)),
+                None => Err(ParseExpressionError(
+                    "Expected values for In operator, got none".into(),
+                )),
+            },
+            "NotIn" => match values {
+                Some(values) => Ok(Expression::NotIn(key, values)),
+                None => Err(ParseExpressionError(
+                    "Expected values for NotIn operator, got none".into(),
+                )),
+            },
+            "Exists" => Ok(Expression::Exists(key)),
+            "DoesNotExist" => Ok(Expression::DoesNotExist(key)),
+            _ => Err(ParseExpressionError(format!(
+                "Invalid expression operator {}",
+                requirement.operator
+            ))),
+        }
+    }
+}
+
+impl From<Selector> for LabelSelector {
+    fn from(value: Selector) -> Self {
+        let mut match_labels = BTreeMap::new();
+        let mut match_expressions = vec![];
+        for expr in value.0 {
+            match expr {
+                // A key can only appear once in `matchLabels`, so conflicting repeats become requirements
+                Expression::Equal(key, value) => {
+                    if let Some(existing) = match_labels.get(&key) {
+                        if existing != &value {
+                            match_expressions.push(LabelSelectorRequirement {
+                                key,
+                                operator: "In".into(),
+                                values: Some(vec![value]),
+                            });
+                        }
+                    } else {
+                        match_labels.insert(key, value);
+                    }
+                }
+                Expression::In(key, values) => match_expressions.push(LabelSelectorRequirement {
+                    key,
+                    operator: "In".into(),
+                    values: Some(values.into_iter().collect()),
+                }),
+                Expression::NotIn(key, values) => match_expressions.push(LabelSelectorRequirement {
+                    key,
+                    operator: "NotIn".into(),
+                    values: Some(values.into_iter().collect()),
+                }),
+                Expression::Exists(key) => match_expressions.push(LabelSelectorRequirement {
+                    key,
+                    operator: "Exists".into(),
+                    values: None,
+                }),
+                Expression::DoesNotExist(key) => match_expressions.push(LabelSelectorRequirement {
+                    key,
+                    operator: "DoesNotExist".into(),
+                    values: None,
+                }),
+                // LabelSelector has no representation for inequality, but it is equivalent to NotIn
+                Expression::NotEqual(key, value) => match_expressions.push(LabelSelectorRequirement {
+                    key,
+                    operator: "NotIn".into(),
+                    values: Some(vec![value]),
+                }),
+            }
+        }
+
+        LabelSelector {
+            match_labels: if match_labels.is_empty() {
+                None
+            } else {
+                Some(match_labels)
+            },
+            match_expressions: if match_expressions.is_empty() {
+                None
+            } else {
+                Some(match_expressions)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_matches() {
+        for (selector, label_selector, labels, msg) in &[
+            (
+                Selector(vec![]),
+                LabelSelector::default(),
+                Default::default(),
+                "empty match",
+            ),
+            (
+                Selector::from_iter(Some(("foo", "bar"))),
+                LabelSelector {
+                    match_labels: Some([("foo".to_string(), "bar".to_string())].into()),
+                    match_expressions: Default::default(),
+                },
+                [("foo".to_string(), "bar".to_string())].into(),
+                "exact label match",
+            ),
+            (
+                Selector::from_iter(Some(("foo", "bar"))),
+                LabelSelector {
+                    match_labels: Some([("foo".to_string(), "bar".to_string())].into()),
+                    match_expressions: None,
+                },
+                [
+                    ("foo".to_string(), "bar".to_string()),
+                    ("bah".to_string(), "baz".to_string()),
+                ]
+                .into(),
+                "sufficient label match",
+            ),
+            (
+                Selector::from_iter(Some(Expression::In(
+                    "foo".into(),
+                    Some("bar".to_string()).into_iter().collect(),
+                ))),
+                LabelSelector {
+                    match_expressions: Some(vec![LabelSelectorRequirement {
+                        key: "foo".into(),
+                        operator: "In".to_string(),
+                        values: Some(vec!["bar".into()]),
+                    }]),
+                    match_labels: None,
+                },
+                [
+                    ("foo".to_string(), "bar".to_string()),
+                    ("bah".to_string(), "baz".to_string()),
+                ]
+                .into(),
+                "In expression match",
+            ),
+            (
+                Selector::from_iter(Some(Expression::NotIn(
+                    "foo".into(),
+                    Some("quux".to_string()).into_iter().collect(),
+                ))),
+                LabelSelector {
+                    match_expressions: Some(vec![LabelSelectorRequirement {
+                        key: "foo".into(),
+                        operator: "NotIn".to_string(),
+                        values: Some(vec!["quux".into()]),
+                    }]),
+                    match_labels: None,
+                },
+                [
+                    ("foo".to_string(), "bar".to_string()),
+                    ("bah".to_string(), "baz".to_string()),
+                ]
+                .into(),
+                "NotIn expression match",
+            ),
+            (
+                Selector::from_iter(Some(Expression::DoesNotExist("foo".into()))),
+                LabelSelector {
+                    match_expressions: Some(vec![LabelSelectorRequirement {
+                        key: "foo".into(),
+                        operator: "DoesNotExist".to_string(),
+                        values: None,
+                    }]),
+                    match_labels: None,
+                },
+                [("bah".to_string(), "baz".to_string())].into(),
+                "DoesNotExist expression match",
+            ),
+        ] {
+            assert!(selector.matches(labels), "{}", msg);
+            let converted: LabelSelector = selector.clone().into();
+            assert_eq!(&converted, label_selector);
+            let converted_selector = Selector::try_from(label_selector.clone()).unwrap();
+            assert_eq!(&converted_selector, selector);
+        }
+    }
+
+    #[test]
+    fn test_label_selector_not_matches() {
+        for (selector, labels, msg) in &[
+            (
+                Selector::from_iter(Some(("foo", "bar"))),
+                Default::default(),
+                "missing label",
+            ),
+            (
+                Selector::from_iter(Some(Expression::In(
+                    "foo".into(),
+                    Some("bar".to_string()).into_iter().collect(),
+                ))),
+                [("foo".to_string(), "quux".to_string())].into(),
+                "In expression value mismatch",
+            ),
+            (
+                Selector::from_iter(Some(Expression::Exists("foo".into()))),
+                [("bah".to_string(), "baz".to_string())].into(),
+                "Exists expression missing key",
+            ),
+            (
+                Selector::from_iter(Some(Expression::NotEqual("foo".into(), "bar".into()))),
+                [("foo".to_string(), "bar".to_string())].into(),
+                "NotEqual expression equal value",
+            ),
+        ] {
+            assert!(!selector.matches(labels), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn test_to_string() {
+        let selector = Selector(vec![
+            Expression::In("foo".into(), ["bar".to_string(), "baz".to_string()].into()),
+            Expression::NotIn("foo".into(), ["bar".to_string(), "baz".to_string()].into()),
+            Expression::Equal("foo".into(), "bar".into()),
+            Expression::NotEqual("foo".into(), "bar".into()),
+            Expression::Exists("foo".into()),
+            Expression::DoesNotExist("foo".into()),
+        ])
+        .to_string();
+
+        assert_eq!(
+            selector,
+            "foo in (bar,baz),foo notin (bar,baz),foo=bar,foo!=bar,foo,!foo"
+        )
+    }
+
+    #[test]
+    fn test_bitand_composes_selectors() {
+        let selector = Expression::Equal("app".into(), "web".into()) & Expression::Exists("team".into());
+        let selector = selector & Selector::from(Expression::DoesNotExist("canary".into()));
+        assert_eq!(selector.to_string(), "app=web,team,!canary");
+    }
+
+    #[test]
+    fn test_prefixed_keys_stay_distinct() {
+        let selector = Selector::from_iter([
+            ("app.kubernetes.io/name", "web"),
+            ("example.com/name", "api"),
+            ("name", "db"),
+        ]);
+        assert_eq!(
+            selector.to_string(),
+            "app.kubernetes.io/name=web,example.com/name=api,name=db"
+        );
+        let converted: LabelSelector = selector.clone().into();
+        assert_eq!(converted.match_labels.as_ref().map(BTreeMap::len), Some(3));
+        assert_eq!(Selector::try_from(converted).unwrap(), selector);
+    }
+
+    #[test]
+    fn test_repeated_equal_keys_are_kept() {
+        let selector = Expression::Equal("example.com/name".into(), "web".into())
+            & Expression::Equal("example.com/name".into(), "api".into());
+        let converted: LabelSelector = selector.clone().into();
+        assert_eq!(
+            converted.match_labels,
+            Some([("example.com/name".to_string(), "web".to_string())].into())
+        );
+        assert_eq!(
+            converted.match_expressions,
+            Some(vec![LabelSelectorRequirement {
+                key: "example.com/name".into(),
+                operator: "In".into(),
+                values: Some(vec!["api".into()]),
+            }])
+        );
+        let labels = [("example.com/name".to_string(), "web".to_string())].into();
+        assert!(!selector.matches(&labels));
+        assert!(!Selector::try_from(converted).unwrap().matches(&labels));
+    }
+
+    #[test]
+    fn test_invalid_requirement_operator() {
+        let req = LabelSelectorRequirement {
+            key: "foo".into(),
+            operator: "Bogus".into(),
+            values: None,
+        };
+        assert!(Expression::try_from(req).is_err());
+        let req = LabelSelectorRequirement {
+            key: "foo".into(),
+            operator: "In".into(),
+            values: None,
+        };
+        assert!(Expression::try_from(req).is_err());
+    }
+}
@@ -17,20 +17,29 @@ pub mod admission;
 pub mod discovery;
 
 pub mod dynamic;
-pub use dynamic::{ApiResource, DynamicObject};
+pub use dynamic::{ApiResource, DynamicObject, LazyDynamicObject, UnexpectedTypeError};
 
 pub mod crd;
 pub use crd::CustomResourceExt;
 
+pub mod fields;
+pub use fields::{FieldExpression, FieldSelector};
+
 pub mod gvk;
 pub use gvk::{GroupVersion, GroupVersionKind, GroupVersionResource};
 
+pub mod labels;
+pub use labels::{Expression, Selector, SelectorExt};
+
 pub mod metadata;
-pub use metadata::{ListMeta, ObjectMeta, TypeMeta};
+pub use metadata::{ListMeta, ObjectMeta, PartialObjectMeta, TypeMeta, WithTypeMeta};
 
 pub mod object;
 pub use object::{NotUsed, Object, ObjectList};
 
+pub mod object_ref;
+pub use object_ref::{ObjectRef, ReferenceError};
+
 pub mod params;
 
 pub mod request;
@@ -51,13 +60,16 @@ pub mod schema;
 
 pub mod subresource;
 
+pub mod table;
+pub use table::Table;
+
 pub mod util;
 
 pub mod watch;
 pub use watch::WatchEvent;
 
 mod error;
-pub use error::ErrorResponse;
+pub use error::{ApplyConflict, ErrorResponse, TypeMismatchError};
 
 mod version;
 pub use version::Version;
@@ -1,6 +1,10 @@
 //! Metadata structs used in traits, lists, and dynamic objects.
+use std::{borrow::Cow, marker::PhantomData};
+
 pub use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ListMeta, ObjectMeta};
-use serde::{Deserialize, Serialize};
+use serde::{ser::Error as _, Deserialize, Serialize, Serializer};
+
+use crate::{DynamicObject, Resource};
 
 /// Type information that is flattened into every kubernetes object
 #[derive(Deserialize, Serialize, Clone, Default, Debug, Eq, PartialEq, Hash)]
@@ -12,3 +16,224 @@ pub struct TypeMeta {
     /// The name of the API
     pub kind: String,
 }
+
+impl TypeMeta {
+    /// Construct the `TypeMeta` of a [`PartialObjectMeta`] response
+    ///
+    /// ```
+    /// use kube_core::TypeMeta;
+    /// let type_meta = TypeMeta::partial_object_meta();
+    /// assert_eq!(type_meta.api_version, "meta.k8s.io/v1");
+    /// assert_eq!(type_meta.kind, "PartialObjectMetadata");
+    /// ```
+    pub fn partial_object_meta() -> Self {
+        Self {
+            api_version: "meta.k8s.io/v1".into(),
+            kind: "PartialObjectMetadata".into(),
+        }
+    }
+
+    /// Construct the `TypeMeta` of a [`Resource`]
+    ///
+    /// ```
+    /// use kube_core::TypeMeta;
+    /// use k8s_openapi::api::apps::v1::Deployment;
+    /// let type_meta = TypeMeta::resource::<Deployment>(&());
+    /// assert_eq!(type_meta.api_version, "apps/v1");
+    /// assert_eq!(type_meta.kind, "Deployment");
+    /// ```
+    pub fn resource<K: Resource>(dt: &K::DynamicType) -> Self {
+        Self {
+            api_version: K::api_version(dt).into_owned(),
+            kind: K::kind(dt).into_owned(),
+        }
+    }
+}
+
+/// Serializes a resource with the `apiVersion` and `kind` of its [`Resource`] implementation
+///
+/// Objects do not always carry their `TypeMeta`, which is required when writing manifests
+/// or server-side apply bodies. The fields are set on output, replacing any existing values.
+///
+/// ```
+/// use kube_core::{ApiResource, DynamicObject, GroupVersionKind, WithTypeMeta};
+/// let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("clux.dev", "v1", "Foo"));
+/// let mut foo = DynamicObject::new("foo", &ar);
+/// foo.types = None;
+/// let json = serde_json::to_value(WithTypeMeta::with_dyntype(&foo, &ar)).unwrap();
+/// assert_eq!(json["apiVersion"], "clux.dev/v1");
+/// assert_eq!(json["kind"], "Foo");
+/// ```
+pub struct WithTypeMeta<'a, K> {
+    resource: &'a K,
+    types: TypeMeta,
+}
+
+impl<'a, K: Resource> WithTypeMeta<'a, K> {
+    /// Wrap a resource whose type information is known at compile time
+    pub fn new(resource: &'a K) -> Self
+    where
+        K::DynamicType: Default,
+    {
+        Self::with_dyntype(resource, &K::DynamicType::default())
+    }
+
+    /// Wrap a resource along with its runtime type information
+    pub fn with_dyntype(resource: &'a K, dt: &K::DynamicType) -> Self {
+        Self {
+            resource,
+            types: TypeMeta::resource::<K>(dt),
+        }
+    }
+}
+
+impl<K: Serialize> Serialize for WithTypeMeta<'_, K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut value = serde_json::to_value(self.resource).map_err(S::Error::custom)?;
+        let map = value
+            .as_object_mut()
+            .ok_or_else(|| S::Error::custom("resource is not serialized as an object"))?;
+        map.insert("apiVersion".into(), self.types.api_version.clone().into());
+        map.insert("kind".into(), self.types.kind.clone().into());
+        value.serialize(serializer)
+    }
+}
+
+/// A metadata-only representation of an object of kind `K`
+///
+/// This is the `meta.k8s.io/v1` `PartialObjectMetadata` type returned by the apiserver
+/// when requesting metadata only, and can be used in place of the full type when only
+/// the [`ObjectMeta`] of the objects is of interest.
+///
+/// It implements [`Resource`] by delegating to `K`, so it uses the same urls as `K`.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialObjectMeta<K = DynamicObject> {
+    /// The type fields, not always present
+    #[serde(flatten, default)]
+    pub types: Option<TypeMeta>,
+    /// Standard object's metadata
+    #[serde(default)]
+    pub metadata: ObjectMeta,
+    /// Type information for static dispatch
+    #[serde(skip, default)]
+    pub _phantom: PhantomData<K>,
+}
+
+// Manual impls to avoid requiring bounds on `K`, which is only used as a marker
+impl<K> Clone for PartialObjectMeta<K> {
+    fn clone(&self) -> Self {
+        Self {
+            types: self.types.clone(),
+            metadata: self.metadata.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K> std::fmt::Debug for PartialObjectMeta<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PartialObjectMeta")
+            .field("types", &self.types)
+            .field("metadata", &self.metadata)
+            .finish()
+    }
+}
+
+impl<K> Default for PartialObjectMeta<K> {
+    fn default() -> Self {
+        Self {
+            types: Some(TypeMeta::partial_object_meta()),
+            metadata: ObjectMeta::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K> PartialEq for PartialObjectMeta<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.types == other.types && self.metadata == other.metadata
+    }
+}
+
+impl<K> From<ObjectMeta> for PartialObjectMeta<K> {
+    fn from(metadata: ObjectMeta) -> Self {
+        Self {
+            metadata,
+            ..Self::default()
+        }
+    }
+}
+
+impl<K: Resource> Resource for PartialObjectMeta<K> {
+    type DynamicType = K::DynamicType;
+    type Scope = K::Scope;
+
+    fn kind(dt: &Self::DynamicType) -> Cow<'_, str> {
+        K::kind(dt)
+    }
+
+    fn group(dt: &Self::DynamicType) -> Cow<'_, str> {
+        K::group(dt)
+    }
+
+    fn version(dt: &Self::DynamicType) -> Cow<'_, str> {
+        K::version(dt)
+    }
+
+    fn plural(dt: &Self::DynamicType) -> Cow<'_, str> {
+        K::plural(dt)
+    }
+
+    fn meta(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+
+    fn meta_mut(&mut self) -> &mut ObjectMeta {
+        &mut self.metadata
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ObjectMeta, PartialObjectMeta, TypeMeta};
+    use crate::{object::ObjectList, Resource, ResourceExt};
+    use k8s_openapi::api::core::v1::Pod;
+
+    #[test]
+    fn partial_object_meta_uses_underlying_resource() {
+        assert_eq!(PartialObjectMeta::<Pod>::kind(&()), "Pod");
+        assert_eq!(
+            PartialObjectMeta::<Pod>::url_path(&(), Some("ns")),
+            Pod::url_path(&(), Some("ns"))
+        );
+    }
+
+    #[test]
+    fn partial_object_meta_deserializes() {
+        let json = serde_json::json!({
+            "apiVersion": "meta.k8s.io/v1",
+            "kind": "PartialObjectMetadataList",
+            "metadata": { "resourceVersion": "10" },
+            "items": [{
+                "apiVersion": "meta.k8s.io/v1",
+                "kind": "PartialObjectMetadata",
+                "metadata": { "name": "blog", "namespace": "dev", "labels": { "app": "blog" } }
+            }]
+        });
+        let list: ObjectList<PartialObjectMeta<Pod>> = serde_json::from_value(json).unwrap();
+        assert_eq!(list.metadata.resource_version.as_deref(), Some("10"));
+        let pom = &list.items[0];
+        assert_eq!(pom.types, Some(TypeMeta::partial_object_meta()));
+        assert_eq!(pom.name_any(), "blog");
+        assert_eq!(pom.namespace().as_deref(), Some("dev"));
+        assert_eq!(pom.labels().get("app").map(String::as_str), Some("blog"));
+
+        let from_meta: PartialObjectMeta<Pod> = ObjectMeta {
+            name: Some("blog".into()),
+            ..ObjectMeta::default()
+        }
+        .into();
+        assert_eq!(from_meta.name_any(), "blog");
+    }
+}
@@ -73,17 +73,104 @@ impl<T: Clone> ObjectList<T> {
     pub fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = &mut T> + 'a {
         self.items.iter_mut()
     }
+
+    /// The resourceVersion the list was served at
+    ///
+    /// This is the version to start a watch from after listing.
+    pub fn resource_version(&self) -> Option<&str> {
+        self.metadata.resource_version.as_deref()
+    }
+
+    /// The token to pass as [`ListParams::continue_token`](crate::params::ListParams::continue_token)
+    /// to retrieve the next page
+    ///
+    /// Returns `None` when this is the last page.
+    pub fn continue_token(&self) -> Option<&str> {
+        self.metadata
+            .continue_
+            .as_deref()
+            .filter(|token| !token.is_empty())
+    }
+
+    /// The number of items remaining after this page, if the apiserver could compute it
+    ///
+    /// This is only an estimate, and is not set when using label or field selectors.
+    pub fn remaining_item_count(&self) -> Option<i64> {
+        self.metadata.remaining_item_count
+    }
+
+    /// Whether more pages can be retrieved with [`ObjectList::continue_token`]
+    pub fn has_more(&self) -> bool {
+        self.continue_token().is_some()
+    }
 }
 
 impl<T: Clone> IntoIterator for ObjectList<T> {
-    type IntoIter = ::std::vec::IntoIter<Self::Item>;
+    type IntoIter = IntoIter<T>;
     type Item = T;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.items.into_iter()
+        IntoIter {
+            metadata: self.metadata,
+            items: self.items.into_iter(),
+        }
     }
 }
 
+/// An owning iterator over the items of an [`ObjectList`]
+///
+/// Keeps the [`ListMeta`] of the list around, so that pagination and resource versions
+/// can still be inspected while (or after) consuming the items.
+///
+/// ```
+/// use kube::api::{ListMeta, ObjectList};
+///
+/// let metadata = ListMeta {
+///     resource_version: Some("1234".into()),
+///     ..ListMeta::default()
+/// };
+/// let mut iter = ObjectList { metadata, items: vec![1, 2] }.into_iter();
+/// assert_eq!(iter.next(), Some(1));
+/// assert_eq!(iter.metadata().resource_version.as_deref(), Some("1234"));
+/// ```
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    metadata: ListMeta,
+    items: std::vec::IntoIter<T>,
+}
+
+impl<T> IntoIter<T> {
+    /// The metadata of the list being iterated over
+    pub fn metadata(&self) -> &ListMeta {
+        &self.metadata
+    }
+
+    /// Consumes the iterator, returning the list metadata
+    pub fn into_metadata(self) -> ListMeta {
+        self.metadata
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.items.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.items.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
 impl<'a, T: Clone> IntoIterator for &'a ObjectList<T> {
     type IntoIter = ::std::slice::Iter<'a, T>;
     type Item = &'a T;
@@ -142,6 +229,45 @@ pub trait HasStatus {
     fn status_mut(&mut self) -> &mut Option<Self::Status>;
 }
 
+/// A server-side apply patch that only sets the `status` of a resource
+///
+/// Applying this through the status subresource makes the field manager own exactly the status fields
+/// that are set, without touching the `spec` or fields owned by other managers.
+/// Use it with [`Patch::Apply`](crate::params::Patch::Apply).
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusApply<S> {
+    /// The api version of the resource being patched
+    pub api_version: String,
+    /// The kind of the resource being patched
+    pub kind: String,
+    /// The status fields to apply
+    pub status: S,
+}
+
+impl<S> StatusApply<S> {
+    /// Construct a status apply patch for `K`
+    pub fn new<K>(status: S) -> Self
+    where
+        K: Resource + HasStatus<Status = S>,
+        K::DynamicType: Default,
+    {
+        Self::new_with::<K>(&K::DynamicType::default(), status)
+    }
+
+    /// Construct a status apply patch for `K` with a dynamic type
+    pub fn new_with<K>(dt: &K::DynamicType, status: S) -> Self
+    where
+        K: Resource + HasStatus<Status = S>,
+    {
+        Self {
+            api_version: K::api_version(dt).into_owned(),
+            kind: K::kind(dt).into_owned(),
+            status,
+        }
+    }
+}
+
 // -------------------------------------------------------
 
 /// A standard Kubernetes object with `.spec` and `.status`.
@@ -152,12 +278,32 @@ pub trait HasStatus {
 ///
 /// This can be used to tie existing resources to smaller, local struct variants to optimize for memory use.
 /// E.g. if you are only interested in a few fields, but you store tons of them in memory with reflectors.
+///
+/// It can also stand in for custom resources whose structs are maintained without `kube::derive`.
+/// It works with the dynamic `Api` constructors, the status subresource methods, and the `watcher`,
+/// and implements `JsonSchema` with the `schema` feature so that a CRD schema can be generated from it.
+///
+/// ```no_run
+/// use kube::{api::{Api, ApiResource, GroupVersionKind, Object, Patch, PatchParams, StatusApply}, Client};
+/// #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+/// struct FooSpec { replicas: u32 }
+/// #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+/// struct FooStatus { ready: bool }
+/// type Foo = Object<FooSpec, FooStatus>;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let ar = ApiResource::from_gvk(&GroupVersionKind::gvk("clux.dev", "v1", "Foo"));
+///     let foos: Api<Foo> = Api::default_namespaced_with(client, &ar);
+///     let foo = foos.get("foo").await?;
+///     let status = StatusApply::new_with::<Foo>(&ar, FooStatus { ready: foo.spec.replicas > 0 });
+///     foos.patch_status("foo", &PatchParams::apply("foo-controller"), &Patch::Apply(status)).await?;
+///     Ok(())
+/// }
+/// ```
 #[derive(Deserialize, Serialize, Clone, Debug)]
-pub struct Object<P, U>
-where
-    P: Clone,
-    U: Clone,
-{
+pub struct Object<P, U = NotUsed> {
     /// The type fields, not always present
     #[serde(flatten, default)]
     pub types: Option<TypeMeta>,
@@ -181,11 +327,7 @@ where
     pub status: Option<U>,
 }
 
-impl<P, U> Object<P, U>
-where
-    P: Clone,
-    U: Clone,
-{
+impl<P, U> Object<P, U> {
     /// A constructor that takes Resource values from an `ApiResource`
     pub fn new(name: &str, ar: &ApiResource, spec: P) -> Self {
         Self {
@@ -208,13 +350,16 @@ where
         self.metadata.namespace = Some(ns.into());
         self
     }
+
+    /// Attach a status to an Object
+    #[must_use]
+    pub fn with_status(mut self, status: U) -> Self {
+        self.status = Some(status);
+        self
+    }
 }
 
-impl<P, U> Resource for Object<P, U>
-where
-    P: Clone,
-    U: Clone,
-{
+impl<P, U> Resource for Object<P, U> {
     type DynamicType = ApiResource;
     type Scope = DynamicResourceScope;
 
@@ -247,11 +392,7 @@ where
     }
 }
 
-impl<P, U> HasSpec for Object<P, U>
-where
-    P: Clone,
-    U: Clone,
-{
+impl<P, U> HasSpec for Object<P, U> {
     type Spec = P;
 
     fn spec(&self) -> &Self::Spec {
@@ -263,11 +404,7 @@ where
     }
 }
 
-impl<P, U> HasStatus for Object<P, U>
-where
-    P: Clone,
-    U: Clone,
-{
+impl<P, U> HasStatus for Object<P, U> {
     type Status = U;
 
     fn status(&self) -> Option<&Self::Status> {
@@ -279,18 +416,75 @@ where
     }
 }
 
+/// Describes the `spec` and `status` of the object, as used in the schema of a CRD
+///
+/// The metadata and type fields are left out, since the apiserver validates those itself.
+#[cfg(feature = "schema")]
+impl<P: schemars::JsonSchema, U: schemars::JsonSchema> schemars::JsonSchema for Object<P, U> {
+    fn schema_name() -> String {
+        format!("Object_for_{}_and_{}", P::schema_name(), U::schema_name())
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, ObjectValidation, SchemaObject};
+
+        SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                properties: [
+                    ("spec".to_string(), gen.subschema_for::<P>()),
+                    ("status".to_string(), gen.subschema_for::<Option<U>>()),
+                ]
+                .into_iter()
+                .collect(),
+                required: std::iter::once("spec".to_string()).collect(),
+                ..ObjectValidation::default()
+            })),
+            ..SchemaObject::default()
+        }
+        .into()
+    }
+}
+
 /// Empty struct for when data should be discarded
 ///
 /// Not using [`()`](https://doc.rust-lang.org/stable/std/primitive.unit.html), because serde's
 /// [`Deserialize`](serde::Deserialize) `impl` is too strict.
 #[derive(Clone, Deserialize, Serialize, Default, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NotUsed {}
 
 #[cfg(test)]
 mod test {
-    use super::{ApiResource, HasSpec, HasStatus, NotUsed, Object, Resource};
+    use super::{ApiResource, HasSpec, HasStatus, NotUsed, Object, ObjectList, Resource};
     use crate::resource::ResourceExt;
 
+    #[test]
+    fn object_list_exposes_pagination() {
+        let list: ObjectList<String> = serde_json::from_value(serde_json::json!({
+            "metadata": { "resourceVersion": "42", "continue": "token", "remainingItemCount": 3 },
+            "items": ["a", "b"]
+        }))
+        .unwrap();
+        assert_eq!(list.resource_version(), Some("42"));
+        assert_eq!(list.continue_token(), Some("token"));
+        assert_eq!(list.remaining_item_count(), Some(3));
+        assert!(list.has_more());
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next().as_deref(), Some("a"));
+        assert_eq!(iter.metadata().continue_.as_deref(), Some("token"));
+
+        let last: ObjectList<String> = serde_json::from_value(serde_json::json!({
+            "metadata": { "continue": "" },
+            "items": []
+        }))
+        .unwrap();
+        assert_eq!(last.continue_token(), None);
+        assert!(!last.has_more());
+    }
+
     #[test]
     fn simplified_k8s_object() {
         use k8s_openapi::api::core::v1::Pod;
@@ -323,7 +517,7 @@ mod test {
         assert_eq!(mypod.types.as_ref().unwrap().api_version, "v1");
 
         assert_eq!(mypod.namespace().unwrap(), "dev");
-        assert_eq!(mypod.name(), "blog");
+        assert_eq!(mypod.name_unchecked(), "blog");
         assert!(mypod.status().is_none());
         assert_eq!(mypod.spec().containers[0], ContainerSimple {
             image: "blog".into()
@@ -335,4 +529,37 @@ mod test {
         assert_eq!(PodSimple::kind(&ar), "Pod");
         assert_eq!(PodSimple::group(&ar), "");
     }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn object_schema() {
+        #[derive(Clone, schemars::JsonSchema)]
+        struct FooSpec {
+            #[allow(dead_code)]
+            replicas: u32,
+        }
+        let schema = schemars::gen::SchemaSettings::openapi3()
+            .with(|s| s.inline_subschemas = true)
+            .into_generator()
+            .into_root_schema_for::<Object<FooSpec>>();
+        let schema = serde_json::to_value(schema).unwrap();
+        let spec = &schema["properties"]["spec"];
+        assert_eq!(spec["properties"]["replicas"]["type"], "integer");
+        assert_eq!(schema["properties"]["status"]["type"], "object");
+        assert_eq!(schema["required"], serde_json::json!(["spec"]));
+    }
+
+    #[test]
+    fn status_apply_patch() {
+        use super::StatusApply;
+        use k8s_openapi::api::core::v1::Pod;
+
+        let ar = ApiResource::erase::<Pod>(&());
+        type PodSimple = Object<NotUsed, String>;
+        let patch = StatusApply::new_with::<PodSimple>(&ar, "ready".to_string());
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!({ "apiVersion": "v1", "kind": "Pod", "status": "ready" })
+        );
+    }
 }
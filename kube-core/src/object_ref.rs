@@ -0,0 +1,658 @@
+//! A canonical reference type for Kubernetes objects
+use crate::{
+    discovery::Scope,
+    dynamic::{ApiResource, DynamicObject},
+    error::TypeMismatchError,
+    metadata::{ObjectMeta, TypeMeta},
+    resource::{Resource, ResourceExt},
+};
+use k8s_openapi::{api::core::v1::ObjectReference, apimachinery::pkg::apis::meta::v1::OwnerReference};
+use std::{
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// A typed and namedspaced (if relevant) reference to a Kubernetes object
+///
+/// `K` may be either the object type or `DynamicObject`, in which case the
+/// type is stored at runtime. Erased `ObjectRef`s pointing to different types
+/// are still considered different.
+///
+/// ```
+/// use kube_core::ObjectRef;
+/// use k8s_openapi::api::core::v1::{ConfigMap, Secret};
+/// assert_ne!(
+///     ObjectRef::<ConfigMap>::new("a").erase(),
+///     ObjectRef::<Secret>::new("a").erase(),
+/// );
+/// ```
+#[non_exhaustive]
+pub struct ObjectRef<K: Resource> {
+    /// The dynamic type information of the object
+    pub dyntype: K::DynamicType,
+    /// The name of the object
+    pub name: String,
+    /// The namespace of the object
+    ///
+    /// May only be `None` if the kind is cluster-scoped (not located in a namespace).
+    /// Note that it *is* acceptable for an `ObjectRef` to a cluster-scoped resource to
+    /// have a namespace. These are, however, not considered equal:
+    ///
+    /// ```
+    /// # use kube_core::ObjectRef;
+    /// # use k8s_openapi::api::core::v1::ConfigMap;
+    /// assert_ne!(ObjectRef::<ConfigMap>::new("foo"), ObjectRef::new("foo").within("bar"));
+    /// ```
+    pub namespace: Option<String>,
+    /// Extra information about the object being referred to
+    ///
+    /// This is *not* considered when comparing objects, but may be used when converting to and from other representations,
+    /// such as [`OwnerReference`] or [`ObjectReference`].
+    pub extra: Extra,
+}
+
+// Manual impls rather than derives, since the bounds apply to `K::DynamicType` rather than `K`,
+// and `extra` is intentionally excluded from comparisons and hashing.
+impl<K: Resource> Debug for ObjectRef<K>
+where
+    K::DynamicType: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectRef")
+            .field("dyntype", &self.dyntype)
+            .field("name", &self.name)
+            .field("namespace", &self.namespace)
+            .field("extra", &self.extra)
+            .finish()
+    }
+}
+
+impl<K: Resource> PartialEq for ObjectRef<K>
+where
+    K::DynamicType: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.dyntype == other.dyntype && self.name == other.name && self.namespace == other.namespace
+    }
+}
+
+impl<K: Resource> Eq for ObjectRef<K> where K::DynamicType: Eq {}
+
+impl<K: Resource> Hash for ObjectRef<K>
+where
+    K::DynamicType: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dyntype.hash(state);
+        self.name.hash(state);
+        self.namespace.hash(state);
+    }
+}
+
+impl<K: Resource> Clone for ObjectRef<K>
+where
+    K::DynamicType: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            dyntype: self.dyntype.clone(),
+            name: self.name.clone(),
+            namespace: self.namespace.clone(),
+            extra: self.extra.clone(),
+        }
+    }
+}
+
+/// Non-vital information about an object being referred to
+///
+/// See [`ObjectRef::extra`].
+#[derive(Default, Debug, Clone)]
+#[non_exhaustive]
+pub struct Extra {
+    /// The version of the resource at the time of reference
+    pub resource_version: Option<String>,
+    /// The uid of the object
+    pub uid: Option<String>,
+}
+
+/// Failed to convert between [`ObjectRef`], [`OwnerReference`] and [`ObjectReference`]
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReferenceError {
+    /// The reference points to another type than the requested one
+    #[error("reference is of another type: {0}")]
+    TypeMismatch(#[source] TypeMismatchError),
+
+    /// A field required by the target representation is not set
+    #[error("reference has no {0}")]
+    MissingField(&'static str),
+}
+
+impl<K: Resource> ObjectRef<K>
+where
+    K::DynamicType: Default,
+{
+    /// Creates an `ObjectRef` to a named object of a statically known type
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self::new_with(name, Default::default())
+    }
+
+    /// Creates an `ObjectRef` from an object of a statically known type
+    #[must_use]
+    pub fn from_obj(obj: &K) -> Self
+    where
+        K: Resource,
+    {
+        Self::from_obj_with(obj, Default::default())
+    }
+}
+
+impl<K: Resource> ObjectRef<K> {
+    /// Creates an `ObjectRef` to a named object using the given dynamic type
+    #[must_use]
+    pub fn new_with(name: &str, dyntype: K::DynamicType) -> Self {
+        Self {
+            dyntype,
+            name: name.into(),
+            namespace: None,
+            extra: Extra::default(),
+        }
+    }
+
+    /// Sets the namespace of the referenced object
+    #[must_use]
+    pub fn within(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Creates `ObjectRef` from the resource and dynamic type.
+    #[must_use]
+    pub fn from_obj_with(obj: &K, dyntype: K::DynamicType) -> Self
+    where
+        K: Resource,
+    {
+        let meta = obj.meta();
+        Self {
+            dyntype,
+            name: obj.name_unchecked(),
+            namespace: meta.namespace.clone(),
+            extra: Extra::from_obj_meta(meta),
+        }
+    }
+
+    /// Create an `ObjectRef` from an `OwnerReference`
+    ///
+    /// Returns `None` if the types do not match.
+    /// See [`ObjectRef::try_from_owner_ref`] for the reason of a failed conversion.
+    #[must_use]
+    pub fn from_owner_ref(
+        namespace: Option<&str>,
+        owner: &OwnerReference,
+        dyntype: K::DynamicType,
+    ) -> Option<Self> {
+        Self::try_from_owner_ref(namespace, owner, dyntype).ok()
+    }
+
+    /// Create an `ObjectRef` from an `OwnerReference`, located in `namespace`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReferenceError::TypeMismatch`] if the owner is not of type `K`.
+    pub fn try_from_owner_ref(
+        namespace: Option<&str>,
+        owner: &OwnerReference,
+        dyntype: K::DynamicType,
+    ) -> Result<Self, ReferenceError> {
+        check_type::<K>(&dyntype, Some(&owner.api_version), Some(&owner.kind))?;
+        Ok(Self {
+            dyntype,
+            name: owner.name.clone(),
+            namespace: namespace.map(String::from),
+            extra: Extra {
+                resource_version: None,
+                uid: Some(owner.uid.clone()),
+            },
+        })
+    }
+
+    /// Create an `ObjectRef` from an `OwnerReference` of `dependent`
+    ///
+    /// Owners are either cluster-scoped or in the namespace of their dependent,
+    /// so the namespace is taken from `dependent` unless `owner_scope` is [`Scope::Cluster`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReferenceError::TypeMismatch`] if the owner is not of type `K`.
+    pub fn try_from_owner_of(
+        dependent: &ObjectMeta,
+        owner: &OwnerReference,
+        dyntype: K::DynamicType,
+        owner_scope: &Scope,
+    ) -> Result<Self, ReferenceError> {
+        let namespace = match owner_scope {
+            Scope::Cluster => None,
+            Scope::Namespaced => dependent.namespace.as_deref(),
+        };
+        Self::try_from_owner_ref(namespace, owner, dyntype)
+    }
+
+    /// Create an `ObjectRef` from an `ObjectReference`
+    ///
+    /// Returns `None` if the types do not match, or if the reference has no name.
+    /// See [`ObjectRef::try_from_object_reference`] for the reason of a failed conversion.
+    #[must_use]
+    pub fn from_object_reference(obj_ref: &ObjectReference, dyntype: K::DynamicType) -> Option<Self> {
+        Self::try_from_object_reference(obj_ref, dyntype).ok()
+    }
+
+    /// Create an `ObjectRef` from an `ObjectReference`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReferenceError::TypeMismatch`] if the reference is not to type `K`,
+    /// and [`ReferenceError::MissingField`] if it has no apiVersion, kind or name.
+    pub fn try_from_object_reference(
+        obj_ref: &ObjectReference,
+        dyntype: K::DynamicType,
+    ) -> Result<Self, ReferenceError> {
+        check_type::<K>(&dyntype, obj_ref.api_version.as_deref(), obj_ref.kind.as_deref())?;
+        Ok(Self {
+            name: obj_ref.name.clone().ok_or(ReferenceError::MissingField("name"))?,
+            namespace: obj_ref.namespace.clone(),
+            extra: Extra {
+                resource_version: obj_ref.resource_version.clone(),
+                uid: obj_ref.uid.clone(),
+            },
+            dyntype,
+        })
+    }
+
+    /// Create an `OwnerReference` pointing to the referenced object
+    ///
+    /// Returns `None` if the uid of the object is not known.
+    #[must_use]
+    pub fn to_owner_ref(&self) -> Option<OwnerReference> {
+        self.try_to_owner_ref().ok()
+    }
+
+    /// Create an `OwnerReference` pointing to the referenced object
+    ///
+    /// The namespace is not part of an `OwnerReference`, since owners must be cluster-scoped
+    /// or in the namespace of their dependents.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReferenceError::MissingField`] if the uid of the object is not known.
+    pub fn try_to_owner_ref(&self) -> Result<OwnerReference, ReferenceError> {
+        Ok(OwnerReference {
+            api_version: K::api_version(&self.dyntype).into_owned(),
+            kind: K::kind(&self.dyntype).into_owned(),
+            name: self.name.clone(),
+            uid: self
+                .extra
+                .uid
+                .clone()
+                .ok_or(ReferenceError::MissingField("uid"))?,
+            ..OwnerReference::default()
+        })
+    }
+
+    /// Convert into a reference to `K2`
+    ///
+    /// Note that no checking is done on whether this conversion makes sense. For example, every `Service`
+    /// has a corresponding `Endpoints`, but it wouldn't make sense to convert a `Pod` into a `Deployment`.
+    #[must_use]
+    pub fn into_kind_unchecked<K2: Resource>(self, dt2: K2::DynamicType) -> ObjectRef<K2> {
+        ObjectRef {
+            dyntype: dt2,
+            name: self.name,
+            namespace: self.namespace,
+            extra: self.extra,
+        }
+    }
+
+    /// Erase the static type of the reference, storing the type information at runtime instead
+    pub fn erase(self) -> ObjectRef<DynamicObject> {
+        ObjectRef {
+            dyntype: ApiResource::erase::<K>(&self.dyntype),
+            name: self.name,
+            namespace: self.namespace,
+            extra: self.extra,
+        }
+    }
+}
+
+impl<K: Resource> From<ObjectRef<K>> for ObjectReference {
+    fn from(val: ObjectRef<K>) -> Self {
+        let ObjectRef {
+            dyntype: dt,
+            name,
+            namespace,
+            extra: Extra {
+                resource_version,
+                uid,
+            },
+        } = val;
+        ObjectReference {
+            api_version: Some(K::api_version(&dt).into_owned()),
+            kind: Some(K::kind(&dt).into_owned()),
+            field_path: None,
+            name: Some(name),
+            namespace,
+            resource_version,
+            uid,
+        }
+    }
+}
+
+/// Create an `ObjectReference` from an `OwnerReference` of `dependent`, such as for recording events about the owner
+///
+/// The namespace is inferred as by [`ObjectRef::try_from_owner_of`].
+#[must_use]
+pub fn object_reference_from_owner_ref(
+    dependent: &ObjectMeta,
+    owner: &OwnerReference,
+    owner_scope: &Scope,
+) -> ObjectReference {
+    let namespace = match owner_scope {
+        Scope::Cluster => None,
+        Scope::Namespaced => dependent.namespace.clone(),
+    };
+    ObjectReference {
+        api_version: Some(owner.api_version.clone()),
+        kind: Some(owner.kind.clone()),
+        name: Some(owner.name.clone()),
+        namespace,
+        uid: Some(owner.uid.clone()),
+        ..ObjectReference::default()
+    }
+}
+
+/// Create an `OwnerReference` from an `ObjectReference`
+///
+/// The namespace is dropped, as for [`ObjectRef::try_to_owner_ref`].
+///
+/// # Errors
+///
+/// Returns [`ReferenceError::MissingField`] if the reference has no apiVersion, kind, name or uid.
+pub fn owner_ref_from_object_reference(obj_ref: &ObjectReference) -> Result<OwnerReference, ReferenceError> {
+    let field = |value: &Option<String>, name| value.clone().ok_or(ReferenceError::MissingField(name));
+    Ok(OwnerReference {
+        api_version: field(&obj_ref.api_version, "apiVersion")?,
+        kind: field(&obj_ref.kind, "kind")?,
+        name: field(&obj_ref.name, "name")?,
+        uid: field(&obj_ref.uid, "uid")?,
+        ..OwnerReference::default()
+    })
+}
+
+fn check_type<K: Resource>(
+    dyntype: &K::DynamicType,
+    api_version: Option<&str>,
+    kind: Option<&str>,
+) -> Result<(), ReferenceError> {
+    let api_version = api_version.ok_or(ReferenceError::MissingField("apiVersion"))?;
+    let kind = kind.ok_or(ReferenceError::MissingField("kind"))?;
+    let (expected_version, expected_kind) = (K::api_version(dyntype), K::kind(dyntype));
+    if api_version == expected_version && kind == expected_kind {
+        Ok(())
+    } else {
+        let expected = TypeMeta {
+            api_version: expected_version.into_owned(),
+            kind: expected_kind.into_owned(),
+        };
+        let found = TypeMeta {
+            api_version: api_version.to_string(),
+            kind: kind.to_string(),
+        };
+        Err(ReferenceError::TypeMismatch(TypeMismatchError::new(
+            &expected, &found,
+        )))
+    }
+}
+
+impl<K: Resource> Display for ObjectRef<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}/{}",
+            K::kind(&self.dyntype),
+            K::version(&self.dyntype),
+            K::group(&self.dyntype),
+            self.name
+        )?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, ".{}", namespace)?;
+        }
+        Ok(())
+    }
+}
+
+/// Failed to parse an [`ObjectRef`]
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("failed to parse object reference: {0}")]
+pub struct ParseObjectRefError(pub String);
+
+impl<K: Resource> FromStr for ObjectRef<K>
+where
+    K::DynamicType: Default,
+{
+    type Err = ParseObjectRefError;
+
+    /// Parses a `name` or `namespace/name` key, as used by client-go caches
+    ///
+    /// This is not the inverse of the [`Display`] format, which is ambiguous for names containing dots.
+    /// Since namespaces can't contain dots, the [`Display`] format of an `ObjectRef` is rejected
+    /// rather than being misread as a key.
+    ///
+    /// ```
+    /// use kube_core::ObjectRef;
+    /// use k8s_openapi::api::core::v1::{Node, Pod};
+    /// let pod_ref = ObjectRef::<Pod>::new("coredns").within("kube-system");
+    /// assert_eq!("kube-system/coredns".parse(), Ok(pod_ref));
+    /// assert_eq!("node-1".parse(), Ok(ObjectRef::<Node>::new("node-1")));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, name) = match s.split_once('/') {
+            Some((namespace, name)) => (Some(namespace), name),
+            None => (None, s),
+        };
+        let invalid_namespace = |ns: &str| ns.is_empty() || ns.contains('.');
+        if name.is_empty() || name.contains('/') || namespace.map_or(false, invalid_namespace) {
+            return Err(ParseObjectRefError(format!(
+                "expected name or namespace/name, got {:?}",
+                s
+            )));
+        }
+        let obj_ref = Self::new(name);
+        Ok(match namespace {
+            Some(ns) => obj_ref.within(ns),
+            None => obj_ref,
+        })
+    }
+}
+
+impl Extra {
+    fn from_obj_meta(obj_meta: &ObjectMeta) -> Self {
+        Self {
+            resource_version: obj_meta.resource_version.clone(),
+            uid: obj_meta.uid.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    };
+
+    use super::{Extra, ObjectRef, ReferenceError};
+    use k8s_openapi::api::{
+        apps::v1::Deployment,
+        core::v1::{Node, ObjectReference, Pod},
+    };
+
+    #[test]
+    fn display_should_follow_expected_format() {
+        assert_eq!(
+            format!("{}", ObjectRef::<Pod>::new("my-pod").within("my-namespace")),
+            "Pod.v1./my-pod.my-namespace"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                ObjectRef::<Deployment>::new("my-deploy").within("my-namespace")
+            ),
+            "Deployment.v1.apps/my-deploy.my-namespace"
+        );
+        assert_eq!(
+            format!("{}", ObjectRef::<Node>::new("my-node")),
+            "Node.v1./my-node"
+        );
+    }
+
+    #[test]
+    fn display_should_be_transparent_to_representation() {
+        let pod_ref = ObjectRef::<Pod>::new("my-pod").within("my-namespace");
+        assert_eq!(format!("{}", pod_ref), format!("{}", pod_ref.erase()));
+        let deploy_ref = ObjectRef::<Deployment>::new("my-deploy").within("my-namespace");
+        assert_eq!(format!("{}", deploy_ref), format!("{}", deploy_ref.erase()));
+        let node_ref = ObjectRef::<Node>::new("my-node");
+        assert_eq!(format!("{}", node_ref), format!("{}", node_ref.erase()));
+    }
+
+    #[test]
+    fn comparison_should_ignore_extra() {
+        let minimal = ObjectRef::<Pod>::new("my-pod").within("my-namespace");
+        let with_extra = ObjectRef {
+            extra: Extra {
+                resource_version: Some("123".to_string()),
+                uid: Some("638ffacd-f666-4402-ba10-7848c66ef576".to_string()),
+            },
+            ..minimal.clone()
+        };
+
+        // Eq and PartialEq should be unaffected by the contents of `extra`
+        assert_eq!(minimal, with_extra);
+
+        // Hash should be unaffected by the contents of `extra`
+        let hash_value = |value: &ObjectRef<Pod>| {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash_value(&minimal), hash_value(&with_extra));
+    }
+
+    #[test]
+    fn from_str_should_parse_namespace_keys() {
+        assert_eq!(
+            "my-namespace/my-pod".parse::<ObjectRef<Pod>>().unwrap(),
+            ObjectRef::new("my-pod").within("my-namespace")
+        );
+        assert_eq!(
+            "my-node".parse::<ObjectRef<Node>>().unwrap(),
+            ObjectRef::new("my-node")
+        );
+        assert!("".parse::<ObjectRef<Pod>>().is_err());
+        assert!("/my-pod".parse::<ObjectRef<Pod>>().is_err());
+        assert!("a/b/c".parse::<ObjectRef<Pod>>().is_err());
+    }
+
+    #[test]
+    fn from_str_should_roundtrip_keys_but_not_display() {
+        let refs = [
+            ObjectRef::<Deployment>::new("my.deploy").within("my-namespace"),
+            ObjectRef::<Deployment>::new("my-deploy"),
+        ];
+        for obj_ref in refs {
+            let key = match &obj_ref.namespace {
+                Some(ns) => format!("{}/{}", ns, obj_ref.name),
+                None => obj_ref.name.clone(),
+            };
+            assert_eq!(key.parse::<ObjectRef<Deployment>>().unwrap(), obj_ref);
+            assert!(obj_ref.to_string().parse::<ObjectRef<Deployment>>().is_err());
+        }
+    }
+
+    #[test]
+    fn object_reference_roundtrips() {
+        let pod_ref = ObjectRef::<Pod> {
+            extra: Extra {
+                resource_version: Some("123".to_string()),
+                uid: Some("638ffacd-f666-4402-ba10-7848c66ef576".to_string()),
+            },
+            ..ObjectRef::new("my-pod").within("my-namespace")
+        };
+        let obj_ref: ObjectReference = pod_ref.clone().into();
+        let parsed = ObjectRef::<Pod>::from_object_reference(&obj_ref, ()).unwrap();
+        assert_eq!(parsed, pod_ref);
+        assert_eq!(parsed.extra.uid, pod_ref.extra.uid);
+        assert!(ObjectRef::<Node>::from_object_reference(&obj_ref, ()).is_none());
+
+        let owner = pod_ref.to_owner_ref().unwrap();
+        assert_eq!(owner.kind, "Pod");
+        assert_eq!(owner.uid, "638ffacd-f666-4402-ba10-7848c66ef576");
+        assert_eq!(
+            ObjectRef::<Pod>::from_owner_ref(Some("my-namespace"), &owner, ()),
+            Some(pod_ref)
+        );
+        assert!(ObjectRef::<Pod>::new("no-uid").to_owner_ref().is_none());
+    }
+
+    #[test]
+    fn owner_references_infer_namespaces() {
+        use super::{object_reference_from_owner_ref, owner_ref_from_object_reference};
+        use crate::{discovery::Scope, error::TypeMismatchError, metadata::ObjectMeta};
+        use k8s_openapi::api::apps::v1::ReplicaSet;
+
+        let dependent = ObjectMeta {
+            name: Some("web-abc12".into()),
+            namespace: Some("apps".into()),
+            ..ObjectMeta::default()
+        };
+        let owner = ObjectRef::<ReplicaSet> {
+            extra: Extra {
+                resource_version: None,
+                uid: Some("638ffacd-f666-4402-ba10-7848c66ef576".to_string()),
+            },
+            ..ObjectRef::new("web").within("apps")
+        }
+        .try_to_owner_ref()
+        .unwrap();
+
+        let namespaced =
+            ObjectRef::<ReplicaSet>::try_from_owner_of(&dependent, &owner, (), &Scope::Namespaced);
+        assert_eq!(namespaced.unwrap(), ObjectRef::new("web").within("apps"));
+        let cluster = ObjectRef::<ReplicaSet>::try_from_owner_of(&dependent, &owner, (), &Scope::Cluster);
+        assert_eq!(cluster.unwrap(), ObjectRef::new("web"));
+        assert_eq!(
+            ObjectRef::<Deployment>::try_from_owner_ref(None, &owner, ()),
+            Err(ReferenceError::TypeMismatch(TypeMismatchError {
+                expected: "apps/v1/Deployment".into(),
+                found: "apps/v1/ReplicaSet".into(),
+            }))
+        );
+
+        let obj_ref = object_reference_from_owner_ref(&dependent, &owner, &Scope::Namespaced);
+        assert_eq!(obj_ref.namespace.as_deref(), Some("apps"));
+        assert_eq!(owner_ref_from_object_reference(&obj_ref), Ok(owner));
+        let unnamed = ObjectReference {
+            name: None,
+            ..obj_ref
+        };
+        assert_eq!(
+            ObjectRef::<ReplicaSet>::try_from_object_reference(&unnamed, ()),
+            Err(ReferenceError::MissingField("name"))
+        );
+        assert_eq!(
+            owner_ref_from_object_reference(&unnamed),
+            Err(ReferenceError::MissingField("name"))
+        );
+    }
+}
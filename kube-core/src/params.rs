@@ -1,8 +1,8 @@
 //! A port of request parameter *Optionals from apimachinery/types.go
-use crate::request::Error;
+use crate::{fields::FieldSelector, labels::Selector, request::Error};
 use serde::Serialize;
 
-/// Common query parameters used in watch/list/delete calls on collections
+/// Common query parameters used in list/delete calls on collections
 #[derive(Clone, Debug)]
 pub struct ListParams {
     /// A selector to restrict the list of returned objects by their labels.
@@ -15,22 +15,21 @@ pub struct ListParams {
     /// Defaults to everything if `None`.
     pub field_selector: Option<String>,
 
-    /// Timeout for the list/watch call.
+    /// Timeout for the list call.
     ///
     /// This limits the duration of the call, regardless of any activity or inactivity.
-    /// If unset for a watch call, we will use 290s.
-    /// We limit this to 295s due to [inherent watch limitations](https://github.com/kubernetes/kubernetes/issues/6513).
+    /// It is carried over as the [`WatchParams::timeout`] when deriving [`WatchParams`]
+    /// from these `ListParams`, such as by watchers that both list and watch.
     pub timeout: Option<u32>,
 
-    /// Enables watch events with type "BOOKMARK".
+    /// Deprecated equivalent of [`WatchParams::bookmarks`]
     ///
-    /// Servers that do not implement bookmarks ignore this flag and
-    /// bookmarks are sent at the server's discretion. Clients should not
-    /// assume bookmarks are returned at any specific interval, nor may they
-    /// assume the server will send any BOOKMARK event during a session.
-    /// If this is not a watch, this field is ignored.
-    /// If the feature gate WatchBookmarks is not enabled in apiserver,
-    /// this field is ignored.
+    /// This is not sent on list calls, and is only carried over when deriving [`WatchParams`]
+    /// from these `ListParams`.
+    #[deprecated(
+        since = "0.75.0",
+        note = "bookmarks only apply to watches, set them on WatchParams or the watcher::Config instead. This will be removed in 0.78.0."
+    )]
     pub bookmarks: bool,
 
     /// Limit the number of results.
@@ -44,10 +43,47 @@ pub struct ListParams {
     ///
     /// After listing results with a limit, a continue token can be used to fetch another page of results.
     pub continue_token: Option<String>,
+
+    /// An explicit resourceVersion to serve the list from
+    ///
+    /// Unset (the default) gives the most recent version through a quorum read.
+    /// `"0"` allows the apiserver to serve any version from its watch cache, which is cheaper
+    /// but may be arbitrarily stale, and ignores `limit` on older apiservers.
+    /// Any other version is interpreted according to `version_match`.
+    ///
+    /// See <https://kubernetes.io/docs/reference/using-api/api-concepts/#resource-versions> for details.
+    pub resource_version: Option<String>,
+
+    /// How `resource_version` is matched
+    ///
+    /// Requires `resource_version` to be set. When unset, a `resource_version` other than `"0"` returns
+    /// data at least as new as that version.
+    pub version_match: Option<VersionMatch>,
+}
+
+/// How the `resourceVersion` of a list is matched, see [`ListParams::version_match`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VersionMatch {
+    /// Return data at least as new as the `resourceVersion`
+    NotOlderThan,
+    /// Return data at exactly the `resourceVersion`
+    ///
+    /// The apiserver responds with `410 Gone` if that version has been compacted.
+    Exact,
+}
+
+impl VersionMatch {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::NotOlderThan => "NotOlderThan",
+            Self::Exact => "Exact",
+        }
+    }
 }
 
 impl Default for ListParams {
     /// Default `ListParams` without any constricting selectors
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             // bookmarks stable since 1.17, and backwards compatible
@@ -58,6 +94,8 @@ impl Default for ListParams {
             timeout: None,
             limit: None,
             continue_token: None,
+            resource_version: None,
+            version_match: None,
         }
     }
 }
@@ -70,8 +108,40 @@ impl ListParams {
                 return Err(Error::Validation("ListParams::timeout must be < 295s".into()));
             }
         }
+        self.validate_list()
+    }
+
+    /// Validate the parameters sent on list calls, which do not include the watch `timeout`
+    pub(crate) fn validate_list(&self) -> Result<(), Error> {
+        match (&self.resource_version, self.version_match) {
+            (None, Some(_)) => {
+                return Err(Error::Validation(
+                    "ListParams::version_match requires a resource_version".into(),
+                ));
+            }
+            (Some(rv), Some(VersionMatch::Exact)) if rv == "0" => {
+                return Err(Error::Validation(
+                    "ListParams::version_match cannot be Exact for resource_version 0".into(),
+                ));
+            }
+            _ => {}
+        }
+        if self.continue_token.is_some() && self.resource_version.is_some() {
+            return Err(Error::Validation(
+                "ListParams::resource_version cannot be combined with a continue_token".into(),
+            ));
+        }
         Ok(())
     }
+
+    pub(crate) fn populate_version_qp(&self, qp: &mut form_urlencoded::Serializer<String>) {
+        if let Some(rv) = &self.resource_version {
+            qp.append_pair("resourceVersion", rv);
+        }
+        if let Some(version_match) = self.version_match {
+            qp.append_pair("resourceVersionMatch", version_match.as_str());
+        }
+    }
 }
 
 /// Builder interface to ListParams
@@ -84,10 +154,10 @@ impl ListParams {
 ///     .labels("kubernetes.io/lifecycle=spot");
 /// ```
 impl ListParams {
-    /// Configure the timeout for list/watch calls
+    /// Configure the timeout for list calls
     ///
     /// This limits the duration of the call, regardless of any activity or inactivity.
-    /// Defaults to 290s
+    /// See [`ListParams::timeout`] for how it applies to watches.
     #[must_use]
     pub fn timeout(mut self, timeout_secs: u32) -> Self {
         self.timeout = Some(timeout_secs);
@@ -105,6 +175,21 @@ impl ListParams {
         self
     }
 
+    /// Configure a typed field selector, overwriting any previously set field selector.
+    ///
+    /// ```
+    /// use kube::api::ListParams;
+    /// use kube::core::FieldSelector;
+    /// let selector = FieldSelector::default().equal("spec.nodeName", "node-1");
+    /// let lp = ListParams::default().fields_from(&selector);
+    /// assert_eq!(lp.field_selector.unwrap(), "spec.nodeName=node-1");
+    /// ```
+    #[must_use]
+    pub fn fields_from(mut self, selector: &FieldSelector) -> Self {
+        self.field_selector = Some(selector.to_string());
+        self
+    }
+
     /// Configure the selector to restrict the list of returned objects by their labels.
     ///
     /// Defaults to everything.
@@ -115,10 +200,33 @@ impl ListParams {
         self
     }
 
-    /// Disables watch bookmarks to simplify watch handling
+    /// Configure typed label selectors, overwriting any previously set label selector.
     ///
-    /// This is not recommended to use with production watchers as it can cause desyncs.
-    /// See [#219](https://github.com/kube-rs/kube-rs/issues/219) for details.
+    /// ```
+    /// use kube::api::ListParams;
+    /// use kube::core::{Expression, Selector};
+    /// use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+    /// let envs = ["development".to_string(), "sandbox".to_string()];
+    /// let selector = Selector::from(Expression::In("env".into(), envs.into()));
+    /// let lp = ListParams::default().labels_from(&selector);
+    /// let lp = ListParams::default().labels_from(&Expression::Exists("foo".into()).into());
+    /// // Alternatively a LabelSelector from k8s-openapi can be converted
+    /// let selector: Selector = LabelSelector::default().try_into()?;
+    /// let lp = ListParams::default().labels_from(&selector);
+    /// # Ok::<(), kube::core::labels::ParseExpressionError>(())
+    /// ```
+    #[must_use]
+    pub fn labels_from(mut self, selector: &Selector) -> Self {
+        self.label_selector = Some(selector.to_string());
+        self
+    }
+
+    /// Deprecated equivalent of [`WatchParams::disable_bookmarks`]
+    #[deprecated(
+        since = "0.75.0",
+        note = "bookmarks only apply to watches, set them on WatchParams or the watcher::Config instead. This will be removed in 0.78.0."
+    )]
+    #[allow(deprecated)]
     #[must_use]
     pub fn disable_bookmarks(mut self) -> Self {
         self.bookmarks = false;
@@ -138,9 +246,277 @@ impl ListParams {
         self.continue_token = Some(token.to_string());
         self
     }
+
+    /// Serve the list from a `resource_version`
+    ///
+    /// ```
+    /// use kube::api::{ListParams, VersionMatch};
+    /// // Any version, possibly served from the apiserver cache
+    /// let lp = ListParams::default().at("0");
+    /// // Exactly the version of a previous list
+    /// let lp = ListParams::default().at("1234").matching(VersionMatch::Exact);
+    /// ```
+    #[must_use]
+    pub fn at(mut self, resource_version: &str) -> Self {
+        self.resource_version = Some(resource_version.into());
+        self
+    }
+
+    /// Sets how the `resource_version` is matched
+    #[must_use]
+    pub fn matching(mut self, version_match: VersionMatch) -> Self {
+        self.version_match = Some(version_match);
+        self
+    }
+}
+
+/// Common query parameters used in watch calls on collections
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchParams {
+    /// A selector to restrict the list of returned objects by their labels.
+    ///
+    /// Defaults to everything if `None`.
+    pub label_selector: Option<String>,
+
+    /// A selector to restrict the list of returned objects by their fields.
+    ///
+    /// Defaults to everything if `None`.
+    pub field_selector: Option<String>,
+
+    /// Timeout for the watch call.
+    ///
+    /// This limits the duration of the call, regardless of any activity or inactivity.
+    /// If unset, we will use 290s.
+    /// We limit this to 295s due to [inherent watch limitations](https://github.com/kubernetes/kubernetes/issues/6513).
+    pub timeout: Option<u32>,
+
+    /// Enables watch events with type "BOOKMARK".
+    ///
+    /// Servers that do not implement bookmarks ignore this flag and
+    /// bookmarks are sent at the server's discretion. Clients should not
+    /// assume bookmarks are returned at any specific interval, nor may they
+    /// assume the server will send any BOOKMARK event during a session.
+    /// If the feature gate WatchBookmarks is not enabled in apiserver,
+    /// this field is ignored.
+    pub bookmarks: bool,
+
+    /// Start the watch with synthetic `ADDED` events for the current state of the collection
+    ///
+    /// This is a streaming list, which replaces the initial list of a list+watch.
+    /// The end of the initial events is signalled by a bookmark for which
+    /// [`Bookmark::is_initial_events_end`](crate::watch::Bookmark::is_initial_events_end) is true.
+    /// Requires `bookmarks`, and Kubernetes >= 1.27 with the `WatchList` feature gate.
+    pub send_initial_events: bool,
+}
+
+impl Default for WatchParams {
+    /// Default `WatchParams` without any constricting selectors
+    fn default() -> Self {
+        Self {
+            // bookmarks stable since 1.17, and backwards compatible
+            bookmarks: true,
+
+            label_selector: None,
+            field_selector: None,
+            timeout: None,
+            send_initial_events: false,
+        }
+    }
+}
+
+impl WatchParams {
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if let Some(to) = &self.timeout {
+            // https://github.com/kubernetes/kubernetes/issues/6513
+            if *to >= 295 {
+                return Err(Error::Validation("WatchParams::timeout must be < 295s".into()));
+            }
+        }
+        if self.send_initial_events && !self.bookmarks {
+            return Err(Error::Validation(
+                "WatchParams::bookmarks must be set when using send_initial_events".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn populate_qp(&self, qp: &mut form_urlencoded::Serializer<String>) {
+        // https://github.com/kubernetes/kubernetes/issues/6513
+        qp.append_pair("timeoutSeconds", &self.timeout.unwrap_or(290).to_string());
+        if let Some(fields) = &self.field_selector {
+            qp.append_pair("fieldSelector", fields);
+        }
+        if let Some(labels) = &self.label_selector {
+            qp.append_pair("labelSelector", labels);
+        }
+        if self.bookmarks {
+            qp.append_pair("allowWatchBookmarks", "true");
+        }
+        if self.send_initial_events {
+            qp.append_pair("sendInitialEvents", "true");
+            qp.append_pair("resourceVersionMatch", "NotOlderThan");
+        }
+    }
+}
+
+/// Builder interface to WatchParams
+///
+/// Usage:
+/// ```
+/// use kube::api::WatchParams;
+/// let wp = WatchParams::default()
+///     .timeout(60)
+///     .labels("kubernetes.io/lifecycle=spot");
+/// ```
+impl WatchParams {
+    /// Configure the timeout for watch calls
+    ///
+    /// This limits the duration of the call, regardless of any activity or inactivity.
+    /// Defaults to 290s
+    #[must_use]
+    pub fn timeout(mut self, timeout_secs: u32) -> Self {
+        self.timeout = Some(timeout_secs);
+        self
+    }
+
+    /// Configure the selector to restrict the list of returned objects by their fields.
+    ///
+    /// Defaults to everything.
+    /// Supports `=`, `==`, `!=`, and can be comma separated: `key1=value1,key2=value2`.
+    /// The server only supports a limited number of field queries per type.
+    #[must_use]
+    pub fn fields(mut self, field_selector: &str) -> Self {
+        self.field_selector = Some(field_selector.to_string());
+        self
+    }
+
+    /// Configure a typed field selector, overwriting any previously set field selector.
+    #[must_use]
+    pub fn fields_from(mut self, selector: &FieldSelector) -> Self {
+        self.field_selector = Some(selector.to_string());
+        self
+    }
+
+    /// Configure the selector to restrict the list of returned objects by their labels.
+    ///
+    /// Defaults to everything.
+    /// Supports `=`, `==`, `!=`, and can be comma separated: `key1=value1,key2=value2`.
+    #[must_use]
+    pub fn labels(mut self, label_selector: &str) -> Self {
+        self.label_selector = Some(label_selector.to_string());
+        self
+    }
+
+    /// Configure typed label selectors, overwriting any previously set label selector.
+    #[must_use]
+    pub fn labels_from(mut self, selector: &Selector) -> Self {
+        self.label_selector = Some(selector.to_string());
+        self
+    }
+
+    /// Disables watch bookmarks to simplify watch handling
+    ///
+    /// This is not recommended to use with production watchers as it can cause desyncs.
+    /// See [#219](https://github.com/kube-rs/kube-rs/issues/219) for details.
+    #[must_use]
+    pub fn disable_bookmarks(mut self) -> Self {
+        self.bookmarks = false;
+        self
+    }
+
+    /// Constructor for doing streaming lists
+    ///
+    /// Requests the current state of the collection as synthetic `ADDED` events
+    /// on the watch, instead of requiring a separate (possibly paginated) list call.
+    ///
+    /// Requires Kubernetes >= 1.27 with the `WatchList` feature gate enabled.
+    /// See [`WatchParams::send_initial_events`] for details.
+    pub fn streaming_lists() -> Self {
+        Self {
+            send_initial_events: true,
+            bookmarks: true, // required
+            ..WatchParams::default()
+        }
+    }
+
+    /// Request initial events for the current state of the collection
+    ///
+    /// See [`WatchParams::send_initial_events`] for details.
+    #[must_use]
+    pub fn initial_events(mut self) -> Self {
+        self.send_initial_events = true;
+        self
+    }
 }
 
-/// The validation directive to use for `fieldValidation` when using server-side apply.
+impl From<&ListParams> for WatchParams {
+    /// Derive the `WatchParams` for watching the collection listed by a `ListParams`
+    ///
+    /// This carries over the selectors, timeout and deprecated bookmark settings,
+    /// while pagination parameters are dropped since they do not apply to watches.
+    #[allow(deprecated)]
+    fn from(lp: &ListParams) -> Self {
+        Self {
+            label_selector: lp.label_selector.clone(),
+            field_selector: lp.field_selector.clone(),
+            timeout: lp.timeout,
+            bookmarks: lp.bookmarks,
+            send_initial_events: false,
+        }
+    }
+}
+
+/// Common query parameters for get calls
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct GetParams {
+    /// An explicit resourceVersion to serve the read from
+    ///
+    /// Unset (the default) gives the most recent version through a quorum read.
+    /// `"0"` allows the apiserver to serve any version from its watch cache, which is cheaper
+    /// but may be arbitrarily stale.
+    /// Any other version makes the apiserver return an object at least as new as that version.
+    ///
+    /// See <https://kubernetes.io/docs/reference/using-api/api-concepts/#resource-versions> for details.
+    pub resource_version: Option<String>,
+}
+
+impl GetParams {
+    /// Read the most recent version of the object
+    ///
+    /// This is equivalent to `GetParams::default()`.
+    pub fn latest() -> Self {
+        Self::default()
+    }
+
+    /// Read any version of the object, possibly served from the apiserver cache
+    ///
+    /// This is the cheapest read, but the returned object may be stale.
+    pub fn any() -> Self {
+        Self::at("0")
+    }
+
+    /// Read a version of the object that is not older than `resource_version`
+    pub fn at(resource_version: &str) -> Self {
+        Self {
+            resource_version: Some(resource_version.into()),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if let Some(rv) = &self.resource_version {
+            if rv.is_empty() {
+                return Err(Error::Validation(
+                    "GetParams::resource_version cannot be empty".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The validation directive to use for `fieldValidation` on create, update and patch calls.
+///
+/// Requires kubernetes >= 1.25 (or 1.23 with the `ServerSideFieldValidation` feature gate).
 #[derive(Clone, Debug)]
 pub enum ValidationDirective {
     /// Strict mode will fail any invalid manifests.
@@ -180,6 +556,8 @@ pub struct PostParams {
     pub dry_run: bool,
     /// fieldManager is a name of the actor that is making changes
     pub field_manager: Option<String>,
+    /// The server-side validation directive to use for unknown or duplicate fields.
+    pub field_validation: Option<ValidationDirective>,
 }
 
 impl PostParams {
@@ -195,6 +573,52 @@ impl PostParams {
         }
         Ok(())
     }
+
+    pub(crate) fn populate_qp(&self, qp: &mut form_urlencoded::Serializer<String>) {
+        if self.dry_run {
+            qp.append_pair("dryRun", "All");
+        }
+        if let Some(ref fm) = self.field_manager {
+            qp.append_pair("fieldManager", fm);
+        }
+        if let Some(fv) = &self.field_validation {
+            qp.append_pair("fieldValidation", fv.as_str());
+        }
+    }
+
+    /// Perform a dryRun only
+    ///
+    /// The request is validated and admitted by the apiserver, but nothing is persisted.
+    #[must_use]
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Set the validation directive for `fieldValidation`.
+    #[must_use]
+    pub fn validation(mut self, vd: ValidationDirective) -> Self {
+        self.field_validation = Some(vd);
+        self
+    }
+
+    /// Set the validation directive to `Ignore`
+    #[must_use]
+    pub fn validation_ignore(self) -> Self {
+        self.validation(ValidationDirective::Ignore)
+    }
+
+    /// Set the validation directive to `Warn`
+    #[must_use]
+    pub fn validation_warn(self) -> Self {
+        self.validation(ValidationDirective::Warn)
+    }
+
+    /// Set the validation directive to `Strict`
+    #[must_use]
+    pub fn validation_strict(self) -> Self {
+        self.validation(ValidationDirective::Strict)
+    }
 }
 
 /// Describes changes that should be applied to a resource
@@ -239,7 +663,7 @@ impl PostParams {
 pub enum Patch<T: Serialize> {
     /// [Server side apply](https://kubernetes.io/docs/reference/using-api/api-concepts/#server-side-apply)
     ///
-    /// Requires kubernetes >= 1.16
+    /// Requires kubernetes >= 1.16, and a field manager to be set via [`PatchParams::apply`].
     Apply(T),
 
     /// [JSON patch](https://kubernetes.io/docs/tasks/run-application/update-api-object-kubectl-patch/#use-a-json-merge-patch-to-update-a-deployment)
@@ -303,7 +727,9 @@ pub struct PatchParams {
     /// fieldManager is a name of the actor that is making changes. Required for [`Patch::Apply`]
     /// optional for everything else.
     pub field_manager: Option<String>,
-    /// The server-side validation directive to use. Applicable only to [`Patch::Apply`].
+    /// The server-side validation directive to use for unknown or duplicate fields.
+    ///
+    /// Applies to all patch types, but only detects duplicate fields for [`Patch::Apply`] and [`Patch::Merge`].
     pub field_validation: Option<ValidationDirective>,
 }
 
@@ -323,6 +749,11 @@ impl PatchParams {
                 "PatchParams::force only works with Patch::Apply".into(),
             ));
         }
+        if patch.is_apply() && self.field_manager.is_none() {
+            return Err(Error::Validation(
+                "Patch::Apply requires PatchParams::field_manager (see PatchParams::apply)".into(),
+            ));
+        }
         Ok(())
     }
 
@@ -366,7 +797,8 @@ impl PatchParams {
         self
     }
 
-    /// Set the validation directive for `fieldValidation` during server-side apply.
+    /// Set the validation directive for `fieldValidation`.
+    #[must_use]
     pub fn validation(mut self, vd: ValidationDirective) -> Self {
         self.field_validation = Some(vd);
         self
@@ -425,6 +857,13 @@ pub struct DeleteParams {
 }
 
 impl DeleteParams {
+    pub(crate) fn populate_qp(&self, qp: &mut form_urlencoded::Serializer<String>) {
+        // dryRun is also sent in the body, but not every verb reads DeleteOptions from the body
+        if self.dry_run {
+            qp.append_pair("dryRun", "All");
+        }
+    }
+
     /// Construct `DeleteParams` with `PropagationPolicy::Background`.
     ///
     /// This allows the garbage collector to delete the dependents in the background.
@@ -470,12 +909,47 @@ impl DeleteParams {
         self
     }
 
+    /// Delete the object immediately, skipping the grace period.
+    ///
+    /// Equivalent to `grace_period(0)`.
+    #[must_use]
+    pub fn immediately(self) -> Self {
+        self.grace_period(0)
+    }
+
+    /// Set how garbage collection of dependents is performed.
+    #[must_use]
+    pub fn propagation(mut self, policy: PropagationPolicy) -> Self {
+        self.propagation_policy = Some(policy);
+        self
+    }
+
     /// Set the condtions that must be fulfilled before a deletion is carried out.
     #[must_use]
     pub fn preconditions(mut self, preconditions: Preconditions) -> Self {
         self.preconditions = Some(preconditions);
         self
     }
+
+    /// Only delete the object if it still has the given UID.
+    ///
+    /// This prevents deleting an object that has been deleted and recreated with the same name.
+    #[must_use]
+    pub fn precondition_uid(mut self, uid: &str) -> Self {
+        self.preconditions.get_or_insert_with(Preconditions::default).uid = Some(uid.into());
+        self
+    }
+
+    /// Only delete the object if it is still at the given resourceVersion.
+    ///
+    /// This prevents deleting an object that has been modified since it was last read.
+    #[must_use]
+    pub fn precondition_resource_version(mut self, resource_version: &str) -> Self {
+        self.preconditions
+            .get_or_insert_with(Preconditions::default)
+            .resource_version = Some(resource_version.into());
+        self
+    }
 }
 
 // dryRun serialization differ when used as body parameters and query strings:
@@ -501,7 +975,7 @@ where
 }
 #[cfg(test)]
 mod test {
-    use super::{DeleteParams, PatchParams, ValidationDirective};
+    use super::{DeleteParams, Patch, PatchParams, PostParams, PropagationPolicy};
     #[test]
     fn delete_param_serialize() {
         let mut dp = DeleteParams::default();
@@ -530,6 +1004,53 @@ mod test {
         assert_eq!(ser, serde_json::json!({"propagationPolicy": "Orphan"}));
     }
 
+    #[test]
+    fn delete_param_builders() {
+        let dp = DeleteParams::default()
+            .propagation(PropagationPolicy::Foreground)
+            .immediately()
+            .precondition_uid("some-uid")
+            .precondition_resource_version("123");
+        let ser = serde_json::to_value(&dp).unwrap();
+        assert_eq!(
+            ser,
+            serde_json::json!({
+                "gracePeriodSeconds": 0,
+                "propagationPolicy": "Foreground",
+                "preconditions": {"resourceVersion": "123", "uid": "some-uid"}
+            })
+        );
+    }
+
+    #[test]
+    fn patch_param_validates_server_side_apply() {
+        let apply = Patch::Apply(());
+        assert!(PatchParams::default().validate(&apply).is_err());
+        assert!(PatchParams::apply("manager").validate(&apply).is_ok());
+        assert!(PatchParams::apply("manager").force().validate(&apply).is_ok());
+        assert!(PatchParams::apply("manager")
+            .force()
+            .validate(&Patch::Merge(()))
+            .is_err());
+        assert_eq!(apply.content_type(), "application/apply-patch+yaml");
+    }
+
+    #[test]
+    fn post_param_serializes_field_validation() {
+        let pp = PostParams {
+            field_manager: Some("manager".into()),
+            ..PostParams::default()
+        }
+        .validation_strict();
+        let mut qp = form_urlencoded::Serializer::new(String::from("some/resource?"));
+        pp.populate_qp(&mut qp);
+        let urlstr = qp.finish();
+        assert_eq!(
+            String::from("some/resource?&fieldManager=manager&fieldValidation=Strict"),
+            urlstr
+        );
+    }
+
     #[test]
     fn patch_param_serializes_field_validation() {
         let pp = PatchParams::default().validation_ignore();
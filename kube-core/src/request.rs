@@ -1,9 +1,19 @@
 //! Request builder type for arbitrary api types
 use thiserror::Error;
 
-use super::params::{DeleteParams, ListParams, Patch, PatchParams, PostParams};
+use super::params::{DeleteParams, GetParams, ListParams, Patch, PatchParams, PostParams, WatchParams};
 
 pub(crate) const JSON_MIME: &str = "application/json";
+/// Extended Accept Header
+///
+/// Requests a meta.k8s.io/v1 PartialObjectMetadata resource (efficiently
+/// retrieves object metadata)
+///
+/// API Servers running Kubernetes v1.14 and below will retrieve the object and then
+/// convert the metadata.
+pub(crate) const METADATA_MIME: &str = "application/json;as=PartialObjectMetadata;g=meta.k8s.io;v=v1";
+pub(crate) const METADATA_LIST_MIME: &str =
+    "application/json;as=PartialObjectMetadataList;g=meta.k8s.io;v=v1";
 
 /// Possible errors when building a request.
 #[derive(Debug, Error)]
@@ -40,10 +50,38 @@ impl Request {
 
 // -------------------------------------------------------
 
+/// Metadata-only variants of the read methods
+///
+/// These request the `meta.k8s.io/v1` `PartialObjectMetadata` representation,
+/// which can be deserialized into a [`PartialObjectMeta`](crate::metadata::PartialObjectMeta).
+impl Request {
+    /// Get a single metadata instance
+    pub fn get_metadata(&self, name: &str, gp: &GetParams) -> Result<http::Request<Vec<u8>>, Error> {
+        self.get(name, gp).map(|req| with_accept(req, METADATA_MIME))
+    }
+
+    /// List a collection of metadata of a resource
+    pub fn list_metadata(&self, lp: &ListParams) -> Result<http::Request<Vec<u8>>, Error> {
+        self.list(lp).map(|req| with_accept(req, METADATA_LIST_MIME))
+    }
+
+    /// Watch the metadata of a resource at a given version
+    pub fn watch_metadata(&self, wp: &WatchParams, ver: &str) -> Result<http::Request<Vec<u8>>, Error> {
+        self.watch(wp, ver).map(|req| with_accept(req, METADATA_MIME))
+    }
+}
+
+fn with_accept(mut req: http::Request<Vec<u8>>, mime: &'static str) -> http::Request<Vec<u8>> {
+    req.headers_mut()
+        .insert(http::header::ACCEPT, http::HeaderValue::from_static(mime));
+    req
+}
+
 /// Convenience methods found from API conventions
 impl Request {
     /// List a collection of a resource
     pub fn list(&self, lp: &ListParams) -> Result<http::Request<Vec<u8>>, Error> {
+        lp.validate_list()?;
         let target = format!("{}?", self.url_path);
         let mut qp = form_urlencoded::Serializer::new(target);
 
@@ -59,42 +97,28 @@ impl Request {
         if let Some(continue_token) = &lp.continue_token {
             qp.append_pair("continue", continue_token);
         }
+        lp.populate_version_qp(&mut qp);
 
         let urlstr = qp.finish();
         let req = http::Request::get(urlstr);
         req.body(vec![]).map_err(Error::BuildRequest)
     }
 
+    /// List a collection of a resource as a [`Table`](crate::table::Table)
+    pub fn list_table(&self, lp: &ListParams) -> Result<http::Request<Vec<u8>>, Error> {
+        self.list(lp)
+            .map(|req| with_accept(req, crate::table::TABLE_MIME))
+    }
+
     /// Watch a resource at a given version
-    pub fn watch(&self, lp: &ListParams, ver: &str) -> Result<http::Request<Vec<u8>>, Error> {
+    pub fn watch(&self, wp: &WatchParams, ver: &str) -> Result<http::Request<Vec<u8>>, Error> {
         let target = format!("{}?", self.url_path);
         let mut qp = form_urlencoded::Serializer::new(target);
-        lp.validate()?;
-        if lp.limit.is_some() {
-            return Err(Error::Validation(
-                "ListParams::limit cannot be used with a watch.".into(),
-            ));
-        }
-        if lp.continue_token.is_some() {
-            return Err(Error::Validation(
-                "ListParams::continue_token cannot be used with a watch.".into(),
-            ));
-        }
+        wp.validate()?;
 
         qp.append_pair("watch", "true");
         qp.append_pair("resourceVersion", ver);
-
-        // https://github.com/kubernetes/kubernetes/issues/6513
-        qp.append_pair("timeoutSeconds", &lp.timeout.unwrap_or(290).to_string());
-        if let Some(fields) = &lp.field_selector {
-            qp.append_pair("fieldSelector", fields);
-        }
-        if let Some(labels) = &lp.label_selector {
-            qp.append_pair("labelSelector", labels);
-        }
-        if lp.bookmarks {
-            qp.append_pair("allowWatchBookmarks", "true");
-        }
+        wp.populate_qp(&mut qp);
 
         let urlstr = qp.finish();
         let req = http::Request::get(urlstr);
@@ -102,10 +126,16 @@ impl Request {
     }
 
     /// Get a single instance
-    pub fn get(&self, name: &str) -> Result<http::Request<Vec<u8>>, Error> {
-        let target = format!("{}/{}", self.url_path, name);
-        let mut qp = form_urlencoded::Serializer::new(target);
-        let urlstr = qp.finish();
+    pub fn get(&self, name: &str, gp: &GetParams) -> Result<http::Request<Vec<u8>>, Error> {
+        gp.validate()?;
+        let urlstr = if let Some(rv) = &gp.resource_version {
+            let target = format!("{}/{}?", self.url_path, name);
+            form_urlencoded::Serializer::new(target)
+                .append_pair("resourceVersion", rv)
+                .finish()
+        } else {
+            format!("{}/{}", self.url_path, name)
+        };
         let req = http::Request::get(urlstr);
         req.body(vec![]).map_err(Error::BuildRequest)
     }
@@ -115,9 +145,7 @@ impl Request {
         pp.validate()?;
         let target = format!("{}?", self.url_path);
         let mut qp = form_urlencoded::Serializer::new(target);
-        if pp.dry_run {
-            qp.append_pair("dryRun", "All");
-        }
+        pp.populate_qp(&mut qp);
         let urlstr = qp.finish();
         let req = http::Request::post(urlstr).header(http::header::CONTENT_TYPE, JSON_MIME);
         req.body(data).map_err(Error::BuildRequest)
@@ -127,6 +155,7 @@ impl Request {
     pub fn delete(&self, name: &str, dp: &DeleteParams) -> Result<http::Request<Vec<u8>>, Error> {
         let target = format!("{}/{}?", self.url_path, name);
         let mut qp = form_urlencoded::Serializer::new(target);
+        dp.populate_qp(&mut qp);
         let urlstr = qp.finish();
         let body = serde_json::to_vec(&dp).map_err(Error::SerializeBody)?;
         let req = http::Request::delete(urlstr).header(http::header::CONTENT_TYPE, JSON_MIME);
@@ -139,6 +168,7 @@ impl Request {
         dp: &DeleteParams,
         lp: &ListParams,
     ) -> Result<http::Request<Vec<u8>>, Error> {
+        lp.validate()?;
         let target = format!("{}?", self.url_path);
         let mut qp = form_urlencoded::Serializer::new(target);
         if let Some(fields) = &lp.field_selector {
@@ -147,6 +177,10 @@ impl Request {
         if let Some(labels) = &lp.label_selector {
             qp.append_pair("labelSelector", labels);
         }
+        if let Some(timeout) = &lp.timeout {
+            qp.append_pair("timeoutSeconds", &timeout.to_string());
+        }
+        dp.populate_qp(&mut qp);
         let urlstr = qp.finish();
         let body = serde_json::to_vec(&dp).map_err(Error::SerializeBody)?;
         let req = http::Request::delete(urlstr).header(http::header::CONTENT_TYPE, JSON_MIME);
@@ -184,11 +218,10 @@ impl Request {
         pp: &PostParams,
         data: Vec<u8>,
     ) -> Result<http::Request<Vec<u8>>, Error> {
+        pp.validate()?;
         let target = format!("{}/{}?", self.url_path, name);
         let mut qp = form_urlencoded::Serializer::new(target);
-        if pp.dry_run {
-            qp.append_pair("dryRun", "All");
-        }
+        pp.populate_qp(&mut qp);
         let urlstr = qp.finish();
         let req = http::Request::put(urlstr).header(http::header::CONTENT_TYPE, JSON_MIME);
         req.body(data).map_err(Error::BuildRequest)
@@ -210,6 +243,23 @@ impl Request {
         req.body(vec![]).map_err(Error::BuildRequest)
     }
 
+    /// Create an instance of the subresource
+    pub fn create_subresource(
+        &self,
+        subresource_name: &str,
+        name: &str,
+        pp: &PostParams,
+        data: Vec<u8>,
+    ) -> Result<http::Request<Vec<u8>>, Error> {
+        pp.validate()?;
+        let target = format!("{}/{}/{}?", self.url_path, name, subresource_name);
+        let mut qp = form_urlencoded::Serializer::new(target);
+        pp.populate_qp(&mut qp);
+        let urlstr = qp.finish();
+        let req = http::Request::post(urlstr).header(http::header::CONTENT_TYPE, JSON_MIME);
+        req.body(data).map_err(Error::BuildRequest)
+    }
+
     /// Patch an instance of the subresource
     pub fn patch_subresource<P: serde::Serialize>(
         &self,
@@ -239,11 +289,10 @@ impl Request {
         pp: &PostParams,
         data: Vec<u8>,
     ) -> Result<http::Request<Vec<u8>>, Error> {
+        pp.validate()?;
         let target = format!("{}/{}/{}?", self.url_path, name, subresource_name);
         let mut qp = form_urlencoded::Serializer::new(target);
-        if pp.dry_run {
-            qp.append_pair("dryRun", "All");
-        }
+        pp.populate_qp(&mut qp);
         let urlstr = qp.finish();
         let req = http::Request::put(urlstr).header(http::header::CONTENT_TYPE, JSON_MIME);
         req.body(data).map_err(Error::BuildRequest)
@@ -368,7 +417,7 @@ mod test {
 
     /// -----------------------------------------------------------------
     /// Tests that the misc mappings are also sensible
-    use crate::params::{DeleteParams, ListParams, Patch, PatchParams};
+    use crate::params::{DeleteParams, GetParams, ListParams, Patch, PatchParams, WatchParams};
 
     #[test]
     fn list_path() {
@@ -377,10 +426,77 @@ mod test {
         let req = Request::new(url).list(&gp).unwrap();
         assert_eq!(req.uri(), "/apis/apps/v1/namespaces/ns/deployments");
     }
+    #[test]
+    fn list_path_with_resource_version() {
+        use crate::params::VersionMatch;
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let req = Request::new(&url).list(&ListParams::default().at("0")).unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods?&resourceVersion=0");
+        let lp = ListParams::default().at("1234").matching(VersionMatch::Exact);
+        let req = Request::new(&url).list(&lp).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&resourceVersion=1234&resourceVersionMatch=Exact"
+        );
+
+        let lp = ListParams::default().matching(VersionMatch::NotOlderThan);
+        assert!(Request::new(&url).list(&lp).is_err());
+        let lp = ListParams::default().at("0").matching(VersionMatch::Exact);
+        assert!(Request::new(&url).list(&lp).is_err());
+        let lp = ListParams::default().at("1234").continue_token("token");
+        assert!(Request::new(&url).list(&lp).is_err());
+        // The watch timeout is not sent on lists, so it is not validated either
+        let lp = ListParams::default().timeout(300);
+        assert!(Request::new(url).list(&lp).is_ok());
+    }
+
+    #[test]
+    fn list_table_path() {
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let lp = ListParams::default().labels("app=blog");
+        let req = Request::new(url).list_table(&lp).unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods?&labelSelector=app%3Dblog");
+        assert_eq!(
+            req.headers().get(http::header::ACCEPT).unwrap(),
+            "application/json;as=Table;g=meta.k8s.io;v=v1"
+        );
+    }
+
+    #[test]
+    fn metadata_requests_set_accept_header() {
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let req = Request::new(&url)
+            .get_metadata("mypod", &GetParams::default())
+            .unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/mypod");
+        assert_eq!(
+            req.headers().get(http::header::ACCEPT).unwrap(),
+            super::METADATA_MIME
+        );
+
+        let req = Request::new(&url).list_metadata(&ListParams::default()).unwrap();
+        assert_eq!(
+            req.headers().get(http::header::ACCEPT).unwrap(),
+            super::METADATA_LIST_MIME
+        );
+
+        let req = Request::new(url)
+            .watch_metadata(&WatchParams::default(), "0")
+            .unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=0&timeoutSeconds=290&allowWatchBookmarks=true"
+        );
+        assert_eq!(
+            req.headers().get(http::header::ACCEPT).unwrap(),
+            super::METADATA_MIME
+        );
+    }
+
     #[test]
     fn watch_path() {
         let url = corev1::Pod::url_path(&(), Some("ns"));
-        let gp = ListParams::default();
+        let gp = WatchParams::default();
         let req = Request::new(url).watch(&gp, "0").unwrap();
         assert_eq!(
             req.uri(),
@@ -390,10 +506,7 @@ mod test {
     #[test]
     fn replace_path() {
         let url = appsv1::DaemonSet::url_path(&(), None);
-        let pp = PostParams {
-            dry_run: true,
-            ..Default::default()
-        };
+        let pp = PostParams::default().dry_run();
         let req = Request::new(url).replace("myds", &pp, vec![]).unwrap();
         assert_eq!(req.uri(), "/apis/apps/v1/daemonsets/myds?&dryRun=All");
         assert_eq!(
@@ -415,6 +528,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn delete_dry_run() {
+        let url = appsv1::ReplicaSet::url_path(&(), Some("ns"));
+        let dp = DeleteParams::default().dry_run();
+        let req = Request::new(&url).delete("myrs", &dp).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/apis/apps/v1/namespaces/ns/replicasets/myrs?&dryRun=All"
+        );
+        assert_eq!(req.body(), br#"{"dryRun":["All"]}"#);
+
+        let lp = ListParams::default().labels("app=myapp");
+        let req = Request::new(url).delete_collection(&dp, &lp).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/apis/apps/v1/namespaces/ns/replicasets?&labelSelector=app%3Dmyapp&dryRun=All"
+        );
+        assert_eq!(req.body(), br#"{"dryRun":["All"]}"#);
+    }
+
     #[test]
     fn delete_collection_path() {
         let url = appsv1::ReplicaSet::url_path(&(), Some("ns"));
@@ -432,6 +565,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn delete_collection_propagation() {
+        let url = appsv1::ReplicaSet::url_path(&(), Some("ns"));
+        let lp = ListParams::default().fields("metadata.name!=keep").timeout(10);
+        let dp = DeleteParams::foreground();
+        let req = Request::new(url).delete_collection(&dp, &lp).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/apis/apps/v1/namespaces/ns/replicasets?&fieldSelector=metadata.name%21%3Dkeep&timeoutSeconds=10"
+        );
+        let body: serde_json::Value = serde_json::from_slice(req.body()).unwrap();
+        assert_eq!(body, serde_json::json!({"propagationPolicy": "Foreground"}));
+    }
+
     #[test]
     fn namespace_path() {
         let url = corev1::Namespace::url_path(&(), None);
@@ -440,6 +587,17 @@ mod test {
         assert_eq!(req.uri(), "/api/v1/namespaces")
     }
 
+    #[test]
+    fn create_subresource_path() {
+        let url = corev1::ServiceAccount::url_path(&(), Some("ns"));
+        let pp = PostParams::default();
+        let req = Request::new(url)
+            .create_subresource("token", "default", &pp, vec![])
+            .unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/serviceaccounts/default/token?");
+        assert_eq!(req.method(), "POST");
+    }
+
     // subresources with weird version accuracy
     #[test]
     fn patch_status_path() {
@@ -537,10 +695,54 @@ mod test {
     //}
 
     #[test]
-    fn watches_cannot_have_limits() {
-        let lp = ListParams::default().limit(5);
+    fn get_path_with_resource_version() {
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let req = Request::new(&url).get("mypod", &GetParams::default()).unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/mypod");
+        let req = Request::new(&url).get("mypod", &GetParams::any()).unwrap();
+        assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/mypod?&resourceVersion=0");
+        let req = Request::new(url).get("mypod", &GetParams::at("1234")).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods/mypod?&resourceVersion=1234"
+        );
+    }
+
+    #[test]
+    fn watch_streaming_list() {
         let url = corev1::Pod::url_path(&(), Some("ns"));
-        let err = Request::new(url).watch(&lp, "0").unwrap_err();
-        assert!(format!("{}", err).contains("limit cannot be used with a watch"));
+        let wp = WatchParams::streaming_lists();
+        let req = Request::new(&url).watch(&wp, "").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=&timeoutSeconds=290&allowWatchBookmarks=true&sendInitialEvents=true&resourceVersionMatch=NotOlderThan"
+        );
+
+        let wp = WatchParams::default().initial_events().disable_bookmarks();
+        let err = Request::new(url).watch(&wp, "").unwrap_err();
+        assert!(format!("{}", err).contains("bookmarks must be set"));
+    }
+
+    #[test]
+    fn watches_cannot_have_long_timeouts() {
+        let wp = WatchParams::default().timeout(300);
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let err = Request::new(url).watch(&wp, "0").unwrap_err();
+        assert!(format!("{}", err).contains("timeout must be < 295s"));
+    }
+
+    #[test]
+    fn watch_params_from_list_params() {
+        let lp = ListParams::default()
+            .labels("app=myapp")
+            .limit(5)
+            .disable_bookmarks();
+        let wp = WatchParams::from(&lp);
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let req = Request::new(url).watch(&wp, "0").unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods?&watch=true&resourceVersion=0&timeoutSeconds=290&labelSelector=app%3Dmyapp"
+        );
     }
 }
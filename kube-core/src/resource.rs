@@ -4,7 +4,8 @@ use k8s_openapi::{
     apimachinery::pkg::apis::meta::v1::{ManagedFieldsEntry, OwnerReference, Time},
 };
 
-use std::{borrow::Cow, collections::BTreeMap};
+use chrono::{DateTime, Utc};
+use std::{borrow::Cow, collections::BTreeMap, time::Duration};
 
 pub use k8s_openapi::{ClusterResourceScope, NamespaceResourceScope, ResourceScope, SubResourceScope};
 
@@ -37,6 +38,9 @@ pub trait Resource {
     ///
     /// Types from k8s_openapi come with an explicit k8s_openapi::ResourceScope
     /// Dynamic types should select `Scope = DynamicResourceScope`
+    ///
+    /// This is one of [`NamespaceResourceScope`], [`ClusterResourceScope`] or [`DynamicResourceScope`],
+    /// and is used by `Api` constructors to reject namespacing cluster scoped resources at compile time.
     type Scope;
 
     /// Returns kind of this object
@@ -100,16 +104,33 @@ pub trait Resource {
 
     /// Generates a controller owner reference pointing to this resource
     ///
+    /// This sets both `controller` and `blockOwnerDeletion`, as a controller is expected to
+    /// keep its owned objects from being garbage collected while the owner is being deleted.
+    ///
     /// Note: this returns an `Option`, but for objects populated from the apiserver,
     /// this Option can be safely unwrapped.
     fn controller_owner_ref(&self, dt: &Self::DynamicType) -> Option<OwnerReference> {
+        Some(OwnerReference {
+            controller: Some(true),
+            block_owner_deletion: Some(true),
+            ..self.owner_ref(dt)?
+        })
+    }
+
+    /// Generates an owner reference pointing to this resource
+    ///
+    /// Unlike [`controller_owner_ref`](Resource::controller_owner_ref), this leaves the `controller`
+    /// and `blockOwnerDeletion` flags unset, which is appropriate for non-controlling owners.
+    ///
+    /// Note: this returns an `Option`, but for objects populated from the apiserver,
+    /// this Option can be safely unwrapped.
+    fn owner_ref(&self, dt: &Self::DynamicType) -> Option<OwnerReference> {
         let meta = self.meta();
         Some(OwnerReference {
             api_version: Self::api_version(dt).to_string(),
             kind: Self::kind(dt).to_string(),
             name: meta.name.clone()?,
             uid: meta.uid.clone()?,
-            controller: Some(true),
             ..OwnerReference::default()
         })
     }
@@ -185,6 +206,12 @@ pub trait ResourceExt: Resource {
     /// For true uniqueness, prefer [`uid`](ResourceExt::uid).
     fn name_any(&self) -> String;
 
+    /// Returns the name of the resource if it is set
+    ///
+    /// This is the non-panicking equivalent of [`name_unchecked`](ResourceExt::name_unchecked),
+    /// and returns `None` for objects that only have `.metadata.generateName` set.
+    fn name_opt(&self) -> Option<String>;
+
     /// The namespace the resource is in
     fn namespace(&self) -> Option<String>;
     /// The resource version
@@ -196,6 +223,27 @@ pub trait ResourceExt: Resource {
     ///
     /// This is guaranteed to exist on resources received by the apiserver.
     fn creation_timestamp(&self) -> Option<Time>;
+    /// Returns the time elapsed between the creation timestamp and `now`
+    ///
+    /// This is useful for displaying kubectl style `AGE` columns, or for garbage collecting old objects.
+    /// Returns `None` when the creation timestamp is unset, and a zero duration when the
+    /// creation timestamp lies in the future (e.g. due to clock skew).
+    ///
+    /// ```
+    /// use kube_core::{ObjectMeta, ResourceExt};
+    /// use k8s_openapi::{api::core::v1::Pod, apimachinery::pkg::apis::meta::v1::Time};
+    /// use chrono::{Duration, Utc};
+    /// let now = Utc::now();
+    /// let pod = Pod {
+    ///     metadata: ObjectMeta {
+    ///         creation_timestamp: Some(Time(now - Duration::minutes(5))),
+    ///         ..ObjectMeta::default()
+    ///     },
+    ///     ..Pod::default()
+    /// };
+    /// assert_eq!(pod.age(now), Some(std::time::Duration::from_secs(300)));
+    /// ```
+    fn age(&self, now: DateTime<Utc>) -> Option<Duration>;
     /// Returns resource labels
     fn labels(&self) -> &BTreeMap<String, String>;
     /// Provides mutable access to the labels
@@ -216,6 +264,15 @@ pub trait ResourceExt: Resource {
     fn managed_fields(&self) -> &[ManagedFieldsEntry];
     /// Provides mutable access to managed fields
     fn managed_fields_mut(&mut self) -> &mut Vec<ManagedFieldsEntry>;
+    /// Returns the resource with its managed fields removed
+    ///
+    /// The `.metadata.managedFields` entries are only needed to inspect field ownership,
+    /// and are often larger than the rest of the object, so dropping them is recommended
+    /// before caching or diffing objects.
+    #[must_use]
+    fn managed_fields_cleared(self) -> Self
+    where
+        Self: Sized;
 }
 
 // TODO: replace with ordinary static when BTreeMap::new() is no longer
@@ -225,7 +282,7 @@ static EMPTY_MAP: Lazy<BTreeMap<String, String>> = Lazy::new(BTreeMap::new);
 
 impl<K: Resource> ResourceExt for K {
     fn name(&self) -> String {
-        self.meta().name.clone().expect(".metadata.name missing")
+        self.name_unchecked()
     }
 
     fn name_unchecked(&self) -> String {
@@ -240,6 +297,10 @@ impl<K: Resource> ResourceExt for K {
             .unwrap_or_default()
     }
 
+    fn name_opt(&self) -> Option<String> {
+        self.meta().name.clone()
+    }
+
     fn namespace(&self) -> Option<String> {
         self.meta().namespace.clone()
     }
@@ -256,6 +317,11 @@ impl<K: Resource> ResourceExt for K {
         self.meta().creation_timestamp.clone()
     }
 
+    fn age(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let created = self.meta().creation_timestamp.as_ref()?;
+        Some((now - created.0).to_std().unwrap_or_default())
+    }
+
     fn labels(&self) -> &BTreeMap<String, String> {
         self.meta().labels.as_ref().unwrap_or(&*EMPTY_MAP)
     }
@@ -295,4 +361,51 @@ impl<K: Resource> ResourceExt for K {
     fn managed_fields_mut(&mut self) -> &mut Vec<ManagedFieldsEntry> {
         self.meta_mut().managed_fields.get_or_insert_with(Vec::new)
     }
+
+    fn managed_fields_cleared(mut self) -> Self {
+        self.meta_mut().managed_fields = None;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ObjectMeta, Resource};
+    use k8s_openapi::api::core::v1::ConfigMap;
+
+    #[test]
+    fn owner_refs_are_populated() {
+        let cm = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some("owner".into()),
+                uid: Some("1234".into()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        };
+        let oref = cm.owner_ref(&()).unwrap();
+        assert_eq!(oref.api_version, "v1");
+        assert_eq!(oref.kind, "ConfigMap");
+        assert_eq!(oref.name, "owner");
+        assert_eq!(oref.uid, "1234");
+        assert_eq!(oref.controller, None);
+        assert_eq!(oref.block_owner_deletion, None);
+
+        let cref = cm.controller_owner_ref(&()).unwrap();
+        assert_eq!(cref.controller, Some(true));
+        assert_eq!(cref.block_owner_deletion, Some(true));
+    }
+
+    #[test]
+    fn owner_refs_require_name_and_uid() {
+        let cm = ConfigMap {
+            metadata: ObjectMeta {
+                generate_name: Some("owner-".into()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        };
+        assert!(cm.owner_ref(&()).is_none());
+        assert!(cm.controller_owner_ref(&()).is_none());
+    }
 }
@@ -1,5 +1,5 @@
 //! Generic api response types
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// A Kubernetes status object
 ///
@@ -36,7 +36,7 @@ pub struct Status {
 }
 
 /// Status details object on the [`Status`] object
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct StatusDetails {
     /// The name attribute of the resource associated with the status StatusReason (when there is a single name which can be described)
@@ -69,12 +69,12 @@ pub struct StatusDetails {
     ///
     /// Some errors may indicate the client must take an alternate action -
     /// for those errors this field may indicate how long to wait before taking the alternate action.
-    #[serde(default, skip_serializing_if = "num::Zero::is_zero")]
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub retry_after_seconds: u32,
 }
 
 /// Status cause object on the [`StatusDetails`] object
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default, Eq, PartialEq)]
 pub struct StatusCause {
     /// A machine-readable description of the cause of the error. If this value is empty there is no information available.
     #[serde(default, skip_serializing_if = "String::is_empty")]
@@ -92,6 +92,10 @@ pub struct StatusCause {
     pub field: String,
 }
 
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
 #[cfg(test)]
 mod test {
     use super::Status;
@@ -1,4 +1,5 @@
 //! Request builder types and parameters for subresources
+use chrono::{DateTime, SecondsFormat, Utc};
 use std::fmt::Debug;
 
 use crate::{
@@ -30,6 +31,10 @@ pub struct LogParams {
     /// If this value precedes the time a pod was started, only logs since the pod start will be returned.
     /// If this value is in the future, no logs will be returned. Only one of sinceSeconds or sinceTime may be specified.
     pub since_seconds: Option<i64>,
+    /// An absolute timestamp from which to show logs.
+    /// If this value precedes the time a pod was started, only logs since the pod start will be returned.
+    /// If this value is in the future, no logs will be returned. Only one of sinceSeconds or sinceTime may be specified.
+    pub since_time: Option<DateTime<Utc>>,
     /// If set, the number of lines from the end of the logs to show.
     /// If not specified, logs are shown from the creation of the container or sinceSeconds or sinceTime
     pub tail_lines: Option<i64>,
@@ -40,6 +45,11 @@ pub struct LogParams {
 impl Request {
     /// Get a pod logs
     pub fn logs(&self, name: &str, lp: &LogParams) -> Result<http::Request<Vec<u8>>, Error> {
+        if lp.since_seconds.is_some() && lp.since_time.is_some() {
+            return Err(Error::Validation(
+                "LogParams::since_seconds and LogParams::since_time cannot both be set".into(),
+            ));
+        }
         let target = format!("{}/{}/log?", self.url_path, name);
         let mut qp = form_urlencoded::Serializer::new(target);
 
@@ -67,6 +77,10 @@ impl Request {
             qp.append_pair("sinceSeconds", &ss.to_string());
         }
 
+        if let Some(st) = &lp.since_time {
+            qp.append_pair("sinceTime", &st.to_rfc3339_opts(SecondsFormat::Secs, true));
+        }
+
         if let Some(tl) = &lp.tail_lines {
             qp.append_pair("tailLines", &tl.to_string());
         }
@@ -102,13 +116,12 @@ impl Request {
         let pp = &ep.post_options;
         pp.validate()?;
         let mut qp = form_urlencoded::Serializer::new(target);
-        if pp.dry_run {
-            qp.append_pair("dryRun", "All");
-        }
+        pp.populate_qp(&mut qp);
         let urlstr = qp.finish();
         // eviction body parameters are awkward, need metadata with name
+        // apiVersion is left for the server to default, as policy/v1 requires Kubernetes >= 1.22
         let data = serde_json::to_vec(&serde_json::json!({
-            "delete_options": ep.delete_options,
+            "deleteOptions": ep.delete_options,
             "metadata": { "name": name }
         }))
         .map_err(Error::SerializeBody)?;
@@ -145,7 +158,7 @@ pub struct AttachParams {
     pub stderr: bool,
     /// Allocate TTY. Defaults to `false`.
     ///
-    /// NOTE: Terminal resizing is not implemented yet.
+    /// Call [`AttachedProcess::terminal_size`](https://docs.rs/kube/*/kube/api/struct.AttachedProcess.html#method.terminal_size) to resize it.
     pub tty: bool,
 
     /// The maximum amount of bytes that can be written to the internal `stdin`
@@ -341,6 +354,35 @@ impl Request {
     }
 }
 
+// ----------------------------------------------------------------------------
+// Proxy subresource
+// ----------------------------------------------------------------------------
+
+impl Request {
+    /// Proxy a request through the apiserver to a pod, service, or node
+    ///
+    /// The path and query of `request` are appended to the proxy subresource of the named object,
+    /// while the method, headers, and body are passed along unchanged.
+    /// The `name` may carry a port (`name:port`), and for services a scheme (`https:name:port`).
+    pub fn proxy(
+        &self,
+        name: &str,
+        request: http::Request<Vec<u8>>,
+    ) -> Result<http::Request<Vec<u8>>, Error> {
+        if name.is_empty() {
+            return Err(Error::Validation("proxy target name cannot be empty".into()));
+        }
+        let (mut parts, body) = request.into_parts();
+        let path = parts.uri.path_and_query().map_or("/", |pq| pq.as_str());
+        let separator = if path.starts_with('/') { "" } else { "/" };
+        let target = format!("{}/{}/proxy{}{}", self.url_path, name, separator, path);
+        parts.uri = target
+            .parse()
+            .map_err(|e: http::uri::InvalidUri| Error::BuildRequest(e.into()))?;
+        Ok(http::Request::from_parts(parts, body))
+    }
+}
+
 // ----------------------------------------------------------------------------
 // tests
 // ----------------------------------------------------------------------------
@@ -364,12 +406,58 @@ mod test {
             pretty: true,
             previous: true,
             since_seconds: Some(3600),
+            since_time: None,
             tail_lines: Some(4096),
             timestamps: true,
         };
         let req = Request::new(url).logs("mypod", &lp).unwrap();
         assert_eq!(req.uri(), "/api/v1/namespaces/ns/pods/mypod/log?&container=nginx&follow=true&limitBytes=10485760&pretty=true&previous=true&sinceSeconds=3600&tailLines=4096&timestamps=true");
     }
+
+    #[test]
+    fn logs_since_time() {
+        let url = corev1::Pod::url_path(&(), Some("ns"));
+        let lp = LogParams {
+            since_time: Some("2022-07-09T12:30:00Z".parse().unwrap()),
+            ..LogParams::default()
+        };
+        let req = Request::new(&url).logs("mypod", &lp).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/pods/mypod/log?&sinceTime=2022-07-09T12%3A30%3A00Z"
+        );
+
+        let lp = LogParams {
+            since_seconds: Some(3600),
+            ..lp
+        };
+        assert!(Request::new(url).logs("mypod", &lp).is_err());
+    }
+
+    #[test]
+    fn proxy_path() {
+        let url = corev1::Service::url_path(&(), Some("ns"));
+        let inner = http::Request::post("/api/v1/query?q=up")
+            .header(http::header::CONTENT_TYPE, "text/plain")
+            .body(b"ping".to_vec())
+            .unwrap();
+        let req = Request::new(&url).proxy("http:prometheus:9090", inner).unwrap();
+        assert_eq!(
+            req.uri(),
+            "/api/v1/namespaces/ns/services/http:prometheus:9090/proxy/api/v1/query?q=up"
+        );
+        assert_eq!(req.method(), "POST");
+        assert_eq!(req.headers()[http::header::CONTENT_TYPE], "text/plain");
+        assert_eq!(req.body(), b"ping");
+
+        let url = corev1::Node::url_path(&(), None);
+        let inner = http::Request::get("/stats/summary").body(vec![]).unwrap();
+        let req = Request::new(&url).proxy("worker-1:10250", inner).unwrap();
+        assert_eq!(req.uri(), "/api/v1/nodes/worker-1:10250/proxy/stats/summary");
+
+        let inner = http::Request::get("/").body(vec![]).unwrap();
+        assert!(Request::new(url).proxy("", inner).is_err());
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -388,6 +476,9 @@ impl Request {
                 "the number of ports cannot be more than 128".into(),
             ));
         }
+        if ports.contains(&0) {
+            return Err(Error::Validation("ports must be greater than 0".into()));
+        }
 
         if ports.len() > 1 {
             let mut seen = std::collections::HashSet::with_capacity(ports.len());
@@ -0,0 +1,132 @@
+//! Types for the `meta.k8s.io/v1` `Table` representation of resources
+//!
+//! Tables are the server-side rendered form of a list used by `kubectl get`,
+//! including any additional printer columns defined on custom resources.
+use serde::{Deserialize, Serialize};
+
+use crate::metadata::{ListMeta, TypeMeta};
+
+/// The `Accept` header value used to request a [`Table`] from the apiserver
+pub const TABLE_MIME: &str = "application/json;as=Table;g=meta.k8s.io;v=v1";
+
+/// A tabular representation of a set of API resources
+///
+/// The apiserver returns this when a list is requested with the [`TABLE_MIME`] content type.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Table {
+    /// The type fields, not always present
+    #[serde(flatten, default)]
+    pub types: Option<TypeMeta>,
+
+    /// Standard list metadata
+    #[serde(default)]
+    pub metadata: ListMeta,
+
+    /// The columns present in every row
+    #[serde(default)]
+    pub column_definitions: Vec<TableColumnDefinition>,
+
+    /// The rows of the table, one per object
+    #[serde(default)]
+    pub rows: Vec<TableRow>,
+}
+
+/// Describes a column in a [`Table`]
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TableColumnDefinition {
+    /// A human readable name for the column
+    pub name: String,
+
+    /// The OpenAPI type of the column, such as `string`, `integer` or `number`
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// An optional OpenAPI format modifier for the type, such as `name` or `date-time`
+    #[serde(default)]
+    pub format: String,
+
+    /// A human readable description of the column
+    #[serde(default)]
+    pub description: String,
+
+    /// The relative importance of the column
+    ///
+    /// Columns with a `priority` above 0 are only shown in wide output.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// A single row in a [`Table`]
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRow {
+    /// The cells of the row, in the same order as [`Table::column_definitions`]
+    pub cells: Vec<serde_json::Value>,
+
+    /// Additional conditions that apply to the row
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<TableRowCondition>,
+
+    /// The object (or its metadata) this row was rendered from, if requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<serde_json::Value>,
+}
+
+/// A condition that applies to a [`TableRow`]
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRowCondition {
+    /// The type of the condition, such as `Completed`
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// The status of the condition, one of `True`, `False` or `Unknown`
+    pub status: String,
+
+    /// A machine readable reason for the last transition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// A human readable message for the last transition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl Table {
+    /// Returns the index of the column with the given name
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.column_definitions.iter().position(|c| c.name == name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Table;
+
+    #[test]
+    fn deserializes_table() {
+        let table: Table = serde_json::from_value(serde_json::json!({
+            "kind": "Table",
+            "apiVersion": "meta.k8s.io/v1",
+            "metadata": { "resourceVersion": "1234" },
+            "columnDefinitions": [
+                { "name": "Name", "type": "string", "format": "name", "description": "Name of the object", "priority": 0 },
+                { "name": "Age", "type": "string", "format": "", "description": "Creation time", "priority": 0 }
+            ],
+            "rows": [
+                {
+                    "cells": ["blog", "5d"],
+                    "object": { "kind": "PartialObjectMetadata", "apiVersion": "meta.k8s.io/v1", "metadata": { "name": "blog" } }
+                }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(table.types.as_ref().unwrap().kind, "Table");
+        assert_eq!(table.metadata.resource_version.as_deref(), Some("1234"));
+        assert_eq!(table.column_index("Age"), Some(1));
+        assert_eq!(table.rows[0].cells[0], "blog");
+        assert!(table.rows[0].conditions.is_empty());
+    }
+}
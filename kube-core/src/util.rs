@@ -2,39 +2,53 @@
 
 use crate::{
     params::{Patch, PatchParams},
-    request, Request,
+    request, Request, Resource,
 };
-use chrono::Utc;
+use chrono::{SecondsFormat, Utc};
 use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
 
 /// Restartable Resource marker trait
-pub trait Restart {}
+pub trait Restart: Resource<DynamicType = ()> {}
 
 impl Restart for Deployment {}
 impl Restart for DaemonSet {}
 impl Restart for StatefulSet {}
 impl Restart for ReplicaSet {}
 
+/// The pod template annotation used to trigger a rollout, shared with `kubectl rollout restart`
+pub const RESTARTED_AT_ANNOTATION: &str = "kubectl.kubernetes.io/restartedAt";
+
+/// The field manager recorded for changes made by [`Request::restart`]
+pub const RESTART_FIELD_MANAGER: &str = "kube-rollout";
+
 impl Request {
     /// Restart a resource
-    pub fn restart(&self, name: &str) -> Result<http::Request<Vec<u8>>, request::Error> {
+    ///
+    /// Triggers a new rollout by server-side applying the [`RESTARTED_AT_ANNOTATION`] to the pod template.
+    /// The annotation is forcibly taken over, as it may previously have been set by `kubectl`.
+    pub fn restart<K: Restart>(&self, name: &str) -> Result<http::Request<Vec<u8>>, request::Error> {
         let patch = serde_json::json!({
+          "apiVersion": K::api_version(&()),
+          "kind": K::kind(&()),
           "spec": {
             "template": {
               "metadata": {
                 "annotations": {
-                  "kube.kubernetes.io/restartedAt": Utc::now().to_rfc3339()
+                  RESTARTED_AT_ANNOTATION: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
                 }
               }
             }
           }
         });
 
-        let pparams = PatchParams::default();
-        self.patch(name, &pparams, &Patch::Merge(patch))
+        let pparams = PatchParams::apply(RESTART_FIELD_MANAGER).force();
+        self.patch(name, &pparams, &Patch::Apply(patch))
     }
 }
 
+/// The field manager recorded for changes made by [`Request::cordon`] and [`Request::uncordon`]
+pub const CORDON_FIELD_MANAGER: &str = "kube-cordon";
+
 impl Request {
     /// Cordon a resource
     pub fn cordon(&self, name: &str) -> Result<http::Request<Vec<u8>>, request::Error> {
@@ -51,10 +65,14 @@ impl Request {
         node_name: &str,
         value: bool,
     ) -> Result<http::Request<Vec<u8>>, request::Error> {
+        let pparams = PatchParams {
+            field_manager: Some(CORDON_FIELD_MANAGER.into()),
+            ..PatchParams::default()
+        };
         self.patch(
             node_name,
-            &PatchParams::default(),
-            &Patch::Strategic(serde_json::json!({ "spec": { "unschedulable": value } })),
+            &pparams,
+            &Patch::Merge(serde_json::json!({ "spec": { "unschedulable": value } })),
         )
     }
 }
@@ -68,12 +86,24 @@ mod test {
         use k8s_openapi::api::apps::v1 as appsv1;
 
         let url = appsv1::Deployment::url_path(&(), Some("ns"));
-        let req = Request::new(url).restart("mydeploy").unwrap();
-        assert_eq!(req.uri(), "/apis/apps/v1/namespaces/ns/deployments/mydeploy?");
+        let req = Request::new(url)
+            .restart::<appsv1::Deployment>("mydeploy")
+            .unwrap();
+        assert_eq!(
+            req.uri(),
+            "/apis/apps/v1/namespaces/ns/deployments/mydeploy?&force=true&fieldManager=kube-rollout"
+        );
         assert_eq!(req.method(), "PATCH");
         assert_eq!(
             req.headers().get("Content-Type").unwrap().to_str().unwrap(),
-            Patch::Merge(()).content_type()
+            Patch::Apply(()).content_type()
+        );
+        let body: serde_json::Value = serde_json::from_slice(req.body()).unwrap();
+        assert_eq!(body["apiVersion"], "apps/v1");
+        assert_eq!(body["kind"], "Deployment");
+        assert!(
+            body["spec"]["template"]["metadata"]["annotations"]["kubectl.kubernetes.io/restartedAt"]
+                .is_string()
         );
     }
 
@@ -81,13 +111,19 @@ mod test {
     fn cordon_patch_is_correct() {
         use k8s_openapi::api::core::v1::Node;
 
-        let url = Node::url_path(&(), Some("ns"));
-        let req = Request::new(url).cordon("mynode").unwrap();
-        assert_eq!(req.uri(), "/api/v1/namespaces/ns/nodes/mynode?");
+        let url = Node::url_path(&(), None);
+        let req = Request::new(&url).cordon("mynode").unwrap();
+        assert_eq!(req.uri(), "/api/v1/nodes/mynode?&fieldManager=kube-cordon");
         assert_eq!(req.method(), "PATCH");
         assert_eq!(
             req.headers().get("Content-Type").unwrap().to_str().unwrap(),
-            Patch::Strategic(()).content_type()
+            Patch::Merge(()).content_type()
         );
+        let body: serde_json::Value = serde_json::from_slice(req.body()).unwrap();
+        assert_eq!(body, serde_json::json!({ "spec": { "unschedulable": true } }));
+
+        let req = Request::new(&url).uncordon("mynode").unwrap();
+        let body: serde_json::Value = serde_json::from_slice(req.body()).unwrap();
+        assert_eq!(body, serde_json::json!({ "spec": { "unschedulable": false } }));
     }
 }
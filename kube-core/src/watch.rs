@@ -4,7 +4,7 @@
 
 use crate::{error::ErrorResponse, metadata::TypeMeta};
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{collections::BTreeMap, fmt::Debug};
 /// A raw event returned from a watch query
 ///
 /// Note that a watch query returns many of these as newline separated JSON.
@@ -53,10 +53,69 @@ pub struct Bookmark {
     pub metadata: BookmarkMeta,
 }
 
+impl Bookmark {
+    /// Whether this bookmark marks the end of the initial events of a streaming list
+    ///
+    /// When watching with [`WatchParams::send_initial_events`](crate::params::WatchParams::send_initial_events),
+    /// the apiserver sends this bookmark once the current state of the collection has been sent.
+    pub fn is_initial_events_end(&self) -> bool {
+        self.metadata
+            .annotations
+            .get(INITIAL_EVENTS_END_ANNOTATION)
+            .map_or(false, |v| v == "true")
+    }
+}
+
+/// The annotation set on the bookmark that ends the initial events of a streaming list
+pub const INITIAL_EVENTS_END_ANNOTATION: &str = "k8s.io/initial-events-end";
+
 /// Slimed down Metadata for WatchEvent::Bookmark
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BookmarkMeta {
     /// The only field we need from a Bookmark event.
     pub resource_version: String,
+
+    /// Kubernetes annotations, used to signal the end of initial events
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::WatchEvent;
+    use k8s_openapi::api::core::v1::Pod;
+
+    #[test]
+    fn detects_initial_events_end_bookmark() {
+        let ev: WatchEvent<Pod> = serde_json::from_value(serde_json::json!({
+            "type": "BOOKMARK",
+            "object": {
+                "apiVersion": "v1",
+                "kind": "Pod",
+                "metadata": {
+                    "resourceVersion": "1234",
+                    "annotations": { "k8s.io/initial-events-end": "true" }
+                }
+            }
+        }))
+        .unwrap();
+        match ev {
+            WatchEvent::Bookmark(bm) => {
+                assert_eq!(bm.metadata.resource_version, "1234");
+                assert!(bm.is_initial_events_end());
+            }
+            _ => panic!("expected bookmark"),
+        }
+
+        let ev: WatchEvent<Pod> = serde_json::from_value(serde_json::json!({
+            "type": "BOOKMARK",
+            "object": { "apiVersion": "v1", "kind": "Pod", "metadata": { "resourceVersion": "1235" } }
+        }))
+        .unwrap();
+        match ev {
+            WatchEvent::Bookmark(bm) => assert!(!bm.is_initial_events_end()),
+            _ => panic!("expected bookmark"),
+        }
+    }
 }
@@ -77,6 +77,11 @@ mod custom_resource;
 /// ## `#[kube(namespaced)]`
 /// To specify that this is a namespaced resource rather than cluster level.
 ///
+/// This sets the [`Resource::Scope`](kube::core::Resource::Scope) of the generated type to
+/// [`NamespaceResourceScope`](kube::core::NamespaceResourceScope) (defaulting to
+/// [`ClusterResourceScope`](kube::core::ClusterResourceScope)), which ensures that
+/// `Api::namespaced` can only be used with namespaced resources at compile time.
+///
 /// ## `#[kube(struct = "StructName")]`
 /// Customize the name of the generated root struct (defaults to `kind`).
 ///
@@ -18,7 +18,7 @@ use futures::{
     future::{self, BoxFuture},
     ready, stream, Future, FutureExt, Stream, StreamExt, TryFuture, TryFutureExt, TryStream, TryStreamExt,
 };
-use kube_client::api::{Api, DynamicObject, ListParams, Resource};
+use kube_client::api::{Api, DynamicObject, Resource};
 use pin_project::pin_project;
 use serde::de::DeserializeOwned;
 use std::{
@@ -149,6 +149,52 @@ where
     })
 }
 
+/// Enqueues the objects returned by an asynchronous `mapper` for reconciliation
+///
+/// `mapper` runs for one object at a time, in the order of `stream`. Its errors are logged and the object
+/// is skipped, so that a failing `mapper` does not hold up any other triggers.
+pub fn trigger_others_async<K, S, I, Fut>(
+    stream: S,
+    dyntype: <S::Ok as Resource>::DynamicType,
+    mapper: impl Fn(S::Ok) -> Fut,
+) -> impl Stream<Item = Result<ReconcileRequest<K>, S::Error>>
+where
+    S: TryStream,
+    S::Ok: Resource,
+    <S::Ok as Resource>::DynamicType: Clone,
+    K: Resource,
+    Fut: TryFuture<Ok = I>,
+    Fut::Error: std::error::Error + 'static,
+    I: IntoIterator<Item = ObjectRef<K>>,
+{
+    let mapped = stream.and_then(move |obj| {
+        let watched_obj_ref = ObjectRef::from_obj_with(&obj, dyntype.clone()).erase();
+        mapper(obj).into_future().map(move |res| match res {
+            Ok(mapped_obj_refs) => Ok(Some((watched_obj_ref, mapped_obj_refs))),
+            Err(err) => {
+                tracing::error!(
+                    error = &err as &dyn std::error::Error,
+                    object = %watched_obj_ref,
+                    "relation mapper failed, skipping object"
+                );
+                Ok(None)
+            }
+        })
+    });
+    trigger_with(mapped, |mapped: Option<(ObjectRef<DynamicObject>, I)>| {
+        mapped.into_iter().flat_map(|(watched_obj_ref, mapped_obj_refs)| {
+            mapped_obj_refs
+                .into_iter()
+                .map(move |mapped_obj_ref| ReconcileRequest {
+                    obj_ref: mapped_obj_ref,
+                    reason: ReconcileReason::RelatedObjectUpdated {
+                        obj_ref: Box::new(watched_obj_ref.clone()),
+                    },
+                })
+        })
+    })
+}
+
 /// A request to reconcile an object, annotated with why that request was made.
 ///
 /// NOTE: The reason is ignored for comparison purposes. This means that, for example,
@@ -218,11 +264,34 @@ const APPLIER_REQUEUE_BUF_SIZE: usize = 100;
 /// This is the "hard-mode" version of [`Controller`], which allows you some more customization
 /// (such as triggering from arbitrary [`Stream`]s), at the cost of being a bit more verbose.
 pub fn applier<K, QueueStream, ReconcilerFut, Ctx>(
+    reconciler: impl FnMut(Arc<K>, Arc<Ctx>) -> ReconcilerFut,
+    error_policy: impl Fn(&ReconcilerFut::Error, Arc<Ctx>) -> Action,
+    context: Arc<Ctx>,
+    store: Store<K>,
+    queue: QueueStream,
+) -> impl Stream<Item = Result<(ObjectRef<K>, Action), Error<ReconcilerFut::Error, QueueStream::Error>>>
+where
+    K: Clone + Resource + 'static,
+    K::DynamicType: Debug + Eq + Hash + Clone + Unpin,
+    ReconcilerFut: TryFuture<Ok = Action> + Unpin,
+    ReconcilerFut::Error: std::error::Error + 'static,
+    QueueStream: TryStream,
+    QueueStream::Ok: Into<ReconcileRequest<K>>,
+    QueueStream::Error: std::error::Error + 'static,
+{
+    delayed_applier(reconciler, error_policy, context, store, queue, future::ready(()))
+}
+
+/// Like [`applier`], but holds all reconciliations until `ready_to_execute_after` resolves
+///
+/// The `queue` is still polled meanwhile, so requests are deduplicated rather than lost.
+fn delayed_applier<K, QueueStream, ReconcilerFut, Ctx>(
     mut reconciler: impl FnMut(Arc<K>, Arc<Ctx>) -> ReconcilerFut,
     error_policy: impl Fn(&ReconcilerFut::Error, Arc<Ctx>) -> Action,
     context: Arc<Ctx>,
     store: Store<K>,
     queue: QueueStream,
+    ready_to_execute_after: impl Future<Output = ()>,
 ) -> impl Stream<Item = Result<(ObjectRef<K>, Action), Error<ReconcilerFut::Error, QueueStream::Error>>>
 where
     K: Clone + Resource + 'static,
@@ -294,6 +363,7 @@ where
                     None => future::err(Error::ObjectNotFound(request.obj_ref.erase())).right_future(),
                 }
             })
+            .delay_tasks_until(ready_to_execute_after)
             .on_complete(async { tracing::debug!("applier runner terminated") })
         },
     )
@@ -478,12 +548,16 @@ where
     /// The [`ListParams`] controls to the possible subset of objects of `K` that you want to manage
     /// and receive reconcile events for.
     /// For the full set of objects `K` in the given `Api` scope, you can use [`ListParams::default`].
+    /// A [`watcher::Config`] can be passed instead to tune the watcher further.
+    ///
+    /// [`ListParams`]: kube_client::api::ListParams
+    /// [`ListParams::default`]: kube_client::api::ListParams::default
     #[must_use]
-    pub fn new(owned_api: Api<K>, lp: ListParams) -> Self
+    pub fn new(owned_api: Api<K>, config: impl Into<watcher::Config>) -> Self
     where
         K::DynamicType: Default,
     {
-        Self::new_with(owned_api, lp, Default::default())
+        Self::new_with(owned_api, config, Default::default())
     }
 
     /// Create a Controller on a type `K`
@@ -493,6 +567,7 @@ where
     /// The [`ListParams`] lets you define a possible subset of objects of `K` that you want the [`Api`]
     /// to watch - in the Api's  configured scope - and receive reconcile events for.
     /// For the full set of objects `K` in the given `Api` scope, you can use [`ListParams::default`].
+    /// A [`watcher::Config`] can be passed instead to tune the watcher further.
     ///
     /// This variant constructor is for [`dynamic`] types found through discovery. Prefer [`Controller::new`] for static types.
     ///
@@ -500,15 +575,117 @@ where
     /// [`Api`]: kube_client::Api
     /// [`dynamic`]: kube_client::core::dynamic
     /// [`ListParams::default`]: kube_client::api::ListParams::default
-    pub fn new_with(owned_api: Api<K>, lp: ListParams, dyntype: K::DynamicType) -> Self {
+    pub fn new_with(owned_api: Api<K>, config: impl Into<watcher::Config>, dyntype: K::DynamicType) -> Self {
         let writer = Writer::<K>::new(dyntype.clone());
         let reader = writer.as_reader();
-        let mut trigger_selector = stream::SelectAll::new();
-        let self_watcher = trigger_self(
-            reflector(writer, watcher(owned_api, lp)).applied_objects(),
-            dyntype.clone(),
-        )
+        Self::from_reflector(reflector(writer, watcher(owned_api, config)), reader, dyntype)
+    }
+
+    /// Create a Controller triggered by the events of the `reflector` that populates `reader`
+    ///
+    /// Objects listed by the watcher are only stored once the whole (re)list is done,
+    /// so they are enqueued at [`InitDone`](watcher::Event::InitDone) rather than as they are listed,
+    /// which would let the applier look them up before they are in the store.
+    fn from_reflector(
+        reflector: impl Stream<Item = watcher::Result<watcher::Event<K>>> + Send + 'static,
+        reader: Store<K>,
+        dyntype: K::DynamicType,
+    ) -> Self {
+        let self_dyntype = dyntype.clone();
+        let self_reader = reader.clone();
+        let self_watcher = trigger_with(reflector, move |event| {
+            let obj_refs = match event {
+                watcher::Event::Applied(obj) => vec![ObjectRef::from_obj_with(&obj, self_dyntype.clone())],
+                watcher::Event::InitDone => {
+                    let mut obj_refs = Vec::new();
+                    self_reader.for_each(|obj| {
+                        obj_refs.push(ObjectRef::from_obj_with(obj.as_ref(), self_dyntype.clone()));
+                    });
+                    obj_refs
+                }
+                watcher::Event::Deleted(_) | watcher::Event::Init | watcher::Event::InitApply(_) => {
+                    Vec::new()
+                }
+            };
+            obj_refs.into_iter().map(|obj_ref| ReconcileRequest {
+                obj_ref,
+                reason: ReconcileReason::ObjectUpdated,
+            })
+        })
         .boxed();
+        Self::from_self_trigger(self_watcher, reader, dyntype)
+    }
+
+    /// Create a Controller on a type `K` from a shared stream of its objects
+    ///
+    /// The `trigger` is typically a [`ReflectHandle`] of a [`Writer`] that is shared with other controllers
+    /// or user code, with `reader` being a reader of the same store (see [`ReflectHandle::reader`]).
+    /// This lets several controllers reconcile `K` without each of them watching every object of `K`.
+    ///
+    /// The [`reflector`] fed by the shared writer is not driven by the `Controller`, so it must be polled separately.
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::core::v1::Pod;
+    /// # use kube::{api::{Api, ListParams}, Client, runtime::{controller::{Action, Controller}, reflector, watcher}};
+    /// # use futures::StreamExt;
+    /// # use std::{convert::Infallible, sync::Arc};
+    /// # async fn reconcile(_: Arc<Pod>, _: Arc<()>) -> Result<Action, Infallible> { Ok(Action::await_change()) }
+    /// # fn error_policy(_: &Infallible, _: Arc<()>) -> Action { Action::await_change() }
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let pods: Api<Pod> = Api::all(Client::try_default().await?);
+    /// let mut writer = reflector::store::Writer::default();
+    /// let first = writer.subscribe(256);
+    /// let second = writer.subscribe(256);
+    /// let pod_reflector = reflector(writer, watcher(pods, ListParams::default())).for_each(|_| async {});
+    ///
+    /// let first_reader = first.reader();
+    /// let first = Controller::for_shared_stream(first, first_reader)
+    ///     .run(reconcile, error_policy, Arc::new(()))
+    ///     .for_each(|_| async {});
+    /// let second_reader = second.reader();
+    /// let second = Controller::for_shared_stream(second, second_reader)
+    ///     .run(reconcile, error_policy, Arc::new(()))
+    ///     .for_each(|_| async {});
+    /// futures::join!(pod_reflector, first, second);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReflectHandle`]: crate::reflector::store::ReflectHandle
+    /// [`ReflectHandle::reader`]: crate::reflector::store::ReflectHandle::reader
+    #[must_use]
+    pub fn for_shared_stream(trigger: impl Stream<Item = Arc<K>> + Send + 'static, reader: Store<K>) -> Self
+    where
+        K::DynamicType: Default,
+    {
+        Self::for_shared_stream_with(trigger, reader, Default::default())
+    }
+
+    /// Create a Controller on a type `K` from a shared stream of its objects
+    ///
+    /// Same as [`Controller::for_shared_stream`], but accepts a `DynamicType` so it can be used with dynamic resources.
+    pub fn for_shared_stream_with(
+        trigger: impl Stream<Item = Arc<K>> + Send + 'static,
+        reader: Store<K>,
+        dyntype: K::DynamicType,
+    ) -> Self {
+        let self_dyntype = dyntype.clone();
+        let self_watcher = trigger_with(trigger.map(Ok::<_, watcher::Error>), move |obj: Arc<K>| {
+            Some(ReconcileRequest {
+                obj_ref: ObjectRef::from_obj_with(obj.as_ref(), self_dyntype.clone()),
+                reason: ReconcileReason::ObjectUpdated,
+            })
+        })
+        .boxed();
+        Self::from_self_trigger(self_watcher, reader, dyntype)
+    }
+
+    fn from_self_trigger(
+        self_watcher: BoxStream<'static, Result<ReconcileRequest<K>, watcher::Error>>,
+        reader: Store<K>,
+        dyntype: K::DynamicType,
+    ) -> Self {
+        let mut trigger_selector = stream::SelectAll::new();
         trigger_selector.push(self_watcher);
         Self {
             trigger_selector,
@@ -532,6 +709,11 @@ where
     ///
     /// The [`default_backoff`](crate::watcher::default_backoff) follows client-go conventions,
     /// but can be overridden by calling this method.
+    ///
+    /// This backoff pauses the merged stream of all trigger watches whenever one of them returns an error.
+    /// A watch with a [`watcher::Config::backoff`] also waits out its own delay before retrying after
+    /// each error, so setting both makes the delays add up. Prefer leaving [`watcher::Config::backoff`]
+    /// unset on the configs passed to the `Controller`.
     #[must_use]
     pub fn trigger_backoff(mut self, backoff: impl Backoff + Send + 'static) -> Self {
         self.trigger_backoff = Box::new(backoff);
@@ -551,31 +733,94 @@ where
     /// The [`ListParams`] refer to the possible subset of `Child` objects that you want the [`Api`]
     ///  to watch - in the Api's configured scope - and receive reconcile events for.
     /// To watch the full set of `Child` objects in the given `Api` scope, you can use [`ListParams::default`].
+    /// A [`watcher::Config`] can be passed instead to tune the watcher further.
     ///
     /// [`OwnerReference`]: k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference
+    /// [`ListParams`]: kube_client::api::ListParams
+    /// [`ListParams::default`]: kube_client::api::ListParams::default
     #[must_use]
     pub fn owns<Child: Clone + Resource<DynamicType = ()> + DeserializeOwned + Debug + Send + 'static>(
         self,
         api: Api<Child>,
-        lp: ListParams,
+        config: impl Into<watcher::Config>,
     ) -> Self {
-        self.owns_with(api, (), lp)
+        self.owns_with(api, (), config)
     }
 
     /// Specify `Child` objects which `K` owns and should be watched
     ///
-    /// Same as [`Controller::owns`], but accepts a `DynamicType` so it can be used with dynamic resources.
+    /// Same as [`Controller::owns`], but accepts a `DynamicType` so it can be used with dynamic resources,
+    /// such as the [`ApiResource`] of a [`DynamicObject`] found through discovery.
+    /// Either `K` or `Child` (or both) can be dynamic.
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::apps::v1::Deployment;
+    /// # use kube::{api::{Api, ApiResource, DynamicObject, GroupVersionKind, ListParams}, Client};
+    /// # use kube::runtime::{controller::{Action, Controller}, watcher};
+    /// # use std::{convert::Infallible, sync::Arc};
+    /// # async fn reconcile(_: Arc<Deployment>, _: Arc<()>) -> Result<Action, Infallible> { Ok(Action::await_change()) }
+    /// # fn error_policy(_: &Infallible, _: Arc<()>) -> Action { Action::await_change() }
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::try_default().await?;
+    /// let gvk = GroupVersionKind::gvk("monitoring.coreos.com", "v1", "ServiceMonitor");
+    /// let ar = ApiResource::from_gvk(&gvk);
+    /// let monitors = Api::<DynamicObject>::all_with(client.clone(), &ar);
+    /// let config = watcher::Config::from(ListParams::default()).page_size(500);
+    /// Controller::new(Api::<Deployment>::all(client), ListParams::default())
+    ///     .owns_with(monitors, ar, config)
+    ///     .run(reconcile, error_policy, Arc::new(()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ApiResource`]: kube_client::api::ApiResource
+    /// [`DynamicObject`]: kube_client::api::DynamicObject
     #[must_use]
     pub fn owns_with<Child: Clone + Resource + DeserializeOwned + Debug + Send + 'static>(
         mut self,
         api: Api<Child>,
         dyntype: Child::DynamicType,
-        lp: ListParams,
+        config: impl Into<watcher::Config>,
+    ) -> Self
+    where
+        Child::DynamicType: Debug + Eq + Hash + Clone,
+    {
+        let child_watcher = trigger_owners(
+            watcher(api, config).touched_objects(),
+            self.dyntype.clone(),
+            dyntype,
+        );
+        self.trigger_selector.push(child_watcher.boxed());
+        self
+    }
+
+    /// Specify `Child` objects which `K` owns, from a shared stream
+    ///
+    /// Same as [`Controller::owns`], but the `Child` objects are received from a shared stream (such as a
+    /// [`ReflectHandle`](crate::reflector::store::ReflectHandle)) instead of being watched by the `Controller`.
+    /// Since shared streams only carry applied objects, deleting a `Child` does not trigger a reconciliation.
+    #[must_use]
+    pub fn owns_shared_stream<Child: Clone + Resource<DynamicType = ()> + Send + Sync + 'static>(
+        self,
+        trigger: impl Stream<Item = Arc<Child>> + Send + 'static,
+    ) -> Self {
+        self.owns_shared_stream_with(trigger, ())
+    }
+
+    /// Specify `Child` objects which `K` owns, from a shared stream
+    ///
+    /// Same as [`Controller::owns_shared_stream`], but accepts a `DynamicType` so it can be used with dynamic resources.
+    #[must_use]
+    pub fn owns_shared_stream_with<Child: Clone + Resource + Send + Sync + 'static>(
+        mut self,
+        trigger: impl Stream<Item = Arc<Child>> + Send + 'static,
+        dyntype: Child::DynamicType,
     ) -> Self
     where
         Child::DynamicType: Debug + Eq + Hash + Clone,
     {
-        let child_watcher = trigger_owners(watcher(api, lp).touched_objects(), self.dyntype.clone(), dyntype);
+        let children = trigger.map(|obj| Ok::<_, watcher::Error>((*obj).clone()));
+        let child_watcher = trigger_owners(children, self.dyntype.clone(), dyntype);
         self.trigger_selector.push(child_watcher.boxed());
         self
     }
@@ -592,6 +837,10 @@ where
     /// The [`ListParams`] refer to the possible subset of `Watched` objects that you want the [`Api`]
     /// to watch - in the Api's configured scope - and run through the custom mapper.
     /// To watch the full set of `Watched` objects in given the `Api` scope, you can use [`ListParams::default`].
+    /// A [`watcher::Config`] can be passed instead to tune the watcher further.
+    ///
+    /// [`ListParams`]: kube_client::api::ListParams
+    /// [`ListParams::default`]: kube_client::api::ListParams::default
     #[must_use]
     pub fn watches<
         Other: Clone + Resource<DynamicType = ()> + DeserializeOwned + Debug + Send + 'static,
@@ -599,18 +848,22 @@ where
     >(
         self,
         api: Api<Other>,
-        lp: ListParams,
+        config: impl Into<watcher::Config>,
         mapper: impl Fn(Other) -> I + Sync + Send + 'static,
     ) -> Self
     where
         I::IntoIter: Send,
     {
-        self.watches_with(api, (), lp, mapper)
+        self.watches_with(api, (), config, mapper)
     }
 
     /// Specify `Watched` object which `K` has a custom relation to and should be watched
     ///
-    /// Same as [`Controller::watches`], but accepts a `DynamicType` so it can be used with dynamic resources.
+    /// Same as [`Controller::watches`], but accepts a `DynamicType` so it can be used with dynamic resources,
+    /// such as the [`ApiResource`] of a [`DynamicObject`] found through discovery.
+    ///
+    /// [`ApiResource`]: kube_client::api::ApiResource
+    /// [`DynamicObject`]: kube_client::api::DynamicObject
     #[must_use]
     pub fn watches_with<
         Other: Clone + Resource + DeserializeOwned + Debug + Send + 'static,
@@ -619,14 +872,14 @@ where
         mut self,
         api: Api<Other>,
         dyntype: Other::DynamicType,
-        lp: ListParams,
+        config: impl Into<watcher::Config>,
         mapper: impl Fn(Other) -> I + Sync + Send + 'static,
     ) -> Self
     where
         I::IntoIter: Send,
         Other::DynamicType: Clone,
     {
-        let other_watcher = trigger_with(watcher(api, lp).touched_objects(), move |obj| {
+        let other_watcher = trigger_with(watcher(api, config).touched_objects(), move |obj| {
             let watched_obj_ref = ObjectRef::from_obj_with(&obj, dyntype.clone()).erase();
             mapper(obj)
                 .into_iter()
@@ -641,6 +894,84 @@ where
         self
     }
 
+    /// Specify `Watched` object which `K` has a custom relation to, using an asynchronous mapper
+    ///
+    /// Same as [`Controller::watches`], but the `mapper` returns a [`TryFuture`] of the `ObjectRef<K>`s to
+    /// reconcile. This suits relations that have to wait for another system, such as a [`Store`] that
+    /// has not finished its initial list yet.
+    ///
+    /// The mapper runs for one `Watched` object at a time, in the order of the watch events.
+    /// Its errors are logged, and the `Watched` object is not retried until it changes again.
+    ///
+    /// ```no_run
+    /// # use k8s_openapi::api::{apps::v1::Deployment, core::v1::ConfigMap};
+    /// # use kube::{api::{Api, ListParams, ResourceExt}, Client, runtime::{controller::{Action, Controller}, reflector::ObjectRef}};
+    /// # use std::{convert::Infallible, sync::Arc};
+    /// # async fn reconcile(_: Arc<Deployment>, _: Arc<()>) -> Result<Action, Infallible> { Ok(Action::await_change()) }
+    /// # fn error_policy(_: &Infallible, _: Arc<()>) -> Action { Action::await_change() }
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::try_default().await?;
+    /// let controller = Controller::new(Api::<Deployment>::all(client.clone()), ListParams::default());
+    /// let deployments = controller.store();
+    /// controller
+    ///     .watches_async(Api::<ConfigMap>::all(client), ListParams::default(), move |cm| {
+    ///         let deployments = deployments.clone();
+    ///         async move {
+    ///             // Wait for the initial list, so that no Deployment is missed
+    ///             deployments.wait_until_ready().await?;
+    ///             // Deployments label the ConfigMap they are configured by
+    ///             let uses_cm = |d: &Deployment| d.labels().get("config") == Some(&cm.name_any());
+    ///             let found = deployments.filter(uses_cm);
+    ///             Ok::<_, kube::runtime::reflector::store::WriterDropped>(
+    ///                 found.iter().map(|d| ObjectRef::from_obj(&**d)).collect::<Vec<_>>(),
+    ///             )
+    ///         }
+    ///     })
+    ///     .run(reconcile, error_policy, Arc::new(()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn watches_async<Other, I, Fut>(
+        self,
+        api: Api<Other>,
+        config: impl Into<watcher::Config>,
+        mapper: impl Fn(Other) -> Fut + Sync + Send + 'static,
+    ) -> Self
+    where
+        Other: Clone + Resource<DynamicType = ()> + DeserializeOwned + Debug + Send + 'static,
+        Fut: TryFuture<Ok = I> + Send + 'static,
+        Fut::Error: std::error::Error + 'static,
+        I: IntoIterator<Item = ObjectRef<K>> + Send + 'static,
+        I::IntoIter: Send,
+    {
+        self.watches_async_with(api, (), config, mapper)
+    }
+
+    /// Specify `Watched` object which `K` has a custom relation to, using an asynchronous mapper
+    ///
+    /// Same as [`Controller::watches_async`], but accepts a `DynamicType` so it can be used with dynamic resources.
+    #[must_use]
+    pub fn watches_async_with<Other, I, Fut>(
+        mut self,
+        api: Api<Other>,
+        dyntype: Other::DynamicType,
+        config: impl Into<watcher::Config>,
+        mapper: impl Fn(Other) -> Fut + Sync + Send + 'static,
+    ) -> Self
+    where
+        Other: Clone + Resource + DeserializeOwned + Debug + Send + 'static,
+        Other::DynamicType: Clone,
+        Fut: TryFuture<Ok = I> + Send + 'static,
+        Fut::Error: std::error::Error + 'static,
+        I: IntoIterator<Item = ObjectRef<K>> + Send + 'static,
+        I::IntoIter: Send,
+    {
+        let other_watcher = trigger_others_async(watcher(api, config).touched_objects(), dyntype, mapper);
+        self.trigger_selector.push(other_watcher.boxed());
+        self
+    }
+
     /// Trigger a reconciliation for all managed objects whenever `trigger` emits a value
     ///
     /// For example, this can be used to reconcile all objects whenever the controller's configuration changes.
@@ -672,7 +1003,7 @@ where
     /// .reconcile_all_on(reload_rx.map(|_| ()))
     /// .run(
     ///     |o, _| async move {
-    ///         println!("Reconciling {}", o.name());
+    ///         println!("Reconciling {}", o.name_any());
     ///         Ok(Action::await_change())
     ///     },
     ///     |err: &Infallible, _| Err(err).unwrap(),
@@ -727,7 +1058,7 @@ where
     /// .graceful_shutdown_on(tokio::signal::ctrl_c().map(|_| ()))
     /// .run(
     ///     |o, _| async move {
-    ///         println!("Reconciling {}", o.name());
+    ///         println!("Reconciling {}", o.name_any());
     ///         Ok(Action::await_change())
     ///     },
     ///     |err: &Infallible, _| Err(err).unwrap(),
@@ -805,6 +1136,9 @@ where
     /// This creates a stream from all builder calls and starts an applier with
     /// a specified `reconciler` and `error_policy` callbacks. Each of these will be called
     /// with a configurable `context`.
+    ///
+    /// No objects are reconciled until the store has been populated by its first list
+    /// (see [`Store::wait_until_ready`]), so the reconciler never misses an object that merely wasn't listed yet.
     pub fn run<ReconcilerFut, Ctx>(
         self,
         mut reconciler: impl FnMut(Arc<K>, Arc<Ctx>) -> ReconcilerFut,
@@ -816,7 +1150,8 @@ where
         ReconcilerFut: TryFuture<Ok = Action> + Send + 'static,
         ReconcilerFut::Error: std::error::Error + Send + 'static,
     {
-        applier(
+        let reader = self.reader.clone();
+        delayed_applier(
             move |obj, ctx| {
                 CancelableJoinHandle::spawn(
                     reconciler(obj, ctx).into_future().in_current_span(),
@@ -828,6 +1163,11 @@ where
             self.reader,
             StreamBackoff::new(self.trigger_selector, self.trigger_backoff)
                 .take_until(future::select_all(self.graceful_shutdown_selector)),
+            // Objects that are triggered before the first list is stored would not be found
+            async move {
+                // If the writer is gone then the store will never become ready, so reconcile what we have
+                reader.wait_until_ready().await.ok();
+            },
         )
         .take_until(futures::future::select_all(self.forceful_shutdown_selector))
     }
@@ -837,16 +1177,16 @@ where
 mod tests {
     use std::{convert::Infallible, sync::Arc, time::Duration};
 
-    use super::{Action, APPLIER_REQUEUE_BUF_SIZE};
+    use super::{trigger_others_async, trigger_self, Action, APPLIER_REQUEUE_BUF_SIZE};
     use crate::{
         applier,
-        reflector::{self, ObjectRef},
+        reflector::{self, reflector, ObjectRef},
         watcher, Controller,
     };
-    use futures::{pin_mut, StreamExt, TryStreamExt};
+    use futures::{pin_mut, stream, StreamExt, TryStreamExt};
     use k8s_openapi::api::core::v1::ConfigMap;
     use kube_client::{core::ObjectMeta, Api};
-    use tokio::time::timeout;
+    use tokio::time::{pause, sleep, timeout};
 
     fn assert_send<T: Send>(x: T) -> T {
         x
@@ -858,11 +1198,22 @@ mod tests {
         )
     }
 
+    fn config_map(name: &str) -> ConfigMap {
+        ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some("default".to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        }
+    }
+
     // not #[test] because we don't want to actually run it, we just want to assert that it typechecks
     #[allow(dead_code, unused_must_use)]
     fn test_controller_should_be_send() {
         assert_send(
-            Controller::new(mock_type::<Api<ConfigMap>>(), Default::default()).run(
+            Controller::new(mock_type::<Api<ConfigMap>>(), watcher::Config::default()).run(
                 |_, _| async { Ok(mock_type::<Action>()) },
                 |_: &std::io::Error, _| mock_type::<Action>(),
                 Arc::new(()),
@@ -870,6 +1221,76 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn controller_should_reconcile_paginated_list_once_stored() {
+        pause();
+        // The second page of the list only arrives after the applier would have run the first one
+        let first_page = stream::iter(vec![
+            Ok(watcher::Event::Init),
+            Ok(watcher::Event::InitApply(config_map("a"))),
+        ]);
+        let second_page = stream::once(sleep(Duration::from_secs(1))).flat_map(move |()| {
+            stream::iter(vec![
+                Ok(watcher::Event::InitApply(config_map("b"))),
+                Ok(watcher::Event::InitDone),
+            ])
+        });
+        let (reader, writer) = reflector::store();
+        let events = reflector(writer, first_page.chain(second_page).chain(stream::pending()));
+        let mut reconciled = Controller::from_reflector(events, reader, ())
+            .run(
+                |_, _| async { Ok(Action::await_change()) },
+                |_: &Infallible, _| Action::await_change(),
+                Arc::new(()),
+            )
+            .take(2)
+            .map_ok(|(obj_ref, _)| obj_ref.name)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        reconciled.sort();
+        assert_eq!(reconciled, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn trigger_others_async_should_skip_mapper_errors() {
+        let watched = stream::iter(vec![
+            Ok::<_, watcher::Error>(config_map("a")),
+            Ok(config_map("broken")),
+            Ok(config_map("b")),
+        ]);
+        let triggered = trigger_others_async(watched, (), |cm: ConfigMap| async move {
+            let name = cm.metadata.name.unwrap();
+            if name == "broken" {
+                Err(std::fmt::Error)
+            } else {
+                Ok(Some(ObjectRef::<ConfigMap>::new(&format!("{name}-owner"))))
+            }
+        })
+        .map_ok(|req| req.obj_ref.name)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+        assert_eq!(triggered, vec!["a-owner", "b-owner"]);
+    }
+
+    #[tokio::test]
+    async fn failing_async_mapper_should_not_hold_up_other_triggers() {
+        let watched = stream::iter(vec![Ok::<_, watcher::Error>(config_map("watched"))]);
+        let others = trigger_others_async(watched, (), |_: ConfigMap| async {
+            Err::<Option<ObjectRef<ConfigMap>>, _>(std::fmt::Error)
+        });
+        let selves = trigger_self(stream::iter(vec![Ok(config_map("a")), Ok(config_map("b"))]), ());
+        // Any error would pause every trigger through the `trigger_backoff`
+        let mut triggered = stream::select(others, selves)
+            .map_ok(|req| req.obj_ref.name)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        triggered.sort();
+        assert_eq!(triggered, vec!["a", "b"]);
+    }
+
     #[tokio::test]
     async fn applier_must_not_deadlock_if_reschedule_buffer_fills() {
         // This tests that `applier` handles reschedule queue backpressure correctly, by trying to flood it with no-op reconciles
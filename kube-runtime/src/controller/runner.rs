@@ -1,6 +1,6 @@
 use super::future_hash_map::FutureHashMap;
 use crate::scheduler::{ScheduleRequest, Scheduler};
-use futures::{Future, Stream, StreamExt};
+use futures::{future, Future, Stream, StreamExt};
 use pin_project::pin_project;
 use std::{
     hash::Hash,
@@ -14,12 +14,17 @@ use std::{
 /// If an item is to be emitted from the [`Scheduler`] while an equal item is
 /// already being processed then it will be held pending until the current item
 /// is finished.
+///
+/// No items are processed until `ready_to_execute_after` has resolved, see [`Runner::delay_tasks_until`].
 #[pin_project]
-pub struct Runner<T, R, F, MkF> {
+pub struct Runner<T, R, F, MkF, Ready = future::Ready<()>> {
     #[pin]
     scheduler: Scheduler<T, R>,
     run_msg: MkF,
     slots: FutureHashMap<T, F>,
+    #[pin]
+    ready_to_execute_after: Ready,
+    is_ready_to_execute: bool,
 }
 
 impl<T, R, F, MkF> Runner<T, R, F, MkF>
@@ -32,21 +37,47 @@ where
             scheduler,
             run_msg,
             slots: FutureHashMap::default(),
+            ready_to_execute_after: future::ready(()),
+            is_ready_to_execute: false,
+        }
+    }
+
+    /// Holds all items until `ready_to_execute_after` resolves
+    ///
+    /// The scheduler is still polled meanwhile, so that its requests are accepted (and deduplicated)
+    /// rather than backing up into whatever produces them.
+    pub fn delay_tasks_until<Ready>(self, ready_to_execute_after: Ready) -> Runner<T, R, F, MkF, Ready>
+    where
+        Ready: Future<Output = ()>,
+    {
+        Runner {
+            scheduler: self.scheduler,
+            run_msg: self.run_msg,
+            slots: self.slots,
+            ready_to_execute_after,
+            is_ready_to_execute: false,
         }
     }
 }
 
-impl<T, R, F, MkF> Stream for Runner<T, R, F, MkF>
+impl<T, R, F, MkF, Ready> Stream for Runner<T, R, F, MkF, Ready>
 where
     T: Eq + Hash + Clone + Unpin,
     R: Stream<Item = ScheduleRequest<T>>,
     F: Future + Unpin,
     MkF: FnMut(&T) -> F,
+    Ready: Future<Output = ()>,
 {
     type Item = F::Output;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
+        if !*this.is_ready_to_execute {
+            if let Poll::Ready(()) = this.ready_to_execute_after.poll(cx) {
+                *this.is_ready_to_execute = true;
+            }
+        }
+        let is_ready_to_execute = *this.is_ready_to_execute;
         let slots = this.slots;
         let scheduler = &mut this.scheduler;
         let has_active_slots = match slots.poll_next_unpin(cx) {
@@ -58,9 +89,10 @@ where
             // Try to take take a new message that isn't already being processed
             // leave the already-processing ones in the queue, so that we can take them once
             // we're free again.
+            // Until we are ready to execute, hold all messages, to be taken once ready_to_execute_after wakes us.
             let next_msg_poll = scheduler
                 .as_mut()
-                .hold_unless(|msg| !slots.contains_key(msg))
+                .hold_unless(|msg| is_ready_to_execute && !slots.contains_key(msg))
                 .poll_next_unpin(cx);
             match next_msg_poll {
                 Poll::Ready(Some(msg)) => {
@@ -92,7 +124,7 @@ mod tests {
     use crate::scheduler::{scheduler, ScheduleRequest};
     use futures::{
         channel::{mpsc, oneshot},
-        future, poll, SinkExt, StreamExt,
+        future, poll, FutureExt, SinkExt, StreamExt,
     };
     use std::{cell::RefCell, time::Duration};
     use tokio::{
@@ -174,4 +206,27 @@ mod tests {
             Some(8)
         );
     }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn runner_should_hold_messages_until_ready() {
+        let (mut sched_tx, sched_rx) = mpsc::unbounded();
+        let (ready_tx, ready_rx) = oneshot::channel::<()>();
+        let mut runner = Box::pin(
+            Runner::new(scheduler(sched_rx), |msg: &u8| futures::future::ready(*msg))
+                .delay_tasks_until(ready_rx.map(|_| ())),
+        );
+        sched_tx
+            .send(ScheduleRequest {
+                message: 8,
+                run_at: Instant::now(),
+            })
+            .await
+            .unwrap();
+        assert!(poll!(runner.next()).is_pending());
+        ready_tx.send(()).unwrap();
+        assert_eq!(
+            timeout(Duration::from_secs(1), runner.next()).await.unwrap(),
+            Some(8)
+        );
+    }
 }
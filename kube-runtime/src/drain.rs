@@ -0,0 +1,339 @@
+//! Drains nodes ahead of maintenance
+//!
+//! See [`drain`] for the entry point.
+use std::time::Duration;
+
+use backoff::{backoff::Backoff, ExponentialBackoff};
+use futures::future::try_join_all;
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube_client::{
+    api::{DeleteParams, EvictParams, ListParams},
+    Api, Client, ResourceExt,
+};
+use thiserror::Error;
+
+use crate::wait::{self, await_condition, conditions};
+
+/// Annotation set by the kubelet on the apiserver mirrors of static pods
+const MIRROR_POD_ANNOTATION: &str = "kubernetes.io/config.mirror";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to cordon node: {0}")]
+    Cordon(#[source] kube_client::Error),
+    #[error("failed to list pods on node: {0}")]
+    ListPods(#[source] kube_client::Error),
+    #[error("cannot evict pods: {}", .0.join(", "))]
+    Blocked(Vec<String>),
+    #[error("failed to evict pod {name}: {source}")]
+    Evict {
+        name: String,
+        #[source]
+        source: kube_client::Error,
+    },
+    #[error("failed to wait for pod to terminate: {0}")]
+    Await(#[source] wait::Error),
+    #[error("timed out draining node")]
+    Timeout,
+}
+
+/// Parameters controlling which pods [`drain`] evicts, and how
+///
+/// The defaults mirror `kubectl drain --ignore-daemonsets`.
+#[derive(Clone, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct DrainParams {
+    /// Skip pods managed by a `DaemonSet`, rather than refusing to drain
+    ///
+    /// The `DaemonSet` controller ignores the unschedulable taint and would recreate them anyway.
+    pub ignore_daemonsets: bool,
+    /// Evict pods using `emptyDir` volumes, losing their local data
+    pub delete_emptydir_data: bool,
+    /// Evict pods that are not managed by a controller, and will therefore not be recreated
+    pub force: bool,
+    /// Grace period given to each pod to terminate, overriding the pod's own
+    pub grace_period: Option<u32>,
+    /// Give up on the drain after this duration
+    ///
+    /// Evictions blocked by a `PodDisruptionBudget` are retried until this elapses.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for DrainParams {
+    fn default() -> Self {
+        Self {
+            ignore_daemonsets: true,
+            delete_emptydir_data: false,
+            force: false,
+            grace_period: None,
+            timeout: None,
+        }
+    }
+}
+
+impl DrainParams {
+    /// Refuse to drain nodes running `DaemonSet` pods
+    #[must_use]
+    pub fn deny_daemonsets(mut self) -> Self {
+        self.ignore_daemonsets = false;
+        self
+    }
+
+    /// Evict pods using `emptyDir` volumes
+    #[must_use]
+    pub fn delete_emptydir_data(mut self) -> Self {
+        self.delete_emptydir_data = true;
+        self
+    }
+
+    /// Evict pods that are not managed by a controller
+    #[must_use]
+    pub fn force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    /// Override the termination grace period of evicted pods
+    #[must_use]
+    pub fn grace_period(mut self, seconds: u32) -> Self {
+        self.grace_period = Some(seconds);
+        self
+    }
+
+    /// Give up on the drain after `timeout`
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// What to do with a pod running on the drained node
+#[derive(Debug, PartialEq, Eq)]
+enum PodAction {
+    Evict,
+    Skip,
+    Block(&'static str),
+}
+
+fn pod_action(pod: &Pod, params: &DrainParams) -> PodAction {
+    if pod.annotations().contains_key(MIRROR_POD_ANNOTATION) {
+        // Static pods are owned by the kubelet, evicting the mirror would be a no-op
+        return PodAction::Skip;
+    }
+    let finished = pod
+        .status
+        .as_ref()
+        .and_then(|s| s.phase.as_deref())
+        .map_or(false, |phase| phase == "Succeeded" || phase == "Failed");
+    if finished {
+        // Nothing left to lose, so always clean these up
+        return PodAction::Evict;
+    }
+    let controller = pod
+        .owner_references()
+        .iter()
+        .find(|owner| owner.controller == Some(true));
+    match controller {
+        Some(owner) if owner.kind == "DaemonSet" => {
+            return if params.ignore_daemonsets {
+                PodAction::Skip
+            } else {
+                PodAction::Block("managed by a DaemonSet")
+            };
+        }
+        None if !params.force => return PodAction::Block("not managed by a controller"),
+        _ => {}
+    }
+    let uses_emptydir = pod
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.volumes.as_ref())
+        .map_or(false, |volumes| volumes.iter().any(|v| v.empty_dir.is_some()));
+    if uses_emptydir && !params.delete_emptydir_data {
+        return PodAction::Block("uses emptyDir local storage");
+    }
+    PodAction::Evict
+}
+
+/// Drain a node, evicting its pods so that it can be taken down for maintenance
+///
+/// This follows the same procedure as `kubectl drain`:
+///
+/// 1. The node is cordoned, so that no new pods are scheduled onto it,
+///    unless some of its pods would be lost by the drain (see [`DrainParams`])
+/// 2. Mirror pods are skipped, as are `DaemonSet` pods unless [`DrainParams::deny_daemonsets`] is set
+/// 3. All remaining pods are evicted, retrying with a backoff while `PodDisruptionBudget`s block the eviction
+/// 4. The drain completes once every evicted pod has terminated
+///
+/// The node is left cordoned afterwards, use [`Api::uncordon`] to bring it back into service.
+///
+/// # Caveats
+///
+/// A `PodDisruptionBudget` that can never be satisfied blocks the drain forever,
+/// unless a [`DrainParams::timeout`] is set.
+///
+/// # Errors
+///
+/// Fails without cordoning the node or evicting anything if some pods would be lost by the drain
+/// (see [`DrainParams`]), and with [`Error::Timeout`] if the drain takes longer than the configured timeout.
+/// Fails if the client lacks permission to patch nodes, list pods, or create evictions.
+///
+/// # Usage
+///
+/// ```no_run
+/// use kube::runtime::drain::{drain, DrainParams};
+/// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client: kube::Client = todo!();
+/// let params = DrainParams::default().timeout(std::time::Duration::from_secs(300));
+/// drain(client, "worker-1", &params).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn drain(client: Client, node: &str, params: &DrainParams) -> Result<(), Error> {
+    // Check for blocking pods before touching the node, so that a refused drain leaves it schedulable
+    evictable_pods(client.clone(), node, params).await?;
+    let nodes: Api<Node> = Api::all(client.clone());
+    nodes.cordon(node).await.map_err(Error::Cordon)?;
+    // List again to also catch pods that were scheduled before the cordon took effect
+    let evictable = evictable_pods(client.clone(), node, params).await?;
+
+    let ep = EvictParams {
+        delete_options: params
+            .grace_period
+            .map(|secs| DeleteParams::default().grace_period(secs)),
+        ..EvictParams::default()
+    };
+    let evictions = try_join_all(
+        evictable
+            .iter()
+            .map(|pod| evict_and_await(client.clone(), pod, &ep)),
+    );
+    match params.timeout {
+        Some(timeout) => tokio::time::timeout(timeout, evictions)
+            .await
+            .map_err(|_| Error::Timeout)??,
+        None => evictions.await?,
+    };
+    Ok(())
+}
+
+/// List the pods on `node` that should be evicted, failing if any of them block the drain
+async fn evictable_pods(client: Client, node: &str, params: &DrainParams) -> Result<Vec<Pod>, Error> {
+    let lp = ListParams::default().fields(&format!("spec.nodeName={}", node));
+    let pods = Api::<Pod>::all(client).list(&lp).await.map_err(Error::ListPods)?;
+
+    let mut evictable = Vec::new();
+    let mut blocked = Vec::new();
+    for pod in pods {
+        match pod_action(&pod, params) {
+            PodAction::Evict => evictable.push(pod),
+            PodAction::Skip => {}
+            PodAction::Block(reason) => blocked.push(format!(
+                "{}/{} ({})",
+                pod.namespace().unwrap_or_default(),
+                pod.name_any(),
+                reason
+            )),
+        }
+    }
+    if blocked.is_empty() {
+        Ok(evictable)
+    } else {
+        Err(Error::Blocked(blocked))
+    }
+}
+
+/// Evict a single pod, retrying while a `PodDisruptionBudget` forbids it, and wait for it to terminate
+async fn evict_and_await(client: Client, pod: &Pod, ep: &EvictParams) -> Result<(), Error> {
+    let name = pod.name_any();
+    let api: Api<Pod> = match pod.namespace() {
+        Some(ns) => Api::namespaced(client, &ns),
+        None => Api::default_namespaced(client),
+    };
+    let mut backoff = ExponentialBackoff {
+        initial_interval: Duration::from_secs(1),
+        max_interval: Duration::from_secs(30),
+        max_elapsed_time: None,
+        ..ExponentialBackoff::default()
+    };
+    loop {
+        match api.evict(&name, ep).await {
+            Ok(_) => break,
+            // The pod is already gone
+            Err(kube_client::Error::Api(ae)) if ae.code == 404 => return Ok(()),
+            // Evicting the pod would violate a PodDisruptionBudget, try again later
+            Err(kube_client::Error::Api(ae)) if ae.code == 429 => {
+                tracing::debug!(pod = %name, "eviction blocked by disruption budget, retrying");
+                let delay = backoff.next_backoff().unwrap_or(backoff.max_interval);
+                tokio::time::sleep(delay).await;
+            }
+            Err(source) => return Err(Error::Evict { name, source }),
+        }
+    }
+    if let Some(uid) = pod.uid() {
+        await_condition(api, &name, conditions::is_deleted(&uid))
+            .await
+            .map_err(Error::Await)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pod_action, DrainParams, PodAction};
+    use k8s_openapi::api::core::v1::Pod;
+
+    fn pod(value: serde_json::Value) -> Pod {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn owned_by(kind: &str) -> Pod {
+        pod(serde_json::json!({
+            "metadata": {
+                "name": "owned",
+                "ownerReferences": [{
+                    "apiVersion": "apps/v1", "kind": kind, "name": "owner", "uid": "1234", "controller": true
+                }]
+            }
+        }))
+    }
+
+    #[test]
+    fn drain_filters_pods() {
+        let params = DrainParams::default();
+        assert_eq!(pod_action(&owned_by("ReplicaSet"), &params), PodAction::Evict);
+        assert_eq!(pod_action(&owned_by("DaemonSet"), &params), PodAction::Skip);
+        assert!(matches!(
+            pod_action(&owned_by("DaemonSet"), &params.clone().deny_daemonsets()),
+            PodAction::Block(_)
+        ));
+
+        let mirror = pod(serde_json::json!({
+            "metadata": { "name": "mirror", "annotations": { "kubernetes.io/config.mirror": "abc" } }
+        }));
+        assert_eq!(pod_action(&mirror, &params), PodAction::Skip);
+
+        let bare = pod(serde_json::json!({ "metadata": { "name": "bare" } }));
+        assert!(matches!(pod_action(&bare, &params), PodAction::Block(_)));
+        assert_eq!(pod_action(&bare, &params.clone().force()), PodAction::Evict);
+
+        let finished =
+            pod(serde_json::json!({ "metadata": { "name": "done" }, "status": { "phase": "Succeeded" } }));
+        assert_eq!(pod_action(&finished, &params), PodAction::Evict);
+
+        let mut scratch = owned_by("ReplicaSet");
+        scratch.spec = Some(
+            serde_json::from_value(serde_json::json!({
+                "containers": [],
+                "volumes": [{ "name": "scratch", "emptyDir": {} }]
+            }))
+            .unwrap(),
+        );
+        assert!(matches!(pod_action(&scratch, &params), PodAction::Block(_)));
+        assert_eq!(
+            pod_action(&scratch, &params.delete_emptydir_data()),
+            PodAction::Evict
+        );
+    }
+}
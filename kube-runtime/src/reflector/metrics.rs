@@ -0,0 +1,72 @@
+//! Hooks for exporting the activity of reflectors as metrics
+use crate::watcher;
+use std::{sync::Arc, time::SystemTime};
+
+/// Receives the activity of a [`reflector`](crate::reflector()), to export it as metrics
+///
+/// Attach it with [`Writer::with_metrics`](super::store::Writer::with_metrics), once per reflector.
+/// The methods are called synchronously as the reflector applies each event, so they should be cheap,
+/// such as setting a gauge or incrementing a counter. All of them do nothing by default.
+///
+/// A cache that has gone stale shows as a growing number of
+/// [`watch_restarted`](ReflectorMetrics::watch_restarted) calls, along with a
+/// [`last_event`](ReflectorMetrics::last_event) time that stops advancing. The latter alone is not
+/// a sign of trouble, since a watch over objects that do not change stays quiet.
+///
+/// ```
+/// use kube::runtime::{reflector::{metrics::ReflectorMetrics, store::Writer}, watcher};
+/// use k8s_openapi::api::core::v1::Pod;
+/// use std::{sync::{atomic::{AtomicU64, AtomicUsize, Ordering}, Arc}, time::SystemTime};
+///
+/// #[derive(Default)]
+/// struct PodCacheMetrics {
+///     objects: AtomicUsize,
+///     watch_restarts: AtomicU64,
+/// }
+///
+/// impl ReflectorMetrics for PodCacheMetrics {
+///     fn set_objects(&self, count: usize) {
+///         self.objects.store(count, Ordering::Relaxed);
+///     }
+///
+///     fn watch_restarted(&self, _err: &watcher::Error) {
+///         self.watch_restarts.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let metrics = Arc::new(PodCacheMetrics::default());
+/// let writer = Writer::<Pod>::default().with_metrics(metrics.clone());
+/// ```
+pub trait ReflectorMetrics: Send + Sync {
+    /// The store now holds `count` objects
+    fn set_objects(&self, _count: usize) {}
+
+    /// The store was updated from an event of the watcher at `at`
+    ///
+    /// Only called for the [`watcher::Event`]s that reach the store, not for bookmarks or errors.
+    fn last_event(&self, _at: SystemTime) {}
+
+    /// The watcher started to list all objects, either initially or because it could not resume watching
+    fn relisted(&self) {}
+
+    /// The watcher failed with `err`, and restarts on the next poll
+    fn watch_restarted(&self, _err: &watcher::Error) {}
+}
+
+impl<T: ReflectorMetrics + ?Sized> ReflectorMetrics for Arc<T> {
+    fn set_objects(&self, count: usize) {
+        (**self).set_objects(count);
+    }
+
+    fn last_event(&self, at: SystemTime) {
+        (**self).last_event(at);
+    }
+
+    fn relisted(&self) {
+        (**self).relisted();
+    }
+
+    fn watch_restarted(&self, err: &watcher::Error) {
+        (**self).watch_restarted(err);
+    }
+}
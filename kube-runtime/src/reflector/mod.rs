@@ -1,33 +1,95 @@
 //! Caches objects in memory
 
-mod object_ref;
+pub mod metrics;
+pub mod multi_cluster;
 pub mod store;
 
-pub use self::object_ref::{Extra as ObjectRefExtra, ObjectRef};
 use crate::watcher;
-use futures::{Stream, TryStreamExt};
+use futures::{ready, Stream};
 use kube_client::Resource;
-use std::hash::Hash;
-pub use store::{store, Store};
+use pin_project::pin_project;
+use std::{
+    hash::Hash,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pub use kube_client::core::object_ref::{Extra as ObjectRefExtra, ObjectRef};
+pub use multi_cluster::{multi_cluster_reflector, multi_cluster_reflector_with, MultiClusterStore};
+pub use store::{store, Delta, Store};
 
 /// Caches objects from `watcher::Event`s to a local `Store`
 ///
 /// Keep in mind that the `Store` is just a cache, and may be out of date.
 ///
 /// Note: It is a bad idea to feed a single `reflector` from multiple `watcher`s, since
-/// the whole `Store` will be cleared whenever any of them emits a `Restarted` event.
-pub fn reflector<K, W>(mut writer: store::Writer<K>, stream: W) -> impl Stream<Item = W::Item>
+/// the whole `Store` will be replaced whenever any of them finishes a relist with an `InitDone` event.
+///
+/// Objects that expire from a `writer` with a TTL (see [`Writer::with_ttl`](store::Writer::with_ttl))
+/// are emitted as [`Deleted`](watcher::Event::Deleted) events.
+///
+/// Applied objects are also sent to the subscribers of the `writer` (see [`Writer::subscribe`](store::Writer::subscribe)),
+/// and each event is only emitted once all subscribers have received its object.
+pub fn reflector<K, W>(writer: store::Writer<K>, stream: W) -> impl Stream<Item = W::Item>
 where
     K: Resource + Clone,
     K::DynamicType: Eq + Hash + Clone,
     W: Stream<Item = watcher::Result<watcher::Event<K>>>,
 {
-    stream.inspect_ok(move |event| writer.apply_watcher_event(event))
+    Reflect {
+        stream,
+        writer,
+        pending: None,
+    }
+}
+
+/// Stream returned by [`reflector`]
+#[pin_project]
+struct Reflect<W, K: 'static + Resource>
+where
+    K::DynamicType: Eq + Hash,
+{
+    #[pin]
+    stream: W,
+    writer: store::Writer<K>,
+    /// Event that is emitted once its object has been sent to all subscribers
+    pending: Option<watcher::Event<K>>,
+}
+
+impl<W, K> Stream for Reflect<W, K>
+where
+    K: Resource + Clone,
+    K::DynamicType: Eq + Hash + Clone,
+    W: Stream<Item = watcher::Result<watcher::Event<K>>>,
+{
+    type Item = W::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        if me.pending.is_none() {
+            if let Poll::Ready(deleted) = me.writer.poll_evict(cx) {
+                return Poll::Ready(Some(Ok(deleted)));
+            }
+            match ready!(me.stream.poll_next(cx)) {
+                Some(Ok(event)) => {
+                    me.writer.apply_and_dispatch(&event);
+                    *me.pending = Some(event);
+                }
+                Some(Err(err)) => {
+                    me.writer.observe_error(&err);
+                    return Poll::Ready(Some(Err(err)));
+                }
+                item => return Poll::Ready(item),
+            }
+        }
+        ready!(me.writer.poll_dispatch(cx));
+        Poll::Ready(me.pending.take().map(Ok))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{reflector, store, ObjectRef};
+    use super::{metrics::ReflectorMetrics, reflector, store, ObjectRef};
     use crate::watcher;
     use futures::{stream, StreamExt, TryStreamExt};
     use k8s_openapi::{api::core::v1::ConfigMap, apimachinery::pkg::apis::meta::v1::ObjectMeta};
@@ -35,7 +97,21 @@ mod tests {
         distributions::{Bernoulli, Uniform},
         Rng,
     };
-    use std::collections::{BTreeMap, HashMap};
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::{Arc, Mutex},
+        time::{Duration, SystemTime},
+    };
+
+    fn config_map(name: &str) -> ConfigMap {
+        ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        }
+    }
 
     #[tokio::test]
     async fn reflector_applied_should_add_object() {
@@ -114,6 +190,40 @@ mod tests {
         assert_eq!(store.get(&ObjectRef::from_obj(&cm)), None);
     }
 
+    #[tokio::test]
+    async fn reflector_should_dispatch_applied_objects_to_subscribers() {
+        let mut store_w = store::Writer::default();
+        let first = store_w.subscribe(1);
+        let second = store_w.subscribe(1);
+        let store = first.reader();
+        let (cm_a, cm_b) = (config_map("a"), config_map("b"));
+        let reflect = reflector(
+            store_w,
+            stream::iter(vec![
+                Ok(watcher::Event::Init),
+                Ok(watcher::Event::InitApply(cm_a.clone())),
+                Ok(watcher::Event::InitDone),
+                Ok(watcher::Event::Applied(cm_b.clone())),
+                Ok(watcher::Event::Deleted(cm_a.clone())),
+            ]),
+        )
+        .map(|_| ())
+        .collect::<()>();
+        let names = |objs: Vec<std::sync::Arc<ConfigMap>>| {
+            objs.iter()
+                .map(|cm| cm.metadata.name.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+        // Listed objects are only dispatched once they are in the store
+        let first = first.inspect(|cm| assert!(store.get(&ObjectRef::from_obj(cm.as_ref())).is_some()));
+        let ((), first, second) =
+            futures::join!(reflect, first.collect::<Vec<_>>(), second.collect::<Vec<_>>());
+        assert_eq!(names(first), vec!["a", "b"]);
+        assert_eq!(names(second), vec!["a", "b"]);
+        assert_eq!(store.get(&ObjectRef::from_obj(&cm_a)), None);
+        assert_eq!(store.get(&ObjectRef::from_obj(&cm_b)).as_deref(), Some(&cm_b));
+    }
+
     #[tokio::test]
     async fn reflector_restarted_should_clear_objects() {
         let store_w = store::Writer::default();
@@ -136,7 +246,9 @@ mod tests {
             store_w,
             stream::iter(vec![
                 Ok(watcher::Event::Applied(cm_a.clone())),
-                Ok(watcher::Event::Restarted(vec![cm_b.clone()])),
+                Ok(watcher::Event::Init),
+                Ok(watcher::Event::InitApply(cm_b.clone())),
+                Ok(watcher::Event::InitDone),
             ]),
         )
         .map(|_| ())
@@ -184,4 +296,116 @@ mod tests {
             seen_objects.insert(obj.metadata.name.clone().unwrap(), obj);
         }
     }
+
+    #[tokio::test]
+    async fn reflector_should_report_metrics() {
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<String>>, Mutex<Vec<SystemTime>>);
+        impl ReflectorMetrics for Recorder {
+            fn set_objects(&self, count: usize) {
+                self.0.lock().unwrap().push(format!("objects {}", count));
+            }
+
+            fn last_event(&self, at: SystemTime) {
+                self.1.lock().unwrap().push(at);
+            }
+
+            fn relisted(&self) {
+                self.0.lock().unwrap().push("relisted".to_string());
+            }
+
+            fn watch_restarted(&self, _err: &watcher::Error) {
+                self.0.lock().unwrap().push("restarted".to_string());
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let store_w = store::Writer::default().with_metrics(recorder.clone());
+        reflector(
+            store_w,
+            stream::iter(vec![
+                Ok(watcher::Event::Init),
+                Ok(watcher::Event::InitApply(config_map("a"))),
+                Ok(watcher::Event::InitApply(config_map("b"))),
+                Ok(watcher::Event::InitDone),
+                Err(watcher::Error::TooManyObjects),
+                Ok(watcher::Event::Deleted(config_map("a"))),
+            ]),
+        )
+        .map(|_| ())
+        .collect::<()>()
+        .await;
+        assert_eq!(*recorder.0.lock().unwrap(), vec![
+            "relisted",
+            "objects 2",
+            "restarted",
+            "objects 1",
+        ]);
+        // Every event but the error
+        assert_eq!(recorder.1.lock().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn reflector_should_evict_expired_objects() {
+        tokio::time::pause();
+        let store_w = store::Writer::default().with_ttl(Duration::from_secs(10));
+        let store = store_w.as_reader();
+        let events = stream::iter(vec![
+            Ok(watcher::Event::Applied(config_map("a"))),
+            Ok(watcher::Event::Applied(config_map("b"))),
+        ])
+        .chain(stream::once(async {
+            tokio::time::sleep(Duration::from_secs(6)).await;
+            Ok(watcher::Event::Applied(config_map("b")))
+        }))
+        .chain(stream::pending());
+        let start = tokio::time::Instant::now();
+        let mut reflected = reflector(store_w, events).boxed();
+        for _ in 0..3 {
+            assert!(matches!(
+                reflected.try_next().await,
+                Ok(Some(watcher::Event::Applied(_)))
+            ));
+        }
+        assert_eq!(store.len(), 2);
+
+        let evicted = |event| match event {
+            Ok(Some(watcher::Event::Deleted(obj))) => obj.metadata.name.unwrap(),
+            other => panic!("expected a deletion, got {:?}", other),
+        };
+        assert_eq!(evicted(reflected.try_next().await), "a");
+        assert_eq!(store.state(), vec![Arc::new(config_map("b"))]);
+        // Refreshed 6 seconds after it was first applied
+        assert_eq!(evicted(reflected.try_next().await), "b");
+        assert!(start.elapsed() >= Duration::from_secs(16));
+        assert!(store.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reflector_should_keep_relisted_objects_that_did_not_change() {
+        tokio::time::pause();
+        let cm = config_map("a");
+        let list = || {
+            stream::iter(vec![
+                Ok(watcher::Event::Init),
+                Ok(watcher::Event::InitApply(cm.clone())),
+                Ok(watcher::Event::InitDone),
+            ])
+        };
+        let store_w = store::Writer::default().with_ttl(Duration::from_secs(10));
+        let store = store_w.as_reader();
+        // Relisted every 6 seconds, like a watcher with a relist interval
+        let events = list()
+            .chain(stream::once(tokio::time::sleep(Duration::from_secs(6))).flat_map(move |()| list()))
+            .chain(stream::once(tokio::time::sleep(Duration::from_secs(6))).flat_map(move |()| list()))
+            .chain(stream::pending());
+        let mut reflected = reflector(store_w, events).boxed();
+        for _ in 0..9 {
+            assert!(!matches!(
+                reflected.try_next().await,
+                Ok(Some(watcher::Event::Deleted(_)))
+            ));
+        }
+        assert_eq!(store.state(), vec![Arc::new(cm.clone())]);
+    }
 }
@@ -0,0 +1,321 @@
+//! Reflecting a resource from several clusters into a single store
+use super::{metrics::ReflectorMetrics, reflector, store::Writer, ObjectRef, Store};
+use crate::watcher;
+use derivative::Derivative;
+use futures::{FutureExt, Stream, StreamExt};
+use kube_client::{Api, Resource};
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fmt::Debug,
+    hash::Hash,
+    sync::Arc,
+    time::SystemTime,
+};
+use thiserror::Error;
+
+/// The same cluster name was passed to [`multi_cluster_reflector`] more than once
+#[derive(Debug, Error)]
+#[error("cluster {0} was passed more than once")]
+pub struct DuplicateCluster(pub String);
+
+/// A readable cache of the objects of kind `K` in several clusters, keyed by cluster name and [`ObjectRef`]
+///
+/// Each cluster is cached by its own [`Store`], so relisting one cluster leaves the objects of the others alone.
+/// Cloning will produce a new reference to the same backing stores.
+///
+/// Created by [`multi_cluster_reflector`].
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct MultiClusterStore<K: 'static + Resource>
+where
+    K::DynamicType: Eq + Hash,
+{
+    clusters: Arc<BTreeMap<String, ClusterStore<K>>>,
+}
+
+/// The store and health of a single cluster
+struct ClusterStore<K: 'static + Resource>
+where
+    K::DynamicType: Eq + Hash,
+{
+    store: Store<K>,
+    health: Arc<Mutex<ClusterHealth>>,
+}
+
+/// The health of the watcher of a cluster in a [`multi_cluster_reflector`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClusterHealth {
+    /// Whether the first list of the cluster has been applied to its store
+    pub ready: bool,
+    /// When the store was last updated from an event of the watcher
+    pub last_event: Option<SystemTime>,
+    /// The number of errors since the last event of the watcher
+    pub consecutive_errors: u32,
+    /// The cause of the last error of the watcher, if any
+    pub last_error: Option<watcher::ErrorKind>,
+}
+
+impl ClusterHealth {
+    /// Whether the cluster has been listed, and its watcher has not failed since its last event
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.ready && self.consecutive_errors == 0
+    }
+}
+
+/// Records the [`ClusterHealth`] of a cluster from the activity of its reflector
+struct HealthRecorder(Arc<Mutex<ClusterHealth>>);
+
+impl ReflectorMetrics for HealthRecorder {
+    fn last_event(&self, at: SystemTime) {
+        let mut health = self.0.lock();
+        health.last_event = Some(at);
+        health.consecutive_errors = 0;
+    }
+
+    fn watch_restarted(&self, err: &watcher::Error) {
+        let mut health = self.0.lock();
+        health.consecutive_errors = health.consecutive_errors.saturating_add(1);
+        health.last_error = Some(err.kind());
+    }
+}
+
+impl<K: 'static + Resource + Clone> MultiClusterStore<K>
+where
+    K::DynamicType: Eq + Hash + Clone,
+{
+    /// Creates a store for each of the `clusters`, along with the writers to pass to their reflectors
+    fn with_writers(
+        clusters: impl IntoIterator<Item = String>,
+        dyntype: K::DynamicType,
+    ) -> Result<(Self, Vec<(String, Writer<K>)>), DuplicateCluster> {
+        let mut stores = BTreeMap::new();
+        let mut writers = Vec::new();
+        for cluster in clusters {
+            let entry = match stores.entry(cluster.clone()) {
+                Entry::Vacant(entry) => entry,
+                Entry::Occupied(_) => return Err(DuplicateCluster(cluster)),
+            };
+            let health = Arc::new(Mutex::new(ClusterHealth::default()));
+            let writer = Writer::new(dyntype.clone()).with_metrics(HealthRecorder(health.clone()));
+            entry.insert(ClusterStore {
+                store: writer.as_reader(),
+                health,
+            });
+            writers.push((cluster, writer));
+        }
+        let store = Self {
+            clusters: Arc::new(stores),
+        };
+        Ok((store, writers))
+    }
+
+    /// Retrieves the object referred to by `key` in `cluster`, if it is in the cache
+    ///
+    /// See [`Store::get`] for the caveats of reading from a cache.
+    #[must_use]
+    pub fn get(&self, cluster: &str, key: &ObjectRef<K>) -> Option<Arc<K>> {
+        self.clusters.get(cluster)?.store.get(key)
+    }
+
+    /// Returns the objects of all clusters, along with the name of the cluster of each
+    #[must_use]
+    pub fn state(&self) -> Vec<(String, Arc<K>)> {
+        let mut state = Vec::new();
+        for (cluster, entry) in &*self.clusters {
+            entry
+                .store
+                .for_each(|obj| state.push((cluster.clone(), obj.clone())));
+        }
+        state
+    }
+
+    /// Returns the names of the clusters, in order
+    pub fn clusters(&self) -> impl Iterator<Item = &str> {
+        self.clusters.keys().map(String::as_str)
+    }
+
+    /// Returns the store of a single cluster, such as to pass to a [`Controller`](crate::Controller)
+    #[must_use]
+    pub fn cluster(&self, cluster: &str) -> Option<Store<K>> {
+        Some(self.clusters.get(cluster)?.store.clone())
+    }
+
+    /// Returns the current health of the watcher of `cluster`
+    #[must_use]
+    pub fn health(&self, cluster: &str) -> Option<ClusterHealth> {
+        let entry = self.clusters.get(cluster)?;
+        let mut health = entry.health.lock().clone();
+        health.ready = matches!(entry.store.wait_until_ready().now_or_never(), Some(Ok(())));
+        Some(health)
+    }
+}
+
+/// Watches a kind of resource in several clusters, caching their objects in a single [`MultiClusterStore`]
+///
+/// Each cluster is watched through its own `Api` (and so its own `Client`), named by the caller,
+/// with the same watcher `config`. The returned stream merges the events of all clusters, tagged with
+/// the name of their cluster, and must be polled to keep the store up to date.
+///
+/// The clusters are retried independently, each with the [`watcher::Config::backoff`] (or the default
+/// [`BackoffPolicy`](watcher::BackoffPolicy) if it is unset), so an unreachable cluster does not hold up
+/// the others. Its objects stay in the store meanwhile, and its [`ClusterHealth`] reports the failures.
+/// The [`watcher::Config::resume_from`] and [`watcher::Config::checkpoint`] are ignored,
+/// since each cluster has its own `resourceVersion`s.
+///
+/// # Errors
+///
+/// Fails if the same cluster name is passed more than once, since their objects could not be told apart.
+///
+/// ```no_run
+/// use kube::{api::{Api, ListParams}, config::KubeConfigOptions, Client, Config};
+/// use kube::runtime::reflector::multi_cluster_reflector;
+/// use k8s_openapi::api::apps::v1::Deployment;
+/// use futures::StreamExt;
+/// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut clusters = Vec::new();
+/// for context in ["eu-west", "us-east"] {
+///     let options = KubeConfigOptions {
+///         context: Some(context.to_string()),
+///         ..KubeConfigOptions::default()
+///     };
+///     let client = Client::try_from(Config::from_kubeconfig(&options).await?)?;
+///     clusters.push((context.to_string(), Api::<Deployment>::all(client)));
+/// }
+/// let (store, stream) = multi_cluster_reflector(clusters, ListParams::default())?;
+/// tokio::spawn(stream.for_each(|_| futures::future::ready(())));
+/// for cluster in store.clusters() {
+///     println!("{}: {:?}", cluster, store.health(cluster));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn multi_cluster_reflector<K>(
+    clusters: impl IntoIterator<Item = (String, Api<K>)>,
+    config: impl Into<watcher::Config>,
+) -> Result<
+    (
+        MultiClusterStore<K>,
+        impl Stream<Item = (String, watcher::Result<watcher::Event<K>>)> + Send,
+    ),
+    DuplicateCluster,
+>
+where
+    K: Resource + Clone + DeserializeOwned + Debug + Send + Sync + 'static,
+    K::DynamicType: Eq + Hash + Clone + Default + Send + Sync,
+{
+    multi_cluster_reflector_with(clusters, config, Default::default())
+}
+
+/// Watches a kind of resource in several clusters, caching their objects in a single [`MultiClusterStore`]
+///
+/// Same as [`multi_cluster_reflector`], but accepts a `DynamicType` so it can be used with dynamic resources.
+///
+/// # Errors
+///
+/// Fails if the same cluster name is passed more than once.
+pub fn multi_cluster_reflector_with<K>(
+    clusters: impl IntoIterator<Item = (String, Api<K>)>,
+    config: impl Into<watcher::Config>,
+    dyntype: K::DynamicType,
+) -> Result<
+    (
+        MultiClusterStore<K>,
+        impl Stream<Item = (String, watcher::Result<watcher::Event<K>>)> + Send,
+    ),
+    DuplicateCluster,
+>
+where
+    K: Resource + Clone + DeserializeOwned + Debug + Send + Sync + 'static,
+    K::DynamicType: Eq + Hash + Clone + Send + Sync,
+{
+    let mut config = config.into();
+    if config.backoff.is_none() {
+        config = config.default_backoff();
+    }
+    config.resume_from = None;
+    config.checkpoint = None;
+    let (names, apis): (Vec<_>, Vec<_>) = clusters.into_iter().unzip();
+    let (store, writers) = MultiClusterStore::with_writers(names, dyntype)?;
+    let streams = writers.into_iter().zip(apis).map(|((cluster, writer), api)| {
+        reflector(writer, watcher(api, config.clone()))
+            .map(move |event| (cluster.clone(), event))
+            .boxed()
+    });
+    Ok((store, futures::stream::select_all(streams)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DuplicateCluster, MultiClusterStore};
+    use crate::{
+        reflector::{reflector, ObjectRef},
+        watcher,
+    };
+    use futures::{stream, StreamExt};
+    use k8s_openapi::{api::core::v1::ConfigMap, apimachinery::pkg::apis::meta::v1::ObjectMeta};
+
+    fn config_map(name: &str) -> ConfigMap {
+        ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some("ns".to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn should_keep_clusters_apart() {
+        let (store, writers) =
+            MultiClusterStore::with_writers(["a".to_string(), "b".to_string()], ()).unwrap();
+        let mut writers = writers.into_iter();
+        let (_, writer_a) = writers.next().unwrap();
+        let (_, writer_b) = writers.next().unwrap();
+        reflector(
+            writer_a,
+            stream::iter(vec![
+                Ok(watcher::Event::Init),
+                Ok(watcher::Event::InitApply(config_map("shared"))),
+                Ok(watcher::Event::InitDone),
+            ]),
+        )
+        .for_each(|_| futures::future::ready(()))
+        .await;
+        reflector(
+            writer_b,
+            stream::iter(vec![
+                Ok(watcher::Event::Applied(config_map("shared"))),
+                Err(watcher::Error::TooManyObjects),
+            ]),
+        )
+        .for_each(|_| futures::future::ready(()))
+        .await;
+
+        assert_eq!(store.clusters().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(store.state().len(), 2);
+        let shared = ObjectRef::from_obj(&config_map("shared"));
+        assert!(store.get("a", &shared).is_some());
+        assert!(store.get("c", &shared).is_none());
+
+        let health_a = store.health("a").unwrap();
+        assert!(health_a.is_healthy());
+        assert!(health_a.last_event.is_some());
+        let health_b = store.health("b").unwrap();
+        assert!(!health_b.ready);
+        assert_eq!(health_b.consecutive_errors, 1);
+        assert_eq!(health_b.last_error, Some(watcher::ErrorKind::Other));
+    }
+
+    #[test]
+    fn should_reject_duplicate_clusters() {
+        let clusters = ["a".to_string(), "b".to_string(), "a".to_string()];
+        match MultiClusterStore::<ConfigMap>::with_writers(clusters, ()) {
+            Err(DuplicateCluster(cluster)) => assert_eq!(cluster, "a"),
+            Ok(_) => panic!("duplicate cluster was accepted"),
+        }
+    }
+}
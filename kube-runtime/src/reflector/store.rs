@@ -1,41 +1,271 @@
-use super::ObjectRef;
+use super::{metrics::ReflectorMetrics, ObjectRef};
 use crate::watcher;
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use derivative::Derivative;
+use futures::{
+    channel::{mpsc, oneshot},
+    future::Shared,
+    ready, FutureExt, Stream, StreamExt,
+};
 use kube_client::Resource;
-use parking_lot::RwLock;
-use std::{fmt::Debug, hash::Hash, sync::Arc};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    hash::Hash,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+use thiserror::Error;
+use tokio_util::time::delay_queue::{self, DelayQueue};
 
 type Cache<K> = Arc<RwLock<AHashMap<ObjectRef<K>, Arc<K>>>>;
+/// The objects having each key of an index
+type Index<K> = AHashMap<String, AHashSet<ObjectRef<K>>>;
+/// The indices of a store, by name
+type Indices<K> = Arc<RwLock<AHashMap<String, Index<K>>>>;
+type Indexer<K> = Box<dyn Fn(&K) -> Vec<String> + Send + Sync>;
+type Transform<K> = Box<dyn Fn(&mut K) + Send + Sync>;
+type DeltaSubscribers<K> = Arc<Mutex<Vec<mpsc::Sender<Delta<K>>>>>;
 
 /// A writable Store handle
 ///
 /// This is exclusive since it's not safe to share a single `Store` between multiple reflectors.
-/// In particular, `Restarted` events will clobber the state of other connected reflectors.
-#[derive(Debug, Derivative)]
-#[derivative(Default(bound = "K::DynamicType: Default"))]
+/// In particular, `InitDone` events will clobber the state of other connected reflectors.
+/// To share a single reflector between several consumers instead, see [`Writer::subscribe`].
+#[derive(Derivative)]
+#[derivative(Debug(bound = "K: Debug, K::DynamicType: Debug"))]
 pub struct Writer<K: 'static + Resource>
 where
     K::DynamicType: Eq + Hash,
 {
     store: Cache<K>,
     dyntype: K::DynamicType,
+    /// Objects listed since the watcher restarted, which replace the store once the relist is done
+    buffer: AHashMap<ObjectRef<K>, Arc<K>>,
+    /// Senders to the handles returned by [`Writer::subscribe`]
+    subscribers: Vec<mpsc::Sender<Arc<K>>>,
+    /// Stored objects that are waiting to be sent to the subscribers, in order
+    dispatching: VecDeque<Arc<K>>,
+    /// The index of the next subscriber to send the front of `dispatching` to
+    next_subscriber: usize,
+    /// Signals the readers once the first list has been applied, see [`Store::wait_until_ready`]
+    #[derivative(Debug = "ignore")]
+    ready_tx: Option<oneshot::Sender<()>>,
+    #[derivative(Debug = "ignore")]
+    ready_rx: Shared<oneshot::Receiver<()>>,
+    #[derivative(Debug = "ignore")]
+    indices: Indices<K>,
+    /// Functions computing the keys of each object in the named indices, see [`Writer::with_index`]
+    #[derivative(Debug = "ignore")]
+    indexers: Vec<(String, Indexer<K>)>,
+    /// Applied to every object before it is stored, see [`Writer::with_transform`]
+    #[derivative(Debug = "ignore")]
+    transform: Option<Transform<K>>,
+    /// Senders to the streams returned by [`Store::subscribe_deltas`]
+    #[derivative(Debug = "ignore")]
+    delta_subscribers: DeltaSubscribers<K>,
+    /// Receives the activity of the store, see [`Writer::with_metrics`]
+    #[derivative(Debug = "ignore")]
+    metrics: Option<Box<dyn ReflectorMetrics>>,
+    /// Evicts objects that are not refreshed in time, see [`Writer::with_ttl`]
+    #[derivative(Debug = "ignore")]
+    expiry: Option<Expiry<K>>,
 }
 
-impl<K: 'static + Resource + Clone> Writer<K>
+/// The deadlines by which the objects of a store must be refreshed
+struct Expiry<K: 'static + Resource>
+where
+    K::DynamicType: Eq + Hash,
+{
+    ttl: Duration,
+    queue: DelayQueue<ObjectRef<K>>,
+    keys: AHashMap<ObjectRef<K>, delay_queue::Key>,
+}
+
+impl<K: 'static + Resource> Expiry<K>
 where
     K::DynamicType: Eq + Hash + Clone,
+{
+    /// Restarts the TTL of the object at `key`
+    fn refresh(&mut self, key: &ObjectRef<K>) {
+        match self.keys.get(key) {
+            Some(queue_key) => self.queue.reset(queue_key, self.ttl),
+            None => {
+                let queue_key = self.queue.insert(key.clone(), self.ttl);
+                self.keys.insert(key.clone(), queue_key);
+            }
+        }
+    }
+
+    /// Stops tracking the TTL of the object at `key`
+    fn forget(&mut self, key: &ObjectRef<K>) {
+        if let Some(queue_key) = self.keys.remove(key) {
+            self.queue.remove(&queue_key);
+        }
+    }
+
+    /// Stops tracking the TTLs of the objects for which `keep` returns `false`
+    fn retain(&mut self, mut keep: impl FnMut(&ObjectRef<K>) -> bool) {
+        let queue = &mut self.queue;
+        self.keys.retain(|key, queue_key| {
+            if keep(key) {
+                true
+            } else {
+                queue.remove(queue_key);
+                false
+            }
+        });
+    }
+
+    /// Returns the next object whose TTL ran out
+    fn poll_expired(&mut self, cx: &mut Context<'_>) -> Poll<ObjectRef<K>> {
+        match self.queue.poll_expired(cx) {
+            Poll::Ready(Some(expired)) => {
+                let key = expired.into_inner();
+                self.keys.remove(&key);
+                Poll::Ready(key)
+            }
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<K: 'static + Resource> Default for Writer<K>
+where
+    K::DynamicType: Eq + Hash + Default,
+{
+    fn default() -> Self {
+        Self::new(K::DynamicType::default())
+    }
+}
+
+impl<K: 'static + Resource> Writer<K>
+where
+    K::DynamicType: Eq + Hash,
 {
     /// Creates a new Writer with the specified dynamic type.
     ///
     /// If the dynamic type is default-able (for example when writer is used with
     /// `k8s_openapi` types) you can use `Default` instead.
     pub fn new(dyntype: K::DynamicType) -> Self {
+        let (ready_tx, ready_rx) = oneshot::channel();
         Writer {
             store: Default::default(),
             dyntype,
+            buffer: AHashMap::new(),
+            subscribers: Vec::new(),
+            dispatching: VecDeque::new(),
+            next_subscriber: 0,
+            ready_tx: Some(ready_tx),
+            ready_rx: ready_rx.shared(),
+            indices: Default::default(),
+            indexers: Vec::new(),
+            transform: None,
+            delta_subscribers: Default::default(),
+            metrics: None,
+            expiry: None,
         }
     }
+}
+
+impl<K: 'static + Resource + Clone> Writer<K>
+where
+    K::DynamicType: Eq + Hash + Clone,
+{
+    /// Modifies every object before it is stored, such as to drop fields that are never read from the store
+    ///
+    /// Large fields like `managedFields` often take up most of the memory of a store.
+    /// Only the stored objects are modified (along with those sent to [`Writer::subscribe`]),
+    /// while the events emitted by the [`reflector`](crate::reflector()) are left as they are.
+    /// Indices added by [`Writer::with_index`] see the modified objects.
+    ///
+    /// ```
+    /// use kube::{ResourceExt, runtime::reflector::store::Writer};
+    /// use k8s_openapi::api::core::v1::Secret;
+    /// let writer = Writer::<Secret>::default().with_transform(|secret| {
+    ///     secret.managed_fields_mut().clear();
+    ///     secret.annotations_mut().remove("kubectl.kubernetes.io/last-applied-configuration");
+    ///     secret.data = None;
+    /// });
+    /// ```
+    #[must_use]
+    pub fn with_transform(mut self, transform: impl Fn(&mut K) + Send + Sync + 'static) -> Self {
+        self.transform = Some(Box::new(transform));
+        self
+    }
+
+    /// Reports the activity of the store and its [`reflector`](crate::reflector()) to `metrics`
+    ///
+    /// See [`ReflectorMetrics`] for an example.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: impl ReflectorMetrics + 'static) -> Self {
+        self.metrics = Some(Box::new(metrics));
+        self
+    }
+
+    /// Evicts objects that were not applied again within `ttl`, as if they had been deleted
+    ///
+    /// This suits caches of APIs that may lose track of deletions, such as unreliable aggregated APIs,
+    /// and metadata caches that should only keep recently active objects to bound their memory.
+    /// Objects are only refreshed when they change or are relisted, so a watcher that feeds the store
+    /// must relist periodically, with a [`watcher::Config::relist_interval`] that is comfortably shorter
+    /// than the TTL (including the delay until the watch is restarted). Otherwise objects that don't change
+    /// are evicted even though the watch is healthy.
+    ///
+    /// Evicted objects are emitted by the [`reflector`](crate::reflector()) as [`watcher::Event::Deleted`],
+    /// and sent to [`Store::subscribe_deltas`] as [`Delta::Deleted`].
+    /// Objects are only evicted while the reflector is polled, and the store must be used within a Tokio runtime.
+    ///
+    /// ```
+    /// use kube::{api::ListParams, runtime::{reflector::store::Writer, watcher}};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use std::time::Duration;
+    /// let writer = Writer::<Pod>::default().with_ttl(Duration::from_secs(15 * 60));
+    /// let config = watcher::Config::from(ListParams::default()).relist_interval(Duration::from_secs(5 * 60));
+    /// ```
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.expiry = Some(Expiry {
+            ttl,
+            queue: DelayQueue::new(),
+            keys: AHashMap::new(),
+        });
+        self
+    }
+
+    /// Adds a secondary index to the store, so that its objects can be looked up by [`Store::get_by_index`]
+    ///
+    /// The `indexer` returns the keys of an object in the index named `name`, such as the node of a `Pod`
+    /// or the uids of its owners. Objects can have any number of keys, and the index is kept up to date
+    /// as the objects change.
+    ///
+    /// ```
+    /// use kube::runtime::{reflector::{store::Writer, ObjectRef}, watcher};
+    /// use k8s_openapi::api::core::v1::{Pod, PodSpec};
+    /// let mut writer = Writer::<Pod>::default()
+    ///     .with_index("node", |pod: &Pod| pod.spec.as_ref().and_then(|spec| spec.node_name.clone()));
+    /// let store = writer.as_reader();
+    /// let mut pod = Pod::default();
+    /// pod.metadata.name = Some("web".into());
+    /// pod.spec = Some(PodSpec { node_name: Some("worker-3".into()), ..PodSpec::default() });
+    /// writer.apply_watcher_event(&watcher::Event::Applied(pod));
+    /// assert_eq!(store.get_by_index("node", "worker-3").len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_index<I>(mut self, name: &str, indexer: impl Fn(&K) -> I + Send + Sync + 'static) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.indexers.push((
+            name.to_string(),
+            Box::new(move |obj: &K| indexer(obj).into_iter().collect()),
+        ));
+        self.rebuild_indices();
+        self
+    }
 
     /// Return a read handle to the store
     ///
@@ -45,35 +275,383 @@ where
     pub fn as_reader(&self) -> Store<K> {
         Store {
             store: self.store.clone(),
+            ready_rx: self.ready_rx.clone(),
+            indices: self.indices.clone(),
+            delta_subscribers: self.delta_subscribers.clone(),
+        }
+    }
+
+    /// Subscribes to the objects applied to the store, so that a single reflector can be shared
+    ///
+    /// Every object applied by the [`reflector`](crate::reflector()) that this writer is passed to
+    /// is sent to the returned [`ReflectHandle`] once it is in the store. Objects listed when the watcher
+    /// restarts are sent once the whole list has replaced the store. Deleted objects are not sent.
+    ///
+    /// Up to `buffer` objects are queued for each handle, after which the reflector waits for the handle
+    /// to catch up, so every handle must be polled. Dropped handles are unsubscribed.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams}, Client, runtime::{reflector, watcher, WatchStreamExt}};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::StreamExt;
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let pods: Api<Pod> = Api::all(Client::try_default().await?);
+    /// let mut writer = reflector::store::Writer::default();
+    /// let subscriber = writer.subscribe(256);
+    /// let another_subscriber = writer.subscribe(256);
+    /// // The reflector drives both subscribers
+    /// tokio::spawn(reflector(writer, watcher(pods, ListParams::default())).for_each(|_| async {}));
+    /// subscriber.for_each(|pod| async move { println!("applied {:?}", pod.metadata.name) }).await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe(&mut self, buffer: usize) -> ReflectHandle<K> {
+        let (tx, rx) = mpsc::channel(buffer);
+        self.subscribers.push(tx);
+        ReflectHandle {
+            rx,
+            reader: self.as_reader(),
         }
     }
 
     /// Applies a single watcher event to the store
     pub fn apply_watcher_event(&mut self, event: &watcher::Event<K>) {
+        self.apply(event);
+    }
+
+    /// Replaces the contents of the store with objects persisted before a restart, and marks it ready
+    ///
+    /// The objects are applied like those of a relist, so the transform and indices apply to them too.
+    /// Combine this with [`watcher::Config::resume_from`] to resume watching from the version the objects
+    /// were persisted at, as shown for [`watcher::Checkpoint`].
+    pub fn restore(&mut self, objects: impl IntoIterator<Item = K>) {
+        self.apply(&watcher::Event::Init);
+        for obj in objects {
+            self.apply(&watcher::Event::InitApply(obj));
+        }
+        self.apply(&watcher::Event::InitDone);
+    }
+
+    /// Applies a single watcher event to the store, and queues the objects it stored for the subscribers
+    ///
+    /// Listed objects are only queued once the relist is done and they have been swapped into the store.
+    /// The objects are sent by [`Writer::poll_dispatch`].
+    pub(super) fn apply_and_dispatch(&mut self, event: &watcher::Event<K>) {
+        let applied = self.apply(event);
+        if self.subscribers.is_empty() {
+            return;
+        }
         match event {
+            watcher::Event::InitDone => self.dispatching.extend(self.store.read().values().cloned()),
+            _ => self.dispatching.extend(applied),
+        }
+    }
+
+    /// Sends the objects queued by [`Writer::apply_and_dispatch`] to all subscribers
+    pub(super) fn poll_dispatch(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        while let Some(obj) = self.dispatching.front() {
+            while let Some(tx) = self.subscribers.get_mut(self.next_subscriber) {
+                match ready!(tx.poll_ready(cx)) {
+                    Ok(()) => {
+                        // Only fails if the handle was dropped since, which is noticed by the next dispatch
+                        tx.start_send(obj.clone()).ok();
+                        self.next_subscriber += 1;
+                    }
+                    Err(_) => {
+                        self.subscribers.remove(self.next_subscriber);
+                    }
+                }
+            }
+            self.dispatching.pop_front();
+            self.next_subscriber = 0;
+        }
+        Poll::Ready(())
+    }
+
+    /// Sends the changes to the store to the streams returned by [`Store::subscribe_deltas`]
+    ///
+    /// The `deltas` are only computed if there are any subscribers.
+    /// Closed streams, and streams that have fallen too far behind, are unsubscribed.
+    fn send_deltas(&self, deltas: impl FnOnce() -> Vec<Delta<K>>) {
+        let mut subscribers = self.delta_subscribers.lock();
+        if subscribers.is_empty() {
+            return;
+        }
+        let deltas = deltas();
+        let mut next = 0;
+        while let Some(tx) = subscribers.get_mut(next) {
+            let sent = deltas.iter().all(|delta| match tx.try_send(delta.clone()) {
+                Ok(()) => true,
+                Err(err) => {
+                    if err.is_full() {
+                        tracing::warn!("unsubscribing store subscriber that fell behind by its whole buffer");
+                    }
+                    false
+                }
+            });
+            if sent {
+                next += 1;
+            } else {
+                subscribers.remove(next);
+            }
+        }
+    }
+
+    /// Copies an applied object for the store, applying the transform
+    fn prepare(&self, obj: &K) -> Arc<K> {
+        let mut obj = obj.clone();
+        if let Some(transform) = &self.transform {
+            transform(&mut obj);
+        }
+        Arc::new(obj)
+    }
+
+    /// Applies a single watcher event to the store, returning the object it stored, if any
+    ///
+    /// Listed objects are only buffered until the relist is done, so they are not returned.
+    fn apply(&mut self, event: &watcher::Event<K>) -> Option<Arc<K>> {
+        let applied = match event {
             watcher::Event::Applied(obj) => {
                 let key = ObjectRef::from_obj_with(obj, self.dyntype.clone());
-                let obj = Arc::new(obj.clone());
-                self.store.write().insert(key, obj);
+                let obj = self.prepare(obj);
+                let old = self.store.write().insert(key.clone(), obj.clone());
+                self.update_indices(&key, old.as_deref(), Some(&obj));
+                if let Some(expiry) = &mut self.expiry {
+                    expiry.refresh(&key);
+                }
+                if Delta::is_change(old.as_deref(), &obj) {
+                    self.send_deltas(|| {
+                        vec![Delta::Applied {
+                            old,
+                            new: obj.clone(),
+                        }]
+                    });
+                }
+                Some(obj)
             }
             watcher::Event::Deleted(obj) => {
                 let key = ObjectRef::from_obj_with(obj, self.dyntype.clone());
-                self.store.write().remove(&key);
+                let old = self.store.write().remove(&key);
+                self.update_indices(&key, old.as_deref(), None);
+                if let Some(expiry) = &mut self.expiry {
+                    expiry.forget(&key);
+                }
+                // Objects that were never stored did not change the store
+                if let Some(old) = old {
+                    self.send_deltas(|| vec![Delta::Deleted { old }]);
+                }
+                None
+            }
+            watcher::Event::Init => {
+                self.buffer = AHashMap::new();
+                None
+            }
+            watcher::Event::InitApply(obj) => {
+                let key = ObjectRef::from_obj_with(obj, self.dyntype.clone());
+                let obj = self.prepare(obj);
+                if let Some(expiry) = &mut self.expiry {
+                    expiry.refresh(&key);
+                }
+                self.buffer.insert(key, obj);
+                None
+            }
+            watcher::Event::InitDone => {
+                let mut old = std::mem::replace(&mut *self.store.write(), std::mem::take(&mut self.buffer));
+                self.rebuild_indices();
+                if let Some(expiry) = &mut self.expiry {
+                    let store = self.store.read();
+                    expiry.retain(|key| store.contains_key(key));
+                }
+                self.send_deltas(|| {
+                    let store = self.store.read();
+                    let mut deltas = store
+                        .iter()
+                        .filter_map(|(key, new)| {
+                            let old = old.remove(key);
+                            Delta::is_change(old.as_deref(), new).then(|| Delta::Applied {
+                                old,
+                                new: new.clone(),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    deltas.extend(old.into_values().map(|old| Delta::Deleted { old }));
+                    deltas
+                });
+                if let Some(ready_tx) = self.ready_tx.take() {
+                    // Cannot fail, since the writer keeps a receiver
+                    ready_tx.send(()).ok();
+                }
+                None
+            }
+        };
+        self.observe(event);
+        applied
+    }
+
+    /// Reports an applied event to the metrics
+    fn observe(&self, event: &watcher::Event<K>) {
+        if let Some(metrics) = &self.metrics {
+            match event {
+                watcher::Event::Init => metrics.relisted(),
+                watcher::Event::InitApply(_) => {}
+                watcher::Event::Applied(_) | watcher::Event::Deleted(_) | watcher::Event::InitDone => {
+                    metrics.set_objects(self.store.read().len());
+                }
+            }
+            metrics.last_event(SystemTime::now());
+        }
+    }
+
+    /// Evicts the next object whose TTL ran out, returning its synthetic deletion
+    ///
+    /// See [`Writer::with_ttl`].
+    pub(super) fn poll_evict(&mut self, cx: &mut Context<'_>) -> Poll<watcher::Event<K>> {
+        loop {
+            let key = match &mut self.expiry {
+                Some(expiry) => ready!(expiry.poll_expired(cx)),
+                None => return Poll::Pending,
+            };
+            if self.buffer.contains_key(&key) {
+                // Listed by an ongoing relist, which replaces the stored version once done
+                if let Some(expiry) = &mut self.expiry {
+                    expiry.refresh(&key);
+                }
+                continue;
+            }
+            let old = match self.store.write().remove(&key) {
+                Some(old) => old,
+                None => continue,
+            };
+            self.update_indices(&key, Some(&old), None);
+            self.send_deltas(|| vec![Delta::Deleted { old: old.clone() }]);
+            if let Some(metrics) = &self.metrics {
+                metrics.set_objects(self.store.read().len());
+            }
+            return Poll::Ready(watcher::Event::Deleted(K::clone(&old)));
+        }
+    }
+
+    /// Reports an error of the watcher to the metrics
+    pub(super) fn observe_error(&self, err: &watcher::Error) {
+        if let Some(metrics) = &self.metrics {
+            metrics.watch_restarted(err);
+        }
+    }
+}
+
+impl<K: 'static + Resource> Writer<K>
+where
+    K::DynamicType: Eq + Hash + Clone,
+{
+    /// Moves the object at `key` from the index keys of its `old` version to those of its `new` version
+    fn update_indices(&self, key: &ObjectRef<K>, old: Option<&K>, new: Option<&K>) {
+        if self.indexers.is_empty() {
+            return;
+        }
+        let mut indices = self.indices.write();
+        for (name, indexer) in &self.indexers {
+            let index = indices.entry(name.clone()).or_default();
+            if let Some(old) = old {
+                for value in indexer(old) {
+                    if let Some(keys) = index.get_mut(&value) {
+                        keys.remove(key);
+                        if keys.is_empty() {
+                            index.remove(&value);
+                        }
+                    }
+                }
             }
-            watcher::Event::Restarted(new_objs) => {
-                let new_objs = new_objs
-                    .iter()
-                    .map(|obj| {
-                        (
-                            ObjectRef::from_obj_with(obj, self.dyntype.clone()),
-                            Arc::new(obj.clone()),
-                        )
-                    })
-                    .collect::<AHashMap<_, _>>();
-                *self.store.write() = new_objs;
+            if let Some(new) = new {
+                for value in indexer(new) {
+                    index.entry(value).or_default().insert(key.clone());
+                }
             }
         }
     }
+
+    /// Recomputes all indices from the contents of the store
+    fn rebuild_indices(&self) {
+        if self.indexers.is_empty() {
+            return;
+        }
+        let store = self.store.read();
+        let mut indices = self.indices.write();
+        indices.clear();
+        for (name, indexer) in &self.indexers {
+            let mut index = Index::new();
+            for (key, obj) in store.iter() {
+                for value in indexer(obj) {
+                    index.entry(value).or_default().insert(key.clone());
+                }
+            }
+            indices.insert(name.clone(), index);
+        }
+    }
+}
+
+/// A subscription to the objects applied to a shared store, returned by [`Writer::subscribe`]
+///
+/// This is a [`Stream`] of the applied objects, which can be passed to
+/// [`Controller::for_shared_stream`](crate::Controller::for_shared_stream) along with its [`reader`](ReflectHandle::reader).
+#[must_use = "streams do nothing unless polled"]
+pub struct ReflectHandle<K: 'static + Resource>
+where
+    K::DynamicType: Eq + Hash,
+{
+    rx: mpsc::Receiver<Arc<K>>,
+    reader: Store<K>,
+}
+
+impl<K: 'static + Resource> ReflectHandle<K>
+where
+    K::DynamicType: Eq + Hash,
+{
+    /// Returns a read handle to the shared store
+    #[must_use]
+    pub fn reader(&self) -> Store<K> {
+        self.reader.clone()
+    }
+}
+
+impl<K: 'static + Resource> Stream for ReflectHandle<K>
+where
+    K::DynamicType: Eq + Hash,
+{
+    type Item = Arc<K>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+/// A change to a [`Store`], as sent by [`Store::subscribe_deltas`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Delta<K> {
+    /// An object was created or modified
+    Applied {
+        /// The version that was replaced, or `None` if the object was not stored before
+        old: Option<Arc<K>>,
+        /// The version that is now stored
+        new: Arc<K>,
+    },
+    /// An object was removed, either because it was deleted or because a relist no longer found it
+    Deleted {
+        /// The last stored version of the object
+        old: Arc<K>,
+    },
+}
+
+impl<K: Resource> Delta<K> {
+    /// Whether storing `new` in place of `old` changes the object, going by their `resourceVersion`s
+    ///
+    /// Objects without a `resourceVersion` are always considered changed.
+    fn is_change(old: Option<&K>, new: &K) -> bool {
+        let old_version = old.and_then(|old| old.meta().resource_version.as_ref());
+        match (old_version, &new.meta().resource_version) {
+            (Some(old_version), Some(new_version)) => old_version != new_version,
+            _ => true,
+        }
+    }
 }
 
 /// A readable cache of Kubernetes objects of kind `K`
@@ -89,8 +667,19 @@ where
     K::DynamicType: Hash + Eq,
 {
     store: Cache<K>,
+    #[derivative(Debug = "ignore")]
+    ready_rx: Shared<oneshot::Receiver<()>>,
+    #[derivative(Debug = "ignore")]
+    indices: Indices<K>,
+    #[derivative(Debug = "ignore")]
+    delta_subscribers: DeltaSubscribers<K>,
 }
 
+/// The [`Writer`] of a [`Store`] was dropped before the store became ready
+#[derive(Debug, Error)]
+#[error("writer was dropped before store became ready")]
+pub struct WriterDropped(#[source] oneshot::Canceled);
+
 impl<K: 'static + Clone + Resource> Store<K>
 where
     K::DynamicType: Eq + Hash + Clone,
@@ -121,12 +710,125 @@ where
             .cloned()
     }
 
+    /// Waits until the store has been populated by the first list of its reflector
+    ///
+    /// Until then, the store is empty, so this keeps readers such as webhooks or HTTP handlers
+    /// from serving incomplete results while the reflector starts up.
+    /// Resolves immediately once the store is ready, even while later relists are in progress.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the [`Writer`] was dropped before the store became ready.
+    pub async fn wait_until_ready(&self) -> Result<(), WriterDropped> {
+        self.ready_rx.clone().await.map_err(WriterDropped)
+    }
+
+    /// Retrieve `clone()`s of the entries having `key` in the index named `name`
+    ///
+    /// The index must have been added by [`Writer::with_index`], otherwise no entries are returned.
+    /// Like [`Store::get`], the results may be stale.
+    #[must_use]
+    pub fn get_by_index(&self, name: &str, key: &str) -> Vec<Arc<K>> {
+        let store = self.store.read();
+        let indices = self.indices.read();
+        indices
+            .get(name)
+            .and_then(|index| index.get(key))
+            .into_iter()
+            .flatten()
+            .filter_map(|obj_ref| store.get(obj_ref).cloned())
+            .collect()
+    }
+
     /// Return a full snapshot of the current values
+    ///
+    /// This allocates a copy of the whole store, so prefer [`Store::for_each`], [`Store::find`],
+    /// or [`Store::filter`] when only some of the values are needed.
     #[must_use]
     pub fn state(&self) -> Vec<Arc<K>> {
         let s = self.store.read();
         s.values().cloned().collect()
     }
+
+    /// Calls `f` on each of the current values, without copying the store
+    ///
+    /// The store is locked for reading while iterating, which holds up the reflector,
+    /// so `f` should be quick and must not block.
+    /// `f` must not access the store either, since that deadlocks once the reflector is waiting to write.
+    pub fn for_each(&self, mut f: impl FnMut(&Arc<K>)) {
+        for obj in self.store.read().values() {
+            f(obj);
+        }
+    }
+
+    /// Retrieve a `clone()` of any entry matching `predicate`
+    ///
+    /// The store is locked for reading while searching, so like for [`Store::for_each`],
+    /// `predicate` should be quick and must not access the store.
+    #[must_use]
+    pub fn find(&self, mut predicate: impl FnMut(&K) -> bool) -> Option<Arc<K>> {
+        self.store.read().values().find(|obj| predicate(obj)).cloned()
+    }
+
+    /// Retrieve `clone()`s of all entries matching `predicate`
+    ///
+    /// The store is locked for reading while searching, so like for [`Store::for_each`],
+    /// `predicate` should be quick and must not access the store.
+    #[must_use]
+    pub fn filter(&self, mut predicate: impl FnMut(&K) -> bool) -> Vec<Arc<K>> {
+        self.store
+            .read()
+            .values()
+            .filter(|obj| predicate(obj))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the number of entries in the store
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.store.read().len()
+    }
+
+    /// Returns whether the store has no entries, such as before its first list was applied
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.store.read().is_empty()
+    }
+
+    /// Subscribes to the changes to the store, without running another watcher
+    ///
+    /// Unlike [`Writer::subscribe`], this only needs a reader, and reports deletions and the replaced versions
+    /// of objects too. A relist is reported as an [`Applied`](Delta::Applied) delta for every listed object,
+    /// followed by a [`Deleted`](Delta::Deleted) delta for every object that was not listed again.
+    /// Only changes made after subscribing are sent, so use [`Store::state`] for the objects stored already.
+    /// Objects that are applied again with the same `resourceVersion`, such as by a relist, are not reported.
+    ///
+    /// Up to `buffer` deltas are queued until the stream is polled. A stream that falls further behind is
+    /// unsubscribed (and logged), which ends it, since the reflector does not wait for subscribers.
+    /// Subscribe again and resync from [`Store::state`] if that happens.
+    ///
+    /// ```no_run
+    /// use kube::runtime::reflector::{store::Delta, Store};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::StreamExt;
+    /// # async fn wrapper(store: Store<Pod>) {
+    /// let mut deltas = store.subscribe_deltas(256);
+    /// while let Some(delta) = deltas.next().await {
+    ///     match delta {
+    ///         Delta::Applied { old: None, new } => println!("added {:?}", new.metadata.name),
+    ///         Delta::Applied { old: Some(_), new } => println!("modified {:?}", new.metadata.name),
+    ///         Delta::Deleted { old } => println!("removed {:?}", old.metadata.name),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn subscribe_deltas(&self, buffer: usize) -> impl Stream<Item = Delta<K>> {
+        let (tx, rx) = mpsc::channel(buffer);
+        self.delta_subscribers.lock().push(tx);
+        rx
+    }
 }
 
 
@@ -148,10 +850,23 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{store, Writer};
+    use super::{store, Delta, Writer};
     use crate::{reflector::ObjectRef, watcher};
+    use futures::{FutureExt, StreamExt};
     use k8s_openapi::api::core::v1::ConfigMap;
-    use kube_client::api::ObjectMeta;
+    use kube_client::{api::ObjectMeta, ResourceExt};
+    use std::sync::Arc;
+
+    fn config_map(name: &str) -> ConfigMap {
+        ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some("ns".to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        }
+    }
 
     #[test]
     fn should_allow_getting_namespaced_object_by_namespaced_ref() {
@@ -219,4 +934,224 @@ mod tests {
         let store = store_w.as_reader();
         assert_eq!(store.get(&ObjectRef::from_obj(&nsed_cm)).as_deref(), Some(&cm));
     }
+
+    #[test]
+    fn relist_should_replace_store_when_done() {
+        let (old, kept, new) = (config_map("old"), config_map("kept"), config_map("new"));
+        let (store, mut writer) = store();
+        writer.apply_watcher_event(&watcher::Event::Applied(old.clone()));
+        writer.apply_watcher_event(&watcher::Event::Applied(kept.clone()));
+
+        writer.apply_watcher_event(&watcher::Event::Init);
+        writer.apply_watcher_event(&watcher::Event::InitApply(kept.clone()));
+        writer.apply_watcher_event(&watcher::Event::InitApply(new.clone()));
+        // The store is only replaced once the relist is done
+        assert_eq!(store.get(&ObjectRef::from_obj(&old)).as_deref(), Some(&old));
+        assert_eq!(store.get(&ObjectRef::from_obj(&new)), None);
+
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        assert_eq!(store.get(&ObjectRef::from_obj(&old)), None);
+        assert_eq!(store.get(&ObjectRef::from_obj(&kept)).as_deref(), Some(&kept));
+        assert_eq!(store.get(&ObjectRef::from_obj(&new)).as_deref(), Some(&new));
+    }
+
+    #[test]
+    fn should_be_ready_after_first_list() {
+        let (reader, mut writer) = store::<ConfigMap>();
+        assert!(reader.wait_until_ready().now_or_never().is_none());
+        writer.apply_watcher_event(&watcher::Event::Init);
+        assert!(reader.wait_until_ready().now_or_never().is_none());
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        assert!(matches!(reader.wait_until_ready().now_or_never(), Some(Ok(()))));
+        // Stays ready during later relists
+        writer.apply_watcher_event(&watcher::Event::Init);
+        assert!(matches!(reader.wait_until_ready().now_or_never(), Some(Ok(()))));
+
+        let (reader, writer) = store::<ConfigMap>();
+        drop(writer);
+        assert!(matches!(reader.wait_until_ready().now_or_never(), Some(Err(_))));
+    }
+
+    #[test]
+    fn should_maintain_secondary_indices() {
+        let labeled = |name: &str, app: &str| {
+            let mut cm = config_map(name);
+            cm.labels_mut().insert("app".to_string(), app.to_string());
+            cm
+        };
+        let mut writer =
+            Writer::default().with_index("app", |cm: &ConfigMap| cm.labels().get("app").cloned());
+        let reader = writer.as_reader();
+        let names = |app: &str| {
+            let mut names = reader
+                .get_by_index("app", app)
+                .iter()
+                .map(|cm| cm.name_any())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+        writer.apply_watcher_event(&watcher::Event::Applied(labeled("a", "web")));
+        writer.apply_watcher_event(&watcher::Event::Applied(labeled("b", "web")));
+        writer.apply_watcher_event(&watcher::Event::Applied(labeled("c", "db")));
+        assert_eq!(names("web"), vec!["a", "b"]);
+        assert!(reader.get_by_index("missing", "web").is_empty());
+
+        writer.apply_watcher_event(&watcher::Event::Applied(labeled("b", "db")));
+        writer.apply_watcher_event(&watcher::Event::Deleted(labeled("c", "db")));
+        assert_eq!(names("web"), vec!["a"]);
+        assert_eq!(names("db"), vec!["b"]);
+
+        writer.apply_watcher_event(&watcher::Event::Init);
+        writer.apply_watcher_event(&watcher::Event::InitApply(labeled("d", "web")));
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        assert_eq!(names("web"), vec!["d"]);
+        assert!(names("db").is_empty());
+    }
+
+    #[test]
+    fn should_transform_stored_objects() {
+        let mut cm = config_map("obj");
+        cm.data = Some([("key".to_string(), "value".to_string())].into());
+        let mut writer = Writer::default().with_transform(|cm: &mut ConfigMap| cm.data = None);
+        let reader = writer.as_reader();
+        writer.apply_watcher_event(&watcher::Event::Applied(cm.clone()));
+        let stored = reader.get(&ObjectRef::from_obj(&cm)).unwrap();
+        assert_eq!(stored.metadata, cm.metadata);
+        assert_eq!(stored.data, None);
+    }
+
+    #[test]
+    fn should_scan_without_snapshots() {
+        let (reader, mut writer) = store();
+        assert!(reader.is_empty());
+        for name in ["a", "b", "c"] {
+            writer.apply_watcher_event(&watcher::Event::Applied(config_map(name)));
+        }
+        assert_eq!(reader.len(), 3);
+
+        let mut names = vec![];
+        reader.for_each(|cm| names.push(cm.name_any()));
+        names.sort();
+        assert_eq!(names, vec!["a", "b", "c"]);
+
+        assert_eq!(
+            reader.find(|cm| cm.name_any() == "b").as_deref(),
+            Some(&config_map("b"))
+        );
+        assert_eq!(reader.find(|cm| cm.name_any() == "d"), None);
+        assert_eq!(reader.filter(|cm| cm.name_any() != "b").len(), 2);
+    }
+
+    #[test]
+    fn should_restore_persisted_objects() {
+        let (reader, mut writer) = store();
+        writer.apply_watcher_event(&watcher::Event::Applied(config_map("stale")));
+        writer.restore([config_map("a"), config_map("b")]);
+        assert!(matches!(reader.wait_until_ready().now_or_never(), Some(Ok(()))));
+        assert_eq!(reader.len(), 2);
+        assert_eq!(reader.get(&ObjectRef::from_obj(&config_map("stale"))), None);
+
+        // Events of the resumed watch apply on top of the restored objects
+        writer.apply_watcher_event(&watcher::Event::Deleted(config_map("a")));
+        assert_eq!(reader.state(), vec![Arc::new(config_map("b"))]);
+    }
+
+    #[test]
+    fn should_send_deltas_to_subscribers() {
+        let with_value = |name: &str, value: &str| {
+            let mut cm = config_map(name);
+            cm.data = Some([("key".to_string(), value.to_string())].into());
+            cm
+        };
+        let (reader, mut writer) = store();
+        writer.apply_watcher_event(&watcher::Event::Applied(with_value("a", "1")));
+        let mut deltas = reader.subscribe_deltas(16);
+        let mut next = || deltas.next().now_or_never().flatten();
+        assert_eq!(next(), None);
+
+        writer.apply_watcher_event(&watcher::Event::Applied(with_value("a", "2")));
+        assert_eq!(
+            next(),
+            Some(Delta::Applied {
+                old: Some(Arc::new(with_value("a", "1"))),
+                new: Arc::new(with_value("a", "2")),
+            })
+        );
+        writer.apply_watcher_event(&watcher::Event::Applied(with_value("b", "1")));
+        assert_eq!(
+            next(),
+            Some(Delta::Applied {
+                old: None,
+                new: Arc::new(with_value("b", "1")),
+            })
+        );
+        writer.apply_watcher_event(&watcher::Event::Deleted(with_value("b", "1")));
+        assert_eq!(
+            next(),
+            Some(Delta::Deleted {
+                old: Arc::new(with_value("b", "1")),
+            })
+        );
+        // Deleting an object that was not stored changes nothing
+        writer.apply_watcher_event(&watcher::Event::Deleted(with_value("c", "1")));
+        assert_eq!(next(), None);
+
+        writer.apply_watcher_event(&watcher::Event::Init);
+        writer.apply_watcher_event(&watcher::Event::InitApply(with_value("d", "1")));
+        assert_eq!(next(), None);
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        assert_eq!(
+            next(),
+            Some(Delta::Applied {
+                old: None,
+                new: Arc::new(with_value("d", "1")),
+            })
+        );
+        assert_eq!(
+            next(),
+            Some(Delta::Deleted {
+                old: Arc::new(with_value("a", "2")),
+            })
+        );
+        assert_eq!(next(), None);
+    }
+
+    #[test]
+    fn should_only_send_deltas_of_new_versions() {
+        let versioned = |name: &str, resource_version: &str| {
+            let mut cm = config_map(name);
+            cm.metadata.resource_version = Some(resource_version.to_string());
+            cm
+        };
+        let (reader, mut writer) = store();
+        writer.apply_watcher_event(&watcher::Event::Applied(versioned("a", "1")));
+        let mut deltas = reader.subscribe_deltas(16);
+        let mut next = || deltas.next().now_or_never().flatten();
+
+        writer.apply_watcher_event(&watcher::Event::Applied(versioned("a", "1")));
+        writer.restore([versioned("a", "1")]);
+        assert_eq!(next(), None);
+        writer.apply_watcher_event(&watcher::Event::Applied(versioned("a", "2")));
+        assert_eq!(
+            next(),
+            Some(Delta::Applied {
+                old: Some(Arc::new(versioned("a", "1"))),
+                new: Arc::new(versioned("a", "2")),
+            })
+        );
+    }
+
+    #[test]
+    fn should_unsubscribe_lagging_subscribers() {
+        let (reader, mut writer) = store();
+        let deltas = reader.subscribe_deltas(1);
+        for name in ["a", "b", "c", "d"] {
+            writer.apply_watcher_event(&watcher::Event::Applied(config_map(name)));
+        }
+        // The reflector is not held up, and the stream ends after the deltas it had room for
+        assert_eq!(reader.len(), 4);
+        let received = deltas.collect::<Vec<_>>().now_or_never().unwrap();
+        assert!(!received.is_empty() && received.len() < 4);
+    }
 }
@@ -2,7 +2,8 @@ use std::time::{Duration, Instant};
 
 use backoff::{backoff::Backoff, Clock, SystemClock};
 
-/// A [`Backoff`] wrapper that resets after a fixed duration has elapsed.
+/// A [`Backoff`] wrapper that resets after a fixed duration has elapsed,
+/// or when it is [`reset`](Backoff::reset).
 pub struct ResetTimerBackoff<B, C = SystemClock> {
     backoff: B,
     clock: C,
@@ -44,7 +45,8 @@ impl<B: Backoff, C: Clock> Backoff for ResetTimerBackoff<B, C> {
     }
 
     fn reset(&mut self) {
-        // Do not even bother trying to reset here, since `next_backoff` will take care of this when the timer expires.
+        self.backoff.reset();
+        self.last_backoff = None;
     }
 }
 
@@ -0,0 +1,94 @@
+use crate::watcher::{BufferedEvent, Error, Event};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures::{ready, Stream, TryStream};
+use pin_project::pin_project;
+
+#[pin_project]
+/// Stream returned by the [`buffer_restarts`](super::WatchStreamExt::buffer_restarts) method.
+#[must_use = "streams do nothing unless polled"]
+pub struct EventBuffer<St, K> {
+    #[pin]
+    stream: St,
+    listed: Vec<K>,
+}
+impl<St: TryStream<Ok = Event<K>>, K> EventBuffer<St, K> {
+    pub(super) fn new(stream: St) -> Self {
+        Self {
+            stream,
+            listed: vec![],
+        }
+    }
+}
+impl<St, K> Stream for EventBuffer<St, K>
+where
+    St: Stream<Item = Result<Event<K>, Error>>,
+{
+    type Item = Result<BufferedEvent<K>, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        Poll::Ready(loop {
+            break match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(Ok(Event::Applied(obj))) => Some(Ok(BufferedEvent::Applied(obj))),
+                Some(Ok(Event::Deleted(obj))) => Some(Ok(BufferedEvent::Deleted(obj))),
+                Some(Ok(Event::Init)) => {
+                    me.listed.clear();
+                    continue;
+                }
+                Some(Ok(Event::InitApply(obj))) => {
+                    me.listed.push(obj);
+                    continue;
+                }
+                Some(Ok(Event::InitDone)) => Some(Ok(BufferedEvent::Restarted(std::mem::take(me.listed)))),
+                Some(Err(err)) => Some(Err(err)),
+                None => None,
+            };
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::task::Poll;
+
+    use super::{BufferedEvent, Error, Event, EventBuffer};
+    use futures::{pin_mut, poll, stream, StreamExt};
+
+    #[tokio::test]
+    async fn buffers_listed_objects_until_done() {
+        let data = stream::iter([
+            Ok(Event::Init),
+            Ok(Event::InitApply(0)),
+            Err(Error::TooManyObjects),
+            Ok(Event::Init),
+            Ok(Event::InitApply(1)),
+            Ok(Event::InitApply(2)),
+            Ok(Event::InitDone),
+            Ok(Event::Applied(3)),
+            Ok(Event::Deleted(1)),
+        ]);
+        let rx = EventBuffer::new(data);
+        pin_mut!(rx);
+        assert!(matches!(
+            poll!(rx.next()),
+            Poll::Ready(Some(Err(Error::TooManyObjects)))
+        ));
+        // Objects from before the relist started over are dropped
+        assert!(matches!(
+            poll!(rx.next()),
+            Poll::Ready(Some(Ok(BufferedEvent::Restarted(objs)))) if objs == vec![1, 2]
+        ));
+        assert!(matches!(
+            poll!(rx.next()),
+            Poll::Ready(Some(Ok(BufferedEvent::Applied(3))))
+        ));
+        assert!(matches!(
+            poll!(rx.next()),
+            Poll::Ready(Some(Ok(BufferedEvent::Deleted(1))))
+        ));
+        assert!(matches!(poll!(rx.next()), Poll::Ready(None)));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::watcher::{Error, Event};
 use core::{
+    marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -13,14 +14,14 @@ pub struct EventFlatten<St, K> {
     #[pin]
     stream: St,
     emit_deleted: bool,
-    queue: std::vec::IntoIter<K>,
+    _phantom: PhantomData<fn() -> K>,
 }
 impl<St: TryStream<Ok = Event<K>>, K> EventFlatten<St, K> {
     pub(super) fn new(stream: St, emit_deleted: bool) -> Self {
         Self {
             stream,
-            queue: vec![].into_iter(),
             emit_deleted,
+            _phantom: PhantomData,
         }
     }
 }
@@ -33,11 +34,8 @@ where
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut me = self.project();
         Poll::Ready(loop {
-            if let Some(item) = me.queue.next() {
-                break Some(Ok(item));
-            }
             break match ready!(me.stream.as_mut().poll_next(cx)) {
-                Some(Ok(Event::Applied(obj))) => Some(Ok(obj)),
+                Some(Ok(Event::Applied(obj) | Event::InitApply(obj))) => Some(Ok(obj)),
                 Some(Ok(Event::Deleted(obj))) => {
                     if *me.emit_deleted {
                         Some(Ok(obj))
@@ -45,10 +43,7 @@ where
                         continue;
                     }
                 }
-                Some(Ok(Event::Restarted(objs))) => {
-                    *me.queue = objs.into_iter();
-                    continue;
-                }
+                Some(Ok(Event::Init | Event::InitDone)) => continue,
                 Some(Err(err)) => Some(Err(err)),
                 None => return Poll::Ready(None),
             };
@@ -70,7 +65,10 @@ pub(crate) mod tests {
             Ok(Event::Applied(1)),
             Ok(Event::Deleted(0)),
             Ok(Event::Applied(2)),
-            Ok(Event::Restarted(vec![1, 2])),
+            Ok(Event::Init),
+            Ok(Event::InitApply(1)),
+            Ok(Event::InitApply(2)),
+            Ok(Event::InitDone),
             Err(Error::TooManyObjects),
             Ok(Event::Applied(2)),
         ]);
@@ -80,9 +78,7 @@ pub(crate) mod tests {
         assert!(matches!(poll!(rx.next()), Poll::Ready(Some(Ok(1)))));
         // NB: no Deleted events here
         assert!(matches!(poll!(rx.next()), Poll::Ready(Some(Ok(2)))));
-        // Restart comes through, currently in reverse order
-        // (normally on restart they just come in alphabetical order by name)
-        // this is fine though, alphabetical event order has no functional meaning in watchers
+        // Restarts come through object by object
         assert!(matches!(poll!(rx.next()), Poll::Ready(Some(Ok(1)))));
         assert!(matches!(poll!(rx.next()), Poll::Ready(Some(Ok(2)))));
         // Error passed through
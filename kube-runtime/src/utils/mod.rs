@@ -1,12 +1,16 @@
 //! Helpers for manipulating built-in streams
 
 mod backoff_reset_timer;
+mod event_buffer;
 mod event_flatten;
+mod predicate;
 mod stream_backoff;
 mod watch_ext;
 
 pub use backoff_reset_timer::ResetTimerBackoff;
+pub use event_buffer::EventBuffer;
 pub use event_flatten::EventFlatten;
+pub use predicate::{predicates, Predicate, PredicateEventFilter, PredicateFilter};
 pub use stream_backoff::StreamBackoff;
 pub use watch_ext::WatchStreamExt;
 
@@ -0,0 +1,300 @@
+use crate::{
+    reflector::ObjectRef,
+    watcher::{Error, Event},
+};
+use ahash::AHashMap;
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures::{ready, Stream};
+use kube_client::Resource;
+use pin_project::pin_project;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+fn hash<T: Hash + ?Sized>(t: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A property of objects that is compared by [`predicate_filter`](super::WatchStreamExt::predicate_filter)
+///
+/// Implemented for any `Fn(&K) -> Option<u64>` returning a hash of the property,
+/// such as the built-in [`predicates`].
+/// Objects that the property does not apply to (`None`) are always passed through.
+pub trait Predicate<K> {
+    /// Hashes the property of `obj`, or returns `None` if it does not apply
+    fn hash_property(&self, obj: &K) -> Option<u64>;
+}
+
+impl<K, F: Fn(&K) -> Option<u64>> Predicate<K> for F {
+    fn hash_property(&self, obj: &K) -> Option<u64> {
+        (self)(obj)
+    }
+}
+
+/// Hashes the property of `obj` along with its uid, so that objects that are deleted and recreated under
+/// the same name always pass through
+fn hash_seen<K: Resource, P: Predicate<K>>(predicate: &P, obj: &K) -> Option<u64> {
+    let property = predicate.hash_property(obj)?;
+    Some(hash(&(property, obj.meta().uid.as_deref())))
+}
+
+#[pin_project]
+/// Stream returned by the [`predicate_filter`](super::WatchStreamExt::predicate_filter) method.
+///
+/// A stream of objects does not tell when they are deleted, so a hash is kept for every object ever seen.
+/// Use [`PredicateEventFilter`] for long-lived streams with a lot of churn.
+#[must_use = "streams do nothing unless polled"]
+pub struct PredicateFilter<St, K: Resource, P> {
+    #[pin]
+    stream: St,
+    predicate: P,
+    /// Hash of the property and uid last seen for each object
+    seen: AHashMap<ObjectRef<K>, u64>,
+}
+impl<St, K, P> PredicateFilter<St, K, P>
+where
+    St: Stream<Item = Result<K, Error>>,
+    K: Resource,
+    P: Predicate<K>,
+{
+    pub(super) fn new(stream: St, predicate: P) -> Self {
+        Self {
+            stream,
+            predicate,
+            seen: AHashMap::new(),
+        }
+    }
+}
+impl<St, K, P> Stream for PredicateFilter<St, K, P>
+where
+    St: Stream<Item = Result<K, Error>>,
+    K: Resource,
+    K::DynamicType: Default + Eq + Hash,
+    P: Predicate<K>,
+{
+    type Item = Result<K, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        Poll::Ready(loop {
+            break match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(Ok(obj)) => match hash_seen(me.predicate, &obj) {
+                    Some(value) => match me.seen.insert(ObjectRef::from_obj(&obj), value) {
+                        Some(old) if old == value => continue,
+                        _ => Some(Ok(obj)),
+                    },
+                    None => Some(Ok(obj)),
+                },
+                Some(Err(err)) => Some(Err(err)),
+                None => None,
+            };
+        })
+    }
+}
+
+#[pin_project]
+/// Stream returned by the [`predicate_filter_events`](super::WatchStreamExt::predicate_filter_events) method.
+#[must_use = "streams do nothing unless polled"]
+pub struct PredicateEventFilter<St, K: Resource, P> {
+    #[pin]
+    stream: St,
+    predicate: P,
+    /// Hash of the property and uid last seen for each object
+    seen: AHashMap<ObjectRef<K>, u64>,
+    /// The hashes of the objects listed since the last [`Event::Init`], which replace `seen` once listed
+    relisted: Option<AHashMap<ObjectRef<K>, u64>>,
+}
+impl<St, K, P> PredicateEventFilter<St, K, P>
+where
+    St: Stream<Item = Result<Event<K>, Error>>,
+    K: Resource,
+    P: Predicate<K>,
+{
+    pub(super) fn new(stream: St, predicate: P) -> Self {
+        Self {
+            stream,
+            predicate,
+            seen: AHashMap::new(),
+            relisted: None,
+        }
+    }
+}
+impl<St, K, P> Stream for PredicateEventFilter<St, K, P>
+where
+    St: Stream<Item = Result<Event<K>, Error>>,
+    K: Resource,
+    K::DynamicType: Default + Eq + Hash,
+    P: Predicate<K>,
+{
+    type Item = Result<Event<K>, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        Poll::Ready(loop {
+            let event = match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(Ok(event)) => event,
+                other => break other,
+            };
+            match &event {
+                Event::Applied(obj) => {
+                    if let Some(value) = hash_seen(me.predicate, obj) {
+                        if me.seen.insert(ObjectRef::from_obj(obj), value) == Some(value) {
+                            continue;
+                        }
+                    }
+                }
+                Event::Deleted(obj) => {
+                    me.seen.remove(&ObjectRef::from_obj(obj));
+                }
+                Event::Init => *me.relisted = Some(AHashMap::new()),
+                Event::InitApply(obj) => {
+                    if let Some(value) = hash_seen(me.predicate, obj) {
+                        let obj_ref = ObjectRef::from_obj(obj);
+                        let unchanged = me.seen.get(&obj_ref) == Some(&value);
+                        me.relisted
+                            .get_or_insert_with(AHashMap::new)
+                            .insert(obj_ref, value);
+                        if unchanged {
+                            continue;
+                        }
+                    }
+                }
+                // Objects that were not listed again have been deleted in the meantime
+                Event::InitDone => *me.seen = me.relisted.take().unwrap_or_default(),
+            }
+            break Some(Ok(event));
+        })
+    }
+}
+
+/// Built-in [`Predicate`]s for [`predicate_filter`](super::WatchStreamExt::predicate_filter)
+pub mod predicates {
+    use super::hash;
+    use kube_client::{Resource, ResourceExt};
+
+    /// Passes objects whose `metadata.generation` changed
+    ///
+    /// The generation is only bumped by changes to the spec, so this skips status-only updates.
+    /// Objects without a generation (such as `ConfigMap`s) are always passed through.
+    #[must_use]
+    pub fn generation<K: Resource>(obj: &K) -> Option<u64> {
+        obj.meta().generation.map(|generation| hash(&generation))
+    }
+
+    /// Passes objects whose `metadata.resourceVersion` changed, which skips resyncs of unchanged objects
+    #[must_use]
+    pub fn resource_version<K: Resource>(obj: &K) -> Option<u64> {
+        obj.meta().resource_version.as_ref().map(hash)
+    }
+
+    /// Passes objects whose labels changed
+    #[must_use]
+    pub fn labels<K: Resource>(obj: &K) -> Option<u64> {
+        Some(hash(obj.labels()))
+    }
+
+    /// Passes objects whose annotations changed
+    #[must_use]
+    pub fn annotations<K: Resource>(obj: &K) -> Option<u64> {
+        Some(hash(obj.annotations()))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::task::Poll;
+
+    use super::{predicates, Error, Event, PredicateEventFilter, PredicateFilter};
+    use futures::{pin_mut, poll, stream, StreamExt};
+    use k8s_openapi::api::core::v1::Pod;
+    use kube_client::api::ObjectMeta;
+
+    fn pod(name: &str, uid: &str, generation: i64) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.into()),
+                uid: Some(uid.into()),
+                generation: Some(generation),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn skips_objects_with_unchanged_generation() {
+        let data = stream::iter([
+            Ok(pod("a", "1", 1)),
+            Ok(pod("a", "1", 1)),
+            Ok(pod("b", "2", 1)),
+            Err(Error::TooManyObjects),
+            Ok(pod("a", "1", 2)),
+            // Recreated under the same name
+            Ok(pod("b", "3", 1)),
+        ]);
+        let rx = PredicateFilter::new(data, predicates::generation);
+        pin_mut!(rx);
+
+        let mut passed = vec![];
+        while let Poll::Ready(Some(item)) = poll!(rx.next()) {
+            passed.push(item.map(|pod| (pod.metadata.name.unwrap(), pod.metadata.generation.unwrap())));
+        }
+        assert!(matches!(passed.remove(2), Err(Error::TooManyObjects)));
+        let passed = passed.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(passed, vec![
+            ("a".to_string(), 1),
+            ("b".to_string(), 1),
+            ("a".to_string(), 2),
+            ("b".to_string(), 1),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn forgets_deleted_and_unlisted_objects() {
+        let data = stream::iter([
+            Ok(Event::Applied(pod("a", "1", 1))),
+            Ok(Event::Applied(pod("b", "2", 1))),
+            Ok(Event::Applied(pod("c", "3", 1))),
+            Ok(Event::Deleted(pod("a", "1", 1))),
+            // Passes again, since the deleted object was forgotten
+            Ok(Event::Applied(pod("a", "1", 1))),
+            Ok(Event::Init),
+            Ok(Event::InitApply(pod("a", "1", 1))),
+            Ok(Event::InitApply(pod("b", "2", 2))),
+            Ok(Event::InitDone),
+            // Passes again, since it was not listed
+            Ok(Event::Applied(pod("c", "3", 1))),
+        ]);
+        let rx = PredicateEventFilter::new(data, predicates::generation);
+        pin_mut!(rx);
+
+        let mut passed = vec![];
+        while let Poll::Ready(Some(event)) = poll!(rx.next()) {
+            passed.push(match event.unwrap() {
+                Event::Applied(pod) => format!("applied {}", pod.metadata.name.unwrap()),
+                Event::Deleted(pod) => format!("deleted {}", pod.metadata.name.unwrap()),
+                Event::Init => "init".to_string(),
+                Event::InitApply(pod) => format!("listed {}", pod.metadata.name.unwrap()),
+                Event::InitDone => "init done".to_string(),
+            });
+        }
+        assert_eq!(passed, vec![
+            "applied a",
+            "applied b",
+            "applied c",
+            "deleted a",
+            "applied a",
+            "init",
+            "listed b",
+            "init done",
+            "applied c",
+        ]);
+        assert_eq!(rx.seen.len(), 3);
+    }
+}
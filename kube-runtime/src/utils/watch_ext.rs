@@ -1,8 +1,14 @@
 use crate::{
-    utils::{event_flatten::EventFlatten, stream_backoff::StreamBackoff},
+    utils::{
+        event_buffer::EventBuffer,
+        event_flatten::EventFlatten,
+        predicate::{Predicate, PredicateEventFilter, PredicateFilter},
+        stream_backoff::StreamBackoff,
+    },
     watcher,
 };
 use backoff::backoff::Backoff;
+use kube_client::Resource;
 
 use futures::{Stream, TryStream};
 
@@ -36,5 +42,70 @@ pub trait WatchStreamExt: Stream {
     {
         EventFlatten::new(self, true)
     }
+
+    /// Collect the objects listed when a [`watcher()`] stream restarts into a single event
+    ///
+    /// The [`Init`](watcher::Event::Init), [`InitApply`](watcher::Event::InitApply), and [`InitDone`](watcher::Event::InitDone)
+    /// events of a relist are combined into a [`BufferedEvent::Restarted`](watcher::BufferedEvent::Restarted),
+    /// which holds every listed object in memory.
+    fn buffer_restarts<K>(self) -> EventBuffer<Self, K>
+    where
+        Self: Stream<Item = Result<watcher::Event<K>, watcher::Error>> + Sized,
+    {
+        EventBuffer::new(self)
+    }
+
+    /// Filter out objects from a stream of objects whose property has not changed since it was last seen
+    ///
+    /// This is typically chained after [`applied_objects`](WatchStreamExt::applied_objects),
+    /// so that a controller can skip the reconciles caused by updates it does not care about,
+    /// such as status-only updates when filtering on [`predicates::generation`](crate::utils::predicates::generation).
+    /// Errors are passed through, and the hash of the property is kept for every object seen,
+    /// since deletions cannot be told apart in a stream of objects.
+    /// Use [`predicate_filter_events`](WatchStreamExt::predicate_filter_events) to forget deleted objects instead.
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams}, Client, runtime::{watcher, WatchStreamExt, utils::predicates}};
+    /// use k8s_openapi::api::apps::v1::Deployment;
+    /// use futures::TryStreamExt;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::try_default().await?;
+    ///     let deploys: Api<Deployment> = Api::default_namespaced(client);
+    ///     let changed = watcher(deploys, ListParams::default())
+    ///         .applied_objects()
+    ///         .predicate_filter(predicates::generation);
+    ///     let mut changed = Box::pin(changed);
+    ///     while let Some(deploy) = changed.try_next().await? {
+    ///         println!("spec of {:?} changed", deploy.metadata.name);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn predicate_filter<K, P>(self, predicate: P) -> PredicateFilter<Self, K, P>
+    where
+        Self: Stream<Item = Result<K, watcher::Error>> + Sized,
+        K: Resource,
+        P: Predicate<K>,
+    {
+        PredicateFilter::new(self, predicate)
+    }
+
+    /// Filter out events of a [`watcher()`] stream for objects whose property has not changed
+    ///
+    /// Like [`predicate_filter`](WatchStreamExt::predicate_filter), but the hashes of deleted objects are dropped,
+    /// as are those of objects that are not listed again when the watcher restarts,
+    /// so that memory use follows the number of current objects.
+    /// The [`Applied`](watcher::Event::Applied) and [`InitApply`](watcher::Event::InitApply) events
+    /// of unchanged objects are skipped, so the filtered stream must not be passed to a
+    /// [`reflector`](crate::reflector::reflector), which would miss the skipped objects of a relist.
+    fn predicate_filter_events<K, P>(self, predicate: P) -> PredicateEventFilter<Self, K, P>
+    where
+        Self: Stream<Item = Result<watcher::Event<K>, watcher::Error>> + Sized,
+        K: Resource,
+        P: Predicate<K>,
+    {
+        PredicateEventFilter::new(self, predicate)
+    }
 }
 impl<St: ?Sized> WatchStreamExt for St where St: Stream {}
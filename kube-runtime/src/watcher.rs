@@ -2,18 +2,28 @@
 //!
 //! See [`watcher`] for the primary entry point.
 
-use crate::utils::ResetTimerBackoff;
+use crate::utils::{ResetTimerBackoff, WatchStreamExt};
 use backoff::{backoff::Backoff, ExponentialBackoff};
 use derivative::Derivative;
-use futures::{stream::BoxStream, Stream, StreamExt};
+use futures::{future::BoxFuture, stream::BoxStream, FutureExt, Stream, StreamExt};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
 use kube_client::{
-    api::{ListParams, Resource, ResourceExt, WatchEvent},
-    Api,
+    api::{
+        ApiResource, DynamicObject, ListParams, ObjectList, PartialObjectMeta, Resource, ResourceExt,
+        VersionMatch, WatchEvent, WatchParams,
+    },
+    core::{
+        fields::ParseFieldSelectorError, labels::ParseExpressionError, FieldSelector, NamespaceResourceScope,
+        Selector,
+    },
+    Api, Client,
 };
+use parking_lot::Mutex;
 use serde::de::DeserializeOwned;
 use smallvec::SmallVec;
-use std::{clone::Clone, fmt::Debug, time::Duration};
+use std::{clone::Clone, collections::BTreeSet, fmt::Debug, sync::Arc, time::Duration};
 use thiserror::Error;
+use tokio::time::Instant;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -30,6 +40,110 @@ pub enum Error {
 }
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+impl Error {
+    /// Classifies the cause of the error
+    ///
+    /// ```no_run
+    /// use kube::{api::{Api, ListParams}, Client, runtime::watcher};
+    /// use k8s_openapi::api::core::v1::Pod;
+    /// use futures::StreamExt;
+    /// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let pods: Api<Pod> = Api::all(Client::try_default().await?);
+    /// let mut stream = Box::pin(watcher(pods, ListParams::default()));
+    /// while let Some(event) = stream.next().await {
+    ///     match event {
+    ///         Err(err) if err.kind().is_fatal() => eprintln!("watcher needs attention: {}", err),
+    ///         Err(err) => println!("watcher is recovering from: {}", err),
+    ///         Ok(_) => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::InitialListFailed(err) | Error::WatchStartFailed(err) | Error::WatchFailed(err) => {
+                ErrorKind::from_client_error(err)
+            }
+            Error::WatchError(err) => ErrorKind::from_status(err.code),
+            Error::TooManyObjects => ErrorKind::Other,
+        }
+    }
+}
+
+/// The cause of a watcher [`Error`], as returned by [`Error::kind`]
+///
+/// The [`watcher`] keeps retrying after any error, but only some of them are transient.
+/// Use [`ErrorKind::is_fatal`] to find those that need intervention.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The watch fell behind the history kept by the apiserver (`410 Gone`), so the watcher relists
+    Desync,
+    /// The credentials were rejected (`401 Unauthorized`), or could not be loaded
+    Unauthorized,
+    /// Access was denied, typically by RBAC (`403 Forbidden`)
+    Forbidden,
+    /// The resource is not served (`404 Not Found`), such as when its CRD is not installed
+    NotFound,
+    /// The request was rejected as invalid (`400 Bad Request` or `422 Unprocessable Entity`),
+    /// such as for a malformed selector
+    InvalidRequest,
+    /// The apiserver is throttling requests (`429 Too Many Requests`)
+    Throttled,
+    /// The apiserver failed to handle the request (`5xx`)
+    ServerError,
+    /// The connection to the apiserver failed or was interrupted
+    Network,
+    /// The response of the apiserver could not be decoded
+    Decode,
+    /// Any other error
+    Other,
+}
+
+impl ErrorKind {
+    /// Whether the error persists until something outside of the watcher changes,
+    /// such as its RBAC permissions or the installed CRDs
+    ///
+    /// These are worth alerting on, since retrying alone will not resolve them.
+    #[must_use]
+    pub fn is_fatal(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Unauthorized | ErrorKind::Forbidden | ErrorKind::NotFound | ErrorKind::InvalidRequest
+        )
+    }
+
+    fn from_status(code: u16) -> Self {
+        match code {
+            400 | 422 => ErrorKind::InvalidRequest,
+            401 => ErrorKind::Unauthorized,
+            403 => ErrorKind::Forbidden,
+            404 => ErrorKind::NotFound,
+            410 => ErrorKind::Desync,
+            429 => ErrorKind::Throttled,
+            500..=599 => ErrorKind::ServerError,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    fn from_client_error(err: &kube_client::Error) -> Self {
+        match err {
+            kube_client::Error::Api(err) => ErrorKind::from_status(err.code),
+            kube_client::Error::Auth(_) => ErrorKind::Unauthorized,
+            kube_client::Error::HyperError(_)
+            | kube_client::Error::Service(_)
+            | kube_client::Error::RequestTimeout(_)
+            | kube_client::Error::ReadEvents(_) => ErrorKind::Network,
+            kube_client::Error::SerdeError(_)
+            | kube_client::Error::FromUtf8(_)
+            | kube_client::Error::LinesCodecMaxLineLengthExceeded => ErrorKind::Decode,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Watch events returned from the [`watcher`]
 pub enum Event<K> {
@@ -42,23 +156,47 @@ pub enum Event<K> {
     Deleted(K),
     /// The watch stream was restarted, so `Deleted` events may have been missed
     ///
+    /// This is followed by an [`InitApply`](Event::InitApply) for every listed object and an [`InitDone`](Event::InitDone).
+    /// Use [`WatchStreamExt::buffer_restarts`] to receive them as a single [`BufferedEvent::Restarted`] instead.
+    Init,
+    /// An object was listed after the watch stream was restarted
+    ///
+    /// Objects should be buffered until [`InitDone`](Event::InitDone) to replace the store contents atomically.
+    InitApply(K),
+    /// All objects have been listed after the watch stream was restarted
+    ///
     /// Should be used as a signal to replace the store contents atomically.
     ///
-    /// Any objects that were previously [`Applied`](Event::Applied) but are not listed in this event
+    /// Any objects that were previously [`Applied`](Event::Applied) but not listed since [`Init`](Event::Init)
     /// should be assumed to have been [`Deleted`](Event::Deleted).
+    InitDone,
+}
+
+/// A watcher [`Event`] with the objects listed after a restart collected into a single event
+///
+/// Returned by [`WatchStreamExt::buffer_restarts`], for consumers that prefer to handle relists at once
+/// at the cost of buffering every listed object.
+#[derive(Debug, Clone)]
+pub enum BufferedEvent<K> {
+    /// An object was added or modified, see [`Event::Applied`]
+    Applied(K),
+    /// An object was deleted, see [`Event::Deleted`]
+    Deleted(K),
+    /// The watch stream was restarted, and these are all of the listed objects
+    ///
+    /// Any objects that were previously [`Applied`](BufferedEvent::Applied) but are not listed in this event
+    /// should be assumed to have been [`Deleted`](BufferedEvent::Deleted).
     Restarted(Vec<K>),
 }
 
 impl<K> Event<K> {
     /// Flattens out all objects that were added or modified in the event.
     ///
-    /// `Deleted` objects are ignored, all objects mentioned by `Restarted` events are
-    /// emitted individually.
+    /// `Deleted` objects are ignored, and objects listed after a restart are emitted as they arrive.
     pub fn into_iter_applied(self) -> impl Iterator<Item = K> {
         match self {
-            Event::Applied(obj) => SmallVec::from_buf([obj]),
-            Event::Deleted(_) => SmallVec::new(),
-            Event::Restarted(objs) => SmallVec::from_vec(objs),
+            Event::Applied(obj) | Event::InitApply(obj) => SmallVec::from_buf([obj]),
+            Event::Deleted(_) | Event::Init | Event::InitDone => SmallVec::new(),
         }
         .into_iter()
     }
@@ -70,8 +208,8 @@ impl<K> Event<K> {
     /// deleted objects.
     pub fn into_iter_touched(self) -> impl Iterator<Item = K> {
         match self {
-            Event::Applied(obj) | Event::Deleted(obj) => SmallVec::from_buf([obj]),
-            Event::Restarted(objs) => SmallVec::from_vec(objs),
+            Event::Applied(obj) | Event::Deleted(obj) | Event::InitApply(obj) => SmallVec::from_buf([obj]),
+            Event::Init | Event::InitDone => SmallVec::new(),
         }
         .into_iter()
     }
@@ -96,23 +234,408 @@ impl<K> Event<K> {
     #[must_use]
     pub fn modify(mut self, mut f: impl FnMut(&mut K)) -> Self {
         match &mut self {
-            Event::Applied(obj) | Event::Deleted(obj) => (f)(obj),
-            Event::Restarted(objs) => {
-                for k in objs {
-                    (f)(k)
+            Event::Applied(obj) | Event::Deleted(obj) | Event::InitApply(obj) => (f)(obj),
+            Event::Init | Event::InitDone => {}
+        }
+        self
+    }
+}
+
+/// Configuration of a [`watcher`]
+///
+/// A [`ListParams`] converts into a `Config` without a backoff, so it can be passed to [`watcher`] directly.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Parameters of the LIST and WATCH requests
+    pub list_params: ListParams,
+    /// Delay between retries after errors
+    ///
+    /// When unset, the watcher retries as soon as it is polled after an error,
+    /// leaving any delay to the caller (such as [`WatchStreamExt::backoff`]).
+    pub backoff: Option<BackoffPolicy>,
+    /// Number of objects fetched per request of the initial LIST
+    ///
+    /// When set, the list is paged and its objects are emitted as each page arrives,
+    /// which keeps the watcher from buffering every object of a large kind during a relist.
+    /// When unset, all objects are listed by a single request.
+    pub page_size: Option<u32>,
+    /// Which version of the objects is listed when the watcher (re)starts
+    ///
+    /// This takes precedence over the `resource_version` of `list_params`.
+    pub list_semantic: ListSemantic,
+    /// How the current state of the objects is fetched when the watcher (re)starts
+    pub initial_list_strategy: InitialListStrategy,
+    /// A `resourceVersion` to resume watching from, skipping the initial list
+    ///
+    /// See [`Config::resume_from`].
+    pub resume_from: Option<String>,
+    /// Where the watcher records the `resourceVersion` it has progressed to
+    ///
+    /// See [`Config::checkpoint`].
+    pub checkpoint: Option<Checkpoint>,
+    /// Minimum time between periodic relists of all objects
+    ///
+    /// See [`Config::relist_interval`].
+    pub relist_interval: Option<Duration>,
+    /// Whether watch events with type `BOOKMARK` are requested
+    ///
+    /// See [`Config::disable_bookmarks`].
+    pub bookmarks: bool,
+}
+
+/// The `resourceVersion` that a [`watcher`] has progressed to, for resuming it after a restart
+///
+/// The watcher only records a version once the consumer has polled for the event after it,
+/// so any state built from the events (such as a [`Store`]) is at least as new as the recorded version.
+/// Persist the version before a snapshot of that state, and pass both back on restart
+/// through [`Config::resume_from`] and [`Writer::restore`].
+///
+/// Clones share the same version.
+///
+/// ```no_run
+/// use kube::{api::{Api, ListParams}, Client, runtime::{reflector, watcher}};
+/// use k8s_openapi::api::core::v1::Pod;
+/// use futures::StreamExt;
+/// # async fn load() -> Option<(String, Vec<Pod>)> { None }
+/// # async fn save(resource_version: String, pods: Vec<Pod>) {}
+/// # async fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+/// let pods: Api<Pod> = Api::all(Client::try_default().await?);
+/// let checkpoint = watcher::Checkpoint::default();
+/// let mut config = watcher::Config::from(ListParams::default()).checkpoint(&checkpoint);
+/// let mut writer = reflector::store::Writer::default();
+/// if let Some((resource_version, pods)) = load().await {
+///     writer.restore(pods);
+///     config = config.resume_from(resource_version);
+/// }
+/// let store = writer.as_reader();
+/// let stream = reflector(writer, watcher(pods, config));
+/// tokio::spawn(stream.for_each(|_| futures::future::ready(())));
+/// // Periodically, or before shutting down
+/// if let Some(resource_version) = checkpoint.resource_version() {
+///     let pods = store.state().iter().map(|pod| (**pod).clone()).collect();
+///     save(resource_version, pods).await;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+/// [`Store`]: crate::reflector::Store
+/// [`Writer::restore`]: crate::reflector::store::Writer::restore
+#[derive(Clone, Debug, Default)]
+pub struct Checkpoint(Arc<Mutex<Option<String>>>);
+
+impl Checkpoint {
+    /// The last recorded `resourceVersion`, or `None` if the watcher has not listed or resumed yet
+    #[must_use]
+    pub fn resource_version(&self) -> Option<String> {
+        self.0.lock().clone()
+    }
+
+    /// Records the `resourceVersion` of states that can be resumed from
+    fn record<K: Resource + Clone>(&self, state: &State<K>) {
+        match state {
+            State::InitListed { resource_version } | State::Watching { resource_version, .. } => {
+                let mut recorded = self.0.lock();
+                if recorded.as_deref() != Some(resource_version) {
+                    *recorded = Some(resource_version.clone());
                 }
             }
+            _ => {}
         }
+    }
+}
+
+/// Which version of the objects a [`watcher`] lists when it (re)starts
+///
+/// This trades consistency for apiserver load, like the `resourceVersion` of client-go's `ListWatch`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ListSemantic {
+    /// The most recent version, through a quorum read from etcd
+    ///
+    /// This is consistent but the most expensive for the apiserver, and the default.
+    MostRecent,
+    /// Any version, possibly served from the watch cache of the apiserver
+    ///
+    /// This is much cheaper on large clusters, but the listed objects may be stale,
+    /// and older apiservers ignore the [`Config::page_size`].
+    Any,
+    /// A version at least as new as an explicit `resourceVersion`
+    ///
+    /// This is useful to resume from a version that was seen before, such as one persisted by the caller.
+    NotOlderThan(String),
+}
+
+impl Default for ListSemantic {
+    fn default() -> Self {
+        Self::MostRecent
+    }
+}
+
+/// How a [`watcher`] fetches the current state of the objects when it (re)starts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitialListStrategy {
+    /// LIST the objects, possibly over several pages, and then WATCH from the listed `resourceVersion`
+    ///
+    /// This is supported by all apiservers, and the default.
+    ListWatch,
+    /// Receive the objects as the initial events of a WATCH, ending with a bookmark
+    ///
+    /// This streaming list spares the apiserver from building large LIST responses,
+    /// but requires Kubernetes >= 1.27 with the `WatchList` feature gate.
+    /// Apiservers that reject it are transparently handled with [`ListWatch`](InitialListStrategy::ListWatch)
+    /// for the rest of the watcher's lifetime.
+    ///
+    /// Bookmarks are always requested for the streaming list, and [`Config::page_size`] does not apply.
+    StreamingList,
+}
+
+impl Default for InitialListStrategy {
+    fn default() -> Self {
+        Self::ListWatch
+    }
+}
+
+impl Config {
+    /// Retry after errors with a delay following `policy`
+    #[must_use]
+    pub fn backoff(mut self, policy: BackoffPolicy) -> Self {
+        self.backoff = Some(policy);
+        self
+    }
+
+    /// Retry after errors with the [`BackoffPolicy::default`] parameters
+    #[must_use]
+    pub fn default_backoff(self) -> Self {
+        self.backoff(BackoffPolicy::default())
+    }
+
+    /// Page through the initial LIST, fetching `page_size` objects per request
+    #[must_use]
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Sets which version of the objects is listed when the watcher (re)starts
+    ///
+    /// ```
+    /// use kube::{api::ListParams, runtime::watcher::{Config, ListSemantic}};
+    /// // Relist from the apiserver cache rather than etcd
+    /// let config = Config::from(ListParams::default()).list_semantic(ListSemantic::Any);
+    /// ```
+    #[must_use]
+    pub fn list_semantic(mut self, semantic: ListSemantic) -> Self {
+        self.list_semantic = semantic;
+        self
+    }
+
+    /// Only watch objects whose labels match a typed `selector`
+    ///
+    /// This replaces any label selector of the `list_params`.
+    #[must_use]
+    pub fn labels_from(mut self, selector: &Selector) -> Self {
+        self.list_params = self.list_params.labels_from(selector);
+        self
+    }
+
+    /// Only watch objects whose labels match a [`LabelSelector`], such as the selector of a Deployment
+    ///
+    /// ```
+    /// use kube::{api::ListParams, runtime::watcher::Config};
+    /// use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, LabelSelectorRequirement};
+    /// let selector = LabelSelector {
+    ///     match_expressions: Some(vec![LabelSelectorRequirement {
+    ///         key: "tier".into(),
+    ///         operator: "In".into(),
+    ///         values: None,
+    ///     }]),
+    ///     ..LabelSelector::default()
+    /// };
+    /// assert!(Config::from(ListParams::default()).label_selector(selector).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the selector has an invalid requirement, rather than when the apiserver rejects it.
+    pub fn label_selector(self, selector: LabelSelector) -> Result<Self, ParseExpressionError> {
+        Ok(self.labels_from(&Selector::try_from(selector)?))
+    }
+
+    /// Only watch objects whose fields match a typed `selector`
+    ///
+    /// This replaces any field selector of the `list_params`.
+    #[must_use]
+    pub fn fields_from(mut self, selector: &FieldSelector) -> Self {
+        self.list_params = self.list_params.fields_from(selector);
+        self
+    }
+
+    /// Only watch objects whose fields match a field selector string, such as `spec.nodeName=node-1`
+    ///
+    /// # Errors
+    ///
+    /// Fails if the selector cannot be parsed, rather than when the apiserver rejects it.
+    pub fn fields(self, field_selector: &str) -> Result<Self, ParseFieldSelectorError> {
+        Ok(self.fields_from(&field_selector.parse()?))
+    }
+
+    /// Sets how the current state of the objects is fetched when the watcher (re)starts
+    ///
+    /// ```
+    /// use kube::{api::ListParams, runtime::watcher::{Config, InitialListStrategy}};
+    /// // Use streaming lists where the apiserver supports them
+    /// let config = Config::from(ListParams::default()).initial_list_strategy(InitialListStrategy::StreamingList);
+    /// ```
+    #[must_use]
+    pub fn initial_list_strategy(mut self, strategy: InitialListStrategy) -> Self {
+        self.initial_list_strategy = strategy;
+        self
+    }
+
+    /// Disables watch bookmarks
+    ///
+    /// Bookmarks let the watcher keep its `resourceVersion` up to date while no objects change,
+    /// so that it can resume watching rather than relisting after a disconnect.
+    /// See [`WatchParams::disable_bookmarks`] for details.
+    ///
+    /// Streaming lists always request bookmarks, see [`InitialListStrategy::StreamingList`].
+    #[must_use]
+    pub fn disable_bookmarks(mut self) -> Self {
+        self.bookmarks = false;
+        self
+    }
+
+    /// Resumes watching from a `resourceVersion` that was recorded by a [`Checkpoint`], skipping the initial list
+    ///
+    /// This spares the apiserver from relisting every object when a controller restarts,
+    /// but the watcher emits no [`Event::Init`] or [`Event::InitDone`] until it has to relist,
+    /// so the consumer must restore its state as of the same version first, such as with
+    /// [`Writer::restore`](crate::reflector::store::Writer::restore).
+    /// If the version is too old to resume from, the watcher falls back to listing as usual.
+    #[must_use]
+    pub fn resume_from(mut self, resource_version: impl Into<String>) -> Self {
+        self.resume_from = Some(resource_version.into());
+        self
+    }
+
+    /// Records the `resourceVersion` the watcher has progressed to in `checkpoint`
+    #[must_use]
+    pub fn checkpoint(mut self, checkpoint: &Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint.clone());
+        self
+    }
+
+    /// Relists all objects once `interval` has passed since the last list, even if the watch is healthy
+    ///
+    /// The relist starts when the watch is next restarted after the interval, which the apiserver forces
+    /// every [`ListParams::timeout`] seconds (290 by default), so it may be late by up to that long.
+    /// Relists refresh every object, such as for [`Writer::with_ttl`], and recover from any deletions
+    /// that the watch missed. Without an interval, the watcher only relists when it cannot resume watching.
+    ///
+    /// ```
+    /// use kube::{api::ListParams, runtime::watcher::Config};
+    /// use std::time::Duration;
+    /// let config = Config::from(ListParams::default()).relist_interval(Duration::from_secs(30 * 60));
+    /// ```
+    ///
+    /// [`Writer::with_ttl`]: crate::reflector::store::Writer::with_ttl
+    #[must_use]
+    pub fn relist_interval(mut self, interval: Duration) -> Self {
+        self.relist_interval = Some(interval);
         self
     }
 }
 
+impl Default for Config {
+    fn default() -> Self {
+        Self::from(ListParams::default())
+    }
+}
+
+impl From<ListParams> for Config {
+    #[allow(deprecated)]
+    fn from(list_params: ListParams) -> Self {
+        Self {
+            bookmarks: list_params.bookmarks,
+            list_params,
+            backoff: None,
+            page_size: None,
+            list_semantic: ListSemantic::default(),
+            initial_list_strategy: InitialListStrategy::default(),
+            resume_from: None,
+            checkpoint: None,
+            relist_interval: None,
+        }
+    }
+}
+
+/// An exponential backoff with jitter, which resets once the watcher has been stable for a while
+///
+/// The defaults match [`default_backoff`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackoffPolicy {
+    /// Delay after the first error
+    pub initial_interval: Duration,
+    /// Upper bound of the delay
+    pub max_interval: Duration,
+    /// Factor the delay grows by after every consecutive error
+    pub multiplier: f64,
+    /// Fraction of the delay that is randomized, between `0.0` (none) and `1.0`
+    pub jitter: f64,
+    /// Time without errors after which the delay starts over from `initial_interval`
+    pub reset_after: Duration,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_millis(800),
+            max_interval: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 1.0,
+            reset_after: Duration::from_secs(120),
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Builds a [`Backoff`] following this policy, which never gives up
+    #[must_use]
+    pub fn build(&self) -> ResetTimerBackoff<ExponentialBackoff> {
+        let expo = ExponentialBackoff {
+            initial_interval: self.initial_interval,
+            current_interval: self.initial_interval,
+            max_interval: self.max_interval,
+            randomization_factor: self.jitter,
+            multiplier: self.multiplier,
+            max_elapsed_time: None,
+            ..ExponentialBackoff::default()
+        };
+        ResetTimerBackoff::new(expo, self.reset_after)
+    }
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 /// The internal finite state machine driving the [`watcher`]
 enum State<K: Resource + Clone> {
     /// The Watcher is empty, and the next [`poll`](Stream::poll_next) will start the initial LIST to get all existing objects
     Empty,
+    /// The streaming list is starting, as a WATCH that begins with events for the existing objects
+    InitStream,
+    /// The streaming list is in progress, until the bookmark that marks the end of the initial events
+    InitStreaming {
+        #[derivative(Debug = "ignore")]
+        stream: WatchStream<K>,
+    },
+    /// The initial LIST is in progress, and the next page starts at `continue_token`
+    InitPage { continue_token: Option<String> },
+    /// A page of the initial LIST was received, and its objects are being emitted
+    InitPageListed {
+        #[derivative(Debug = "ignore")]
+        objects: std::vec::IntoIter<K>,
+        continue_token: Option<String>,
+        resource_version: String,
+    },
     /// The initial LIST was successful, so we should move on to starting the actual watch.
     InitListed { resource_version: String },
     /// The watch is in progress, from this point we just return events from the server.
@@ -124,30 +647,210 @@ enum State<K: Resource + Clone> {
     Watching {
         resource_version: String,
         #[derivative(Debug = "ignore")]
-        stream: BoxStream<'static, kube_client::Result<WatchEvent<K>>>,
+        stream: WatchStream<K>,
     },
 }
 
+type WatchStream<K> = BoxStream<'static, kube_client::Result<WatchEvent<K>>>;
+
+/// How the watcher queries the apiserver, for either full objects or their metadata
+trait ApiMode {
+    type Value: Resource + Clone + Debug + Send + 'static;
+
+    fn list<'a>(&'a self, lp: &'a ListParams) -> BoxFuture<'a, kube_client::Result<ObjectList<Self::Value>>>;
+
+    fn watch<'a>(
+        &'a self,
+        wp: &'a WatchParams,
+        version: &'a str,
+    ) -> BoxFuture<'a, kube_client::Result<WatchStream<Self::Value>>>;
+}
+
+/// Watches full objects
+#[derive(Clone)]
+struct FullObject<K> {
+    api: Api<K>,
+}
+
+impl<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static> ApiMode for FullObject<K> {
+    type Value = K;
+
+    fn list<'a>(&'a self, lp: &'a ListParams) -> BoxFuture<'a, kube_client::Result<ObjectList<K>>> {
+        self.api.list(lp).boxed()
+    }
+
+    fn watch<'a>(
+        &'a self,
+        wp: &'a WatchParams,
+        version: &'a str,
+    ) -> BoxFuture<'a, kube_client::Result<WatchStream<K>>> {
+        async move { Ok(self.api.watch(wp, version).await?.boxed()) }.boxed()
+    }
+}
+
+/// Watches only the metadata of objects
+struct MetaOnly<K> {
+    api: Api<K>,
+}
+
+impl<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static> ApiMode for MetaOnly<K> {
+    type Value = PartialObjectMeta<K>;
+
+    fn list<'a>(&'a self, lp: &'a ListParams) -> BoxFuture<'a, kube_client::Result<ObjectList<Self::Value>>> {
+        self.api.list_metadata(lp).boxed()
+    }
+
+    fn watch<'a>(
+        &'a self,
+        wp: &'a WatchParams,
+        version: &'a str,
+    ) -> BoxFuture<'a, kube_client::Result<WatchStream<Self::Value>>> {
+        async move { Ok(self.api.watch_metadata(wp, version).await?.boxed()) }.boxed()
+    }
+}
+
+/// Whether the apiserver rejected a streaming list, because it does not support them
+fn is_streaming_list_rejected(err: &kube_client::Error) -> bool {
+    matches!(err, kube_client::Error::Api(err) if err.code == 400 || err.code == 422)
+}
+
 /// Progresses the watcher a single step, returning (event, state)
 ///
 /// This function should be trampolined: if event == `None`
 /// then the function should be called again until it returns a Some.
-async fn step_trampolined<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
-    api: &Api<K>,
-    list_params: &ListParams,
-    state: State<K>,
-) -> (Option<Result<Event<K>>>, State<K>) {
+///
+/// The `config` falls back to [`InitialListStrategy::ListWatch`] if streaming lists are rejected.
+/// The watch is not resumed but relisted once it ends after `relist_at`, see [`Config::relist_interval`].
+async fn step_trampolined<A: ApiMode>(
+    api: &A,
+    config: &mut Config,
+    relist_at: &mut Option<Instant>,
+    state: State<A::Value>,
+) -> (Option<Result<Event<A::Value>>>, State<A::Value>) {
+    let list_params = &config.list_params;
     match state {
-        State::Empty => match api.list(list_params).await {
-            Ok(list) => (Some(Ok(Event::Restarted(list.items))), State::InitListed {
-                resource_version: list.metadata.resource_version.unwrap(),
+        State::Empty => {
+            *relist_at = config.relist_interval.map(|interval| Instant::now() + interval);
+            let next_state = match config.initial_list_strategy {
+                InitialListStrategy::ListWatch => State::InitPage { continue_token: None },
+                InitialListStrategy::StreamingList => State::InitStream,
+            };
+            (Some(Ok(Event::Init)), next_state)
+        }
+        State::InitStream => {
+            let version = match &config.list_semantic {
+                ListSemantic::MostRecent => "",
+                ListSemantic::Any => "0",
+                ListSemantic::NotOlderThan(rv) => rv.as_str(),
+            };
+            let wp = WatchParams {
+                send_initial_events: true,
+                bookmarks: true,
+                ..WatchParams::from(list_params)
+            };
+            match api.watch(&wp, version).await {
+                Ok(stream) => (None, State::InitStreaming { stream }),
+                Err(err) if is_streaming_list_rejected(&err) => {
+                    config.initial_list_strategy = InitialListStrategy::ListWatch;
+                    (None, State::InitPage { continue_token: None })
+                }
+                Err(err) => (Some(Err(err).map_err(Error::WatchStartFailed)), State::InitStream),
+            }
+        }
+        State::InitStreaming { mut stream } => match stream.next().await {
+            Some(Ok(WatchEvent::Added(obj) | WatchEvent::Modified(obj))) => {
+                (Some(Ok(Event::InitApply(obj))), State::InitStreaming { stream })
+            }
+            // Only sent for objects that were already streamed, which is not expected before the end bookmark
+            Some(Ok(WatchEvent::Deleted(_))) => (None, State::InitStreaming { stream }),
+            Some(Ok(WatchEvent::Bookmark(bm))) if bm.is_initial_events_end() => {
+                (Some(Ok(Event::InitDone)), State::Watching {
+                    resource_version: bm.metadata.resource_version,
+                    stream,
+                })
+            }
+            Some(Ok(WatchEvent::Bookmark(_))) => (None, State::InitStreaming { stream }),
+            Some(Ok(WatchEvent::Error(err))) => {
+                let new_state = if err.code == 410 {
+                    State::Empty
+                } else {
+                    State::InitStreaming { stream }
+                };
+                (Some(Err(err).map_err(Error::WatchError)), new_state)
+            }
+            // Older apiservers reject the parameters of streaming lists in the response to the WATCH
+            Some(Err(err)) if is_streaming_list_rejected(&err) => {
+                config.initial_list_strategy = InitialListStrategy::ListWatch;
+                (None, State::InitPage { continue_token: None })
+            }
+            Some(Err(err)) => (Some(Err(err).map_err(Error::WatchFailed)), State::InitStreaming {
+                stream,
             }),
-            Err(err) => (Some(Err(err).map_err(Error::InitialListFailed)), State::Empty),
+            // The initial events were cut short, so the list has to start over
+            None => (None, State::Empty),
         },
-        State::InitListed { resource_version } => match api.watch(list_params, &resource_version).await {
+        State::InitPage { continue_token } => {
+            // Later pages are served from the snapshot of the first one
+            let (resource_version, version_match) = match (&continue_token, &config.list_semantic) {
+                (Some(_), _) | (None, ListSemantic::MostRecent) => (None, None),
+                (None, ListSemantic::Any) => (Some("0".to_string()), None),
+                (None, ListSemantic::NotOlderThan(rv)) => {
+                    (Some(rv.clone()), Some(VersionMatch::NotOlderThan))
+                }
+            };
+            let page_params = ListParams {
+                limit: config.page_size.or(list_params.limit),
+                continue_token: continue_token.clone(),
+                resource_version,
+                version_match,
+                ..list_params.clone()
+            };
+            match api.list(&page_params).await {
+                Ok(list) => (None, State::InitPageListed {
+                    objects: list.items.into_iter(),
+                    continue_token: list.metadata.continue_,
+                    resource_version: list.metadata.resource_version.unwrap_or_default(),
+                }),
+                // The continue token has expired, so the list has to start over
+                Err(kube_client::Error::Api(err)) if err.code == 410 => (
+                    Some(Err(Error::InitialListFailed(kube_client::Error::Api(err)))),
+                    State::Empty,
+                ),
+                Err(err) => (Some(Err(Error::InitialListFailed(err))), State::InitPage {
+                    continue_token,
+                }),
+            }
+        }
+        State::InitPageListed {
+            mut objects,
+            continue_token,
+            resource_version,
+        } => match objects.next() {
+            Some(obj) => (Some(Ok(Event::InitApply(obj))), State::InitPageListed {
+                objects,
+                continue_token,
+                resource_version,
+            }),
+            None => match continue_token.filter(|token| !token.is_empty()) {
+                Some(token) => (None, State::InitPage {
+                    continue_token: Some(token),
+                }),
+                None => (Some(Ok(Event::InitDone)), State::InitListed { resource_version }),
+            },
+        },
+        State::InitListed { resource_version } => match api
+            .watch(
+                &WatchParams {
+                    bookmarks: config.bookmarks,
+                    ..WatchParams::from(list_params)
+                },
+                &resource_version,
+            )
+            .await
+        {
             Ok(stream) => (None, State::Watching {
                 resource_version,
-                stream: stream.boxed(),
+                stream,
             }),
             Err(err) => (
                 Some(Err(err).map_err(Error::WatchStartFailed)),
@@ -192,19 +895,25 @@ async fn step_trampolined<K: Resource + Clone + DeserializeOwned + Debug + Send
                 resource_version,
                 stream,
             }),
+            None if relist_at.map_or(false, |relist_at| relist_at <= Instant::now()) => (None, State::Empty),
             None => (None, State::InitListed { resource_version }),
         },
     }
 }
 
 /// Trampoline helper for `step_trampolined`
-async fn step<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
-    api: &Api<K>,
-    list_params: &ListParams,
-    mut state: State<K>,
-) -> (Result<Event<K>>, State<K>) {
+async fn step<A: ApiMode>(
+    api: &A,
+    config: &mut Config,
+    relist_at: &mut Option<Instant>,
+    mut state: State<A::Value>,
+) -> (Result<Event<A::Value>>, State<A::Value>) {
     loop {
-        match step_trampolined(api, list_params, state).await {
+        // Everything emitted before this step has been consumed
+        if let Some(checkpoint) = &config.checkpoint {
+            checkpoint.record(&state);
+        }
+        match step_trampolined(api, config, relist_at, state).await {
             (Some(result), new_state) => return (result, new_state),
             (None, new_state) => state = new_state,
         }
@@ -216,7 +925,7 @@ async fn step<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
 /// Compared to [`Api::watch`], this automatically tries to recover the stream upon errors.
 ///
 /// Errors from the underlying watch are propagated, after which the stream will go into recovery mode on the next poll.
-/// You can apply your own backoff by not polling the stream for a duration after errors.
+/// Set a [`Config::backoff`] to delay the recovery, or apply your own backoff by not polling the stream for a duration after errors.
 /// Keep in mind that some [`TryStream`](futures::TryStream) combinators (such as
 /// [`try_for_each`](futures::TryStreamExt::try_for_each) and [`try_concat`](futures::TryStreamExt::try_concat))
 /// will terminate eagerly as soon as they receive an [`Err`].
@@ -238,7 +947,7 @@ async fn step<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
 ///
 ///     watcher(pods, ListParams::default()).applied_objects()
 ///         .try_for_each(|p| async move {
-///          println!("Applied: {}", p.name());
+///          println!("Applied: {}", p.name_any());
 ///             Ok(())
 ///         })
 ///         .await?;
@@ -252,23 +961,95 @@ async fn step<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
 /// # Recovery
 ///
 /// The stream will attempt to be recovered on the next poll after an [`Err`] is returned.
-/// This will normally happen immediately, but you can use [`StreamBackoff`](crate::utils::StreamBackoff)
-/// to introduce an artificial delay. [`default_backoff`] returns a suitable default set of parameters.
+/// This will happen immediately unless the [`Config`] has a [`BackoffPolicy`], in which case the watcher
+/// waits for an exponentially growing and randomized delay first, which starts over once it has been stable for a while.
+/// Alternatively, [`StreamBackoff`](crate::utils::StreamBackoff) can introduce an artificial delay.
+/// [`default_backoff`] returns a suitable default set of parameters.
+///
+/// ```no_run
+/// use kube::{api::{Api, ListParams}, Client, runtime::{watcher, WatchStreamExt}};
+/// use k8s_openapi::api::core::v1::Pod;
+/// use futures::TryStreamExt;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+///     let config = watcher::Config::from(ListParams::default()).default_backoff();
+///     let mut stream = Box::pin(watcher(pods, config).applied_objects());
+///     while let Some(pod) = stream.try_next().await? {
+///         println!("Applied: {:?}", pod.metadata.name);
+///     }
+///     Ok(())
+/// }
+/// ```
 ///
 /// If the watch connection is interrupted, then `watcher` will attempt to restart the watch using the last
 /// [resource version](https://kubernetes.io/docs/reference/using-api/api-concepts/#efficient-detection-of-changes)
 /// that we have seen on the stream. If this is successful then the stream is simply resumed from where it left off.
 /// If this fails because the resource version is no longer valid then we start over with a new stream, starting with
-/// an [`Event::Restarted`]. The internals mechanics of recovery should be considered an implementation detail.
+/// an [`Event::Init`]. The internals mechanics of recovery should be considered an implementation detail.
 pub fn watcher<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
     api: Api<K>,
-    list_params: ListParams,
+    config: impl Into<Config>,
 ) -> impl Stream<Item = Result<Event<K>>> + Send {
+    watch_with_mode(FullObject { api }, config.into())
+}
+
+/// Watches the metadata of a Kubernetes Resource for changes continuously
+///
+/// This behaves like [`watcher`], but only requests the [`PartialObjectMeta`] of the objects.
+/// Controllers that only act on names, labels, annotations, or owner references
+/// can use it to avoid transferring and decoding the full objects, and it can feed a [`reflector`]
+/// to cache only the metadata.
+///
+/// ```no_run
+/// use kube::{api::{Api, ListParams, ResourceExt}, Client, runtime::{metadata_watcher, WatchStreamExt}};
+/// use k8s_openapi::api::core::v1::Pod;
+/// use futures::TryStreamExt;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let pods: Api<Pod> = Api::namespaced(client, "apps");
+///     let mut stream = Box::pin(metadata_watcher(pods, ListParams::default()).applied_objects());
+///     while let Some(meta) = stream.try_next().await? {
+///         println!("Applied: {} with labels {:?}", meta.name_any(), meta.labels());
+///     }
+///     Ok(())
+/// }
+/// ```
+/// [`reflector`]: super::reflector::reflector
+pub fn metadata_watcher<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
+    api: Api<K>,
+    config: impl Into<Config>,
+) -> impl Stream<Item = Result<Event<PartialObjectMeta<K>>>> + Send {
+    watch_with_mode(MetaOnly { api }, config.into())
+}
+
+fn watch_with_mode<A: ApiMode + Send + Sync + 'static>(
+    api: A,
+    config: Config,
+) -> impl Stream<Item = Result<Event<A::Value>>> + Send {
+    let backoff = config.backoff.as_ref().map(BackoffPolicy::build);
+    let state = match &config.resume_from {
+        Some(resource_version) => State::InitListed {
+            resource_version: resource_version.clone(),
+        },
+        None => State::Empty,
+    };
+    // The restored objects count as listed when resuming
+    let relist_at = config.relist_interval.map(|interval| Instant::now() + interval);
     futures::stream::unfold(
-        (api, list_params, State::Empty),
-        |(api, list_params, state)| async {
-            let (event, state) = step(&api, &list_params, state).await;
-            Some((event, (api, list_params, state)))
+        (api, config, relist_at, state, backoff, None),
+        |(api, mut config, mut relist_at, state, mut backoff, delay)| async move {
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+            }
+            let (event, state) = step(&api, &mut config, &mut relist_at, state).await;
+            let delay = match (&event, &mut backoff) {
+                (Err(_), Some(backoff)) => backoff.next_backoff(),
+                _ => None,
+            };
+            Some((event, (api, config, relist_at, state, backoff, delay)))
         },
     )
 }
@@ -278,7 +1059,7 @@ pub fn watcher<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
 /// Emits `None` if the object is deleted (or not found), and `Some` if an object is updated (or created/found).
 ///
 /// Compared to [`watcher`], `watch_object` does not return return [`Event`], since there is no need for an atomic
-/// [`Event::Restarted`] when only one object is covered anyway.
+/// restart when only one object is covered anyway.
 pub fn watch_object<K: Resource + Clone + DeserializeOwned + Debug + Send + 'static>(
     api: Api<K>,
     name: &str,
@@ -287,19 +1068,248 @@ pub fn watch_object<K: Resource + Clone + DeserializeOwned + Debug + Send + 'sta
         field_selector: Some(format!("metadata.name={}", name)),
         ..Default::default()
     })
+    .buffer_restarts()
     .map(|event| match event? {
-        Event::Deleted(_) => Ok(None),
+        BufferedEvent::Deleted(_) => Ok(None),
         // We're filtering by object name, so getting more than one object means that either:
         // 1. The apiserver is accepting multiple objects with the same name, or
         // 2. The apiserver is ignoring our query
         // In either case, the K8s apiserver is broken and our API will return invalid data, so
         // we had better bail out ASAP.
-        Event::Restarted(objs) if objs.len() > 1 => Err(Error::TooManyObjects),
-        Event::Restarted(mut objs) => Ok(objs.pop()),
-        Event::Applied(obj) => Ok(Some(obj)),
+        BufferedEvent::Restarted(objs) if objs.len() > 1 => Err(Error::TooManyObjects),
+        BufferedEvent::Restarted(mut objs) => Ok(objs.pop()),
+        BufferedEvent::Applied(obj) => Ok(Some(obj)),
     })
 }
 
+/// Watches several kinds of resources as a single stream, tagging each event with the [`ApiResource`] it belongs to
+///
+/// Each kind is watched across all namespaces by its own [`watcher`] using the same `config`.
+/// Every watcher backs off by itself following the [`Config::backoff`] (or the default [`BackoffPolicy`]
+/// if unset), so a kind that keeps failing (for example because its CRD was removed, or access to it
+/// is forbidden) is retried independently without delaying the others.
+/// Errors are emitted along with the kind that failed.
+/// The [`Config::resume_from`] and [`Config::checkpoint`] are ignored, since the kinds progress independently.
+///
+/// This is suited to generic agents that track many kinds at once, such as audit or backup tools.
+///
+/// ```no_run
+/// use kube::{api::ListParams, discovery::{verbs, Discovery}, Client};
+/// use kube::runtime::watcher::{self, multi_watcher, Event};
+/// use futures::StreamExt;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let discovery = Discovery::new(client.clone()).run().await?;
+///     let resources = discovery
+///         .api_resources()
+///         .filter(|(_, caps)| caps.supports_operation(verbs::LIST) && caps.supports_operation(verbs::WATCH))
+///         .map(|(ar, _)| ar);
+///     let config = watcher::Config::from(ListParams::default()).page_size(500);
+///     let mut events = multi_watcher(client, resources, config).boxed();
+///     while let Some((ar, event)) = events.next().await {
+///         match event {
+///             Ok(Event::Applied(obj)) => println!("{} {:?} applied", ar.kind, obj.metadata.name),
+///             Ok(_) => {}
+///             Err(err) => println!("watching {} failed: {}", ar.kind, err),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn multi_watcher(
+    client: Client,
+    resources: impl IntoIterator<Item = ApiResource>,
+    config: impl Into<Config>,
+) -> impl Stream<Item = (ApiResource, Result<Event<DynamicObject>>)> + Send {
+    let mut config = config.into();
+    if config.backoff.is_none() {
+        config = config.default_backoff();
+    }
+    // The kinds progress independently, so no single version covers all of them
+    config.resume_from = None;
+    config.checkpoint = None;
+    futures::stream::select_all(resources.into_iter().map(move |ar| {
+        let api = Api::<DynamicObject>::all_with(client.clone(), &ar);
+        watcher(api, config.clone())
+            .map(move |event| (ar.clone(), event))
+            .boxed()
+    }))
+}
+
+/// Watches a namespaced Kubernetes Resource in a set of namespaces, as a single stream
+///
+/// This suits controllers that are only granted access to a handful of namespaces,
+/// so that neither [`Api::namespaced`] nor [`Api::all`] fits.
+/// Each namespace is watched by its own [`watcher`], and their events are merged such that
+/// the stream can feed a [`reflector`] like the stream of a single watcher:
+///
+/// - The initial lists of all namespaces are emitted between a single [`Event::Init`] and [`Event::InitDone`],
+///   and the namespaces only resume watching once all of them have been listed.
+/// - If any namespace has to be listed again, then all of them are, since the events cannot express
+///   replacing the objects of a single namespace.
+///
+/// The [`Config::backoff`] is shared by all namespaces, so an error in any namespace delays the whole stream.
+/// The [`Config::resume_from`] and [`Config::checkpoint`] are ignored, since the namespaces progress independently.
+/// Duplicate namespaces are watched once, and the stream ends immediately if there are no namespaces.
+///
+/// ```no_run
+/// use kube::{api::{Api, ListParams}, Client, runtime::{watcher, WatchStreamExt}};
+/// use kube::runtime::watcher::multi_namespace_watcher;
+/// use k8s_openapi::api::core::v1::ConfigMap;
+/// use futures::TryStreamExt;
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = Client::try_default().await?;
+///     let config = watcher::Config::from(ListParams::default()).default_backoff();
+///     let stream = multi_namespace_watcher::<ConfigMap>(client, ["team-a", "team-b"], config);
+///     let mut stream = Box::pin(stream.applied_objects());
+///     while let Some(cm) = stream.try_next().await? {
+///         println!("Applied: {:?} in {:?}", cm.metadata.name, cm.metadata.namespace);
+///     }
+///     Ok(())
+/// }
+/// ```
+/// [`reflector`]: super::reflector::reflector
+/// [`Api::namespaced`]: kube_client::Api::namespaced
+/// [`Api::all`]: kube_client::Api::all
+pub fn multi_namespace_watcher<K>(
+    client: Client,
+    namespaces: impl IntoIterator<Item = impl AsRef<str>>,
+    config: impl Into<Config>,
+) -> impl Stream<Item = Result<Event<K>>> + Send
+where
+    K: Resource<Scope = NamespaceResourceScope> + Clone + DeserializeOwned + Debug + Send + 'static,
+    K::DynamicType: Default,
+{
+    let apis = namespaces
+        .into_iter()
+        .map(|ns| ns.as_ref().to_string())
+        .collect::<BTreeSet<_>>()
+        .iter()
+        .map(|ns| FullObject {
+            api: Api::namespaced(client.clone(), ns),
+        })
+        .collect();
+    watch_namespaces_with_mode(apis, config.into())
+}
+
+fn watch_namespaces_with_mode<A: ApiMode + Clone + Send + Sync + 'static>(
+    apis: Vec<A>,
+    mut config: Config,
+) -> impl Stream<Item = Result<Event<A::Value>>> + Send {
+    // Each namespace retries immediately when polled, leaving the delay to the merged stream
+    let backoff = config.backoff.take().as_ref().map(BackoffPolicy::build);
+    // The namespaces progress independently, so no single version covers all of them
+    config.resume_from = None;
+    config.checkpoint = None;
+    let state = NamespaceWatchers::new(apis, config);
+    futures::stream::unfold(
+        (state, backoff, None),
+        |(mut state, mut backoff, delay)| async move {
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+            }
+            let event = state.next().await?;
+            let delay = match (&event, &mut backoff) {
+                (Err(_), Some(backoff)) => backoff.next_backoff(),
+                _ => None,
+            };
+            Some((event, (state, backoff, delay)))
+        },
+    )
+}
+
+/// How far the [`watcher`] of a namespace in a [`multi_namespace_watcher`] is through the merged initial list
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NamespacePhase {
+    /// The watcher has not started listing yet
+    Pending,
+    /// The watcher is listing its namespace
+    Listing,
+    /// The watcher has listed its namespace, and waits for the other namespaces
+    Listed,
+}
+
+/// The per-namespace watchers merged by a [`multi_namespace_watcher`]
+struct NamespaceWatchers<A: ApiMode> {
+    apis: Vec<A>,
+    config: Config,
+    watchers: Vec<BoxStream<'static, Result<Event<A::Value>>>>,
+    phases: Vec<NamespacePhase>,
+    /// Whether an [`Event::Init`] has to be emitted before any other event
+    init_pending: bool,
+}
+
+impl<A: ApiMode + Clone + Send + Sync + 'static> NamespaceWatchers<A> {
+    fn new(apis: Vec<A>, config: Config) -> Self {
+        let watchers = apis
+            .iter()
+            .map(|api| watch_with_mode(api.clone(), config.clone()).boxed())
+            .collect();
+        let phases = vec![NamespacePhase::Pending; apis.len()];
+        Self {
+            apis,
+            config,
+            watchers,
+            phases,
+            init_pending: true,
+        }
+    }
+
+    /// Restarts the watchers of all namespaces but `listing`, which has already started listing again
+    fn relist_all(&mut self, listing: usize) {
+        for (i, api) in self.apis.iter().enumerate() {
+            if i == listing {
+                self.phases[i] = NamespacePhase::Listing;
+            } else {
+                self.watchers[i] = watch_with_mode(api.clone(), self.config.clone()).boxed();
+                self.phases[i] = NamespacePhase::Pending;
+            }
+        }
+    }
+
+    /// Returns the next merged event, or `None` if there are no namespaces
+    async fn next(&mut self) -> Option<Result<Event<A::Value>>> {
+        if self.watchers.is_empty() {
+            return None;
+        }
+        loop {
+            if self.init_pending {
+                self.init_pending = false;
+                return Some(Ok(Event::Init));
+            }
+            // While listing, namespaces that are done wait for the others so that their watch events
+            // are not interleaved with the initial list
+            let listing = self.phases.iter().any(|phase| *phase != NamespacePhase::Listed);
+            let nexts = self
+                .watchers
+                .iter_mut()
+                .zip(&self.phases)
+                .enumerate()
+                .filter(|(_, (_, phase))| !listing || **phase != NamespacePhase::Listed)
+                .map(|(i, (watcher, _))| watcher.next().map(move |event| (i, event)));
+            let ((i, event), _, _) = futures::future::select_all(nexts).await;
+            match event? {
+                Ok(Event::Init) if self.phases[i] == NamespacePhase::Pending => {
+                    self.phases[i] = NamespacePhase::Listing;
+                }
+                Ok(Event::Init) => {
+                    self.relist_all(i);
+                    self.init_pending = true;
+                }
+                Ok(Event::InitDone) => {
+                    self.phases[i] = NamespacePhase::Listed;
+                    if self.phases.iter().all(|phase| *phase == NamespacePhase::Listed) {
+                        return Some(Ok(Event::InitDone));
+                    }
+                }
+                event => return Some(event),
+            }
+        }
+    }
+}
+
 /// Default watch [`Backoff`] inspired by Kubernetes' client-go.
 ///
 /// Note that the exact parameters used herein should not be considered stable.
@@ -308,13 +1318,458 @@ pub fn watch_object<K: Resource + Clone + DeserializeOwned + Debug + Send + 'sta
 /// for more details.
 #[must_use]
 pub fn default_backoff() -> impl Backoff + Send + Sync {
-    let expo = backoff::ExponentialBackoff {
-        initial_interval: Duration::from_millis(800),
-        max_interval: Duration::from_secs(30),
-        randomization_factor: 1.0,
-        multiplier: 2.0,
-        max_elapsed_time: None,
-        ..ExponentialBackoff::default()
+    BackoffPolicy::default().build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        watch_namespaces_with_mode, watch_with_mode, ApiMode, BackoffPolicy, Checkpoint, Config, Error,
+        ErrorKind, Event, InitialListStrategy, MetaOnly, WatchStream,
+    };
+    use backoff::backoff::Backoff;
+    use futures::{future::BoxFuture, stream, FutureExt, StreamExt};
+    use k8s_openapi::{
+        api::core::v1::ConfigMap,
+        apimachinery::pkg::apis::meta::v1::{ListMeta, ObjectMeta},
     };
-    ResetTimerBackoff::new(expo, Duration::from_secs(120))
+    use kube_client::{
+        api::{ListParams, ObjectList, WatchEvent, WatchParams},
+        error::ErrorResponse,
+        Api, Client,
+    };
+    use parking_lot::Mutex;
+    use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+    /// Serves canned LIST and WATCH responses, and records the requests of the watcher
+    ///
+    /// Every WATCH ends 30 seconds after its events, like a watch that times out on the apiserver,
+    /// and once there are no more canned WATCH responses, they never end.
+    #[derive(Clone, Default)]
+    struct MockApi {
+        lists: Arc<Mutex<VecDeque<kube_client::Result<ObjectList<ConfigMap>>>>>,
+        watches: Arc<Mutex<VecDeque<kube_client::Result<Vec<WatchEvent<ConfigMap>>>>>>,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MockApi {
+        fn list(self, resource_version: &str, items: Vec<ConfigMap>) -> Self {
+            self.lists.lock().push_back(Ok(ObjectList {
+                metadata: ListMeta {
+                    resource_version: Some(resource_version.to_string()),
+                    ..ListMeta::default()
+                },
+                items,
+            }));
+            self
+        }
+
+        fn list_error(self, code: u16) -> Self {
+            let err = kube_client::Error::Api(error_response(code));
+            self.lists.lock().push_back(Err(err));
+            self
+        }
+
+        fn watch(self, events: Vec<WatchEvent<ConfigMap>>) -> Self {
+            self.watches.lock().push_back(Ok(events));
+            self
+        }
+
+        fn watch_error(self, code: u16) -> Self {
+            let err = kube_client::Error::Api(error_response(code));
+            self.watches.lock().push_back(Err(err));
+            self
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().clone()
+        }
+    }
+
+    impl ApiMode for MockApi {
+        type Value = ConfigMap;
+
+        fn list<'a>(
+            &'a self,
+            _lp: &'a ListParams,
+        ) -> BoxFuture<'a, kube_client::Result<ObjectList<ConfigMap>>> {
+            self.requests.lock().push("list".to_string());
+            let list = self.lists.lock().pop_front().expect("unexpected list");
+            async move { list }.boxed()
+        }
+
+        fn watch<'a>(
+            &'a self,
+            wp: &'a WatchParams,
+            version: &'a str,
+        ) -> BoxFuture<'a, kube_client::Result<WatchStream<ConfigMap>>> {
+            let request = if wp.send_initial_events {
+                "stream".to_string()
+            } else {
+                format!("watch {}", version)
+            };
+            self.requests.lock().push(request);
+            let stream = match self.watches.lock().pop_front() {
+                Some(Err(err)) => return async move { Err(err) }.boxed(),
+                Some(Ok(events)) => stream::iter(events.into_iter().map(Ok))
+                    .chain(
+                        stream::once(tokio::time::sleep(Duration::from_secs(30)))
+                            .filter_map(|()| async { None }),
+                    )
+                    .boxed(),
+                None => stream::pending().boxed(),
+            };
+            async move { Ok(stream) }.boxed()
+        }
+    }
+
+    fn config_map(name: &str, resource_version: &str) -> ConfigMap {
+        ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                resource_version: Some(resource_version.to_string()),
+                ..ObjectMeta::default()
+            },
+            ..ConfigMap::default()
+        }
+    }
+
+    fn error_response(code: u16) -> ErrorResponse {
+        ErrorResponse {
+            status: "Failure".to_string(),
+            message: format!("failed with {}", code),
+            reason: String::new(),
+            code,
+            details: None,
+        }
+    }
+
+    /// Describes an event by its kind and the name of its object
+    fn describe(event: &Event<ConfigMap>) -> String {
+        match event {
+            Event::Applied(obj) => format!("applied {}", obj.metadata.name.as_ref().unwrap()),
+            Event::Deleted(obj) => format!("deleted {}", obj.metadata.name.as_ref().unwrap()),
+            Event::Init => "init".to_string(),
+            Event::InitApply(obj) => format!("listed {}", obj.metadata.name.as_ref().unwrap()),
+            Event::InitDone => "init done".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn watcher_should_relist_after_relist_interval() {
+        tokio::time::pause();
+        let api = MockApi::default()
+            .list("1", vec![config_map("a", "1")])
+            .watch(vec![])
+            .watch(vec![])
+            .list("2", vec![config_map("a", "1")]);
+        let config = Config::from(ListParams::default()).relist_interval(Duration::from_secs(60));
+        let events = watch_with_mode(api.clone(), config)
+            .take(6)
+            .map(|event| describe(&event.unwrap()))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events, vec![
+            "init",
+            "listed a",
+            "init done",
+            "init",
+            "listed a",
+            "init done"
+        ]);
+        // The watch is resumed until it ends after the interval
+        assert_eq!(api.requests(), vec!["list", "watch 1", "watch 1", "list"]);
+    }
+
+    #[tokio::test]
+    async fn watcher_should_resume_without_listing() {
+        let api = MockApi::default().watch(vec![WatchEvent::Modified(config_map("a", "6"))]);
+        let config = Config::from(ListParams::default()).resume_from("5");
+        let events = watch_with_mode(api.clone(), config)
+            .take(1)
+            .map(|event| describe(&event.unwrap()))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events, vec!["applied a"]);
+        assert_eq!(api.requests(), vec!["watch 5"]);
+    }
+
+    #[tokio::test]
+    async fn watcher_should_relist_when_resumed_version_is_gone() {
+        let api = MockApi::default()
+            .watch(vec![WatchEvent::Error(error_response(410))])
+            .list("7", vec![config_map("a", "6")]);
+        let config = Config::from(ListParams::default()).resume_from("5");
+        let events = watch_with_mode(api.clone(), config)
+            .take(4)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(matches!(&events[0], Err(Error::WatchError(err)) if err.code == 410));
+        let events = events[1..]
+            .iter()
+            .map(|event| describe(event.as_ref().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec!["init", "listed a", "init done"]);
+        assert_eq!(api.requests(), vec!["watch 5", "list"]);
+    }
+
+    #[tokio::test]
+    async fn checkpoint_should_only_record_consumed_versions() {
+        tokio::time::pause();
+        let api = MockApi::default()
+            .list("1", vec![config_map("a", "1")])
+            .watch(vec![WatchEvent::Modified(config_map("a", "2"))]);
+        let checkpoint = Checkpoint::default();
+        let config = Config::from(ListParams::default()).checkpoint(&checkpoint);
+        let mut events = Box::pin(watch_with_mode(api, config).map(|event| describe(&event.unwrap())));
+        for expected in ["init", "listed a", "init done"] {
+            assert_eq!(events.next().await.unwrap(), expected);
+        }
+        // The consumer may not have applied the list yet
+        assert_eq!(checkpoint.resource_version(), None);
+        assert_eq!(events.next().await.unwrap(), "applied a");
+        assert_eq!(checkpoint.resource_version().as_deref(), Some("1"));
+        // Polling again means that the modification has been applied, even if the watch stays quiet
+        assert!(tokio::time::timeout(Duration::from_secs(60), events.next())
+            .await
+            .is_err());
+        assert_eq!(checkpoint.resource_version().as_deref(), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn watcher_should_fall_back_to_listing_when_streaming_lists_are_rejected() {
+        let api = MockApi::default()
+            .watch_error(422)
+            .list("1", vec![config_map("a", "1")])
+            .watch(vec![WatchEvent::Error(error_response(410))])
+            .list("2", vec![config_map("a", "2")]);
+        let config =
+            Config::from(ListParams::default()).initial_list_strategy(InitialListStrategy::StreamingList);
+        let events = watch_with_mode(api.clone(), config)
+            .take(7)
+            .map(|event| match event {
+                Ok(event) => describe(&event),
+                Err(_) => "error".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events, vec![
+            "init",
+            "listed a",
+            "init done",
+            "error",
+            "init",
+            "listed a",
+            "init done",
+        ]);
+        // The rejection is remembered, so relists don't try streaming again
+        assert_eq!(api.requests(), vec!["stream", "list", "watch 1", "list"]);
+    }
+
+    #[tokio::test]
+    async fn metadata_watcher_should_list_and_watch_metadata() {
+        use futures::pin_mut;
+        use http::{header, Request, Response};
+        use hyper::Body;
+        use tower_test::mock;
+
+        let (mock_service, handle) = mock::pair::<Request<Body>, Response<Body>>();
+        let spawned = tokio::spawn(async move {
+            pin_mut!(handle);
+            let list = serde_json::json!({
+                "apiVersion": "meta.k8s.io/v1",
+                "kind": "PartialObjectMetadataList",
+                "metadata": { "resourceVersion": "1" },
+                "items": [{ "metadata": { "name": "a", "resourceVersion": "1" } }],
+            });
+            let watch = serde_json::json!({
+                "type": "ADDED",
+                "object": { "metadata": { "name": "b", "resourceVersion": "2" } },
+            });
+            for (watching, body) in [(false, list), (true, watch)] {
+                let (request, send) = handle.next_request().await.expect("service not called");
+                assert_eq!(request.uri().path(), "/api/v1/namespaces/ns/configmaps");
+                let query = request.uri().query().unwrap_or_default();
+                assert_eq!(query.contains("watch=true"), watching, "{}", query);
+                let accept = request.headers().get(header::ACCEPT).unwrap().to_str().unwrap();
+                assert!(accept.contains("as=PartialObjectMetadata"), "{}", accept);
+                send.send_response(Response::builder().body(Body::from(body.to_string())).unwrap());
+            }
+        });
+
+        let api: Api<ConfigMap> = Api::namespaced(Client::new(mock_service, "default"), "ns");
+        let events = watch_with_mode(MetaOnly { api }, Config::default())
+            .take(4)
+            .map(|event| match event.unwrap() {
+                Event::Init => "init".to_string(),
+                Event::InitApply(meta) => format!("listed {}", meta.metadata.name.unwrap()),
+                Event::InitDone => "init done".to_string(),
+                Event::Applied(meta) => format!("applied {}", meta.metadata.name.unwrap()),
+                Event::Deleted(meta) => format!("deleted {}", meta.metadata.name.unwrap()),
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events, vec!["init", "listed a", "init done", "applied b"]);
+        spawned.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn namespace_watchers_should_merge_initial_lists() {
+        tokio::time::pause();
+        let ns_a = MockApi::default()
+            .list("1", vec![config_map("a", "1")])
+            .watch(vec![WatchEvent::Modified(config_map("a", "2"))]);
+        let ns_b = MockApi::default()
+            .list("3", vec![config_map("b", "3")])
+            .watch(vec![WatchEvent::Deleted(config_map("b", "4"))]);
+        let config = Config::from(ListParams::default());
+        let events = watch_namespaces_with_mode(vec![ns_a.clone(), ns_b.clone()], config)
+            .take(6)
+            .map(|event| describe(&event.unwrap()))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events, vec![
+            "init",
+            "listed a",
+            "listed b",
+            "init done",
+            "applied a",
+            "deleted b"
+        ]);
+        assert_eq!(ns_a.requests(), vec!["list", "watch 1"]);
+        assert_eq!(ns_b.requests(), vec!["list", "watch 3"]);
+    }
+
+    #[tokio::test]
+    async fn namespace_watchers_should_relist_every_namespace_together() {
+        tokio::time::pause();
+        // Only the watch of namespace a expires, once both namespaces have been listed
+        let ns_a = MockApi::default()
+            .list("1", vec![config_map("a", "1")])
+            .watch(vec![WatchEvent::Error(error_response(410))])
+            .list("5", vec![config_map("a", "5")]);
+        let ns_b = MockApi::default()
+            .list("2", vec![config_map("b", "2")])
+            .list("6", vec![config_map("b", "6")]);
+        let config = Config::from(ListParams::default());
+        let events = watch_namespaces_with_mode(vec![ns_a.clone(), ns_b.clone()], config)
+            .take(9)
+            .map(|event| match event {
+                Ok(event) => describe(&event),
+                Err(_) => "error".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(events, vec![
+            "init",
+            "listed a",
+            "listed b",
+            "init done",
+            "error",
+            "init",
+            "listed a",
+            "listed b",
+            "init done",
+        ]);
+        // Namespace b is listed again without ever starting its watch
+        assert_eq!(ns_a.requests(), vec!["list", "watch 1", "list"]);
+        assert_eq!(ns_b.requests(), vec!["list", "list"]);
+    }
+
+    #[tokio::test]
+    async fn namespace_watchers_should_share_the_backoff() {
+        tokio::time::pause();
+        let ns_a = MockApi::default()
+            .list_error(500)
+            .list("1", vec![config_map("a", "1")]);
+        let ns_b = MockApi::default().list("2", vec![config_map("b", "2")]);
+        let config = Config::from(ListParams::default()).backoff(BackoffPolicy {
+            initial_interval: Duration::from_secs(10),
+            jitter: 0.0,
+            ..BackoffPolicy::default()
+        });
+        let mut events = Box::pin(watch_namespaces_with_mode(vec![ns_a, ns_b], config));
+        assert!(matches!(events.next().await, Some(Ok(Event::Init))));
+        let failed = events.next().await.unwrap();
+        assert!(matches!(failed, Err(Error::InitialListFailed(_))));
+        // The healthy namespace is held up by the failing one as well
+        let start = tokio::time::Instant::now();
+        let listed = describe(&events.next().await.unwrap().unwrap());
+        assert!(start.elapsed() >= Duration::from_secs(10));
+        let mut listed = vec![listed, describe(&events.next().await.unwrap().unwrap())];
+        listed.sort();
+        assert_eq!(listed, vec!["listed a", "listed b"]);
+        assert!(matches!(events.next().await, Some(Ok(Event::InitDone))));
+    }
+
+    #[test]
+    fn error_kinds() {
+        use kube_client::Error as ClientError;
+        let api = |code| ClientError::Api(error_response(code));
+        let timeout = ClientError::RequestTimeout(Duration::from_secs(1));
+        let io = ClientError::ReadEvents(std::io::ErrorKind::BrokenPipe.into());
+        let utf8 = ClientError::FromUtf8(String::from_utf8(vec![0xff]).unwrap_err());
+        let cases = vec![
+            (Error::InitialListFailed(api(403)), ErrorKind::Forbidden),
+            (Error::InitialListFailed(api(400)), ErrorKind::InvalidRequest),
+            (Error::WatchStartFailed(api(404)), ErrorKind::NotFound),
+            (Error::WatchStartFailed(api(401)), ErrorKind::Unauthorized),
+            (Error::WatchStartFailed(api(429)), ErrorKind::Throttled),
+            (Error::WatchFailed(api(503)), ErrorKind::ServerError),
+            (Error::WatchFailed(api(418)), ErrorKind::Other),
+            (Error::WatchFailed(timeout), ErrorKind::Network),
+            (Error::WatchFailed(io), ErrorKind::Network),
+            (Error::WatchFailed(utf8), ErrorKind::Decode),
+            (
+                Error::WatchFailed(ClientError::LinesCodecMaxLineLengthExceeded),
+                ErrorKind::Decode,
+            ),
+            (Error::WatchError(error_response(410)), ErrorKind::Desync),
+            (Error::WatchError(error_response(422)), ErrorKind::InvalidRequest),
+            (Error::TooManyObjects, ErrorKind::Other),
+        ];
+        for (err, kind) in cases {
+            assert_eq!(err.kind(), kind, "{}", err);
+        }
+        assert!(ErrorKind::Forbidden.is_fatal());
+        assert!(!ErrorKind::Desync.is_fatal());
+    }
+
+    #[test]
+    fn backoff_policy_should_start_over_when_reset() {
+        let mut backoff = BackoffPolicy {
+            initial_interval: Duration::from_secs(1),
+            jitter: 0.0,
+            ..BackoffPolicy::default()
+        }
+        .build();
+        assert_eq!(backoff.next_backoff(), Some(Duration::from_secs(1)));
+        assert_eq!(backoff.next_backoff(), Some(Duration::from_secs(2)));
+        backoff.reset();
+        assert_eq!(backoff.next_backoff(), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn backoff_policy_should_jitter_around_the_interval() {
+        let policy = BackoffPolicy {
+            initial_interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(8),
+            jitter: 0.5,
+            ..BackoffPolicy::default()
+        };
+        let mut backoff = policy.build();
+        let mut interval = policy.initial_interval;
+        for _ in 0..8 {
+            let delay = backoff.next_backoff().unwrap();
+            // Allow for rounding at the upper end of the randomized range
+            let range = interval.mul_f64(0.5)..=interval.mul_f64(1.5) + Duration::from_millis(1);
+            assert!(range.contains(&delay), "{:?} not in {:?}", delay, range);
+            interval = (interval * 2).min(policy.max_interval);
+        }
+        // Separately built backoffs are randomized independently, so that watchers don't retry in lockstep
+        let delays = (0..16)
+            .map(|_| policy.build().next_backoff().unwrap())
+            .collect::<std::collections::HashSet<_>>();
+        assert!(delays.len() > 1);
+    }
 }
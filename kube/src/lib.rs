@@ -30,7 +30,7 @@
 //!     // Read pods in the configured namespace into the typed interface from k8s-openapi
 //!     let pods: Api<Pod> = Api::default_namespaced(client);
 //!     for p in pods.list(&ListParams::default()).await? {
-//!         println!("found pod {}", p.name());
+//!         println!("found pod {}", p.name_any());
 //!     }
 //!     Ok(())
 //! }
@@ -90,7 +90,7 @@
 //!     let lp = ListParams::default();
 //!     let mut apply_stream = watcher(foos, lp).applied_objects().boxed();
 //!     while let Some(f) = apply_stream.try_next().await? {
-//!         println!("saw apply to {}", f.name());
+//!         println!("saw apply to {}", f.name_any());
 //!     }
 //!     Ok(())
 //! }
@@ -224,6 +224,23 @@ mod test {
         assert_eq!(a1.resource_url(), a2.resource_url());
     }
 
+    #[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
+    #[kube(group = "clux.dev", version = "v1", kind = "Bar")]
+    #[kube(crates(kube_core = "crate::core"))] // for dev-dep test structure
+    pub struct BarSpec {
+        name: String,
+    }
+
+    #[test]
+    fn custom_resource_generates_scope_markers() {
+        use crate::core::{ClusterResourceScope, NamespaceResourceScope};
+        fn assert_namespaced<K: Resource<Scope = NamespaceResourceScope>>() {}
+        fn assert_cluster<K: Resource<Scope = ClusterResourceScope>>() {}
+
+        assert_namespaced::<Foo>();
+        assert_cluster::<Bar>();
+    }
+
     use k8s_openapi::{
         api::core::v1::ConfigMap,
         apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition,
@@ -465,7 +482,10 @@ mod test {
         }))?;
 
         let pp = PostParams::default();
-        assert_eq!(data.name(), pods.create(&pp, &data).await?.name());
+        assert_eq!(
+            data.name_unchecked(),
+            pods.create(&pp, &data).await?.name_unchecked()
+        );
 
         // Watch it phase for a few seconds
         let is_running = await_condition(pods.clone(), "busybox-kube4", conditions::is_pod_running());